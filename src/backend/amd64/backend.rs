@@ -19,13 +19,36 @@ impl Backend for BackendAMD64 {
     }
 
     fn get_arguments_in_registers(&self, sizes: &Vec<IRSize>) -> Vec<bool> {
-        let mut result = Vec::with_capacity(sizes.len());
-        let mut ireg = 0;
-        for _size in sizes {
-            result.push(ireg < 6);
-            ireg += 1;
+        BackendAMD64::get_argument_registers(sizes)
+            .iter()
+            .map(|class| class.is_some())
+            .collect()
+    }
+
+    // Aggregates of at most two eightbytes are passed in registers
+    // An eightbyte is passed in a floating point register if it only contains floating point values
+    fn get_aggregate_registers(
+        &self,
+        size: usize,
+        fields: &[(usize, IRSize)],
+    ) -> Option<Vec<IRSize>> {
+        if size == 0 || size > 16 {
+            return None;
         }
-        result
+        let registers = (0..(size + 7) / 8)
+            .map(|eightbyte| {
+                let float = fields
+                    .iter()
+                    .filter(|(offset, _)| offset / 8 == eightbyte)
+                    .all(|(_, size)| size.is_float());
+                if float {
+                    IRSize::F64
+                } else {
+                    IRSize::S64
+                }
+            })
+            .collect();
+        Some(registers)
     }
 
     fn argument_evaluation_direction_registers(&self) -> crate::backend::Direction {
@@ -70,8 +93,21 @@ impl Backend for BackendAMD64 {
                 stack_align: 8,
                 irsize: IRSize::P,
             },
+            float: TypeInfo {
+                size: 4,
+                align: 4,
+                stack_align: 4,
+                irsize: IRSize::F32,
+            },
+            double: TypeInfo {
+                size: 8,
+                align: 8,
+                stack_align: 8,
+                irsize: IRSize::F64,
+            },
 
-            size_t: Long,
+            size_t: ULong,
+            ptrdiff_t: Long,
         }
     }
 }
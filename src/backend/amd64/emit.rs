@@ -1,4 +1,4 @@
-use super::{registers::Register, BackendAMD64};
+use super::{registers::Register, BackendAMD64, MOVE_COPY_LIMIT, VA_SAVE_AREA_SIZE};
 use crate::{ir::*, utility::padding};
 
 impl BackendAMD64 {
@@ -12,18 +12,23 @@ impl BackendAMD64 {
         use IRInstruction::*;
         match instruction {
             Ret(_size, _vreg) => (
-                if !self.is_last_instruction(index) {
-                    format!("\tjmp .end\n")
-                } else {
-                    String::new()
+                {
+                    let load = self.load_return_registers();
+                    if !self.is_last_instruction(index) {
+                        format!("{}\tjmp .end\n", load)
+                    } else {
+                        load
+                    }
                 },
                 false,
             ),
             Call(_size, _vreg, _, arguments) | CallV(_size, _vreg, _, arguments) => (
                 {
-                    let length = arguments.count;
+                    let stack_arguments = self.get_stack_argument_count(&arguments.sizes);
+                    let float_registers = self.get_float_register_count(&arguments.sizes);
                     let alignment = self.get_stack_alignment(arguments);
-                    let alignment_instruction = if length <= 6 {
+                    let store = self.store_return_registers(arguments);
+                    let alignment_instruction = if stack_arguments == 0 {
                         self.stack_alignment_instruction(alignment)
                     } else {
                         String::new()
@@ -51,37 +56,206 @@ impl BackendAMD64 {
                         )
                     };
 
+                    // The number of floating point registers is passed for variadic functions
+                    let float_count = if float_registers == 0 {
+                        String::from("\txor eax,eax\n")
+                    } else {
+                        format!("\tmov eax,{}\n", float_registers)
+                    };
+
                     format!(
-                        "{}\txor eax,eax\n{}",
+                        "{}{}{}{}",
                         alignment_instruction,
-                        if length > 6 || alignment != 0 {
-                            // Only hold for integer and pointer arguments
+                        float_count,
+                        if stack_arguments > 0 || alignment != 0 {
                             format!(
                                 "\tcall {} {}\n\tadd rsp,{}\n",
                                 callable,
                                 outside_file,
-                                8 * (std::cmp::max(6, length) - 6) + alignment as usize
+                                8 * stack_arguments + alignment as usize
                             )
                         } else {
                             format!("\tcall {} {}\n", callable, outside_file,)
-                        }
+                        },
+                        store
                     )
                 },
                 false,
             ),
-            Arg(_size, _vreg, Some(index)) => (
+            // Aggregates are copied to the stack
+            &Arg(IRSize::B(size), vreg, call) => {
+                let size = size + padding(size, 8);
+                let from = self.allocation[vreg as usize][index].unwrap();
+                (
+                    format!(
+                        "{}\tsub rsp,{}\n{}",
+                        self.argument_alignment(call),
+                        size,
+                        self.emit_copy(&format!("{:.64}", from), "rsp", size)
+                    ),
+                    false,
+                )
+            }
+            Arg(_size, _vreg, call) => (self.argument_alignment(*call), true),
+            &Copy(IRSize::B(size), from, to) => {
+                let from = self.allocation[from as usize][index].unwrap();
+                let to = self.allocation[to as usize][index].unwrap();
+                (
+                    self.emit_copy(&format!("{:.64}", from), &format!("{:.64}", to), size),
+                    false,
+                )
+            }
+            // The va_list holds the offsets of the next argument registers in the save area
+            // and the address of the next stack argument
+            &VaStart(list) => {
+                let list = self.allocation[list as usize][index].unwrap();
+                let registers = BackendAMD64::get_argument_registers(&self.arguments.sizes);
+                let sizes = self.arguments.sizes.iter().zip(registers);
+                let (floats, ints): (Vec<_>, Vec<_>) = sizes
+                    .filter(|(_, class)| class.is_some())
+                    .partition(|(size, _)| size.is_float());
+                let stack_arguments = self.get_stack_argument_count(&self.arguments.sizes);
+                (
+                    format!(
+                        "\tmov dword [{list:.64}],{gp}\n\tmov dword [{list:.64}+4],{fp}\n\
+                         \tlea r11,[rbp+{overflow}]\n\tmov [{list:.64}+8],r11\n\
+                         \tlea r11,[rbp+{save}]\n\tmov [{list:.64}+16],r11\n",
+                        list = list,
+                        gp = 8 * ints.len(),
+                        fp = 48 + 16 * floats.len(),
+                        overflow = 16 + 8 * stack_arguments,
+                        save = self.va_save_area()
+                    ),
+                    false,
+                )
+            }
+            VaArg(size, result, list, parts) => {
+                (self.emit_va_arg(index, *size, *result, *list, parts), false)
+            }
+            // The table stores the offset of each target relative to the start of the table
+            JmpTable(_size, vreg, labels) => (
                 {
-                    if let IRInstruction::Call(_size, _result, _name, arguments) =
-                        &self.instructions[*index]
-                    {
-                        let alignment = self.get_stack_alignment(arguments);
-                        self.stack_alignment_instruction(alignment)
-                    } else {
-                        String::new()
+                    let mut result = format!(
+                        "\tlea r11, [.LT{index}]\n\tmovsxd r10, dword [r11+{:.64}*4]\n\
+                         \tadd r10, r11\n\tjmp r10\n\
+                         section .rodata\n\talign 4\n.LT{index}:\n",
+                        self.allocation[*vreg as usize][index].unwrap(),
+                        index = index
+                    );
+                    for label in labels {
+                        result.push_str(&format!("\tdd .L{}-.LT{}\n", label, index));
                     }
+                    result.push_str("section .text\n");
+                    result
                 },
-                true,
+                false,
             ),
+            // Floating point constants are loaded from read-only data
+            &Imm(size, vreg, value) if size.is_float() => {
+                let reg = self.allocation[vreg as usize][index].unwrap();
+                if value == 0 {
+                    (format!("\txorps {}, {}\n", reg, reg), false)
+                } else {
+                    let (load, align, data) = match size {
+                        IRSize::F32 => ("movss", 4, "dd"),
+                        _ => ("movsd", 8, "dq"),
+                    };
+                    (
+                        format!(
+                            "\t{} {}, [.LF{}]\n\
+                             section .rodata\n\talign {}\n.LF{}:\n\t{} {:#x}\n\
+                             section .text\n",
+                            load, reg, index, align, index, data, value
+                        ),
+                        false,
+                    )
+                }
+            }
+            // Unordered comparisons set the parity flag and are never equal
+            Eq(size, result, left, right) | Ne(size, result, left, right) if size.is_float() => {
+                let (initial, set) = match instruction {
+                    Eq(..) => (0, "sete"),
+                    _ => (1, "setne"),
+                };
+                let compare = match size {
+                    IRSize::F32 => "ucomiss",
+                    _ => "ucomisd",
+                };
+                let result = self.allocation[*result as usize][index].unwrap();
+                (
+                    format!(
+                        "\tmov {:.32}, {}\n\t{} {}, {}\n\tjp .LC{}\n\t{} {:.8}\n.LC{}:\n",
+                        result,
+                        initial,
+                        compare,
+                        self.allocation[*left as usize][index].unwrap(),
+                        self.allocation[*right as usize][index].unwrap(),
+                        index,
+                        set,
+                        result,
+                        index
+                    ),
+                    false,
+                )
+            }
+            // Values above the signed range are halved, converted and doubled
+            // The lowest bit is kept to round correctly
+            Cvuf(to_s, to_r, _, from_r) => {
+                let convert = match to_s {
+                    IRSize::F32 => "cvtsi2ss",
+                    _ => "cvtsi2sd",
+                };
+                let add = match to_s {
+                    IRSize::F32 => "addss",
+                    _ => "addsd",
+                };
+                let to = self.allocation[*to_r as usize][index].unwrap();
+                let from = self.allocation[*from_r as usize][index].unwrap();
+                (
+                    format!(
+                        "\ttest {from:.64}, {from:.64}\n\tjs .LU{index}\n\
+                         \t{convert} {to}, {from:.64}\n\tjmp .LV{index}\n\
+                         .LU{index}:\n\tmov r10, {from:.64}\n\tshr r10, 1\n\
+                         \tmov r11, {from:.64}\n\tand r11, 1\n\tor r10, r11\n\
+                         \t{convert} {to}, r10\n\t{add} {to}, {to}\n.LV{index}:\n",
+                        from = from,
+                        to = to,
+                        index = index,
+                        convert = convert,
+                        add = add
+                    ),
+                    false,
+                )
+            }
+            // Conversions to integers truncate towards zero
+            // Values from 2^63 only fit in an unsigned long, so they are lowered by 2^63 first
+            // and the top bit is set afterwards. The source is restored, as this is exact
+            Cvfi(_, to_r, from_s, from_r) => {
+                let (suffix, data, limit) = match from_s {
+                    IRSize::F32 => ("ss", "dd", 0x5f000000u64),
+                    _ => ("sd", "dq", 0x43e0000000000000),
+                };
+                let to = self.allocation[*to_r as usize][index].unwrap();
+                let from = self.allocation[*from_r as usize][index].unwrap();
+                (
+                    format!(
+                        "\tucomi{s} {from}, [.LF{index}]\n\tjae .LU{index}\n\
+                         \tcvtt{s}2si {to:.64}, {from}\n\tjmp .LV{index}\n\
+                         .LU{index}:\n\tsub{s} {from}, [.LF{index}]\n\
+                         \tcvtt{s}2si {to:.64}, {from}\n\tadd{s} {from}, [.LF{index}]\n\
+                         \tbts {to:.64}, 63\n.LV{index}:\n\
+                         section .rodata\n\talign 8\n.LF{index}:\n\t{data} {limit:#x}\n\
+                         section .text\n",
+                        from = from,
+                        to = to,
+                        index = index,
+                        s = suffix,
+                        data = data,
+                        limit = limit
+                    ),
+                    false,
+                )
+            }
             Cvs(
                 to_s @ (IRSize::S64 | IRSize::S32 | IRSize::S16 | IRSize::S8),
                 to_r,
@@ -97,11 +271,41 @@ impl BackendAMD64 {
                 ),
                 false,
             ),
+            // Writing a 32 bit register already clears the upper half
+            Cvu(
+                to_s @ (IRSize::S64 | IRSize::S32 | IRSize::S16 | IRSize::S8),
+                to_r,
+                from_s @ (IRSize::S64 | IRSize::S32 | IRSize::S16 | IRSize::S8),
+                from_r,
+            ) if to_s > from_s => (
+                if *from_s == IRSize::S32 {
+                    format!(
+                        "\tmov {:.32},{:.32}\n",
+                        self.allocation[*to_r as usize][index].unwrap(),
+                        self.allocation[*from_r as usize][index].unwrap(),
+                    )
+                } else {
+                    format!(
+                        "\tmovzx {:.to_w$},{:.from_w$}\n",
+                        self.allocation[*to_r as usize][index].unwrap(),
+                        self.allocation[*from_r as usize][index].unwrap(),
+                        from_w = from_s.to_bit_width(),
+                        to_w = to_s.to_bit_width()
+                    )
+                },
+                false,
+            ),
             Cvs(
                 to_s @ (IRSize::S64 | IRSize::S32 | IRSize::S16 | IRSize::S8),
                 to_r,
                 from_s @ (IRSize::S64 | IRSize::S32 | IRSize::S16 | IRSize::S8),
                 from_r,
+            )
+            | Cvu(
+                to_s @ (IRSize::S64 | IRSize::S32 | IRSize::S16 | IRSize::S8),
+                to_r,
+                from_s @ (IRSize::S64 | IRSize::S32 | IRSize::S16 | IRSize::S8),
+                from_r,
             ) => {
                 let _ = (to_s, to_r, from_s, from_r);
                 let width = if *to_s == IRSize::S64 || *from_s == IRSize::S64 {
@@ -125,40 +329,279 @@ impl BackendAMD64 {
         }
     }
 
-    pub fn emit_function_declaration(&self, name: &String) -> String {
-        format!("section .text\nextern {}\n", name)
+    // The first argument of a call aligns the stack for all arguments
+    fn argument_alignment(&self, call: Option<usize>) -> String {
+        match call.map(|index| &self.instructions[index]) {
+            Some(IRInstruction::Call(.., arguments) | IRInstruction::CallV(.., arguments)) => {
+                let alignment = self.get_stack_alignment(arguments);
+                self.stack_alignment_instruction(alignment)
+            }
+            _ => String::new(),
+        }
+    }
+
+    // The argument is taken from the register save area if all its parts still fit in registers
+    // Otherwise it is taken from the stack, with the address of the argument ending up in r11
+    // Aggregates with floating point parts are rearranged in the save area to be contiguous
+    fn emit_va_arg(
+        &self,
+        index: usize,
+        size: IRSize,
+        result: u32,
+        list: u32,
+        parts: &[IRSize],
+    ) -> String {
+        let list = format!("{:.64}", self.allocation[list as usize][index].unwrap());
+        let result = self.allocation[result as usize][index].unwrap();
+        // A scalar is taken like an aggregate consisting of a single part
+        let aggregate = !parts.is_empty();
+        let scalar = [size];
+        let parts = if aggregate { parts } else { &scalar[..] };
+
+        let mut code = String::new();
+        let step = match parts {
+            [IRSize::B(size)] => *size as i32 + padding(*size as i32, 8),
+            _ => {
+                let ints = parts.iter().filter(|part| !part.is_float()).count() as i32;
+                let floats = parts.len() as i32 - ints;
+                code.push_str(&format!("\tmov r11d,[{}]\n\tmov r10d,[{}+4]\n", list, list));
+                if ints > 0 {
+                    code.push_str(&format!(
+                        "\tcmp r11d,{}\n\tja .LVA{}\n",
+                        48 - 8 * ints,
+                        index
+                    ));
+                }
+                if floats > 0 {
+                    code.push_str(&format!(
+                        "\tcmp r10d,{}\n\tja .LVA{}\n",
+                        VA_SAVE_AREA_SIZE - 16 * floats,
+                        index
+                    ));
+                }
+                code.push_str(&format!(
+                    "\tadd r11,[{}+16]\n\tadd r10,[{}+16]\n",
+                    list, list
+                ));
+                if ints > 0 {
+                    code.push_str(&format!("\tadd dword [{}],{}\n", list, 8 * ints));
+                }
+                if floats > 0 {
+                    code.push_str(&format!("\tadd dword [{}+4],{}\n", list, 16 * floats));
+                }
+                let float = |part: &IRSize| part.is_float();
+                code.push_str(match parts {
+                    [a, b] if float(a) && float(b) => {
+                        "\tmov r11,[r10+16]\n\tmov [r10+8],r11\n\tmov r11,r10\n"
+                    }
+                    [a, _] if float(a) => "\tmov r11,[r11]\n\tmov [r10+8],r11\n\tmov r11,r10\n",
+                    [_, b] if float(b) => {
+                        "\tmov r11,[r11]\n\txchg r11,[r10]\n\tmov [r10+8],r11\n\tmov r11,r10\n"
+                    }
+                    [a] if float(a) => "\tmov r11,r10\n",
+                    _ => "",
+                });
+                code.push_str(&format!("\tjmp .LVB{}\n.LVA{}:\n", index, index));
+                8 * parts.len() as i32
+            }
+        };
+        code.push_str(&format!(
+            "\tmov r11,[{list}+8]\n\tlea r10,[r11+{step}]\n\tmov [{list}+8],r10\n.LVB{index}:\n",
+            list = list,
+            step = step,
+            index = index
+        ));
+
+        // Aggregates are represented by their address
+        let load = match size {
+            _ if aggregate => format!("\tmov {:.64},r11\n", result),
+            IRSize::F32 => format!("\tmovss {},[r11]\n", result),
+            IRSize::F64 => format!("\tmovsd {},[r11]\n", result),
+            _ => format!(
+                "\tmov {:.width$},[r11]\n",
+                result,
+                width = size.to_bit_width()
+            ),
+        };
+        code.push_str(&load);
+        code
+    }
+
+    // The argument registers are stored in the save area of a variadic function
+    // The vector registers are only stored if al indicates that they hold arguments
+    fn emit_va_save_area(&self) -> String {
+        use Register::*;
+        let save = self.va_save_area();
+        let mut result = String::new();
+        for (i, register) in [Rdi, Rsi, Rdx, Rcx, R8, R9].iter().enumerate() {
+            result.push_str(&format!(
+                "\tmov [rbp+{}],{:.64}\n",
+                save + 8 * i as i32,
+                register
+            ));
+        }
+        result.push_str("\ttest al,al\n\tje .va_save_end\n");
+        let vector_registers = [Xmm0, Xmm1, Xmm2, Xmm3, Xmm4, Xmm5, Xmm6, Xmm7];
+        for (i, register) in vector_registers.iter().enumerate() {
+            result.push_str(&format!(
+                "\tmovsd [rbp+{}],{:.64}\n",
+                save + 48 + 16 * i as i32,
+                register
+            ));
+        }
+        result.push_str(".va_save_end:\n");
+        result
+    }
+
+    fn va_save_area(&self) -> i32 {
+        -8 * self.get_callee_saved_registers().len() as i32 - VA_SAVE_AREA_SIZE
+    }
+
+    // Small copies are done with a sequence of moves through r11
+    fn emit_copy(&self, from: &str, to: &str, size: u16) -> String {
+        if size > MOVE_COPY_LIMIT {
+            return format!(
+                "\tmov rsi,{}\n\tmov rdi,{}\n\tmov ecx,{}\n\trep movsb\n",
+                from, to, size
+            );
+        }
+        let mut result = String::new();
+        let mut offset = 0;
+        for &(width, register) in &[(8, "r11"), (4, "r11d"), (2, "r11w"), (1, "r11b")] {
+            while size - offset >= width {
+                result.push_str(&format!(
+                    "\tmov {},[{}+{}]\n\tmov [{}+{}],{}\n",
+                    register, from, offset, to, offset, register
+                ));
+                offset += width;
+            }
+        }
+        result
+    }
+
+    // An aggregate returned in registers is stored in a temporary of the caller
+    fn store_return_registers(&self, arguments: &IRArguments) -> String {
+        let offset = match arguments.return_variable {
+            Some(variable) => self.local_offsets[&variable],
+            None => return String::new(),
+        };
+        BackendAMD64::get_return_registers(&arguments.return_sizes)
+            .iter()
+            .enumerate()
+            .map(|(i, register)| {
+                let instruction = if register.is_float() { "movsd" } else { "mov" };
+                format!(
+                    "\t{} [rbp+{}],{:.64}\n",
+                    instruction,
+                    offset + 8 * i as i32,
+                    register
+                )
+            })
+            .collect()
+    }
+
+    // The address of an aggregate returned in registers is in rax, so rax is loaded last
+    fn load_return_registers(&self) -> String {
+        let registers = BackendAMD64::get_return_registers(&self.arguments.return_sizes);
+        let mut loads: Vec<_> = registers.iter().enumerate().collect();
+        loads.sort_by_key(|&(_, &register)| register == Register::Rax);
+        loads
+            .into_iter()
+            .map(|(i, register)| {
+                let instruction = if register.is_float() { "movsd" } else { "mov" };
+                format!("\t{} {:.64},[rax+{}]\n", instruction, register, 8 * i)
+            })
+            .collect()
+    }
+
+    // Exported symbols are made visible to the linker and imported symbols are declared
+    pub fn emit_linkage(&self, name: &String, linkage: IRLinkage) -> String {
+        match linkage {
+            IRLinkage::Export => format!("global {}\n", name),
+            IRLinkage::Local => String::new(),
+            IRLinkage::Import => format!("extern {}\n", name),
+        }
     }
 
     pub fn emit_global_definition(
         &self,
         name: &String,
-        value: i128,
+        values: &[IRGlobalValue],
         size: &IRSize,
         count: usize,
+        read_only: bool,
     ) -> String {
         let _ = count;
-        let (align, c) = match size {
-            IRSize::S8 => (1, 'b'),
-            IRSize::S16 => (2, 'w'),
-            IRSize::S32 => (4, 'd'),
-            IRSize::P | IRSize::S64 => (8, 'q'),
-            IRSize::V | IRSize::B(_) => unreachable!(),
+        // Addresses are filled in by the dynamic linker, so read only globals containing them
+        // go in .data.rel.ro, which is only made read only after relocation
+        let relocated = values
+            .iter()
+            .any(|value| matches!(value, IRGlobalValue::Address(..)));
+        let section = match (read_only, relocated) {
+            (true, true) => "data.rel.ro progbits alloc noexec write",
+            (true, false) => "rodata",
+            (false, _) => "data",
         };
-        format!(
-            "section .data\n\talign {}\n{}:\n\td{} {}\n",
-            align, name, c, value
-        )
+        let mut result = format!(
+            "section .{}\n\talign {}\n{}:\n",
+            section,
+            global_alignment(size),
+            name
+        );
+        for value in values {
+            match value {
+                IRGlobalValue::Value(size, value) => {
+                    let c = match size {
+                        IRSize::S8 => 'b',
+                        IRSize::S16 => 'w',
+                        IRSize::S32 | IRSize::F32 => 'd',
+                        IRSize::P | IRSize::S64 | IRSize::F64 => 'q',
+                        IRSize::V | IRSize::B(_) => unreachable!(),
+                    };
+                    // Floating point values are stored as their bit pattern
+                    let value = if size.is_float() {
+                        format!("{:#x}", value)
+                    } else {
+                        value.to_string()
+                    };
+                    result.push_str(&format!("\td{} {}\n", c, value));
+                }
+                IRGlobalValue::Zero(size) => {
+                    result.push_str(&format!("\ttimes {} db 0\n", size));
+                }
+                IRGlobalValue::Address(name, offset) => {
+                    result.push_str(&format!("\tdq {}{:+}\n", name, offset));
+                }
+            }
+        }
+        result
     }
 
-    pub fn emit_common(&self, name: &String, size: &IRSize, count: usize) -> String {
-        let (align, size) = match size {
-            IRSize::S8 => (1, 1),
-            IRSize::S16 => (2, 2),
-            IRSize::S32 => (4, 4),
-            IRSize::P | IRSize::S64 => (8, 8),
-            IRSize::B(size) => (std::cmp::min(*size, 16) as i32, *size as i32),
+    // Read only globals cannot be placed in .bss, so they are explicitly zeroed in .rodata
+    pub fn emit_common(
+        &self,
+        name: &String,
+        size: &IRSize,
+        count: usize,
+        read_only: bool,
+    ) -> String {
+        let align = global_alignment(size);
+        let size = match size {
+            IRSize::S8 => 1,
+            IRSize::S16 => 2,
+            IRSize::S32 | IRSize::F32 => 4,
+            IRSize::P | IRSize::S64 | IRSize::F64 => 8,
+            IRSize::B(size) => *size as i32,
             IRSize::V => unreachable!(),
         };
+        if read_only {
+            return format!(
+                "section .rodata\n\talign {}\n{}:\n\ttimes {} db 0\n",
+                align,
+                name,
+                size * count as i32
+            );
+        }
         format!(
             "section .bss\n\talignb {}\n{}:\n\tresb {}\n",
             align,
@@ -184,10 +627,11 @@ impl BackendAMD64 {
     // Should be handwritten for any backend
     // Might use a macro to generate parts
     // Emits the prologue for a function, such that it will be correct for the compiler
-    pub fn emit_prologue(&mut self) -> String {
+    pub fn emit_prologue(&mut self, linkage: IRLinkage) -> String {
         let mut prologue = format!(
-            "global {}\nsection .text\n{}:\n",
-            self.function_name, self.function_name
+            "{}section .text\n{}:\n",
+            self.emit_linkage(&self.function_name, linkage),
+            self.function_name
         );
 
         let callee_saved_registers = self.get_callee_saved_registers();
@@ -214,6 +658,10 @@ impl BackendAMD64 {
                 self.stack_size + offset - 8 * callee_saved_registers.len() as i32
             ));
         }
+
+        if self.arguments.variadic {
+            prologue.push_str(&self.emit_va_save_area());
+        }
         prologue
     }
 
@@ -250,12 +698,11 @@ impl BackendAMD64 {
         epilogue
     }
 
-    pub fn emit_strings(&self, strings: &Vec<String>) -> String {
+    pub fn emit_strings(&self, strings: &Vec<Vec<u8>>) -> String {
         let mut result = String::from("section .data\n");
         for (string, i) in strings.iter().zip(0..) {
             result.push_str(&format!(".__string{}:\n\tdb ", i));
-            for c in string.chars() {
-                let b = c as u8;
+            for b in string {
                 result.push_str(&format!("{},", b))
             }
             result.push_str("0\n")
@@ -265,7 +712,38 @@ impl BackendAMD64 {
 
     pub fn emit_move(&self, modification: &super::RegisterRelocation<Register>) -> String {
         use super::RegisterRelocation::*;
+        let float_move = match modification {
+            &Reload(IRSize::F32, ..) | &Spill(IRSize::F32, ..) | &SpillEarly(IRSize::F32, ..) => {
+                "movss"
+            }
+            _ => "movsd",
+        };
         match modification {
+            &TwoAddressMove(size, from, to)
+            | &Move(size, from, to)
+            | &MoveAfter(size, from, to)
+                if size.is_float() =>
+            {
+                format!("\tmovaps {}, {}\n", to, from)
+            }
+            &Reload(size, reg, mem) if size.is_float() => {
+                format!("\t{} {}, [rbp-{}]\n", float_move, reg, mem)
+            }
+            &Spill(size, reg, mem) | &SpillEarly(size, reg, mem) if size.is_float() => {
+                format!("\t{} [rbp-{}], {}\n", float_move, mem, reg)
+            }
+            // The value is copied through an integer register of the same width
+            &MemMove(size, from, to, reg) if size.is_float() => {
+                let size = size.to_bit_width();
+                format!(
+                    "\tmov {:.size$}, [rbp-{}]\n\tmov [rbp-{}], {:.size$}\n",
+                    reg,
+                    from,
+                    to,
+                    reg,
+                    size = size
+                )
+            }
             &TwoAddressMove(size, from, to)
             | &Move(size, from, to)
             | &MoveAfter(size, from, to) => {
@@ -294,3 +772,15 @@ impl BackendAMD64 {
         }
     }
 }
+
+// Scalars are aligned to their size and aggregates to the next power of two, up to 16 bytes
+fn global_alignment(size: &IRSize) -> usize {
+    match size {
+        IRSize::S8 => 1,
+        IRSize::S16 => 2,
+        IRSize::S32 | IRSize::F32 => 4,
+        IRSize::P | IRSize::S64 | IRSize::F64 => 8,
+        IRSize::B(size) => std::cmp::min((*size as usize).next_power_of_two(), 16),
+        IRSize::V => unreachable!(),
+    }
+}
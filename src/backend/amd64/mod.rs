@@ -20,10 +20,12 @@ rburg::rburg_main! {
     default_register_sizes: {
         pi64 => 64,
         i32i16i8 => 32,
+        f32f64 => 64,
     }
     instructions:
 :       Nop(#_l)                        ""
 :       Ret pi64i32i16i8v(_a %eax)      #"return"
+:       Ret f32f64(_a %xmm0)            #"return"
 :       Store i8(r %ireg, a %ireg)      "\tmov [{a:.64}],{r:.8}\n"
 :       Store i8(r %ireg, a adr)        "\tmov [{a}],{r:.8}\n"
 :       Store i16(r %ireg, a %ireg)     "\tmov [{a:.64}],{r:.16}\n"
@@ -34,12 +36,17 @@ rburg::rburg_main! {
 :       Store i32(Imm(#i),a adr)        "\tmov dword[{a}],{i}\n"
 :       Store Pi64(Imm(#i),a adr)       "\tmov qword[{a}],{i}\n"
 :       Store Pi64(Imm(#i),a %ireg)     "\tmov qword[{a:.64}],{i}\n"
+:       Store f32(r %freg, a %ireg)     "\tmovss [{a:.64}],{r}\n"
+:       Store f32(r %freg, a adr)       "\tmovss [{a}],{r}\n"
+:       Store f64(r %freg, a %ireg)     "\tmovsd [{a:.64}],{r}\n"
+:       Store f64(r %freg, a adr)       "\tmovsd [{a}],{r}\n"
 :       Label(#i)                       ".L{i}:\n"
 %ireg:  Label(#i)                       ".L{i}:\n"
 :       Jmp(#i)                         "\tjmp .L{i}\n" {2}
 :       Jmp(#i)                         "\t;jmp .L{i}\n" {self.empty_jump_target(index)}
 :       Jcc pi64i32(r %ireg,#l)         "\ttest {r},{r}\n\tjnz .L{l}\n" {2}
 :       Jnc pi64i32(r %ireg,#l)         "\ttest {r},{r}\n\tjz .L{l}\n"  {2}
+:       JmpTable i64(_r %ireg)          #"#jump table" {4}
 
 scale:  Imm i32i64(#i)                  "{i}" {self.scale(index)}
 con:    Imm i32i64(#i)                  "{i}"
@@ -74,6 +81,12 @@ mcon64:  m mem64            "{m}"
 %ireg:  Cvs s64s32(Load s16(r %ireg))       "\tmovsx {res}, word [{r:.64}]\n"
 %ireg:  Cvs s64(Load s32(a adr))            "\tmovsx {res:.64}, dword [{a}]\n"
 %ireg:  Cvs s64(Load s32(r %ireg))          "\tmovsx {res:.64}, dword [{r:.64}]\n"
+%ireg:  Cvu s64s32(Load s8(a adr))          "\tmovzx {res}, byte [{a}]\n"
+%ireg:  Cvu s64s32(Load s8(r %ireg))        "\tmovzx {res}, byte [{r:.64}]\n"
+%ireg:  Cvu s64s32(Load s16(a adr))         "\tmovzx {res}, word [{a}]\n"
+%ireg:  Cvu s64s32(Load s16(r %ireg))       "\tmovzx {res}, word [{r:.64}]\n"
+%ireg:  Cvu s64(Load s32(a adr))            "\tmov {res:.32}, dword [{a}]\n"
+%ireg:  Cvu s64(Load s32(r %ireg))          "\tmov {res:.32}, dword [{r:.64}]\n"
 
 %ireg:  Add pi64i32(a %ireg , b %ireg)      ?"\tadd {res}, {b} ; {res} = {a} + {b}\n"   {1}
 
@@ -83,12 +96,25 @@ mcon64:  m mem64            "{m}"
 %ireg:  Mul s32s64(a %ireg , b %ireg)       ?"\timul {res}, {b} ; {res} = {a} * {b}\n"  {1}
 %eax:   Div s32(a %eax  , b %ireg)           "\tcdq\n\tidiv {b} ; {res} = {a} / {b}\n"  {1}
 %eax:   Div s64(a %eax  , b %ireg)           "\tcqo\n\tidiv {b:.64}     ; {res:.64} = {a:.64} / {b:.64}\n"  {1}
+%edx:   Mod s32(a %eax  , b %ireg)           "\tcdq\n\tidiv {b} ; {res} = {a} mod {b}\n"  {1}
+%edx:   Mod s64(a %eax  , b %ireg)           "\tcqo\n\tidiv {b:.64}     ; {res:.64} = {a:.64} mod {b:.64}\n"  {1}
+%eax:   Divu s32(a %eax  , b %ireg)          "\txor edx, edx\n\tdiv {b} ; {res} = {a} / {b}\n"  {1}
+%eax:   Divu s64(a %eax  , b %ireg)          "\txor edx, edx\n\tdiv {b:.64}     ; {res:.64} = {a:.64} / {b:.64}\n"  {1}
+%edx:   Modu s32(a %eax  , b %ireg)          "\txor edx, edx\n\tdiv {b} ; {res} = {a} mod {b}\n"  {1}
+%edx:   Modu s64(a %eax  , b %ireg)          "\txor edx, edx\n\tdiv {b:.64}     ; {res:.64} = {a:.64} mod {b:.64}\n"  {1}
 
 %ireg:  And i64i32 (a %ireg , b %ireg)      ?"\tand {res}, {b} ; {res} = {a} & {b}\n"   {1}
 %ireg:  Or  i64i32 (a %ireg , b %ireg)      ?"\tor  {res}, {b} ; {res} = {a} | {b}\n"   {1}
 %ireg:  Xor i64i32 (a %ireg , b %ireg)      ?"\txor {res}, {b} ; {res} = {a} ^ {b}\n"   {1}
 %ireg:  Xor i64i32 (a %ireg , Imm(#_i))     ?"\tnot {res} ; {res} = ~{a}\n"             {self.range(self.get_right_index(index),-1,-1)+1}
 
+%ireg:  Shl i64i32 (a %ireg , b %cl)        ?"\tshl {res}, {b:.8} ; {res} = {a} << {b}\n"  {1}
+%ireg:  Sar i64i32 (a %ireg , b %cl)        ?"\tsar {res}, {b:.8} ; {res} = {a} >> {b}\n"  {1}
+%ireg:  Shr i64i32 (a %ireg , b %cl)        ?"\tshr {res}, {b:.8} ; {res} = {a} >> {b}\n"  {1}
+%ireg:  Shl i64i32 (a %ireg , Imm(#i))      ?"\tshl {res}, {i} ; {res} = {a} << {i}\n"     {self.range(self.get_right_index(index),0,255)+1}
+%ireg:  Sar i64i32 (a %ireg , Imm(#i))      ?"\tsar {res}, {i} ; {res} = {a} >> {i}\n"     {self.range(self.get_right_index(index),0,255)+1}
+%ireg:  Shr i64i32 (a %ireg , Imm(#i))      ?"\tshr {res}, {i} ; {res} = {a} >> {i}\n"     {self.range(self.get_right_index(index),0,255)+1}
+
 %ireg:  Eq pi64i32(a %ireg , b %ireg)       "\tcmp {a}, {b}\n\tsete {res:.8}\n\tmovsx {res},{res:.8}; {res} = {a} == {b}\n"    {3}
 %ireg:  Ne pi64i32(a %ireg , b %ireg)       "\tcmp {a}, {b}\n\tsetne {res:.8}\n\tmovsx {res},{res:.8}; {res} = {a} == {b}\n"   {3}
 %ireg:  Eq pi64i32(a %ireg , Imm(#i))       "\ttest {a}, {a}\n\tsetz {res:.8}\n\tmovsx {res},{res:.8}; {res} = {a} == {i}\n"   {self.range(self.get_right_index(index),0,0)+2}
@@ -99,6 +125,11 @@ mcon64:  m mem64            "{m}"
 %ireg:  Gt s32s64 (a %ireg , b %ireg)       "\tcmp {a}, {b}\n\tsetg {res:.8}\n\tmovsx {res},{res:.8}; {res} = {a} == {b}\n"       {3}
 %ireg:  Ge s32s64 (a %ireg , b %ireg)       "\tcmp {a}, {b}\n\tsetge {res:.8}\n\tmovsx {res},{res:.8}; {res} = {a} == {b}\n"      {3}
 
+%ireg:  Ltu s32s64 (a %ireg , b %ireg)      "\tcmp {a}, {b}\n\tsetb {res:.8}\n\tmovsx {res},{res:.8}; {res} = {a} == {b}\n"       {3}
+%ireg:  Leu s32s64 (a %ireg , b %ireg)      "\tcmp {a}, {b}\n\tsetbe {res:.8}\n\tmovsx {res},{res:.8}; {res} = {a} == {b}\n"      {3}
+%ireg:  Gtu s32s64 (a %ireg , b %ireg)      "\tcmp {a}, {b}\n\tseta {res:.8}\n\tmovsx {res},{res:.8}; {res} = {a} == {b}\n"       {3}
+%ireg:  Geu s32s64 (a %ireg , b %ireg)      "\tcmp {a}, {b}\n\tsetae {res:.8}\n\tmovsx {res},{res:.8}; {res} = {a} == {b}\n"      {3}
+
 %ireg:  Lt p (a %ireg , b %ireg)            "\tcmp {a:.64}, {b:.64}\n\tsetb {res:.8}\n\tmovsx {res},{res:.8}; {res} = {a} == {b}\n"       {3}
 %ireg:  Le p (a %ireg , b %ireg)            "\tcmp {a:.64}, {b:.64}\n\tsetbe {res:.8}\n\tmovsx {res},{res:.8}; {res} = {a} == {b}\n"      {3}
 %ireg:  Gt p (a %ireg , b %ireg)            "\tcmp {a:.64}, {b:.64}\n\tseta {res:.8}\n\tmovsx {res},{res:.8}; {res} = {a} == {b}\n"       {3}
@@ -114,29 +145,83 @@ mcon64:  m mem64            "{m}"
 :  Jcc(Gt s32s64 (a %ireg , b %ireg),#l)    "\tcmp {a}, {b}\n\tjg .L{l}\n"       {1}
 :  Jcc(Ge s32s64 (a %ireg , b %ireg),#l)    "\tcmp {a}, {b}\n\tjge .L{l}\n"       {1}
 
+:  Jcc(Ltu s32s64 (a %ireg , b %ireg),#l)   "\tcmp {a}, {b}\n\tjb  .L{l}\n"       {1}
+:  Jcc(Leu s32s64 (a %ireg , b %ireg),#l)   "\tcmp {a}, {b}\n\tjbe .L{l}\n"       {1}
+:  Jcc(Gtu s32s64 (a %ireg , b %ireg),#l)   "\tcmp {a}, {b}\n\tja  .L{l}\n"       {1}
+:  Jcc(Geu s32s64 (a %ireg , b %ireg),#l)   "\tcmp {a}, {b}\n\tjae .L{l}\n"       {1}
+
 :  Jcc(Lt p (a %ireg , b %ireg),#l)         "\tcmp {a:.64}, {b:.64}\n\tjb  .L{l}\n"      {1}
 :  Jcc(Le p (a %ireg , b %ireg),#l)         "\tcmp {a:.64}, {b:.64}\n\tjbe .L{l}\n"      {1}
 :  Jcc(Gt p (a %ireg , b %ireg),#l)         "\tcmp {a:.64}, {b:.64}\n\tja  .L{l}\n"      {1}
 :  Jcc(Ge p (a %ireg , b %ireg),#l)         "\tcmp {a:.64}, {b:.64}\n\tjae .L{l}\n"      {1}
 
+%freg:  Imm f32f64(#_i)                 #"#float constant" {1}
+%freg:  Load f32(a adr)                 "\tmovss {res}, [{a}]\n"
+%freg:  Load f32(a %ireg)               "\tmovss {res}, [{a:.64}]\n"
+%freg:  Load f64(a adr)                 "\tmovsd {res}, [{a}]\n"
+%freg:  Load f64(a %ireg)               "\tmovsd {res}, [{a:.64}]\n"
+
+%freg:  Add f32(a %freg , b %freg)      ?"\taddss {res}, {b} ; {res} = {a} + {b}\n"   {1}
+%freg:  Add f64(a %freg , b %freg)      ?"\taddsd {res}, {b} ; {res} = {a} + {b}\n"   {1}
+%freg:  Sub f32(a %freg , b %freg)      ?"\tsubss {res}, {b} ; {res} = {a} - {b}\n"   {1}
+%freg:  Sub f64(a %freg , b %freg)      ?"\tsubsd {res}, {b} ; {res} = {a} - {b}\n"   {1}
+%freg:  Mul f32(a %freg , b %freg)      ?"\tmulss {res}, {b} ; {res} = {a} * {b}\n"   {1}
+%freg:  Mul f64(a %freg , b %freg)      ?"\tmulsd {res}, {b} ; {res} = {a} * {b}\n"   {1}
+%freg:  Div f32(a %freg , b %freg)      ?"\tdivss {res}, {b} ; {res} = {a} / {b}\n"   {1}
+%freg:  Div f64(a %freg , b %freg)      ?"\tdivsd {res}, {b} ; {res} = {a} / {b}\n"   {1}
+
+%ireg:  Eq f32f64(a %freg , b %freg)    #"#float compare" {4}
+%ireg:  Ne f32f64(a %freg , b %freg)    #"#float compare" {4}
+%ireg:  Lt f32(a %freg , b %freg)       "\tucomiss {b}, {a}\n\tseta {res:.8}\n\tmovzx {res:.32},{res:.8}; {res} = {a} < {b}\n"    {3}
+%ireg:  Le f32(a %freg , b %freg)       "\tucomiss {b}, {a}\n\tsetae {res:.8}\n\tmovzx {res:.32},{res:.8}; {res} = {a} <= {b}\n"  {3}
+%ireg:  Gt f32(a %freg , b %freg)       "\tucomiss {a}, {b}\n\tseta {res:.8}\n\tmovzx {res:.32},{res:.8}; {res} = {a} > {b}\n"    {3}
+%ireg:  Ge f32(a %freg , b %freg)       "\tucomiss {a}, {b}\n\tsetae {res:.8}\n\tmovzx {res:.32},{res:.8}; {res} = {a} >= {b}\n"  {3}
+%ireg:  Lt f64(a %freg , b %freg)       "\tucomisd {b}, {a}\n\tseta {res:.8}\n\tmovzx {res:.32},{res:.8}; {res} = {a} < {b}\n"    {3}
+%ireg:  Le f64(a %freg , b %freg)       "\tucomisd {b}, {a}\n\tsetae {res:.8}\n\tmovzx {res:.32},{res:.8}; {res} = {a} <= {b}\n"  {3}
+%ireg:  Gt f64(a %freg , b %freg)       "\tucomisd {a}, {b}\n\tseta {res:.8}\n\tmovzx {res:.32},{res:.8}; {res} = {a} > {b}\n"    {3}
+%ireg:  Ge f64(a %freg , b %freg)       "\tucomisd {a}, {b}\n\tsetae {res:.8}\n\tmovzx {res:.32},{res:.8}; {res} = {a} >= {b}\n"  {3}
+
+%freg:  Cvf f64(r %freg)                "\tcvtss2sd {res}, {r}\n"      {1}
+%freg:  Cvf f32(r %freg)                "\tcvtsd2ss {res}, {r}\n"      {1}
+%freg:  Cvif f32(r %ireg)               "\tcvtsi2ss {res}, {r:.64}\n"  {1}
+%freg:  Cvif f64(r %ireg)               "\tcvtsi2sd {res}, {r:.64}\n"  {1}
+%freg:  Cvuf f32f64(r %ireg)            #"#unsigned to float" {6}
+%ireg:  Cvfi s64(r %freg)               #"#float to integer"  {1}
+
 %ireg:  Cvp (_r %ireg)                  #"#extend/truncuate" {2}
 %ireg:  Cvs s64s32s16s8(_r %ireg)       #"#extend/truncuate" {2}
+%ireg:  Cvu s64s32s16s8(_r %ireg)       #"#extend/truncuate" {2}
 
 :       Arg pi32i64(r %ireg)            #"\tpush {r:.64}\n" {1}
+:       Arg f32(r %freg)                #"\tsub rsp,8\n\tmovss [rsp],{r}\n" {1}
+:       Arg f64(r %freg)                #"\tsub rsp,8\n\tmovsd [rsp],{r}\n" {1}
+:       Arg (_r %ireg)                  #"#aggregate argument" {self.aggregate(index)}
+:       Copy(_a %ireg, _b %ireg)        #"#copy" {1}
+:       VaStart(_a %ireg)               #"#va_start" {1}
+%ireg:  VaArg pi64i32i16i8(_a %ireg)    #"#va_arg" {1}
+%freg:  VaArg f32f64(_a %ireg)          #"#va_arg" {1}
 %eax:   Call pi64i32i16i8v(#name)       #"#call {name}\n" {20}
 %eax:   CallV pi64i32i16i8v(r %callv)   #"#call {r}\n"    {20}
+%xmm0:  Call f32f64(#name)              #"#call {name}\n" {20}
+%xmm0:  CallV f32f64(r %callv)          #"#call {r}\n"    {20}
 :       Call v(#name)                   #"#call {name}\n" {20}
 :       CallV v(r %callv)               #"#call {r}\n"    {20}
 }
 
+// Larger aggregates are copied with rep movsb instead of a sequence of moves
+const MOVE_COPY_LIMIT: u16 = 64;
+
+// Variadic functions save the 6 integer and 8 vector argument registers of 16 bytes each
+const VA_SAVE_AREA_SIZE: i32 = 6 * 8 + 8 * 16;
+
 impl BackendAMD64 {
     super::rburg_template::get_rule! {}
     super::rburg_template::reduce_instruction! {}
     super::rburg_template::emit_asm! {}
 
     fn get_stack_alignment(&self, arguments: &IRArguments) -> i32 {
-        let length = arguments.count as i32;
-        let extra_stack_size = (std::cmp::max(length, 6) - 6) * 8;
+        let stack_arguments = self.get_stack_argument_count(&arguments.sizes) as i32;
+        let extra_stack_size = stack_arguments * 8;
         let next_alignment = self.stack_size + extra_stack_size as i32 + 0;
         match next_alignment % 16 {
             0 => 0,
@@ -158,7 +243,21 @@ impl BackendAMD64 {
         let instruction = &self.instructions[index];
         use IRInstruction::*;
         match instruction {
-            Div(..) => vec![Register::Rdx],
+            Div(size, ..) | Divu(size, ..) if !size.is_float() => vec![Register::Rdx],
+            // The quotient overwrites rax, while the remainder is the result
+            Mod(..) | Modu(..) => vec![Register::Rax, Register::Rdx],
+            // Used to calculate the address of the jump target
+            JmpTable(..) => vec![Register::R10, Register::R11],
+            // Used to halve unsigned values that do not fit in a signed integer
+            Cvuf(..) => vec![Register::R10, Register::R11],
+            // Small aggregates are copied through r11, larger ones with rep movsb
+            Copy(IRSize::B(size), ..) | Arg(IRSize::B(size), ..) if *size <= MOVE_COPY_LIMIT => {
+                vec![Register::R11]
+            }
+            Copy(..) | Arg(IRSize::B(_), ..) => vec![Register::Rsi, Register::Rdi, Register::Rcx],
+            // Used to calculate the addresses in the va_list
+            VaStart(..) => vec![Register::R11],
+            VaArg(..) => vec![Register::R10, Register::R11],
             _ => Vec::new(),
         }
     }
@@ -169,21 +268,91 @@ impl BackendAMD64 {
         use IRInstruction::*;
         use Register::*;
         match instruction {
-            Call(..) => vec![Rcx, Rdx, Rsi, Rdi, R8, R9, R10, R11],
+            // The register holding the result is not clobbered
+            Call(size, ..) | CallV(size, ..) => {
+                let mut clobbered = vec![Rcx, Rdx, Rsi, Rdi, R8, R9, R10, R11];
+                clobbered.extend_from_slice(&[
+                    Xmm1, Xmm2, Xmm3, Xmm4, Xmm5, Xmm6, Xmm7, Xmm8, Xmm9, Xmm10, Xmm11, Xmm12,
+                    Xmm13, Xmm14, Xmm15,
+                ]);
+                match size {
+                    IRSize::F32 | IRSize::F64 => clobbered.push(Rax),
+                    IRSize::V => clobbered.extend_from_slice(&[Rax, Xmm0]),
+                    _ => clobbered.push(Xmm0),
+                }
+                clobbered
+            }
             _ => Vec::new(),
         }
     }
 
-    fn get_call_regs(&self, sizes: &Vec<IRSize>) -> Vec<&'static RegisterClass<Register>> {
-        let mut result = Vec::with_capacity(sizes.len());
+    // Integer and floating point arguments use separate registers
+    // Arguments are passed on the stack once the registers of their kind run out
+    // Aggregates that are not split into registers are always passed on the stack
+    fn get_argument_registers(sizes: &[IRSize]) -> Vec<Option<&'static RegisterClass<Register>>> {
         let mut ireg_index = 0usize;
-        for _size in sizes {
-            if ireg_index < 6 {
-                result.push(&Register::CALL_REGS[ireg_index]);
-                ireg_index += 1;
-            }
+        let mut freg_index = 0usize;
+        sizes
+            .iter()
+            .map(|size| {
+                if let IRSize::B(_) = size {
+                    None
+                } else if size.is_float() {
+                    freg_index += 1;
+                    FLOAT_CALL_REGS.get(freg_index - 1)
+                } else {
+                    ireg_index += 1;
+                    CALL_REGS.get(ireg_index - 1)
+                }
+            })
+            .collect()
+    }
+
+    fn get_call_regs(&self, sizes: &Vec<IRSize>) -> Vec<&'static RegisterClass<Register>> {
+        BackendAMD64::get_argument_registers(sizes)
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+
+    // Gives the number of stack slots used by the arguments
+    fn get_stack_argument_count(&self, sizes: &Vec<IRSize>) -> usize {
+        BackendAMD64::get_argument_registers(sizes)
+            .iter()
+            .zip(sizes)
+            .filter(|(class, _)| class.is_none())
+            .map(|(_, size)| BackendAMD64::get_stack_slots(size))
+            .sum()
+    }
+
+    fn get_stack_slots(size: &IRSize) -> usize {
+        match size {
+            IRSize::B(size) => (*size as usize + 7) / 8,
+            _ => 1,
         }
-        result
+    }
+
+    // Integer parts of an aggregate are returned in rax and rdx, floating parts in xmm0 and xmm1
+    fn get_return_registers(sizes: &[IRSize]) -> Vec<Register> {
+        let mut iregs = [Register::Rax, Register::Rdx].iter();
+        let mut fregs = [Register::Xmm0, Register::Xmm1].iter();
+        sizes
+            .iter()
+            .map(|size| match size.is_float() {
+                true => *fregs.next().unwrap(),
+                false => *iregs.next().unwrap(),
+            })
+            .collect()
+    }
+
+    // The number of floating point registers used is passed in al for variadic functions
+    fn get_float_register_count(&self, sizes: &Vec<IRSize>) -> usize {
+        let registers = BackendAMD64::get_argument_registers(sizes);
+        sizes
+            .iter()
+            .zip(registers)
+            .filter(|(size, class)| size.is_float() && class.is_some())
+            .count()
     }
 
     // Should depend on sizes and allignment as given by the backend
@@ -193,27 +362,45 @@ impl BackendAMD64 {
         variable_types: &Vec<IRVariable>,
         arguments: &IRArguments,
     ) -> (HashMap<u32, i32>, i32) {
-        let mut arg_offset = 8;
-        let mut offset = 0;
+        // The register save area of a variadic function is directly below the saved registers
+        let mut offset = if arguments.variadic {
+            -VA_SAVE_AREA_SIZE
+        } else {
+            0
+        };
         let mut result = HashMap::new();
         let callee_saved_registers = self.get_callee_saved_registers();
         let saved_offset = -8 * callee_saved_registers.len() as i32;
 
         log::trace!("{:?}", arguments.arguments);
 
+        // Stack arguments are stored above the return address in the order of the arguments
+        let mut arg_offset = 16;
+        let mut stack_arguments = HashMap::new();
+        let argument_iter = arguments.arguments.iter().zip(&arguments.variables);
+        for ((_, variable), size) in argument_iter
+            .zip(&arguments.sizes)
+            .filter(|((argument, _), _)| argument.is_none())
+        {
+            if let Some(variable) = variable {
+                stack_arguments.insert(*variable, arg_offset);
+            }
+            arg_offset += 8 * BackendAMD64::get_stack_slots(size) as i32;
+        }
+
         for i in 0..variable_types.len() {
             let count = variable_types[i].count as i32;
             let var = variable_types[i].number;
             result.insert(
                 var,
-                match arguments.arguments.get(var as usize) {
+                match stack_arguments.get(&var) {
                     // Either a normal variable or an argument passed via register
-                    None | Some(Some(..)) => {
+                    None => {
                         offset += padding(
                             offset,
                             match variable_types[i].size {
-                                IRSize::S8 | IRSize::S16 | IRSize::S32 => -4,
-                                IRSize::P | IRSize::S64 => -8,
+                                IRSize::S8 | IRSize::S16 | IRSize::S32 | IRSize::F32 => -4,
+                                IRSize::P | IRSize::S64 | IRSize::F64 => -8,
                                 IRSize::B(size) => -(std::cmp::max(size, 4) as i32),
                                 IRSize::V => unreachable!(),
                             },
@@ -222,18 +409,15 @@ impl BackendAMD64 {
                             * match variable_types[i].size {
                                 IRSize::S8 => -1,
                                 IRSize::S16 => -2,
-                                IRSize::S32 => -4,
-                                IRSize::P | IRSize::S64 => -8,
+                                IRSize::S32 | IRSize::F32 => -4,
+                                IRSize::P | IRSize::S64 | IRSize::F64 => -8,
                                 IRSize::B(size) => -(size as i32),
                                 IRSize::V => unreachable!(),
                             };
                         offset + saved_offset
                     }
                     // Stack argument
-                    Some(None) => {
-                        arg_offset += 8;
-                        arg_offset
-                    }
+                    Some(&arg_offset) => arg_offset,
                 },
             );
         }
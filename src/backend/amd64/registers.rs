@@ -1,196 +1,284 @@
-use crate::backend::register_allocation::{RegisterClass, RegisterInterface};
-
-use std::fmt::Display;
-
-// Registers classes that are used. Should be automatically generated
-pub const REG_COUNT: usize = 14;
-#[allow(dead_code)]
-
-pub const REG_CLASS_EAX: RegisterClass<Register> = RegisterClass::new(&[
-    true, false, false, false, false, false, false, false, false, false, false, false, false, false,
-]);
-
-pub const REG_CLASS_CALLV: RegisterClass<Register> = RegisterClass::new(&[
-    false, true, true, true, true, true, true, true, true, true, true, true, true, true,
-]);
-
-pub const REG_CLASS_EDI: RegisterClass<Register> = RegisterClass::new(&[
-    false, false, false, true, false, false, false, false, false, false, false, false, false, false,
-]);
-pub const REG_CLASS_ESI: RegisterClass<Register> = RegisterClass::new(&[
-    false, false, false, false, true, false, false, false, false, false, false, false, false, false,
-]);
-pub const REG_CLASS_ECX: RegisterClass<Register> = RegisterClass::new(&[
-    false, true, false, false, false, false, false, false, false, false, false, false, false, false,
-]);
-pub const REG_CLASS_EDX: RegisterClass<Register> = RegisterClass::new(&[
-    false, false, true, false, false, false, false, false, false, false, false, false, false, false,
-]);
-pub const REG_CLASS_R8: RegisterClass<Register> = RegisterClass::new(&[
-    false, false, false, false, false, true, false, false, false, false, false, false, false, false,
-]);
-pub const REG_CLASS_R9: RegisterClass<Register> = RegisterClass::new(&[
-    false, false, false, false, false, false, true, false, false, false, false, false, false, false,
-]);
-pub const CALL_REGS: &'static [RegisterClass<Register>] = &[
-    REG_CLASS_EDI,
-    REG_CLASS_ESI,
-    REG_CLASS_EDX,
-    REG_CLASS_ECX,
-    REG_CLASS_R8,
-    REG_CLASS_R9,
-];
-pub const REG_CLASS_IREG: RegisterClass<Register> = RegisterClass::new(&[true; REG_COUNT]);
-#[allow(dead_code)]
-pub const REG_CLASS_EMPTY: RegisterClass<Register> = RegisterClass::new(&[false; REG_COUNT]);
-
-pub const REG_LOOKUP: [Register; REG_COUNT] = {
-    use Register::*;
-    [
-        Rax, Rcx, Rdx, Rdi, Rsi, R8, R9, R10, R11, Rbx, R12, R13, R14, R15,
-    ]
-};
-
-impl RegisterClass<Register> {
-    pub const fn new(registers: &'static [bool]) -> RegisterClass<Register> {
-        RegisterClass {
-            phantom: std::marker::PhantomData,
-            registers,
-        }
-    }
-}
-
-// Currently only caller safed registers
-// An enum for all available registers to show effects
-#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash)]
-pub enum Register {
-    Rax = 0,
-    Rcx = 1,
-    Rdx = 2,
-    Rdi = 3,
-    Rsi = 4,
-    R8 = 5,
-    R9 = 6,
-    R10 = 7,
-    R11 = 8,
-    Rbx = 9,
-    R12 = 10,
-    R13 = 11,
-    R14 = 12,
-    R15 = 13,
-}
-impl Register {
-    pub fn to_string(&self) -> &'static str {
-        self.to_string_i32()
-    }
-
-    pub fn to_string_i64(&self) -> &'static str {
-        match self {
-            Self::Rax => "rax",
-            Self::Rcx => "rcx",
-            Self::Rdx => "rdx",
-            Self::Rdi => "rdi",
-            Self::Rsi => "rsi",
-            Self::R8 => "r8",
-            Self::R9 => "r9",
-            Self::R10 => "r10",
-            Self::R11 => "r11",
-            Self::Rbx => "rbx",
-            Self::R12 => "r12",
-            Self::R13 => "r13",
-            Self::R14 => "r14",
-            Self::R15 => "r15",
-        }
-    }
-
-    pub fn to_string_i32(&self) -> &'static str {
-        match self {
-            Self::Rax => "eax",
-            Self::Rcx => "ecx",
-            Self::Rdx => "edx",
-            Self::Rdi => "edi",
-            Self::Rsi => "esi",
-            Self::R8 => "r8d",
-            Self::R9 => "r9d",
-            Self::R10 => "r10d",
-            Self::R11 => "r11d",
-            Self::Rbx => "ebx",
-            Self::R12 => "r12d",
-            Self::R13 => "r13d",
-            Self::R14 => "r14d",
-            Self::R15 => "r15d",
-        }
-    }
-
-    pub fn to_string_i16(&self) -> &'static str {
-        match self {
-            Self::Rax => "ax",
-            Self::Rcx => "cx",
-            Self::Rdx => "dx",
-            Self::Rdi => "di",
-            Self::Rsi => "si",
-            Self::R8 => "r8w",
-            Self::R9 => "r9w",
-            Self::R10 => "r10w",
-            Self::R11 => "r11w",
-            Self::Rbx => "bx",
-            Self::R12 => "r12w",
-            Self::R13 => "r13w",
-            Self::R14 => "r14w",
-            Self::R15 => "r15w",
-        }
-    }
-
-    pub fn to_string_i8(&self) -> &'static str {
-        match self {
-            Self::Rax => "al",
-            Self::Rcx => "cl",
-            Self::Rdx => "dl",
-            Self::Rdi => "dil",
-            Self::Rsi => "sil",
-            Self::R8 => "r8b",
-            Self::R9 => "r9b",
-            Self::R10 => "r10b",
-            Self::R11 => "r11b",
-            Self::Rbx => "bl",
-            Self::R12 => "r12b",
-            Self::R13 => "r13b",
-            Self::R14 => "r14b",
-            Self::R15 => "r15b",
-        }
-    }
-}
-
-impl Display for Register {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match f.precision() {
-            Some(8) => write!(f, "{}", self.to_string_i8())?,
-            Some(16) => write!(f, "{}", self.to_string_i16())?,
-            Some(32) => write!(f, "{}", self.to_string_i32())?,
-            Some(64) => write!(f, "{}", self.to_string_i64())?,
-            Some(s) => log::error!("Unsupported precision {}", s),
-            None => write!(f, "{}", self.to_string())?,
-        }
-        Ok(())
-    }
-}
-
-impl Into<usize> for Register {
-    fn into(self) -> usize {
-        self as usize
-    }
-}
-
-impl Into<u32> for Register {
-    fn into(self) -> u32 {
-        self as u32
-    }
-}
-
-impl RegisterInterface for Register {
-    const REG_COUNT: usize = REG_COUNT;
-    const REG_LOOKUP: &'static [Self] = &REG_LOOKUP;
-    const REG_DEFAULT: Self = Register::Rax;
-    const REG_DEFAULT_CLASS: RegisterClass<Self> = REG_CLASS_IREG;
-    const CALL_REGS: &'static [RegisterClass<Self>] = CALL_REGS;
-}
+use crate::backend::register_allocation::{RegisterClass, RegisterInterface};
+use crate::ir::IRSize;
+
+use std::fmt::Display;
+
+// Creates a register class from a list of registers at compile time
+macro_rules! register_class {
+    ($($register:ident),*) => {{
+        const REGISTERS: [bool; REG_COUNT] = class(&[$(Register::$register),*]);
+        RegisterClass::new(&REGISTERS)
+    }};
+}
+
+const fn class(registers: &[Register]) -> [bool; REG_COUNT] {
+    let mut result = [false; REG_COUNT];
+    let mut index = 0;
+    while index < registers.len() {
+        result[registers[index] as usize] = true;
+        index += 1;
+    }
+    result
+}
+
+// Registers classes that are used. Should be automatically generated
+pub const REG_COUNT: usize = 30;
+#[allow(dead_code)]
+
+pub const REG_CLASS_EAX: RegisterClass<Register> = register_class!(Rax);
+
+pub const REG_CLASS_CALLV: RegisterClass<Register> =
+    register_class!(Rcx, Rdx, Rdi, Rsi, R8, R9, R10, R11, Rbx, R12, R13, R14, R15);
+
+pub const REG_CLASS_EDI: RegisterClass<Register> = register_class!(Rdi);
+pub const REG_CLASS_ESI: RegisterClass<Register> = register_class!(Rsi);
+pub const REG_CLASS_ECX: RegisterClass<Register> = register_class!(Rcx);
+// Shift counts are required to be in cl
+pub const REG_CLASS_CL: RegisterClass<Register> = REG_CLASS_ECX;
+pub const REG_CLASS_EDX: RegisterClass<Register> = register_class!(Rdx);
+pub const REG_CLASS_R8: RegisterClass<Register> = register_class!(R8);
+pub const REG_CLASS_R9: RegisterClass<Register> = register_class!(R9);
+pub const CALL_REGS: &'static [RegisterClass<Register>] = &[
+    REG_CLASS_EDI,
+    REG_CLASS_ESI,
+    REG_CLASS_EDX,
+    REG_CLASS_ECX,
+    REG_CLASS_R8,
+    REG_CLASS_R9,
+];
+
+pub const REG_CLASS_XMM0: RegisterClass<Register> = register_class!(Xmm0);
+pub const REG_CLASS_XMM1: RegisterClass<Register> = register_class!(Xmm1);
+pub const REG_CLASS_XMM2: RegisterClass<Register> = register_class!(Xmm2);
+pub const REG_CLASS_XMM3: RegisterClass<Register> = register_class!(Xmm3);
+pub const REG_CLASS_XMM4: RegisterClass<Register> = register_class!(Xmm4);
+pub const REG_CLASS_XMM5: RegisterClass<Register> = register_class!(Xmm5);
+pub const REG_CLASS_XMM6: RegisterClass<Register> = register_class!(Xmm6);
+pub const REG_CLASS_XMM7: RegisterClass<Register> = register_class!(Xmm7);
+// Floating point arguments are passed in xmm0-xmm7
+pub const FLOAT_CALL_REGS: &'static [RegisterClass<Register>] = &[
+    REG_CLASS_XMM0,
+    REG_CLASS_XMM1,
+    REG_CLASS_XMM2,
+    REG_CLASS_XMM3,
+    REG_CLASS_XMM4,
+    REG_CLASS_XMM5,
+    REG_CLASS_XMM6,
+    REG_CLASS_XMM7,
+];
+
+pub const REG_CLASS_IREG: RegisterClass<Register> =
+    register_class!(Rax, Rcx, Rdx, Rdi, Rsi, R8, R9, R10, R11, Rbx, R12, R13, R14, R15);
+pub const REG_CLASS_FREG: RegisterClass<Register> = register_class!(
+    Xmm0, Xmm1, Xmm2, Xmm3, Xmm4, Xmm5, Xmm6, Xmm7, Xmm8, Xmm9, Xmm10, Xmm11, Xmm12, Xmm13, Xmm14,
+    Xmm15
+);
+#[allow(dead_code)]
+pub const REG_CLASS_EMPTY: RegisterClass<Register> = RegisterClass::new(&[false; REG_COUNT]);
+
+pub const REG_LOOKUP: [Register; REG_COUNT] = {
+    use Register::*;
+    [
+        Rax, Rcx, Rdx, Rdi, Rsi, R8, R9, R10, R11, Rbx, R12, R13, R14, R15, Xmm0, Xmm1, Xmm2, Xmm3,
+        Xmm4, Xmm5, Xmm6, Xmm7, Xmm8, Xmm9, Xmm10, Xmm11, Xmm12, Xmm13, Xmm14, Xmm15,
+    ]
+};
+
+impl RegisterClass<Register> {
+    pub const fn new(registers: &'static [bool]) -> RegisterClass<Register> {
+        RegisterClass {
+            phantom: std::marker::PhantomData,
+            registers,
+        }
+    }
+}
+
+// Currently only caller safed registers
+// An enum for all available registers to show effects
+#[derive(Clone, Debug, Copy, PartialEq, Eq, Hash)]
+pub enum Register {
+    Rax = 0,
+    Rcx = 1,
+    Rdx = 2,
+    Rdi = 3,
+    Rsi = 4,
+    R8 = 5,
+    R9 = 6,
+    R10 = 7,
+    R11 = 8,
+    Rbx = 9,
+    R12 = 10,
+    R13 = 11,
+    R14 = 12,
+    R15 = 13,
+    Xmm0 = 14,
+    Xmm1 = 15,
+    Xmm2 = 16,
+    Xmm3 = 17,
+    Xmm4 = 18,
+    Xmm5 = 19,
+    Xmm6 = 20,
+    Xmm7 = 21,
+    Xmm8 = 22,
+    Xmm9 = 23,
+    Xmm10 = 24,
+    Xmm11 = 25,
+    Xmm12 = 26,
+    Xmm13 = 27,
+    Xmm14 = 28,
+    Xmm15 = 29,
+}
+impl Register {
+    pub fn to_string(&self) -> &'static str {
+        self.to_string_i32()
+    }
+
+    pub fn is_float(&self) -> bool {
+        *self as usize >= Register::Xmm0 as usize
+    }
+
+    // The xmm registers have the same name for every size
+    pub fn to_string_xmm(&self) -> &'static str {
+        match self {
+            Self::Xmm0 => "xmm0",
+            Self::Xmm1 => "xmm1",
+            Self::Xmm2 => "xmm2",
+            Self::Xmm3 => "xmm3",
+            Self::Xmm4 => "xmm4",
+            Self::Xmm5 => "xmm5",
+            Self::Xmm6 => "xmm6",
+            Self::Xmm7 => "xmm7",
+            Self::Xmm8 => "xmm8",
+            Self::Xmm9 => "xmm9",
+            Self::Xmm10 => "xmm10",
+            Self::Xmm11 => "xmm11",
+            Self::Xmm12 => "xmm12",
+            Self::Xmm13 => "xmm13",
+            Self::Xmm14 => "xmm14",
+            Self::Xmm15 => "xmm15",
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn to_string_i64(&self) -> &'static str {
+        match self {
+            Self::Rax => "rax",
+            Self::Rcx => "rcx",
+            Self::Rdx => "rdx",
+            Self::Rdi => "rdi",
+            Self::Rsi => "rsi",
+            Self::R8 => "r8",
+            Self::R9 => "r9",
+            Self::R10 => "r10",
+            Self::R11 => "r11",
+            Self::Rbx => "rbx",
+            Self::R12 => "r12",
+            Self::R13 => "r13",
+            Self::R14 => "r14",
+            Self::R15 => "r15",
+            _ => self.to_string_xmm(),
+        }
+    }
+
+    pub fn to_string_i32(&self) -> &'static str {
+        match self {
+            Self::Rax => "eax",
+            Self::Rcx => "ecx",
+            Self::Rdx => "edx",
+            Self::Rdi => "edi",
+            Self::Rsi => "esi",
+            Self::R8 => "r8d",
+            Self::R9 => "r9d",
+            Self::R10 => "r10d",
+            Self::R11 => "r11d",
+            Self::Rbx => "ebx",
+            Self::R12 => "r12d",
+            Self::R13 => "r13d",
+            Self::R14 => "r14d",
+            Self::R15 => "r15d",
+            _ => self.to_string_xmm(),
+        }
+    }
+
+    pub fn to_string_i16(&self) -> &'static str {
+        match self {
+            Self::Rax => "ax",
+            Self::Rcx => "cx",
+            Self::Rdx => "dx",
+            Self::Rdi => "di",
+            Self::Rsi => "si",
+            Self::R8 => "r8w",
+            Self::R9 => "r9w",
+            Self::R10 => "r10w",
+            Self::R11 => "r11w",
+            Self::Rbx => "bx",
+            Self::R12 => "r12w",
+            Self::R13 => "r13w",
+            Self::R14 => "r14w",
+            Self::R15 => "r15w",
+            _ => self.to_string_xmm(),
+        }
+    }
+
+    pub fn to_string_i8(&self) -> &'static str {
+        match self {
+            Self::Rax => "al",
+            Self::Rcx => "cl",
+            Self::Rdx => "dl",
+            Self::Rdi => "dil",
+            Self::Rsi => "sil",
+            Self::R8 => "r8b",
+            Self::R9 => "r9b",
+            Self::R10 => "r10b",
+            Self::R11 => "r11b",
+            Self::Rbx => "bl",
+            Self::R12 => "r12b",
+            Self::R13 => "r13b",
+            Self::R14 => "r14b",
+            Self::R15 => "r15b",
+            _ => self.to_string_xmm(),
+        }
+    }
+}
+
+impl Display for Register {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match f.precision() {
+            _ if self.is_float() => write!(f, "{}", self.to_string_xmm())?,
+            Some(8) => write!(f, "{}", self.to_string_i8())?,
+            Some(16) => write!(f, "{}", self.to_string_i16())?,
+            Some(32) => write!(f, "{}", self.to_string_i32())?,
+            Some(64) => write!(f, "{}", self.to_string_i64())?,
+            Some(s) => log::error!("Unsupported precision {}", s),
+            None => write!(f, "{}", self.to_string())?,
+        }
+        Ok(())
+    }
+}
+
+impl Into<usize> for Register {
+    fn into(self) -> usize {
+        self as usize
+    }
+}
+
+impl Into<u32> for Register {
+    fn into(self) -> u32 {
+        self as u32
+    }
+}
+
+impl RegisterInterface for Register {
+    const REG_COUNT: usize = REG_COUNT;
+    const REG_LOOKUP: &'static [Self] = &REG_LOOKUP;
+    const REG_DEFAULT: Self = Register::Rax;
+    const REG_DEFAULT_CLASS: RegisterClass<Self> = REG_CLASS_IREG;
+    const CALL_REGS: &'static [RegisterClass<Self>] = CALL_REGS;
+
+    fn size_class(size: IRSize) -> RegisterClass<Self> {
+        match size {
+            IRSize::F32 | IRSize::F64 => REG_CLASS_FREG,
+            _ => REG_CLASS_IREG,
+        }
+    }
+}
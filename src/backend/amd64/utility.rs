@@ -16,4 +16,12 @@ impl BackendAMD64 {
             }
         }
     }
+
+    // Only matches instructions that operate on an aggregate
+    pub fn aggregate(&self, index: u32) -> u16 {
+        match self.instructions[index as usize].get_size() {
+            IRSize::B(_) => 0,
+            _ => 0xfff,
+        }
+    }
 }
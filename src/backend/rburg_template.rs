@@ -62,8 +62,8 @@ macro_rules! emit_asm {
             }
         }
 
-        fn emit_asm(&mut self, strings: &Vec<String>) -> String {
-            let mut result = self.emit_prologue();
+        fn emit_asm(&mut self, strings: &Vec<Vec<u8>>, linkage: IRLinkage) -> String {
+            let mut result = self.emit_prologue(linkage);
             for instruction in 0..self.instructions.len() {
                 for modification in &self.reg_relocations[instruction] {
                     if !modification.after()
@@ -202,7 +202,7 @@ macro_rules! generate {
             self.fix_stack_size(old_stack_size);
 
             log::info!("Starting assembly generation");
-            let assembly = self.emit_asm(&function.strings);
+            let assembly = self.emit_asm(&function.strings, function.linkage);
             log::info!("Assembly:\n{}", assembly);
             assembly
         }
@@ -212,12 +212,13 @@ macro_rules! generate {
         fn generate_globals(&mut self, globals: &Vec<IRGlobal>) -> String {
             let mut result = String::new();
             for global in globals {
-                if global.function {
-                    result.push_str(&self.emit_function_declaration(&global.name));
-                } else if let Some(value) = global.value {
-                    result.push_str(&self.emit_global_definition(&global.name, value, &global.size,global.count));
+                result.push_str(&self.emit_linkage(&global.name, global.linkage));
+                if global.linkage == IRLinkage::Import {
+                    continue;
+                } else if let Some(values) = &global.value {
+                    result.push_str(&self.emit_global_definition(&global.name, values, &global.size,global.count,global.read_only));
                 } else {
-                    result.push_str(&self.emit_common(&global.name, &global.size,global.count));
+                    result.push_str(&self.emit_common(&global.name, &global.size,global.count,global.read_only));
                 }
             }
             result
@@ -314,11 +315,23 @@ macro_rules! register_backend {
             fn get_arguments<'a>(&'a self) -> &'a Vec<Option<u32>> {
                 &self.arguments.arguments
             }
+
+            fn get_argument_classes(&self) -> Vec<RegisterClass<Self::RegisterType>> {
+                self.get_call_regs(&self.arguments.sizes)
+                    .into_iter()
+                    .cloned()
+                    .collect()
+            }
             fn get_vreg_count(&self) -> u32 {
                 self.vreg_count
             }
 
             fn get_vreg_size(&self, vreg:u32) -> IRSize {
+                // Arguments are not defined by an instruction
+                let argument = self.arguments.arguments.iter().position(|&arg| arg == Some(vreg));
+                if let Some(argument) = argument {
+                    return self.arguments.sizes[argument];
+                }
                 let location = self.definition_index[vreg as usize];
                 BackendAMD64::get_vreg_size(self, location, vreg)
             }
@@ -362,7 +375,10 @@ macro_rules! register_backend {
             fn is_jump(&self, index: usize) -> bool {
                 matches!(
                     &self.instructions[index],
-                    IRInstruction::Jcc(..) | IRInstruction::Jnc(..) | IRInstruction::Jmp(..),
+                    IRInstruction::Jcc(..)
+                        | IRInstruction::Jnc(..)
+                        | IRInstruction::Jmp(..)
+                        | IRInstruction::JmpTable(..),
                 )
             }
         }
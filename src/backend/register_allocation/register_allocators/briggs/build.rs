@@ -36,6 +36,15 @@ pub fn build<R: RegisterInterface, B: RegisterBackend<RegisterType = R>>(
         numbers.vreg2live.clone(),
         numbers.length,
     );
+
+    // A live range can only be assigned a register that fits the size of its value
+    for live_range in R::REG_COUNT..numbers.length {
+        let vreg = numbers.live_ranges[live_range].vregs[0];
+        let class = R::size_class(backend.get_vreg_size(vreg));
+        for reg in !class {
+            graph.let_interfere(live_range as u32, reg.into());
+        }
+    }
     //log::trace!("Copies:{:?}", numbers.copies);
 
     build_first_iteration(backend, ins_info, cfg, &numbers, &mut graph, spill_code);
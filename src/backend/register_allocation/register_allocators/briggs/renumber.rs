@@ -177,6 +177,7 @@ pub(super) fn renumber<R: RegisterInterface, B: RegisterBackend<RegisterType = R
     }
 
     let mut index = 0;
+    let classes = backend.get_argument_classes();
     for arg in backend.get_arguments() {
         if let Some(arg) = *arg {
             let live_range = live_ranges.len() as u32;
@@ -185,7 +186,7 @@ pub(super) fn renumber<R: RegisterInterface, B: RegisterBackend<RegisterType = R
                 live_ranges.push(LiveRange::new(arg, range.clone()));
             }
 
-            let source = R::CALL_REGS[index].is_target().unwrap();
+            let source = classes[index].is_target().unwrap();
             let source: usize = source.into();
 
             let copy = VregCopy::ArgumentCopy {
@@ -29,10 +29,11 @@ impl<R: RegisterInterface, B: RegisterBackend<RegisterType = R>> RegisterAllocat
         let mut used_registers = vec![false; R::REG_COUNT];
 
         let mut index = 0;
+        let classes = backend.get_argument_classes();
         for arg in backend.get_arguments() {
             if let Some(arg) = arg {
                 assignments.allocation[*arg as usize]
-                    .start(*try_allocate2(&R::CALL_REGS[index]).unwrap(), 0);
+                    .start(*try_allocate2(&classes[index]).unwrap(), 0);
                 index += 1;
             }
         }
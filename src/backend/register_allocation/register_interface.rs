@@ -26,6 +26,9 @@ where
     const REG_DEFAULT: Self;
     const REG_DEFAULT_CLASS: RegisterClass<Self>;
     const CALL_REGS: &'static [RegisterClass<Self>];
+
+    // The class of registers that can hold a value of the given size
+    fn size_class(size: IRSize) -> RegisterClass<Self>;
 }
 
 pub trait RegisterBackend {
@@ -40,6 +43,7 @@ pub trait RegisterBackend {
     fn get_instructions<'a>(&'a self) -> &Vec<IRInstruction>;
     fn get_rule(&self, index: usize) -> u16;
     fn get_arguments<'a>(&'a self) -> &'a Vec<Option<u32>>;
+    fn get_argument_classes(&self) -> Vec<RegisterClass<Self::RegisterType>>;
     fn get_function_length(&self) -> usize;
     fn get_vreg_count(&self) -> u32;
     fn get_vreg_size(&self, vreg: u32) -> IRSize;
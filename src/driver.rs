@@ -1,225 +1,235 @@
-use std::ffi::OsStr;
-use std::path::Path;
-use std::process::Command;
-
-use crate::compiler;
-use crate::options::{OptionStage, Options};
-
-#[derive(PartialEq, PartialOrd, Clone, Copy, Debug)]
-pub enum Stage {
-    Exe,
-    Obj,
-    Asm,
-    Ppc,
-    C,
-}
-
-impl From<OptionStage> for Stage {
-    fn from(stage: OptionStage) -> Self {
-        if stage.ppc {
-            Stage::Ppc
-        } else if stage.asm {
-            Stage::Asm
-        } else if stage.obj {
-            Stage::Obj
-        } else {
-            Stage::Exe
-        }
-    }
-}
-
-/// Derive the starting stage from the filename
-fn filename2stage(filename: &str) -> Stage {
-    let extension = Path::new(filename)
-        .extension()
-        .and_then(OsStr::to_str)
-        .unwrap_or("");
-    match extension {
-        "c" => Stage::C,
-        "ppc" => Stage::Ppc,
-        "s" | "asm" => Stage::Asm,
-        _ => Stage::Obj,
-    }
-}
-
-// Return either the current filename or the last_filename
-// Depending if we're in the last stage
-fn new_or_final<'a>(
-    filename: &'a String,
-    last_filename: &'a String,
-    last_stage: Stage,
-    stage: Stage,
-) -> &'a String {
-    if stage == last_stage {
-        last_filename
-    } else {
-        filename
-    }
-}
-
-// This function calls all seperate sub-components with the correct parameters being
-// - Preprocessor
-// - Compiler
-// - Assembler
-// - Linking
-pub fn drive(options: Options) -> Result<(), ()> {
-    log::info!("driver started");
-    let last_stage = options.last_stage.clone().into();
-    let last_filename = options.output.clone();
-
-    if options.input.len() > 1 && last_stage != Stage::Exe {
-        log::error!("Multiple inputs must lead to an executable file");
-        return Err(());
-    }
-
-    let mut link_files = Vec::new();
-
-    for filename in options.input.clone() {
-        let file_stem = Path::new(&filename)
-            .file_stem()
-            .and_then(OsStr::to_str)
-            .unwrap_or(&filename)
-            .chars()
-            .map(|c| match c {
-                '\\' => '/',
-                _ => c,
-            })
-            .collect::<String>();
-
-        let begin_stage = filename2stage(&filename);
-
-        let parent = Path::new(&filename)
-            .parent()
-            .expect("")
-            .to_str()
-            .expect("msg");
-
-        let parent = parent
-            .chars()
-            .map(|c| match c {
-                '\\' => '/',
-                _ => c,
-            })
-            .collect::<String>();
-
-        let temp_directory = option_env!("UTCC_TEMP_DIR").unwrap_or(&parent);
-
-        log::debug!("Going from {:?} to {:?}", begin_stage, last_stage);
-        log::debug!("file_stem {}, parent {}", file_stem, parent);
-        let mut next_filename = filename.clone();
-
-        if begin_stage == Stage::C {
-            // Invoke preprocessor
-
-            let preprocess_filename = next_filename;
-            let compiler_filename = String::from(temp_directory) + "/" + &file_stem + ".ppc";
-            let compiler_filename =
-                new_or_final(&compiler_filename, &last_filename, last_stage, Stage::C);
-            next_filename = compiler_filename.clone();
-
-            log::info!("Preprocessor started");
-
-            let include_directory = format!("{}/include/", env!("CARGO_MANIFEST_DIR"));
-            let include_directory = option_env!("UTCC_INCLUDE_DIR").unwrap_or(&include_directory);
-            let include_directory = format!("-I{}", include_directory);
-            let target_directory = format!("{}/x86-64/", include_directory);
-
-            let output = Command::new("cpp")
-                .args([
-                    "-nostdinc",
-                    &include_directory,
-                    &target_directory,
-                    "-o",
-                    &compiler_filename,
-                    &preprocess_filename,
-                ])
-                .output()
-                .expect("failed to run assembler");
-
-            log::info!(
-                "status {}\nstdout: {}\nstderr: {}",
-                output.status,
-                String::from_utf8(output.stdout).unwrap(),
-                String::from_utf8(output.stderr).unwrap()
-            );
-            if !output.status.success() {
-                return Err(());
-            }
-        }
-        if begin_stage >= Stage::Ppc && last_stage < Stage::Ppc {
-            // Invoke compiler
-            let compiler_filename = next_filename;
-            let assembler_filename = String::from(temp_directory) + "/" + &file_stem + ".s";
-            let assembler_filename =
-                new_or_final(&assembler_filename, &last_filename, last_stage, Stage::Ppc);
-            next_filename = assembler_filename.clone();
-
-            log::info!(
-                "Compiler started -o {} {}",
-                assembler_filename,
-                compiler_filename
-            );
-
-            compiler::compile(compiler_filename, assembler_filename.clone(), &options)
-                .map_err(|_| ())?;
-            log::info!("Compiler finished");
-        }
-        if begin_stage >= Stage::Asm && last_stage < Stage::Asm {
-            // Invoke assembler
-            let assembler_filename = next_filename;
-            if parent.contains(":/") {}
-            let linker_filename = String::from(temp_directory) + "/" + &file_stem + ".o";
-            let linker_filename =
-                new_or_final(&linker_filename, &last_filename, last_stage, Stage::Asm);
-            next_filename = linker_filename.clone();
-
-            log::info!(
-                "Assembler started -o {} {}",
-                linker_filename,
-                assembler_filename
-            );
-
-            let output = Command::new("nasm")
-                .args(["-felf64", "-o", &linker_filename, &assembler_filename])
-                .output()
-                .expect("failed to run assembler");
-
-            log::info!(
-                "status {}\nstdout: {}\nstderr: {}",
-                output.status,
-                String::from_utf8(output.stdout).unwrap(),
-                String::from_utf8(output.stderr).unwrap()
-            );
-            if !output.status.success() {
-                return Err(());
-            }
-        }
-        link_files.push(next_filename);
-    }
-
-    if last_stage < Stage::Obj {
-        // Invoke linker
-        let filenames: Vec<&str> = link_files.iter().map(|s| &s as &str).collect();
-        let result = last_filename.clone();
-
-        log::info!("Linker started -o {} {:?}", result, link_files);
-
-        let output = Command::new("cc")
-            .args(["-m64", "-fPIC"])
-            .args(["-o", &result])
-            .args(&filenames)
-            .output()
-            .expect("failed to run linker");
-
-        log::info!(
-            "status {}\nstdout: {}\nstderr: {}",
-            output.status,
-            String::from_utf8(output.stdout).unwrap(),
-            String::from_utf8(output.stderr).unwrap()
-        );
-        if !output.status.success() {
-            return Err(());
-        }
-    }
-    Ok(())
-}
+use std::ffi::OsStr;
+use std::path::Path;
+use std::process::Command;
+
+use crate::compiler;
+use crate::preprocessor::{self, Preprocessor};
+use crate::options::{OptionStage, Options};
+
+#[derive(PartialEq, PartialOrd, Clone, Copy, Debug)]
+pub enum Stage {
+    Exe,
+    Obj,
+    Asm,
+    Ppc,
+    C,
+}
+
+impl From<OptionStage> for Stage {
+    fn from(stage: OptionStage) -> Self {
+        if stage.ppc {
+            Stage::Ppc
+        } else if stage.asm {
+            Stage::Asm
+        } else if stage.obj {
+            Stage::Obj
+        } else {
+            Stage::Exe
+        }
+    }
+}
+
+/// Derive the starting stage from the filename
+fn filename2stage(filename: &str) -> Stage {
+    let extension = Path::new(filename)
+        .extension()
+        .and_then(OsStr::to_str)
+        .unwrap_or("");
+    match extension {
+        "c" => Stage::C,
+        "ppc" => Stage::Ppc,
+        "s" | "asm" => Stage::Asm,
+        _ => Stage::Obj,
+    }
+}
+
+// Return either the current filename or the last_filename
+// Depending if we're in the last stage
+fn new_or_final<'a>(
+    filename: &'a String,
+    last_filename: &'a String,
+    last_stage: Stage,
+    stage: Stage,
+) -> &'a String {
+    if stage == last_stage {
+        last_filename
+    } else {
+        filename
+    }
+}
+
+// This function calls all seperate sub-components with the correct parameters being
+// - Preprocessor
+// - Compiler
+// - Assembler
+// - Linking
+pub fn drive(options: Options) -> Result<(), ()> {
+    log::info!("driver started");
+    let last_stage = options.last_stage.clone().into();
+    let last_filename = options.output.clone();
+
+    if options.input.len() > 1 && last_stage != Stage::Exe {
+        log::error!("Multiple inputs must lead to an executable file");
+        return Err(());
+    }
+
+    let mut link_files = Vec::new();
+
+    for filename in options.input.clone() {
+        let file_stem = Path::new(&filename)
+            .file_stem()
+            .and_then(OsStr::to_str)
+            .unwrap_or(&filename)
+            .chars()
+            .map(|c| match c {
+                '\\' => '/',
+                _ => c,
+            })
+            .collect::<String>();
+
+        let begin_stage = filename2stage(&filename);
+
+        let parent = Path::new(&filename)
+            .parent()
+            .expect("")
+            .to_str()
+            .expect("msg");
+
+        let parent = parent
+            .chars()
+            .map(|c| match c {
+                '\\' => '/',
+                _ => c,
+            })
+            .collect::<String>();
+
+        let temp_directory = option_env!("UTCC_TEMP_DIR").unwrap_or(&parent);
+
+        log::debug!("Going from {:?} to {:?}", begin_stage, last_stage);
+        log::debug!("file_stem {}, parent {}", file_stem, parent);
+        let mut next_filename = filename.clone();
+
+        if begin_stage == Stage::C {
+            // Invoke preprocessor
+            let preprocess_filename = next_filename;
+
+            log::info!("Preprocessor started");
+
+            let include_directory = format!("{}/include/", env!("CARGO_MANIFEST_DIR"));
+            let include_directory = option_env!("UTCC_INCLUDE_DIR").unwrap_or(&include_directory);
+            let target_directory = format!("{}/x86-64/", include_directory);
+
+            let mut include_directories = options.include_directories.clone();
+            include_directories.push(include_directory.to_string());
+            include_directories.push(target_directory);
+
+            let mut preprocessor = Preprocessor::new(include_directories);
+            let (tokens, preprocessor_errors) = preprocessor.preprocess(&preprocess_filename);
+            if preprocessor_errors.is_err() {
+                log::info!("Exited due to errors in the preprocessor");
+                return Err(());
+            }
+            log::info!("Preprocessor finished");
+
+            if last_stage == Stage::Ppc {
+                next_filename = last_filename.clone();
+                compiler::write(last_filename.clone(), preprocessor::to_text(&tokens)).map_err(
+                    |err| {
+                        eprintln!("{}", err);
+                    },
+                )?;
+            } else {
+                // Invoke compiler directly on the preprocessed tokens
+                let assembler_filename = String::from(temp_directory) + "/" + &file_stem + ".s";
+                let assembler_filename =
+                    new_or_final(&assembler_filename, &last_filename, last_stage, Stage::Ppc);
+                next_filename = assembler_filename.clone();
+
+                log::info!(
+                    "Compiler started -o {} {}",
+                    assembler_filename,
+                    preprocess_filename
+                );
+
+                compiler::compile_tokens(tokens, assembler_filename.clone(), &options)
+                    .map_err(|_| ())?;
+                log::info!("Compiler finished");
+            }
+        }
+        if begin_stage == Stage::Ppc && last_stage < Stage::Ppc {
+            // Invoke compiler
+            let compiler_filename = next_filename;
+            let assembler_filename = String::from(temp_directory) + "/" + &file_stem + ".s";
+            let assembler_filename =
+                new_or_final(&assembler_filename, &last_filename, last_stage, Stage::Ppc);
+            next_filename = assembler_filename.clone();
+
+            log::info!(
+                "Compiler started -o {} {}",
+                assembler_filename,
+                compiler_filename
+            );
+
+            compiler::compile(compiler_filename, assembler_filename.clone(), &options)
+                .map_err(|_| ())?;
+            log::info!("Compiler finished");
+        }
+        if begin_stage >= Stage::Asm && last_stage < Stage::Asm {
+            // Invoke assembler
+            let assembler_filename = next_filename;
+            if parent.contains(":/") {}
+            let linker_filename = String::from(temp_directory) + "/" + &file_stem + ".o";
+            let linker_filename =
+                new_or_final(&linker_filename, &last_filename, last_stage, Stage::Asm);
+            next_filename = linker_filename.clone();
+
+            log::info!(
+                "Assembler started -o {} {}",
+                linker_filename,
+                assembler_filename
+            );
+
+            let output = Command::new("nasm")
+                .args(["-felf64", "-o", &linker_filename, &assembler_filename])
+                .output()
+                .expect("failed to run assembler");
+
+            log::info!(
+                "status {}\nstdout: {}\nstderr: {}",
+                output.status,
+                String::from_utf8(output.stdout).unwrap(),
+                String::from_utf8(output.stderr).unwrap()
+            );
+            if !output.status.success() {
+                return Err(());
+            }
+        }
+        link_files.push(next_filename);
+    }
+
+    if last_stage < Stage::Obj {
+        // Invoke linker
+        let filenames: Vec<&str> = link_files.iter().map(|s| &s as &str).collect();
+        let result = last_filename.clone();
+
+        log::info!("Linker started -o {} {:?}", result, link_files);
+
+        let output = Command::new("cc")
+            .args(["-m64", "-fPIC"])
+            .args(["-o", &result])
+            .args(&filenames)
+            .output()
+            .expect("failed to run linker");
+
+        log::info!(
+            "status {}\nstdout: {}\nstderr: {}",
+            output.status,
+            String::from_utf8(output.stdout).unwrap(),
+            String::from_utf8(output.stderr).unwrap()
+        );
+        if !output.status.success() {
+            return Err(());
+        }
+    }
+    Ok(())
+}
@@ -1,297 +1,738 @@
-use crate::{
-    backend::{Backend, TypeInfo, TypeInfoTable},
-    ir::*,
-    options::OptimizationSettings,
-    parser::{
-        ast::{BinaryExpressionType, SizeofType},
-        Type, TypeNode,
-    },
-};
-
-use super::jump_eval::JumpType;
-
-pub struct EvaluationContext<'a> {
-    pub vreg_counter: u32,
-    pub label_counter: u32,
-    pub variables: Vec<IRVariable>,
-    pub strings: Vec<String>,
-    pub unfixed_continue: Vec<(usize, u32)>,
-    pub unfixed_break: Vec<(usize, u32)>,
-    pub loop_depth: u32,
-    pub struct_size_table: &'a Vec<TypeInfo>,
-    pub struct_offset_table: &'a Vec<Vec<usize>>,
-    pub backend: &'a dyn Backend,
-    pub type_info: TypeInfoTable,
-
-    pub optimization_settings: &'a OptimizationSettings,
-}
-
-impl<'a> EvaluationContext<'a> {
-    pub fn next_vreg(&mut self) -> u32 {
-        let vreg = self.vreg_counter;
-        self.vreg_counter += 1;
-        vreg
-    }
-    pub fn next_label(&mut self) -> u32 {
-        let label = self.label_counter;
-        self.label_counter += 1;
-        label
-    }
-    pub fn add_string(&mut self, string: &String) -> u32 {
-        let number = self.strings.len() as u32;
-        self.strings.push(string.clone());
-        number
-    }
-}
-
-impl<'a> EvaluationContext<'a> {
-    pub fn insert_place_holder_jump(&mut self, result: &mut Vec<IRInstruction>) -> (usize, u32) {
-        let index = result.len();
-        result.push(IRInstruction::Jmp(0));
-
-        let label = self.insert_label(result);
-
-        (index, label)
-    }
-
-    pub fn insert_place_holder_jump_phi(
-        &mut self,
-        result: &mut Vec<IRInstruction>,
-        phi: Box<IRPhi>,
-    ) -> (usize, u32) {
-        let index = result.len();
-        result.push(IRInstruction::Jmp(0));
-
-        let label = self.insert_phi_label(result, phi);
-
-        (index, label)
-    }
-
-    pub fn insert_fall_through(&mut self, result: &mut Vec<IRInstruction>) -> u32 {
-        let label = self.label_counter;
-        result.push(IRInstruction::Jmp(label));
-        self.insert_label(result)
-    }
-
-    pub fn insert_label(&mut self, result: &mut Vec<IRInstruction>) -> u32 {
-        let label = self.next_label();
-        result.push(IRInstruction::Label(None, label));
-
-        label
-    }
-
-    pub fn insert_phi_label(&mut self, result: &mut Vec<IRInstruction>, phi: Box<IRPhi>) -> u32 {
-        let label = self.next_label();
-        result.push(IRInstruction::Label(Some(phi), label));
-
-        label
-    }
-
-    pub fn get_current_label(&self) -> u32 {
-        let label = self.label_counter - 1;
-        label
-    }
-}
-
-impl<'a> EvaluationContext<'a> {
-    pub fn enter_loop(&mut self) {
-        self.loop_depth += 1;
-    }
-
-    pub fn add_break(&mut self, index: usize) {
-        self.unfixed_break.push((index, self.loop_depth))
-    }
-
-    pub fn add_continue(&mut self, index: usize) {
-        self.unfixed_continue.push((index, self.loop_depth))
-    }
-
-    pub fn fix_jumps(
-        &mut self,
-        result: &mut Vec<IRInstruction>,
-        jumps: &[(usize, u32, IRSize, JumpType)],
-        label: u32,
-    ) {
-        for &(index, vreg, size, jump_type) in jumps {
-            result[index] = match jump_type {
-                JumpType::Jcc => IRInstruction::Jcc(size, vreg, label),
-                JumpType::Jnc => IRInstruction::Jnc(size, vreg, label),
-            }
-        }
-    }
-
-    pub fn fix_break_continue(
-        &mut self,
-        result: &mut Vec<IRInstruction>,
-        break_label: u32,
-        coninue_label: u32,
-    ) {
-        self.unfixed_break = self
-            .unfixed_break
-            .iter()
-            .filter_map(|(i, depth)| {
-                if *depth == self.loop_depth {
-                    result[*i] = IRInstruction::Jmp(break_label);
-                    None
-                } else {
-                    Some((*i, *depth))
-                }
-            })
-            .collect();
-
-        self.unfixed_continue = self
-            .unfixed_continue
-            .iter()
-            .filter_map(|(i, depth)| {
-                if *depth == self.loop_depth {
-                    result[*i] = IRInstruction::Jmp(coninue_label);
-                    None
-                } else {
-                    Some((*i, *depth))
-                }
-            })
-            .collect();
-
-        self.loop_depth -= 1;
-    }
-}
-
-impl TypeInfoTable {
-    pub fn get_irsize(&self, typ: &TypeNode, struct_info: &Vec<TypeInfo>) -> IRSize {
-        use TypeNode::*;
-        match typ {
-            Char => self.char.irsize,
-            Short => self.short.irsize,
-            Int => self.int.irsize,
-            Long => self.long.irsize,
-            Pointer => IRSize::P,
-            Struct(index) => IRSize::B(struct_info[*index].size as u16),
-            Void => IRSize::V,
-            _ => unreachable!(),
-        }
-    }
-
-    fn get_sizeof(&self, typ: &TypeNode, struct_info: &Vec<TypeInfo>) -> u32 {
-        use TypeNode::*;
-        let res = match typ {
-            Char => self.char.size,
-            Short => self.short.size,
-            Int => self.int.size,
-            Long => self.long.size,
-            Pointer => self.pointer.size,
-            Struct(index) => struct_info[*index].size,
-            Void => 1,
-            _ => unreachable!(),
-        };
-        res as u32
-    }
-
-    fn get_size2(&self, typ: &Type, struct_info: &Vec<TypeInfo>) -> IRSize {
-        self.get_irsize(&typ.nodes[0], struct_info)
-    }
-
-    pub fn eval_sizeof(&self, typ: &SizeofType, struct_info: &Vec<TypeInfo>) -> u32 {
-        let ast_type = match typ {
-            SizeofType::Type(_, typ) => typ,
-            SizeofType::Expression(exp) => &exp.ast_type,
-        };
-        self.sizeof(ast_type, struct_info)
-    }
-
-    pub fn sizeof_element(&self, typ: &Type, struct_info: &Vec<TypeInfo>) -> u32 {
-        if typ.is_array() {
-            let (array_type, _) = typ.deconstruct();
-            self.get_sizeof(&array_type, struct_info)
-        } else {
-            self.get_sizeof(&typ.nodes[0], struct_info)
-        }
-    }
-
-    pub fn sizeof(&self, typ: &Type, struct_info: &Vec<TypeInfo>) -> u32 {
-        if typ.is_array() {
-            let (array_type, array_size) = typ.deconstruct();
-            self.get_sizeof(&array_type, struct_info) * (array_size as u32)
-        } else {
-            self.get_sizeof(&typ.nodes[0], struct_info)
-        }
-    }
-
-    pub fn int_ptr(&self, signed: bool) -> IRSize {
-        assert!(signed); //Unsigned integers are currently unsupported
-        match self.pointer.size {
-            8 => IRSize::S64,
-            4 => IRSize::S32,
-            2 => IRSize::S16,
-            _ => unreachable!(),
-        }
-    }
-
-    pub fn size_t(&self) -> Type {
-        vec![self.size_t.clone()].into()
-    }
-}
-
-pub trait EvaluateSize {
-    fn type_info<'a>(&'a self) -> &'a TypeInfoTable;
-    fn struct_size_table<'a>(&'a self) -> &'a Vec<TypeInfo>;
-
-    fn eval_sizeof(&self, typ: &SizeofType) -> u32 {
-        self.type_info().eval_sizeof(typ, &self.struct_size_table())
-    }
-    fn get_size(&self, typ: &Type) -> IRSize {
-        self.type_info().get_size2(typ, &self.struct_size_table())
-    }
-    fn sizeof(&self, typ: &Type) -> u32 {
-        self.type_info().sizeof(typ, &self.struct_size_table())
-    }
-    fn sizeof_element(&self, typ: &Type) -> u32 {
-        self.type_info()
-            .sizeof_element(typ, &self.struct_size_table())
-    }
-    fn int_ptr(&self, signed: bool) -> IRSize {
-        self.type_info().int_ptr(signed)
-    }
-    fn size_t(&self) -> Type {
-        self.type_info().size_t()
-    }
-}
-
-impl<'b> EvaluateSize for EvaluationContext<'b> {
-    fn type_info(&self) -> &TypeInfoTable {
-        &self.type_info
-    }
-    fn struct_size_table<'a>(&'a self) -> &'a Vec<TypeInfo> {
-        &self.struct_size_table
-    }
-}
-
-impl<'a> EvaluationContext<'a> {
-    pub fn promote(
-        &mut self,
-        result: &mut Vec<IRInstruction>,
-        size: IRSize,
-        from: IRSize,
-        vreg: u32,
-    ) -> u32 {
-        if size == from {
-            return vreg;
-        }
-        let temp = self.next_vreg();
-        result.push(IRInstruction::Cvs(size, temp, from, vreg));
-        temp
-    }
-}
-
-impl BinaryExpressionType {
-    pub fn get_size(&self, context: &mut EvaluationContext, left: &Type, _right: &Type) -> IRSize {
-        use BinaryExpressionType::*;
-        let size = context.get_size(left);
-        match self {
-            Subtract => size,
-            Add => size,
-            Multiply | Divide | BinOr | BinAnd | Equal | Inequal | Less | LessEqual | Greater
-            | GreaterEqual => size,
-            _ => unreachable!(),
-        }
-    }
-}
+use crate::{
+    backend::{Backend, TypeInfo, TypeInfoTable},
+    ir::*,
+    options::OptimizationSettings,
+    parser::{
+        ast::{BinaryExpressionType, SizeofType},
+        r#type::{BitField, StructType},
+        Type, TypeNode,
+    },
+    semantic_analysis::type_promotion::TypePromotion,
+};
+
+use super::jump_eval::{JumpType, SwitchLabels};
+use std::collections::HashMap;
+
+pub struct EvaluationContext<'a> {
+    pub vreg_counter: u32,
+    pub label_counter: u32,
+    pub variables: Vec<IRVariable>,
+    pub strings: Vec<Vec<u8>>,
+    pub unfixed_continue: Vec<(usize, u32)>,
+    pub unfixed_break: Vec<(usize, u32)>,
+    pub loop_depth: u32,
+    pub switch_labels: Vec<SwitchLabels>,
+    pub labels: HashMap<String, u32>,
+    pub unfixed_goto: Vec<(usize, String)>,
+    pub globals: Vec<IRGlobal>,
+    pub temporaries: Vec<IRVariable>,
+    pub unfixed_temporaries: Vec<usize>,
+    pub return_pointer: Option<u32>,
+    pub struct_type_table: &'a Vec<StructType>,
+    pub struct_size_table: &'a Vec<TypeInfo>,
+    pub struct_offset_table: &'a Vec<Vec<usize>>,
+    pub backend: &'a dyn Backend,
+    pub type_info: TypeInfoTable,
+
+    pub optimization_settings: &'a OptimizationSettings,
+}
+
+impl<'a> EvaluationContext<'a> {
+    pub fn next_vreg(&mut self) -> u32 {
+        let vreg = self.vreg_counter;
+        self.vreg_counter += 1;
+        vreg
+    }
+    pub fn next_label(&mut self) -> u32 {
+        let label = self.label_counter;
+        self.label_counter += 1;
+        label
+    }
+    pub fn add_string(&mut self, string: &Vec<u8>) -> u32 {
+        let number = self.strings.len() as u32;
+        self.strings.push(string.clone());
+        number
+    }
+}
+
+impl<'a> EvaluationContext<'a> {
+    pub fn insert_place_holder_jump(&mut self, result: &mut Vec<IRInstruction>) -> (usize, u32) {
+        let index = result.len();
+        result.push(IRInstruction::Jmp(0));
+
+        let label = self.insert_label(result);
+
+        (index, label)
+    }
+
+    pub fn insert_place_holder_jump_phi(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        phi: Box<IRPhi>,
+    ) -> (usize, u32) {
+        let index = result.len();
+        result.push(IRInstruction::Jmp(0));
+
+        let label = self.insert_phi_label(result, phi);
+
+        (index, label)
+    }
+
+    pub fn insert_fall_through(&mut self, result: &mut Vec<IRInstruction>) -> u32 {
+        let label = self.label_counter;
+        result.push(IRInstruction::Jmp(label));
+        self.insert_label(result)
+    }
+
+    pub fn insert_label(&mut self, result: &mut Vec<IRInstruction>) -> u32 {
+        let label = self.next_label();
+        result.push(IRInstruction::Label(None, label));
+
+        label
+    }
+
+    pub fn insert_phi_label(&mut self, result: &mut Vec<IRInstruction>, phi: Box<IRPhi>) -> u32 {
+        let label = self.next_label();
+        result.push(IRInstruction::Label(Some(phi), label));
+
+        label
+    }
+
+    pub fn get_current_label(&self) -> u32 {
+        let label = self.label_counter - 1;
+        label
+    }
+}
+
+impl<'a> EvaluationContext<'a> {
+    pub fn enter_loop(&mut self) {
+        self.loop_depth += 1;
+    }
+
+    pub fn add_break(&mut self, index: usize) {
+        self.unfixed_break.push((index, self.loop_depth))
+    }
+
+    pub fn add_continue(&mut self, index: usize) {
+        self.unfixed_continue.push((index, self.loop_depth))
+    }
+
+    pub fn fix_jumps(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        jumps: &[(usize, u32, IRSize, JumpType)],
+        label: u32,
+    ) {
+        for &(index, vreg, size, jump_type) in jumps {
+            result[index] = match jump_type {
+                JumpType::Jcc => IRInstruction::Jcc(size, vreg, label),
+                JumpType::Jnc => IRInstruction::Jnc(size, vreg, label),
+            }
+        }
+    }
+
+    pub fn fix_break_continue(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        break_label: u32,
+        coninue_label: u32,
+    ) {
+        self.unfixed_break = self
+            .unfixed_break
+            .iter()
+            .filter_map(|(i, depth)| {
+                if *depth == self.loop_depth {
+                    result[*i] = IRInstruction::Jmp(break_label);
+                    None
+                } else {
+                    Some((*i, *depth))
+                }
+            })
+            .collect();
+
+        self.unfixed_continue = self
+            .unfixed_continue
+            .iter()
+            .filter_map(|(i, depth)| {
+                if *depth == self.loop_depth {
+                    result[*i] = IRInstruction::Jmp(coninue_label);
+                    None
+                } else {
+                    Some((*i, *depth))
+                }
+            })
+            .collect();
+
+        self.loop_depth -= 1;
+    }
+
+    // A switch statement is only a target for break, continue goes to the enclosing loop
+    pub fn fix_break(&mut self, result: &mut Vec<IRInstruction>, break_label: u32) {
+        let loop_depth = self.loop_depth;
+        self.unfixed_break.retain(|&(i, depth)| {
+            if depth == loop_depth {
+                result[i] = IRInstruction::Jmp(break_label);
+                false
+            } else {
+                true
+            }
+        });
+
+        for (_, depth) in &mut self.unfixed_continue {
+            if *depth == loop_depth {
+                *depth -= 1;
+            }
+        }
+
+        self.loop_depth -= 1;
+    }
+
+    // A goto to a label that has not been seen yet is fixed when the label is evaluated
+    pub fn add_goto(&mut self, result: &mut Vec<IRInstruction>, index: usize, label: &String) {
+        match self.labels.get(label) {
+            Some(&target) => result[index] = IRInstruction::Jmp(target),
+            None => self.unfixed_goto.push((index, label.clone())),
+        }
+    }
+
+    pub fn add_label(&mut self, result: &mut Vec<IRInstruction>, label: &String, target: u32) {
+        self.labels.insert(label.clone(), target);
+        self.unfixed_goto.retain(|(i, name)| {
+            if name == label {
+                result[*i] = IRInstruction::Jmp(target);
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+// Temporaries store aggregates that are not stored in a variable, like the result of a call
+// They are only numbered once all variables of the function are known
+impl<'a> EvaluationContext<'a> {
+    pub fn add_temporary(&mut self, size: IRSize) -> u32 {
+        let number = self.temporaries.len() as u32;
+        self.temporaries.push(IRVariable {
+            number,
+            size,
+            count: 1,
+        });
+        number
+    }
+
+    pub fn insert_temporary_address(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        temporary: u32,
+    ) -> u32 {
+        let addr = self.next_vreg();
+        self.unfixed_temporaries.push(result.len());
+        result.push(IRInstruction::AddrL(IRSize::P, addr, temporary as usize));
+        addr
+    }
+
+    // Places the temporaries after the variables of the function
+    pub fn fix_temporaries(&mut self, result: &mut Vec<IRInstruction>) {
+        let base = self.variables.len() as u32;
+        for index in self.unfixed_temporaries.drain(..) {
+            match &mut result[index] {
+                IRInstruction::AddrL(_, _, variable) => *variable += base as usize,
+                IRInstruction::Call(.., arguments) | IRInstruction::CallV(.., arguments) => {
+                    if let Some(variable) = &mut arguments.return_variable {
+                        *variable += base;
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+        for mut temporary in self.temporaries.drain(..) {
+            temporary.number += base;
+            self.variables.push(temporary);
+        }
+    }
+}
+
+// Aggregates are passed in registers if the backend allows it and in memory otherwise
+impl<'a> EvaluationContext<'a> {
+    // Gives the offset and size of every scalar value in a type
+    fn get_aggregate_fields(&self, typ: &Type, offset: usize, fields: &mut Vec<(usize, IRSize)>) {
+        let (element, count) = typ.deconstruct();
+        let element_size = self.sizeof_element(typ) as usize;
+        for i in 0..count {
+            let offset = offset + i * element_size;
+            if let TypeNode::Struct(index) = element {
+                let members = self.struct_type_table[index].members.iter().flatten();
+                for ((_, member), member_offset) in members.zip(&self.struct_offset_table[index]) {
+                    self.get_aggregate_fields(member, offset + member_offset, fields);
+                }
+            } else {
+                let size = self.type_info.get_irsize(&element, self.struct_size_table);
+                fields.push((offset, size));
+            }
+        }
+    }
+
+    // Gives the registers an aggregate is passed in, or None if it is passed in memory
+    pub fn get_aggregate_registers(&self, typ: &Type) -> Option<Vec<IRSize>> {
+        let mut fields = Vec::new();
+        self.get_aggregate_fields(typ, 0, &mut fields);
+        self.backend
+            .get_aggregate_registers(self.sizeof(typ) as usize, &fields)
+    }
+
+    // The size of the memory that is read or written by the registers of an aggregate
+    pub fn get_aggregate_size(&self, parts: &[IRSize]) -> IRSize {
+        let size: usize = parts.iter().map(|size| size.to_bit_width() / 8).sum();
+        IRSize::B(size as u16)
+    }
+
+    // Gives the sizes in which each argument is passed
+    // An aggregate is passed in memory if not all of its parts fit in registers
+    // Aggregates returned in memory get a pointer to the result as first argument
+    pub fn get_argument_sizes(&self, types: &[Type], return_pointer: bool) -> Vec<Vec<IRSize>> {
+        let mut sizes: Vec<Vec<IRSize>> = types
+            .iter()
+            .map(|typ| match typ.is_struct() {
+                true => self
+                    .get_aggregate_registers(typ)
+                    .unwrap_or_else(|| vec![self.get_size(typ)]),
+                false => vec![self.get_size(typ)],
+            })
+            .collect();
+        if return_pointer {
+            sizes.insert(0, vec![IRSize::P]);
+        }
+
+        loop {
+            let flat: Vec<IRSize> = sizes.iter().flatten().cloned().collect();
+            let in_registers = self.backend.get_arguments_in_registers(&flat);
+            let mut start = 0;
+            let split = sizes.iter().position(|parts| {
+                let registers = &in_registers[start..start + parts.len()];
+                start += parts.len();
+                registers.contains(&true) && registers.contains(&false)
+            });
+            match split {
+                Some(arg) => {
+                    sizes[arg] = vec![self.get_size(&types[arg - return_pointer as usize])]
+                }
+                None => return sizes,
+            }
+        }
+    }
+
+    // Aggregates are read in whole registers, so they are first copied if their size is smaller
+    pub fn pad_aggregate(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        parts: &[IRSize],
+        size: IRSize,
+        addr: u32,
+    ) -> u32 {
+        let padded_size = self.get_aggregate_size(parts);
+        if padded_size == size {
+            return addr;
+        }
+        let temporary = self.add_temporary(padded_size);
+        let padded = self.insert_temporary_address(result, temporary);
+        result.push(IRInstruction::Copy(size, addr, padded));
+        padded
+    }
+
+    pub fn load_parts(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        parts: &[IRSize],
+        addr: u32,
+    ) -> Vec<u32> {
+        let mut offset = 0;
+        let mut vregs = Vec::new();
+        for &size in parts {
+            let part = self.offset_address(result, addr, offset);
+            let vreg = self.next_vreg();
+            result.push(IRInstruction::Load(size, vreg, part, false));
+            vregs.push(vreg);
+            offset += size.to_bit_width() / 8;
+        }
+        vregs
+    }
+
+    pub fn store_parts(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        parts: &[IRSize],
+        vregs: &[u32],
+        addr: u32,
+    ) {
+        let mut offset = 0;
+        for (&size, &vreg) in parts.iter().zip(vregs) {
+            let part = self.offset_address(result, addr, offset);
+            result.push(IRInstruction::Store(size, vreg, part, false));
+            offset += size.to_bit_width() / 8;
+        }
+    }
+
+    pub(super) fn offset_address(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        addr: u32,
+        offset: usize,
+    ) -> u32 {
+        if offset == 0 {
+            return addr;
+        }
+        let constant = self.next_vreg();
+        let part = self.next_vreg();
+        result.push(IRInstruction::Imm(IRSize::P, constant, offset as i128));
+        result.push(IRInstruction::Add(IRSize::P, part, addr, constant));
+        part
+    }
+}
+
+impl TypeInfoTable {
+    pub fn get_irsize(&self, typ: &TypeNode, struct_info: &Vec<TypeInfo>) -> IRSize {
+        use TypeNode::*;
+        match typ {
+            Char | UChar => self.char.irsize,
+            Short | UShort => self.short.irsize,
+            Int | UInt => self.int.irsize,
+            Long | ULong => self.long.irsize,
+            Float => self.float.irsize,
+            Double => self.double.irsize,
+            Pointer => IRSize::P,
+            Struct(index) => IRSize::B(struct_info[*index].size as u16),
+            Void => IRSize::V,
+            _ => unreachable!(),
+        }
+    }
+
+    fn get_sizeof(&self, typ: &TypeNode, struct_info: &Vec<TypeInfo>) -> u32 {
+        use TypeNode::*;
+        let res = match typ {
+            Char | UChar => self.char.size,
+            Short | UShort => self.short.size,
+            Int | UInt => self.int.size,
+            Long | ULong => self.long.size,
+            Float => self.float.size,
+            Double => self.double.size,
+            Pointer => self.pointer.size,
+            Struct(index) => struct_info[*index].size,
+            Void => 1,
+            _ => unreachable!(),
+        };
+        res as u32
+    }
+
+    fn get_alignof(&self, typ: &TypeNode, struct_info: &Vec<TypeInfo>) -> u32 {
+        use TypeNode::*;
+        let res = match typ {
+            Char | UChar => self.char.align,
+            Short | UShort => self.short.align,
+            Int | UInt => self.int.align,
+            Long | ULong => self.long.align,
+            Float => self.float.align,
+            Double => self.double.align,
+            Pointer => self.pointer.align,
+            Struct(index) => struct_info[*index].align,
+            Void => 1,
+            _ => unreachable!(),
+        };
+        res as u32
+    }
+
+    fn get_size2(&self, typ: &Type, struct_info: &Vec<TypeInfo>) -> IRSize {
+        self.get_irsize(&typ.nodes[0], struct_info)
+    }
+
+    pub fn eval_sizeof(&self, typ: &SizeofType, struct_info: &Vec<TypeInfo>) -> u32 {
+        let ast_type = match typ {
+            SizeofType::Type(_, typ) => typ,
+            SizeofType::Expression(exp) => &exp.ast_type,
+        };
+        self.sizeof(ast_type, struct_info)
+    }
+
+    pub fn sizeof_element(&self, typ: &Type, struct_info: &Vec<TypeInfo>) -> u32 {
+        if typ.is_array() {
+            let (array_type, _) = typ.deconstruct();
+            self.get_sizeof(&array_type, struct_info)
+        } else {
+            self.get_sizeof(&typ.nodes[0], struct_info)
+        }
+    }
+
+    // Arrays are aligned like their elements
+    pub fn alignof(&self, typ: &Type, struct_info: &Vec<TypeInfo>) -> u32 {
+        let (element, _) = typ.deconstruct();
+        self.get_alignof(&element, struct_info)
+    }
+
+    pub fn sizeof(&self, typ: &Type, struct_info: &Vec<TypeInfo>) -> u32 {
+        if typ.is_array() {
+            let (array_type, array_size) = typ.deconstruct();
+            self.get_sizeof(&array_type, struct_info) * (array_size as u32)
+        } else {
+            self.get_sizeof(&typ.nodes[0], struct_info)
+        }
+    }
+
+    pub fn int_ptr(&self, _signed: bool) -> IRSize {
+        match self.pointer.size {
+            8 => IRSize::S64,
+            4 => IRSize::S32,
+            2 => IRSize::S16,
+            _ => unreachable!(),
+        }
+    }
+
+    pub fn size_t(&self) -> Type {
+        vec![self.size_t.clone()].into()
+    }
+
+    pub fn ptrdiff_t(&self) -> Type {
+        vec![self.ptrdiff_t.clone()].into()
+    }
+}
+
+pub trait EvaluateSize {
+    fn type_info<'a>(&'a self) -> &'a TypeInfoTable;
+    fn struct_size_table<'a>(&'a self) -> &'a Vec<TypeInfo>;
+
+    fn eval_sizeof(&self, typ: &SizeofType) -> u32 {
+        self.type_info().eval_sizeof(typ, &self.struct_size_table())
+    }
+    fn get_size(&self, typ: &Type) -> IRSize {
+        self.type_info().get_size2(typ, &self.struct_size_table())
+    }
+    fn sizeof(&self, typ: &Type) -> u32 {
+        self.type_info().sizeof(typ, &self.struct_size_table())
+    }
+    fn sizeof_element(&self, typ: &Type) -> u32 {
+        self.type_info()
+            .sizeof_element(typ, &self.struct_size_table())
+    }
+    fn int_ptr(&self, signed: bool) -> IRSize {
+        self.type_info().int_ptr(signed)
+    }
+    fn size_t(&self) -> Type {
+        self.type_info().size_t()
+    }
+}
+
+impl<'b> EvaluateSize for EvaluationContext<'b> {
+    fn type_info(&self) -> &TypeInfoTable {
+        &self.type_info
+    }
+    fn struct_size_table<'a>(&'a self) -> &'a Vec<TypeInfo> {
+        &self.struct_size_table
+    }
+}
+
+impl<'a> EvaluationContext<'a> {
+    pub fn promote(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        size: IRSize,
+        from: IRSize,
+        vreg: u32,
+    ) -> u32 {
+        if size == from {
+            return vreg;
+        }
+        if size.is_float() || from.is_float() {
+            return self.convert_float(result, size, from, false, vreg);
+        }
+        let temp = self.next_vreg();
+        result.push(IRInstruction::Cvs(size, temp, from, vreg));
+        temp
+    }
+
+    // Converts a value of type from to size
+    // Unsigned values are zero extended instead of sign extended
+    pub fn convert(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        size: IRSize,
+        from: &Type,
+        vreg: u32,
+    ) -> u32 {
+        let from_size = self.get_size(&from.array_promotion());
+        if size.is_float() || from_size.is_float() {
+            return self.convert_float(result, size, from_size, from.is_unsigned(), vreg);
+        }
+        if !from.is_unsigned() {
+            return self.promote(result, size, from_size, vreg);
+        }
+        if size == from_size {
+            return vreg;
+        }
+        let temp = self.next_vreg();
+        result.push(IRInstruction::Cvu(size, temp, from_size, vreg));
+        temp
+    }
+
+    // Conversions between integers and floating point values always go through a 64 bit integer
+    fn convert_float(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        size: IRSize,
+        from: IRSize,
+        unsigned: bool,
+        vreg: u32,
+    ) -> u32 {
+        if size == from {
+            return vreg;
+        }
+        let temp = self.next_vreg();
+        match (size.is_float(), from.is_float()) {
+            (true, true) => result.push(IRInstruction::Cvf(size, temp, from, vreg)),
+            (true, false) => {
+                let vreg = match (from, unsigned) {
+                    (IRSize::S64, _) => vreg,
+                    (_, false) => self.promote(result, IRSize::S64, from, vreg),
+                    (_, true) => {
+                        let wide = self.next_vreg();
+                        result.push(IRInstruction::Cvu(IRSize::S64, wide, from, vreg));
+                        wide
+                    }
+                };
+                // Unsigned values smaller than 64 bit fit in a signed 64 bit integer
+                if unsigned && from == IRSize::S64 {
+                    result.push(IRInstruction::Cvuf(size, temp, IRSize::S64, vreg));
+                } else {
+                    result.push(IRInstruction::Cvif(size, temp, IRSize::S64, vreg));
+                }
+            }
+            // Values out of range of a signed 64 bit integer are undefined behaviour,
+            // so the conversion can always handle the full unsigned range
+            (false, true) => {
+                result.push(IRInstruction::Cvfi(IRSize::S64, temp, from, vreg));
+                return self.promote(result, size, IRSize::S64, temp);
+            }
+            (false, false) => unreachable!(),
+        }
+        temp
+    }
+
+    // Floating point values cannot be tested directly, so they are compared with zero first
+    pub fn condition(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        size: IRSize,
+        vreg: u32,
+    ) -> (IRSize, u32) {
+        if !size.is_float() {
+            return (size, vreg);
+        }
+        let zero = self.next_vreg();
+        let cond = self.next_vreg();
+        result.push(IRInstruction::Imm(size, zero, 0));
+        result.push(IRInstruction::Ne(size, cond, vreg, zero));
+        (self.type_info.int.irsize, cond)
+    }
+}
+
+// Bit-fields are accessed through the storage unit of their declared type
+// The unit is worked on in the promoted size of that type, in which the bit-field is shifted
+impl<'a> EvaluationContext<'a> {
+    // Gives the value of a bit-field in the promoted size of its type
+    pub fn load_bit_field(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        typ: &Type,
+        bit_field: BitField,
+        addr: u32,
+        volatile: bool,
+    ) -> u32 {
+        let unit_size = self.get_size(typ);
+        let unit = self.next_vreg();
+        result.push(IRInstruction::Load(unit_size, unit, addr, volatile));
+        let size = self.get_size(&typ.promote());
+        let unit = self.convert(result, size, typ, unit);
+        self.extract_bit_field(result, typ, bit_field, unit)
+    }
+
+    // Replaces the bits of a bit-field in its unit with a value in the promoted size of its type
+    // Gives the new value of the bit-field, which only keeps the bits that fit in it
+    pub fn store_bit_field(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        typ: &Type,
+        bit_field: BitField,
+        addr: u32,
+        value: u32,
+        volatile: bool,
+    ) -> u32 {
+        let unit_size = self.get_size(typ);
+        let size = self.get_size(&typ.promote());
+        let unit = self.next_vreg();
+        result.push(IRInstruction::Load(unit_size, unit, addr, volatile));
+        let unit = self.convert(result, size, typ, unit);
+
+        // The mask is sign extended from the size, such that it is a valid constant of that size
+        let shift = 128 - size.to_bit_width();
+        let mask = ((1i128 << bit_field.width) - 1) << bit_field.offset;
+        let mask = (mask << shift) >> shift;
+
+        let offset = self.next_vreg();
+        let shifted = self.next_vreg();
+        let field_mask = self.next_vreg();
+        let field = self.next_vreg();
+        let other_mask = self.next_vreg();
+        let other = self.next_vreg();
+        let combined = self.next_vreg();
+        result.push(IRInstruction::Imm(size, offset, bit_field.offset as i128));
+        result.push(IRInstruction::Shl(size, shifted, value, offset));
+        result.push(IRInstruction::Imm(size, field_mask, mask));
+        result.push(IRInstruction::And(size, field, shifted, field_mask));
+        result.push(IRInstruction::Imm(size, other_mask, !mask));
+        result.push(IRInstruction::And(size, other, unit, other_mask));
+        result.push(IRInstruction::Or(size, combined, other, field));
+
+        let stored = self.promote(result, unit_size, size, combined);
+        result.push(IRInstruction::Store(unit_size, stored, addr, volatile));
+        self.extract_bit_field(result, typ, bit_field, combined)
+    }
+
+    // Shifting the bit-field to the top and back removes the other bits
+    // Signed bit-fields are sign extended by the arithmetic shift
+    fn extract_bit_field(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        typ: &Type,
+        bit_field: BitField,
+        unit: u32,
+    ) -> u32 {
+        let size = self.get_size(&typ.promote());
+        let bits = size.to_bit_width() as u32;
+        let left = self.next_vreg();
+        let top = self.next_vreg();
+        let right = self.next_vreg();
+        let vreg = self.next_vreg();
+        let left_shift = bits - bit_field.offset - bit_field.width;
+        let right_shift = bits - bit_field.width;
+        result.push(IRInstruction::Imm(size, left, left_shift as i128));
+        result.push(IRInstruction::Shl(size, top, unit, left));
+        result.push(IRInstruction::Imm(size, right, right_shift as i128));
+        result.push(match typ.is_unsigned() {
+            true => IRInstruction::Shr(size, vreg, top, right),
+            false => IRInstruction::Sar(size, vreg, top, right),
+        });
+        vreg
+    }
+}
+
+impl BinaryExpressionType {
+    pub fn get_size(&self, context: &mut EvaluationContext, left: &Type, _right: &Type) -> IRSize {
+        use BinaryExpressionType::*;
+        let size = context.get_size(left);
+        match self {
+            Subtract => size,
+            Add => size,
+            Multiply | Divide | Modulo | BinOr | BinAnd | BinXor | LeftShift | RightShift
+            | Equal | Inequal | Less | LessEqual | Greater | GreaterEqual => size,
+            _ => unreachable!(),
+        }
+    }
+}
@@ -1,561 +1,834 @@
-use super::{Evaluate, EvaluationContext};
-use crate::eval::evaluation_context::EvaluateSize;
-use crate::eval::jump_eval::JumpType;
-use crate::ir::*;
-use crate::parser::{ast::*, Type};
-use crate::semantic_analysis::type_promotion::TypePromotion;
-
-impl Evaluate for Expression {
-    fn eval(&self, result: &mut Vec<IRInstruction>, context: &mut EvaluationContext) -> u32 {
-        use BinaryExpressionType::*;
-        use ExpressionVariant::*;
-        use UnaryExpressionType::*;
-        match &self.variant {
-            &ConstI(value) => {
-                let vreg = context.next_vreg();
-                result.push(IRInstruction::Imm(IRSize::S32, vreg, value));
-                vreg
-            }
-
-            CString(value) => {
-                let addr = context.next_vreg();
-                let number = context.add_string(value);
-                result.push(IRInstruction::AddrG(
-                    IRSize::P,
-                    addr,
-                    format!(".__string{}", number),
-                ));
-                addr
-            }
-
-            Ident(..) => {
-                let addr = self.eval_lvalue(result, context);
-                self.optional_load(result, context, addr)
-            }
-
-            Sizeof(typ) => {
-                let size = context.eval_sizeof(typ) as i128;
-                let size_t = context.get_size(&context.size_t());
-                let vreg = context.next_vreg();
-                result.push(IRInstruction::Imm(size_t, vreg, size));
-                vreg
-            }
-
-            Function(func, arguments) => {
-                let size = context.get_size(&self.ast_type);
-                let count = arguments.len();
-                let empty = vec![Type::empty(); count];
-                let sizes = func.ast_type.get_function_arguments().unwrap_or(&empty);
-                let argument_sizes: Vec<IRSize> = arguments
-                    .iter()
-                    .map(|e| context.get_size(&e.ast_type.array_promotion()))
-                    .collect();
-
-                let sizes = if !sizes.is_empty() {
-                    sizes
-                        .iter()
-                        .filter(|&t| !t.is_void())
-                        .map(|t| context.get_size(&t.clone().remove_name().array_promotion()))
-                        .collect()
-                } else {
-                    argument_sizes.clone()
-                };
-
-                log::debug!(
-                    "Evaluating call {} with arguments {:?}",
-                    func.ast_type,
-                    sizes
-                );
-                let in_registers = context.backend.get_arguments_in_registers(&sizes);
-                let count = arguments.len();
-                let mut first = true;
-                let mut arg_index = None;
-                use crate::backend::Direction;
-                match context.backend.argument_evaluation_direction_stack() {
-                    Direction::Left2Right => {
-                        for arg in 0..arguments.len() {
-                            if !in_registers[arg] {
-                                let arg_size = argument_sizes[arg];
-                                let vreg = arguments[arg].eval(result, context);
-                                let vreg = context.promote(result, sizes[arg], arg_size, vreg);
-
-                                if first {
-                                    arg_index = Some(result.len());
-                                    result.push(IRInstruction::Arg(
-                                        sizes[arg].clone(),
-                                        vreg,
-                                        Some(0),
-                                    ));
-                                    first = false;
-                                } else {
-                                    result.push(IRInstruction::Arg(sizes[arg].clone(), vreg, None));
-                                }
-                            }
-                        }
-                    }
-                    Direction::Right2Left => {
-                        for arg in (0..arguments.len()).rev() {
-                            if !in_registers[arg] {
-                                let arg_size = argument_sizes[arg];
-                                let vreg = arguments[arg].eval(result, context);
-                                let vreg = context.promote(result, sizes[arg], arg_size, vreg);
-                                if first {
-                                    arg_index = Some(result.len());
-                                    result.push(IRInstruction::Arg(
-                                        sizes[arg].clone(),
-                                        vreg,
-                                        Some(0),
-                                    ));
-                                    first = false;
-                                } else {
-                                    result.push(IRInstruction::Arg(sizes[arg].clone(), vreg, None));
-                                }
-                            }
-                        }
-                    }
-                }
-
-                let arguments = match context.backend.argument_evaluation_direction_registers() {
-                    Direction::Left2Right => (0..arguments.len())
-                        .filter(|&arg| in_registers[arg])
-                        .map(|arg| {
-                            Some({
-                                let arg_size = argument_sizes[arg];
-                                let vreg = arguments[arg].eval(result, context);
-                                context.promote(result, sizes[arg], arg_size, vreg)
-                            })
-                        })
-                        .collect(),
-                    Direction::Right2Left => (0..arguments.len())
-                        .rev()
-                        .filter(|&arg| in_registers[arg])
-                        .map(|arg| {
-                            Some({
-                                let arg_size = argument_sizes[arg];
-                                let vreg = arguments[arg].eval(result, context);
-                                context.promote(result, sizes[arg], arg_size, vreg)
-                            })
-                        })
-                        .collect(),
-                };
-
-                let arguments = Box::new(IRArguments {
-                    variables: Vec::new(), //Not used in expressions, only in globals
-                    arguments,
-                    sizes,
-                    count,
-                });
-
-                if let (Ident(name, ..), true) =
-                    (&func.variant, !func.ast_type.is_function_pointer())
-                {
-                    let vreg = context.next_vreg();
-                    let index = result.len();
-                    if let Some(arg_index) = arg_index {
-                        if let IRInstruction::Arg(_, _, Some(fix)) = &mut result[arg_index] {
-                            *fix = index;
-                        }
-                    }
-                    result.push(IRInstruction::Call(size, vreg, name.clone(), arguments));
-                    vreg
-                } else {
-                    // Function pointers are not yet supported
-                    let addr = func.eval(result, context);
-                    let vreg = context.next_vreg();
-                    let index = result.len();
-                    if let Some(arg_index) = arg_index {
-                        if let IRInstruction::Arg(_, _, Some(fix)) = &mut result[arg_index] {
-                            *fix = index;
-                        }
-                    }
-                    result.push(IRInstruction::CallV(size, vreg, addr, arguments));
-                    vreg
-                }
-            }
-
-            Member(..) => {
-                let addr = self.eval_lvalue(result, context);
-                self.optional_load(result, context, addr)
-            }
-
-            Assign(left, right) => {
-                let size = context.get_size(&self.ast_type);
-                let right_size = context.get_size(&right.ast_type.array_promotion());
-                let vreg = right.eval(result, context);
-                let vreg = context.promote(result, size, right_size, vreg);
-                let addr = left.eval_lvalue(result, context);
-
-                result.push(IRInstruction::Store(size, vreg, addr));
-                vreg
-            }
-
-            #[allow(unused_variables)]
-            Ternary(cond, left, right) => {
-                //let cond_size = context.get_size(&cond.ast_type);
-                let size = context.get_size(&self.ast_type);
-                let left_size = context.get_size(&left.ast_type);
-                let right_size = context.get_size(&right.ast_type);
-
-                //let cond = cond.eval(result, context);
-                let cond = cond.condition_eval(result, context, JumpType::Jnc);
-
-                //let (if_index, _) = context.insert_place_holder_jump(result);
-                let left = left.eval(result, context);
-                let left = context.promote(result, size, left_size, left);
-
-                let if_label = context.get_current_label();
-                let (else_index, _) = context.insert_place_holder_jump(result);
-
-                let right = right.eval(result, context);
-                let right = context.promote(result, size, right_size, right);
-
-                let vreg = context.next_vreg();
-                let else_label = context.get_current_label();
-                let (last_index, label) = context.insert_place_holder_jump_phi(
-                    result,
-                    IRPhi::ternary(size, (if_label, else_label), vreg, (left, right)),
-                );
-
-                //result[if_index] = IRInstruction::Jnc(cond_size, cond, else_label);
-                context.fix_jumps(result, &cond, else_label);
-                result[else_index] = IRInstruction::Jmp(label);
-                result[last_index] = IRInstruction::Jmp(label);
-                vreg
-            }
-
-            // Could benefit from constants in phi nodes
-            Binary(op @ (LogOr | LogAnd), left, right) => {
-                let left_size = context.get_size(&left.ast_type);
-                let right_size = context.get_size(&right.ast_type);
-                let left = left.eval(result, context);
-                let first_operand = {
-                    let vreg = context.next_vreg();
-                    result.push(IRInstruction::Imm(
-                        IRSize::S32,
-                        vreg,
-                        match op {
-                            LogOr => 1,
-                            LogAnd => 0,
-                            _ => unreachable!(),
-                        },
-                    ));
-                    vreg
-                };
-                let start_label = context.get_current_label();
-                let (left_jmp, _) = context.insert_place_holder_jump(result);
-
-                let right = right.eval(result, context);
-                let second_operand = {
-                    let temp = context.next_vreg();
-                    let vreg = context.next_vreg();
-                    result.push(IRInstruction::Imm(right_size, temp, 0));
-                    result.push(IRInstruction::Ne(right_size, vreg, right, temp));
-                    vreg
-                };
-                let vreg = context.next_vreg();
-                let left_label = context.get_current_label();
-                let (right_jmp, right_label) = context.insert_place_holder_jump_phi(
-                    result,
-                    IRPhi::ternary(
-                        IRSize::S32,
-                        (start_label, left_label),
-                        vreg,
-                        (first_operand, second_operand),
-                    ),
-                );
-
-                result[right_jmp] = IRInstruction::Jmp(right_label);
-                result[left_jmp] = match op {
-                    LogOr => IRInstruction::Jcc(left_size, left, right_label),
-                    LogAnd => IRInstruction::Jnc(left_size, left, right_label),
-                    _ => unreachable!(),
-                };
-                vreg
-            }
-
-            Binary(Subtract, left, right)
-                if (left.ast_type.is_pointer() || left.ast_type.is_array())
-                    && (right.ast_type.is_pointer() || left.ast_type.is_array()) =>
-            {
-                let int_ptr_size = context.int_ptr(true);
-                let left_vreg = left.eval(result, context);
-                let right = right.eval(result, context);
-
-                let vreg = context.next_vreg();
-                result.push(IRInstruction::Sub(IRSize::P, vreg, left_vreg, right));
-
-                // IF we are subtracting two pointers we need to devide their distance by the sizeof the pointed-to object
-                let size = context.sizeof(&left.ast_type.clone().deref());
-                if size != 1 {
-                    let constant = context.next_vreg();
-                    let right = vreg;
-                    let vreg = context.next_vreg();
-                    result.push(IRInstruction::Imm(int_ptr_size, constant, size as i128));
-                    result.push(IRInstruction::Div(int_ptr_size, vreg, right, constant));
-                    vreg
-                } else {
-                    vreg
-                }
-            }
-
-            Binary(op @ (Subtract | Add | Index), left, right)
-                if left.ast_type.is_pointer()
-                    || right.ast_type.is_pointer()
-                    || left.ast_type.is_array()
-                    || right.ast_type.is_array() =>
-            {
-                let (left_vreg, right) = self.eval_pointer_addition(result, context);
-                let vreg = context.next_vreg();
-                match op {
-                    Add => {
-                        result.push(IRInstruction::Add(IRSize::P, vreg, left_vreg, right));
-                        vreg
-                    }
-                    Subtract => {
-                        result.push(IRInstruction::Sub(IRSize::P, vreg, left_vreg, right));
-                        vreg
-                    }
-                    Index => {
-                        let addr = vreg;
-                        result.push(IRInstruction::Add(IRSize::P, addr, left_vreg, right));
-                        self.optional_load(result, context, addr)
-                    }
-                    _ => unreachable!(),
-                }
-            }
-
-            Binary(Comma, left, right) => {
-                let _left = left.eval(result, context);
-                let right = right.eval(result, context);
-                right
-            }
-
-            Binary(
-                op @ (Equal | Inequal | Less | LessEqual | Greater | GreaterEqual),
-                left,
-                right,
-            ) => {
-                let size = context
-                    .get_size(&(left.ast_type.promote(), right.ast_type.promote()).promote());
-                let left_size = context.get_size(&left.ast_type);
-                let right_size = context.get_size(&right.ast_type);
-
-                let left = left.eval(result, context);
-                let right = right.eval(result, context);
-                let left = context.promote(result, size, left_size, left);
-                let right = context.promote(result, size, right_size, right);
-                let vreg = context.next_vreg();
-
-                result.push(match op {
-                    Equal => IRInstruction::Eq(size, vreg, left, right),
-                    Inequal => IRInstruction::Ne(size, vreg, left, right),
-                    Less => IRInstruction::Lt(size, vreg, left, right),
-                    LessEqual => IRInstruction::Le(size, vreg, left, right),
-                    Greater => IRInstruction::Gt(size, vreg, left, right),
-                    GreaterEqual => IRInstruction::Ge(size, vreg, left, right),
-                    _ => unreachable!(),
-                });
-                vreg
-            }
-
-            // TODO add IRInstruction for adding number to pointer?
-            // Or add conversion from integer in int* +/- int
-            // Adding/subtracting pointer does not currently lead to correct behaviour
-            Binary(op, left, right) => {
-                let left_type = &left.ast_type;
-                let right_type = &right.ast_type;
-                let size = context.get_size(&self.ast_type);
-
-                let left = left.eval(result, context);
-                let right = right.eval(result, context);
-
-                let left_size = context.get_size(&left_type); //
-                let right_size = context.get_size(&right_type);
-
-                let left = context.promote(result, size, left_size, left);
-                let right = context.promote(result, size, right_size, right);
-                let vreg = context.next_vreg();
-
-                result.push(match op {
-                    Add => IRInstruction::Add(size, vreg, left, right),
-                    Subtract => IRInstruction::Sub(size, vreg, left, right),
-                    Multiply => IRInstruction::Mul(size, vreg, left, right),
-                    Divide => IRInstruction::Div(size, vreg, left, right),
-
-                    BinOr => IRInstruction::Or(size, vreg, left, right),
-                    BinAnd => IRInstruction::And(size, vreg, left, right),
-
-                    Comma | LogOr | LogAnd | Equal | Inequal | Less | LessEqual | Greater
-                    | GreaterEqual | Index => unreachable!(),
-                });
-                vreg
-            }
-
-            Cast(exp, _) => {
-                let exp_size = context.get_size(&exp.ast_type);
-                let size = context.get_size(&self.ast_type);
-                let vreg = exp.eval(result, context);
-                context.promote(result, size, exp_size, vreg)
-            }
-
-            Unary(Identity, exp) => exp.eval(result, context),
-            Unary(Address, exp) => exp.eval_lvalue(result, context),
-            Unary(Deref, _exp) => {
-                let addr = self.eval_lvalue(result, context);
-                self.optional_load(result, context, addr)
-            }
-
-            Unary(LogNot, exp) => {
-                let size = context.get_size(&exp.ast_type);
-                let left = exp.eval(result, context);
-                let right = context.next_vreg();
-                let vreg = context.next_vreg();
-                result.push(IRInstruction::Imm(size, right, 0));
-                result.push(IRInstruction::Eq(size, vreg, left, right));
-                vreg
-            }
-
-            Unary(op, exp) => {
-                let size = context.get_size(&self.ast_type);
-                let exp_size = context.get_size(&exp.ast_type);
-
-                let left = exp.eval(result, context);
-                let left = context.promote(result, size, exp_size, left);
-                let right = context.next_vreg();
-                let vreg = context.next_vreg();
-
-                match op {
-                    Negate => result.push(IRInstruction::Imm(size, right, 0)),
-                    BinNot => result.push(IRInstruction::Imm(size, right, -1)),
-                    _ => (),
-                }
-                result.push(match op {
-                    Negate => IRInstruction::Sub(size, vreg, right, left),
-                    BinNot => IRInstruction::Xor(size, vreg, left, right),
-                    LogNot | Identity | Address | Deref => unreachable!(),
-                });
-                vreg
-            }
-        }
-    }
-}
-
-impl Expression {
-    fn eval_lvalue(&self, result: &mut Vec<IRInstruction>, context: &mut EvaluationContext) -> u32 {
-        use BinaryExpressionType::*;
-        use ExpressionVariant::*;
-        use UnaryExpressionType::*;
-        match &self.variant {
-            Ident(_name, symbol_number, false) => {
-                let addr = context.next_vreg();
-                result.push(IRInstruction::AddrL(
-                    IRSize::P,
-                    addr,
-                    *symbol_number as usize,
-                ));
-                addr
-            }
-            Ident(name, _symbol_number, true) => {
-                let addr = context.next_vreg();
-                result.push(IRInstruction::AddrG(IRSize::P, addr, name.clone()));
-                addr
-            }
-            Unary(Deref, exp) => exp.eval(result, context),
-            Binary(Index, ..) => {
-                let (left, right) = self.eval_pointer_addition(result, context);
-                let addr = context.next_vreg();
-                result.push(IRInstruction::Add(IRSize::P, addr, left, right));
-                addr
-            }
-            Member(exp, _, indirect, index) => {
-                let left = exp.eval_lvalue(result, context);
-                let left = if *indirect {
-                    let vreg = context.next_vreg();
-                    result.push(IRInstruction::Load(IRSize::P, vreg, left));
-                    vreg
-                } else {
-                    left
-                };
-
-                let struct_index = exp.ast_type.get_struct_index();
-                let offset = context.struct_offset_table[struct_index][*index as usize];
-
-                let right = context.next_vreg();
-                let addr = context.next_vreg();
-                result.push(IRInstruction::Imm(IRSize::P, right, offset as i128));
-                result.push(IRInstruction::Add(IRSize::P, addr, left, right));
-                addr
-            }
-
-            _ => {
-                unreachable!()
-            }
-        }
-    }
-}
-
-impl Expression {
-    fn optional_load(
-        &self,
-        result: &mut Vec<IRInstruction>,
-        context: &mut EvaluationContext,
-        addr: u32,
-    ) -> u32 {
-        if !self.ast_type.is_array() && !self.ast_type.is_function() {
-            let size = context.get_size(&self.ast_type);
-            let vreg = context.next_vreg();
-            result.push(IRInstruction::Load(size, vreg, addr));
-            vreg
-        } else {
-            addr
-        }
-    }
-
-    fn eval_pointer_addition(
-        &self,
-        result: &mut Vec<IRInstruction>,
-        context: &mut EvaluationContext,
-    ) -> (u32, u32) {
-        let (_, left, right) = match &self.variant {
-            ExpressionVariant::Binary(op, left, right) => (op, left, right),
-            _ => unreachable!(),
-        };
-        // Swap pointers such that left is always a pointer
-        // This also ensures that matching only needs to consider left side
-        let (left, right) = if right.ast_type.is_pointer() {
-            (right, left)
-        } else {
-            (left, right)
-        };
-
-        let right_size = context.get_size(&right.ast_type);
-        let int_ptr_size = context.int_ptr(true);
-        let left_vreg = left.eval(result, context);
-        let mut right = right.eval(result, context);
-
-        //Conversions are only inserted if sizeof(right) != sizeof(pointer)
-        //This means that on ILP32 and IP16 environments convert is often not inserted
-        if right_size != IRSize::P && right_size != int_ptr_size {
-            let vreg = context.next_vreg();
-            result.push(IRInstruction::Cvs(
-                context.int_ptr(true),
-                vreg,
-                right_size,
-                right,
-            ));
-            right = vreg
-        }
-
-        // We must multiply the right side with sizeof(*left)
-        // The constant will always be added on the right side
-        let size = context.sizeof(&left.ast_type.clone().deref());
-        if size != 1 {
-            let constant = context.next_vreg();
-            let vreg = context.next_vreg();
-            result.push(IRInstruction::Imm(int_ptr_size, constant, size as i128));
-            result.push(IRInstruction::Mul(int_ptr_size, vreg, right, constant));
-            right = vreg
-        }
-        (left_vreg, right)
-    }
-}
+use super::{Evaluate, EvaluationContext};
+use crate::eval::evaluation_context::EvaluateSize;
+use crate::eval::jump_eval::JumpType;
+use crate::ir::*;
+use crate::parser::{ast::*, r#type::BitField, Type};
+use crate::semantic_analysis::type_promotion::TypePromotion;
+
+impl Evaluate for Expression {
+    fn eval(&self, result: &mut Vec<IRInstruction>, context: &mut EvaluationContext) -> u32 {
+        use BinaryExpressionType::*;
+        use ExpressionVariant::*;
+        use UnaryExpressionType::*;
+        match &self.variant {
+            &ConstI(value) => {
+                let vreg = context.next_vreg();
+                result.push(IRInstruction::Imm(IRSize::S32, vreg, value));
+                vreg
+            }
+
+            &ConstF(value) => {
+                let size = context.get_size(&self.ast_type);
+                let vreg = context.next_vreg();
+                result.push(IRInstruction::Imm(size, vreg, size.float_bits(value)));
+                vreg
+            }
+
+            CString(value) => {
+                let addr = context.next_vreg();
+                let number = context.add_string(value);
+                result.push(IRInstruction::AddrG(
+                    IRSize::P,
+                    addr,
+                    format!(".__string{}", number),
+                ));
+                addr
+            }
+
+            Ident(..) => {
+                let addr = self.eval_lvalue(result, context);
+                self.optional_load(result, context, addr)
+            }
+
+            Sizeof(typ) => {
+                let size = context.eval_sizeof(typ) as i128;
+                let size_t = context.get_size(&context.size_t());
+                let vreg = context.next_vreg();
+                result.push(IRInstruction::Imm(size_t, vreg, size));
+                vreg
+            }
+
+            // The va_list is an array, so it evaluates to the address of the list
+            VaStart(list, _) => {
+                let addr = list.eval(result, context);
+                result.push(IRInstruction::VaStart(addr));
+                addr
+            }
+
+            // Aggregates are passed in the same parts as an argument of the same type
+            VaArg(list, _) => {
+                let addr = list.eval(result, context);
+                let vreg = context.next_vreg();
+                let (size, parts) = match self.ast_type.is_struct() {
+                    true => {
+                        let size = context.get_size(&self.ast_type);
+                        let parts = context.get_aggregate_registers(&self.ast_type);
+                        (IRSize::P, parts.unwrap_or_else(|| vec![size]))
+                    }
+                    false => (context.get_size(&self.ast_type), Vec::new()),
+                };
+                result.push(IRInstruction::VaArg(size, vreg, addr, parts));
+                vreg
+            }
+
+            VaEnd(list) => list.eval(result, context),
+
+            VaCopy(dest, src) => {
+                let size = context.get_size(&dest.ast_type.array_promotion().deref());
+                let from = src.eval(result, context);
+                let to = dest.eval(result, context);
+                result.push(IRInstruction::Copy(size, from, to));
+                to
+            }
+
+            Function(func, arguments) => {
+                let size = context.get_size(&self.ast_type);
+                let count = arguments.len();
+                // Arguments without a prototype or after the named arguments are promoted
+                let mut types: Vec<Type> = match func.ast_type.get_function_arguments() {
+                    Some(types) => types
+                        .iter()
+                        .filter(|&t| !t.is_void())
+                        .map(|t| t.clone().remove_name().array_promotion())
+                        .collect(),
+                    None => Vec::new(),
+                };
+                let named = types.len();
+                let unnamed = arguments.iter().skip(named);
+                types.extend(unnamed.map(|e| e.ast_type.argument_promotion()));
+
+                // Aggregates are returned in registers or in memory pointed to by a hidden argument
+                let return_sizes = match self.ast_type.is_struct() {
+                    true => context.get_aggregate_registers(&self.ast_type),
+                    false => None,
+                };
+                let return_pointer = self.ast_type.is_struct() && return_sizes.is_none();
+                let sizes = context.get_argument_sizes(&types, return_pointer);
+                let flat_sizes: Vec<IRSize> = sizes.iter().flatten().cloned().collect();
+
+                log::debug!(
+                    "Evaluating call {} with arguments {:?}",
+                    func.ast_type,
+                    flat_sizes
+                );
+                // All parts of an argument are either passed in registers or on the stack
+                let in_registers = context.backend.get_arguments_in_registers(&flat_sizes);
+                let mut start = 0;
+                let in_registers: Vec<bool> = sizes
+                    .iter()
+                    .map(|parts| {
+                        start += parts.len();
+                        in_registers[start - parts.len()]
+                    })
+                    .collect();
+                let (sizes, in_registers) = if return_pointer {
+                    (&sizes[1..], &in_registers[1..])
+                } else {
+                    (&sizes[..], &in_registers[..])
+                };
+
+                use crate::backend::Direction;
+                let order = |direction: Direction| -> Vec<usize> {
+                    match direction {
+                        Direction::Left2Right => (0..count).collect(),
+                        Direction::Right2Left => (0..count).rev().collect(),
+                    }
+                };
+
+                // A called function pointer is evaluated before the arguments are passed
+                let addr = match &func.variant {
+                    Ident(..) if !func.ast_type.is_function_pointer() => None,
+                    _ => Some(func.eval(result, context)),
+                };
+
+                let mut arg_index = None;
+                for arg in order(context.backend.argument_evaluation_direction_stack()) {
+                    if !in_registers[arg] {
+                        let vreg = arguments[arg].eval_argument(result, context, &sizes[arg])[0];
+                        let fix = match arg_index {
+                            None => {
+                                arg_index = Some(result.len());
+                                Some(0)
+                            }
+                            Some(_) => None,
+                        };
+                        result.push(IRInstruction::Arg(sizes[arg][0], vreg, fix));
+                    }
+                }
+
+                let mut registers = Vec::new();
+                for arg in order(context.backend.argument_evaluation_direction_registers()) {
+                    if in_registers[arg] {
+                        let vregs = arguments[arg].eval_argument(result, context, &sizes[arg]);
+                        registers.extend(vregs.into_iter().map(Some));
+                    }
+                }
+                if return_pointer {
+                    let temporary = context.add_temporary(size);
+                    let addr = context.insert_temporary_address(result, temporary);
+                    registers.insert(0, Some(addr));
+                }
+
+                let return_variable = return_sizes
+                    .as_ref()
+                    .map(|parts| context.add_temporary(context.get_aggregate_size(parts)));
+                let size = match (return_pointer, return_variable) {
+                    (true, _) => IRSize::P,
+                    (_, Some(_)) => IRSize::V,
+                    _ => size,
+                };
+                let arguments = Box::new(IRArguments {
+                    variables: Vec::new(), //Not used in expressions, only in globals
+                    arguments: registers,
+                    sizes: flat_sizes,
+                    count,
+                    return_sizes: return_sizes.unwrap_or_default(),
+                    return_variable,
+                    variadic: func.ast_type.is_variadic(),
+                });
+
+                let vreg = if let (Ident(name, ..), None) = (&func.variant, addr) {
+                    let vreg = context.next_vreg();
+                    let index = result.len();
+                    if let Some(arg_index) = arg_index {
+                        if let IRInstruction::Arg(_, _, Some(fix)) = &mut result[arg_index] {
+                            *fix = index;
+                        }
+                    }
+                    result.push(IRInstruction::Call(size, vreg, name.clone(), arguments));
+                    vreg
+                } else {
+                    let addr = addr.unwrap();
+                    let vreg = context.next_vreg();
+                    let index = result.len();
+                    if let Some(arg_index) = arg_index {
+                        if let IRInstruction::Arg(_, _, Some(fix)) = &mut result[arg_index] {
+                            *fix = index;
+                        }
+                    }
+                    result.push(IRInstruction::CallV(size, vreg, addr, arguments));
+                    vreg
+                };
+
+                // The call stores an aggregate returned in registers in a temporary
+                match return_variable {
+                    Some(temporary) => {
+                        context.unfixed_temporaries.push(result.len() - 1);
+                        context.insert_temporary_address(result, temporary)
+                    }
+                    None => vreg,
+                }
+            }
+
+            Member(..) => {
+                let addr = self.eval_lvalue(result, context);
+                self.load_value(result, context, addr)
+            }
+
+            // Aggregates are represented by their address
+            Assign(left, right) if self.ast_type.is_struct() => {
+                let size = context.get_size(&self.ast_type);
+                let from = right.eval(result, context);
+                let addr = left.eval_lvalue(result, context);
+                result.push(IRInstruction::Copy(size, from, addr));
+                addr
+            }
+
+            Assign(left, right) => {
+                let size = context.get_size(&self.ast_type);
+                let vreg = right.eval(result, context);
+                let vreg = context.convert(result, size, &right.ast_type, vreg);
+                let addr = left.eval_lvalue(result, context);
+                left.store_value(result, context, addr, vreg)
+            }
+
+            // The address of the left side is only evaluated once
+            // Variables are the exception, such that they can still be promoted by mem2reg
+            CompoundAssign(op @ (Add | Subtract), left, right) if left.ast_type.is_pointer() => {
+                let volatile = left.ast_type.is_volatile();
+                let addr = left.eval_lvalue(result, context);
+                let value = context.next_vreg();
+                result.push(IRInstruction::Load(IRSize::P, value, addr, volatile));
+
+                let right_vreg = right.eval(result, context);
+                let right =
+                    scale_offset(result, context, &left.ast_type, &right.ast_type, right_vreg);
+                let vreg = context.next_vreg();
+                result.push(match op {
+                    Add => IRInstruction::Add(IRSize::P, vreg, value, right),
+                    Subtract => IRInstruction::Sub(IRSize::P, vreg, value, right),
+                    _ => unreachable!(),
+                });
+                let addr = left.reuse_lvalue(result, context, addr);
+                result.push(IRInstruction::Store(IRSize::P, vreg, addr, volatile));
+                vreg
+            }
+
+            // The operation is done in the promoted type and converted back before storing
+            CompoundAssign(op, left, right) => {
+                let left_size = context.get_size(&left.ast_type);
+                let op_type = match op {
+                    LeftShift | RightShift => left.ast_type.promote(),
+                    _ => (left.ast_type.promote(), right.ast_type.promote()).promote(),
+                };
+                let size = context.get_size(&op_type);
+
+                let addr = left.eval_lvalue(result, context);
+                let value = left.load_value(result, context, addr);
+                let right_vreg = right.eval(result, context);
+
+                let value = context.convert(result, size, &left.ast_type, value);
+                let right = context.convert(result, size, &right.ast_type, right_vreg);
+                let vreg = context.next_vreg();
+                result.push(op.to_instruction(&op_type, size, vreg, value, right));
+
+                let vreg = context.promote(result, left_size, size, vreg);
+                let addr = left.reuse_lvalue(result, context, addr);
+                left.store_value(result, context, addr, vreg)
+            }
+
+            #[allow(unused_variables)]
+            Ternary(cond, left, right) => {
+                //let cond_size = context.get_size(&cond.ast_type);
+                let size = context.get_size(&self.ast_type);
+                let left_type = &left.ast_type;
+                let right_type = &right.ast_type;
+
+                //let cond = cond.eval(result, context);
+                let cond = cond.condition_eval(result, context, JumpType::Jnc);
+
+                //let (if_index, _) = context.insert_place_holder_jump(result);
+                let left = left.eval(result, context);
+                let left = context.convert(result, size, left_type, left);
+
+                let if_label = context.get_current_label();
+                let (else_index, _) = context.insert_place_holder_jump(result);
+
+                let right = right.eval(result, context);
+                let right = context.convert(result, size, right_type, right);
+
+                let vreg = context.next_vreg();
+                let else_label = context.get_current_label();
+                let (last_index, label) = context.insert_place_holder_jump_phi(
+                    result,
+                    IRPhi::ternary(size, (if_label, else_label), vreg, (left, right)),
+                );
+
+                //result[if_index] = IRInstruction::Jnc(cond_size, cond, else_label);
+                context.fix_jumps(result, &cond, else_label);
+                result[else_index] = IRInstruction::Jmp(label);
+                result[last_index] = IRInstruction::Jmp(label);
+                vreg
+            }
+
+            // Could benefit from constants in phi nodes
+            Binary(op @ (LogOr | LogAnd), left, right) => {
+                let left_size = context.get_size(&left.ast_type);
+                let right_size = context.get_size(&right.ast_type);
+                let left = left.eval(result, context);
+                let (left_size, left) = context.condition(result, left_size, left);
+                let first_operand = {
+                    let vreg = context.next_vreg();
+                    result.push(IRInstruction::Imm(
+                        IRSize::S32,
+                        vreg,
+                        match op {
+                            LogOr => 1,
+                            LogAnd => 0,
+                            _ => unreachable!(),
+                        },
+                    ));
+                    vreg
+                };
+                let start_label = context.get_current_label();
+                let (left_jmp, _) = context.insert_place_holder_jump(result);
+
+                let right = right.eval(result, context);
+                let second_operand = {
+                    let temp = context.next_vreg();
+                    let vreg = context.next_vreg();
+                    result.push(IRInstruction::Imm(right_size, temp, 0));
+                    result.push(IRInstruction::Ne(right_size, vreg, right, temp));
+                    vreg
+                };
+                let vreg = context.next_vreg();
+                let left_label = context.get_current_label();
+                let (right_jmp, right_label) = context.insert_place_holder_jump_phi(
+                    result,
+                    IRPhi::ternary(
+                        IRSize::S32,
+                        (start_label, left_label),
+                        vreg,
+                        (first_operand, second_operand),
+                    ),
+                );
+
+                result[right_jmp] = IRInstruction::Jmp(right_label);
+                result[left_jmp] = match op {
+                    LogOr => IRInstruction::Jcc(left_size, left, right_label),
+                    LogAnd => IRInstruction::Jnc(left_size, left, right_label),
+                    _ => unreachable!(),
+                };
+                vreg
+            }
+
+            Binary(Subtract, left, right)
+                if (left.ast_type.is_pointer() || left.ast_type.is_array())
+                    && (right.ast_type.is_pointer() || right.ast_type.is_array()) =>
+            {
+                let int_ptr_size = context.int_ptr(true);
+                let left_vreg = left.eval(result, context);
+                let right = right.eval(result, context);
+
+                let vreg = context.next_vreg();
+                result.push(IRInstruction::Sub(IRSize::P, vreg, left_vreg, right));
+
+                // IF we are subtracting two pointers we need to devide their distance by the sizeof the pointed-to object
+                let size = context.sizeof(&left.ast_type.clone().deref());
+                if size != 1 {
+                    let constant = context.next_vreg();
+                    let right = vreg;
+                    let vreg = context.next_vreg();
+                    result.push(IRInstruction::Imm(int_ptr_size, constant, size as i128));
+                    result.push(IRInstruction::Div(int_ptr_size, vreg, right, constant));
+                    vreg
+                } else {
+                    vreg
+                }
+            }
+
+            Binary(op @ (Subtract | Add | Index), left, right)
+                if left.ast_type.is_pointer()
+                    || right.ast_type.is_pointer()
+                    || left.ast_type.is_array()
+                    || right.ast_type.is_array() =>
+            {
+                let (left_vreg, right) = self.eval_pointer_addition(result, context);
+                let vreg = context.next_vreg();
+                match op {
+                    Add => {
+                        result.push(IRInstruction::Add(IRSize::P, vreg, left_vreg, right));
+                        vreg
+                    }
+                    Subtract => {
+                        result.push(IRInstruction::Sub(IRSize::P, vreg, left_vreg, right));
+                        vreg
+                    }
+                    Index => {
+                        let addr = vreg;
+                        result.push(IRInstruction::Add(IRSize::P, addr, left_vreg, right));
+                        self.optional_load(result, context, addr)
+                    }
+                    _ => unreachable!(),
+                }
+            }
+
+            Binary(Comma, left, right) => {
+                let _left = left.eval(result, context);
+                let right = right.eval(result, context);
+                right
+            }
+
+            Binary(
+                op @ (Equal | Inequal | Less | LessEqual | Greater | GreaterEqual),
+                left,
+                right,
+            ) => {
+                let op_type = (left.ast_type.promote(), right.ast_type.promote()).promote();
+                let size = context.get_size(&op_type);
+                let left_type = &left.ast_type;
+                let right_type = &right.ast_type;
+
+                let left = left.eval(result, context);
+                let right = right.eval(result, context);
+                let left = context.convert(result, size, left_type, left);
+                let right = context.convert(result, size, right_type, right);
+                let vreg = context.next_vreg();
+
+                result.push(op.to_instruction(&op_type, size, vreg, left, right));
+                vreg
+            }
+
+            // TODO add IRInstruction for adding number to pointer?
+            // Or add conversion from integer in int* +/- int
+            // Adding/subtracting pointer does not currently lead to correct behaviour
+            Binary(op, left, right) => {
+                let left_type = &left.ast_type;
+                let right_type = &right.ast_type;
+                let size = context.get_size(&self.ast_type);
+
+                let left = left.eval(result, context);
+                let right = right.eval(result, context);
+
+                let left = context.convert(result, size, left_type, left);
+                let right = context.convert(result, size, right_type, right);
+                let vreg = context.next_vreg();
+                result.push(op.to_instruction(&self.ast_type, size, vreg, left, right));
+                vreg
+            }
+
+            Cast(exp, _) => {
+                let size = context.get_size(&self.ast_type);
+                let vreg = exp.eval(result, context);
+                context.convert(result, size, &exp.ast_type, vreg)
+            }
+
+            // The address is only evaluated once, except for variables
+            // Postfix operators give the old value, prefix operators the new value
+            Unary(op @ (PreIncrement | PreDecrement | PostIncrement | PostDecrement), exp) => {
+                let size = context.get_size(&exp.ast_type);
+                let addr = exp.eval_lvalue(result, context);
+                let old = exp.load_value(result, context, addr);
+
+                // Pointers are stepped by the size of the pointed-to object
+                // Small integers are stepped in their promoted type
+                let (op_size, step_size, step) = if exp.ast_type.is_pointer() {
+                    let step = context.sizeof(&exp.ast_type.clone().deref());
+                    (IRSize::P, context.int_ptr(true), step as i128)
+                } else if exp.ast_type.is_float() {
+                    (size, size, size.float_bits(1.0))
+                } else {
+                    let op_size = context.get_size(&exp.ast_type.promote());
+                    (op_size, op_size, 1)
+                };
+
+                let value = context.convert(result, op_size, &exp.ast_type, old);
+                let constant = context.next_vreg();
+                let vreg = context.next_vreg();
+                result.push(IRInstruction::Imm(step_size, constant, step));
+                result.push(match op {
+                    PreIncrement | PostIncrement => {
+                        IRInstruction::Add(op_size, vreg, value, constant)
+                    }
+                    _ => IRInstruction::Sub(op_size, vreg, value, constant),
+                });
+                let new = context.promote(result, size, op_size, vreg);
+                let addr = exp.reuse_lvalue(result, context, addr);
+                let new = exp.store_value(result, context, addr, new);
+
+                match op {
+                    PreIncrement | PreDecrement => new,
+                    _ => old,
+                }
+            }
+
+            Unary(Identity, exp) => exp.eval(result, context),
+            Unary(Address, exp) => exp.eval_lvalue(result, context),
+            Unary(Deref, _exp) => {
+                let addr = self.eval_lvalue(result, context);
+                self.optional_load(result, context, addr)
+            }
+
+            Unary(LogNot, exp) => {
+                let size = context.get_size(&exp.ast_type);
+                let left = exp.eval(result, context);
+                let right = context.next_vreg();
+                let vreg = context.next_vreg();
+                result.push(IRInstruction::Imm(size, right, 0));
+                result.push(IRInstruction::Eq(size, vreg, left, right));
+                vreg
+            }
+
+            Unary(op, exp) => {
+                let size = context.get_size(&self.ast_type);
+
+                let left = exp.eval(result, context);
+                let left = context.convert(result, size, &exp.ast_type, left);
+                let right = context.next_vreg();
+                let vreg = context.next_vreg();
+
+                // Floats are negated by multiplying with -1.0, which also flips the sign of zero
+                match op {
+                    Negate if size.is_float() => {
+                        result.push(IRInstruction::Imm(size, right, size.float_bits(-1.0)))
+                    }
+                    Negate => result.push(IRInstruction::Imm(size, right, 0)),
+                    BinNot => result.push(IRInstruction::Imm(size, right, -1)),
+                    _ => (),
+                }
+                result.push(match op {
+                    Negate if size.is_float() => IRInstruction::Mul(size, vreg, left, right),
+                    Negate => IRInstruction::Sub(size, vreg, right, left),
+                    BinNot => IRInstruction::Xor(size, vreg, left, right),
+                    LogNot | Identity | Address | Deref | PreIncrement | PreDecrement
+                    | PostIncrement | PostDecrement => unreachable!(),
+                });
+                vreg
+            }
+        }
+    }
+}
+
+impl Expression {
+    fn eval_lvalue(&self, result: &mut Vec<IRInstruction>, context: &mut EvaluationContext) -> u32 {
+        use BinaryExpressionType::*;
+        use ExpressionVariant::*;
+        use UnaryExpressionType::*;
+        match &self.variant {
+            Ident(_name, symbol_number, false) => {
+                let addr = context.next_vreg();
+                result.push(IRInstruction::AddrL(
+                    IRSize::P,
+                    addr,
+                    *symbol_number as usize,
+                ));
+                addr
+            }
+            Ident(name, _symbol_number, true) => {
+                let addr = context.next_vreg();
+                result.push(IRInstruction::AddrG(IRSize::P, addr, name.clone()));
+                addr
+            }
+            Unary(Deref, exp) => exp.eval(result, context),
+            Binary(Index, ..) => {
+                let (left, right) = self.eval_pointer_addition(result, context);
+                let addr = context.next_vreg();
+                result.push(IRInstruction::Add(IRSize::P, addr, left, right));
+                addr
+            }
+            Member(exp, _, indirect, index) => {
+                let left = exp.eval_lvalue(result, context);
+                let left = if *indirect {
+                    let vreg = context.next_vreg();
+                    let volatile = exp.ast_type.is_volatile();
+                    result.push(IRInstruction::Load(IRSize::P, vreg, left, volatile));
+                    vreg
+                } else {
+                    left
+                };
+
+                let struct_index = exp.ast_type.get_struct_index();
+                let offset = context.struct_offset_table[struct_index][*index as usize];
+
+                let right = context.next_vreg();
+                let addr = context.next_vreg();
+                result.push(IRInstruction::Imm(IRSize::P, right, offset as i128));
+                result.push(IRInstruction::Add(IRSize::P, addr, left, right));
+                addr
+            }
+            // Aggregates that are not an lvalue, like the result of a call, are still in memory
+            _ if self.ast_type.is_struct() => self.eval(result, context),
+
+            _ => {
+                unreachable!()
+            }
+        }
+    }
+}
+
+impl Expression {
+    // Gets the address of an lvalue for a second access
+    // Variables get a fresh address, because mem2reg requires every address to be used once
+    // Other lvalues reuse their address, because evaluating them again can have side effects
+    fn reuse_lvalue(
+        &self,
+        result: &mut Vec<IRInstruction>,
+        context: &mut EvaluationContext,
+        addr: u32,
+    ) -> u32 {
+        match &self.variant {
+            ExpressionVariant::Ident(..) => self.eval_lvalue(result, context),
+            _ => addr,
+        }
+    }
+
+    // Bit-fields are loaded and stored through their storage unit
+    fn load_value(
+        &self,
+        result: &mut Vec<IRInstruction>,
+        context: &mut EvaluationContext,
+        addr: u32,
+    ) -> u32 {
+        match self.get_bit_field(context) {
+            Some((typ, bit_field)) => {
+                let volatile = self.ast_type.is_volatile();
+                let vreg = context.load_bit_field(result, &typ, bit_field, addr, volatile);
+                let size = context.get_size(&self.ast_type);
+                context.convert(result, size, &typ.promote(), vreg)
+            }
+            None => self.optional_load(result, context, addr),
+        }
+    }
+
+    // Gives the value of the lvalue after the store
+    fn store_value(
+        &self,
+        result: &mut Vec<IRInstruction>,
+        context: &mut EvaluationContext,
+        addr: u32,
+        vreg: u32,
+    ) -> u32 {
+        let volatile = self.ast_type.is_volatile();
+        match self.get_bit_field(context) {
+            Some((typ, bit_field)) => {
+                let promoted = typ.promote();
+                let size = context.get_size(&promoted);
+                let value = context.convert(result, size, &self.ast_type, vreg);
+                let vreg = context.store_bit_field(result, &typ, bit_field, addr, value, volatile);
+                let size = context.get_size(&self.ast_type);
+                context.convert(result, size, &promoted, vreg)
+            }
+            None => {
+                let size = context.get_size(&self.ast_type);
+                result.push(IRInstruction::Store(size, vreg, addr, volatile));
+                vreg
+            }
+        }
+    }
+
+    // Gives the declared type and the position of the bit-field a member refers to
+    fn get_bit_field(&self, context: &EvaluationContext) -> Option<(Type, BitField)> {
+        let (exp, index) = match &self.variant {
+            ExpressionVariant::Member(exp, _, _, index) => (exp, *index as usize),
+            _ => return None,
+        };
+        let struct_type = &context.struct_type_table[exp.ast_type.get_struct_index()];
+        let bit_field = struct_type.bit_fields[index]?;
+        let members = struct_type.members.as_ref().unwrap();
+        Some((members[index].1.clone(), bit_field))
+    }
+
+    fn optional_load(
+        &self,
+        result: &mut Vec<IRInstruction>,
+        context: &mut EvaluationContext,
+        addr: u32,
+    ) -> u32 {
+        if !self.ast_type.is_array() && !self.ast_type.is_function() && !self.ast_type.is_struct() {
+            let size = context.get_size(&self.ast_type);
+            let vreg = context.next_vreg();
+            let volatile = self.ast_type.is_volatile();
+            result.push(IRInstruction::Load(size, vreg, addr, volatile));
+            vreg
+        } else {
+            addr
+        }
+    }
+
+    // Evaluates an argument into the parts in which it is passed
+    fn eval_argument(
+        &self,
+        result: &mut Vec<IRInstruction>,
+        context: &mut EvaluationContext,
+        parts: &[IRSize],
+    ) -> Vec<u32> {
+        let vreg = self.eval(result, context);
+        if !self.ast_type.is_struct() {
+            return vec![context.convert(result, parts[0], &self.ast_type, vreg)];
+        }
+        match parts {
+            [IRSize::B(_)] => vec![vreg],
+            _ => {
+                let size = context.get_size(&self.ast_type);
+                let addr = context.pad_aggregate(result, parts, size, vreg);
+                context.load_parts(result, parts, addr)
+            }
+        }
+    }
+
+    fn eval_pointer_addition(
+        &self,
+        result: &mut Vec<IRInstruction>,
+        context: &mut EvaluationContext,
+    ) -> (u32, u32) {
+        let (_, left, right) = match &self.variant {
+            ExpressionVariant::Binary(op, left, right) => (op, left, right),
+            _ => unreachable!(),
+        };
+        // Swap pointers and arrays such that left is always a pointer or array
+        // This also ensures that matching only needs to consider left side
+        let (left, right) = if right.ast_type.is_pointer() || right.ast_type.is_array() {
+            (right, left)
+        } else {
+            (left, right)
+        };
+
+        let left_vreg = left.eval(result, context);
+        let right_vreg = right.eval(result, context);
+        let right = scale_offset(result, context, &left.ast_type, &right.ast_type, right_vreg);
+        (left_vreg, right)
+    }
+}
+
+// Converts an integer that is added to a pointer into an offset in bytes
+fn scale_offset(
+    result: &mut Vec<IRInstruction>,
+    context: &mut EvaluationContext,
+    pointer_type: &Type,
+    right_type: &Type,
+    mut right: u32,
+) -> u32 {
+    let right_size = context.get_size(right_type);
+    let int_ptr_size = context.int_ptr(true);
+
+    //Conversions are only inserted if sizeof(right) != sizeof(pointer)
+    //This means that on ILP32 and IP16 environments convert is often not inserted
+    if right_size != IRSize::P && right_size != int_ptr_size {
+        let vreg = context.next_vreg();
+        result.push(IRInstruction::Cvs(
+            context.int_ptr(true),
+            vreg,
+            right_size,
+            right,
+        ));
+        right = vreg
+    }
+
+    // We must multiply the right side with sizeof(*left)
+    // The constant will always be added on the right side
+    let size = context.sizeof(&pointer_type.clone().deref());
+    if size != 1 {
+        let constant = context.next_vreg();
+        let vreg = context.next_vreg();
+        result.push(IRInstruction::Imm(int_ptr_size, constant, size as i128));
+        result.push(IRInstruction::Mul(int_ptr_size, vreg, right, constant));
+        right = vreg
+    }
+    right
+}
+
+impl BinaryExpressionType {
+    // Gets the instruction for a binary operation on two integers of the same size
+    // The operation is chosen based on the signedness of the type it is done in
+    pub(super) fn to_instruction(
+        &self,
+        typ: &Type,
+        size: IRSize,
+        vreg: u32,
+        left: u32,
+        right: u32,
+    ) -> IRInstruction {
+        use BinaryExpressionType::*;
+        let unsigned = typ.is_unsigned();
+        match self {
+            Add => IRInstruction::Add(size, vreg, left, right),
+            Subtract => IRInstruction::Sub(size, vreg, left, right),
+            Multiply => IRInstruction::Mul(size, vreg, left, right),
+            Divide if unsigned => IRInstruction::Divu(size, vreg, left, right),
+            Divide => IRInstruction::Div(size, vreg, left, right),
+            Modulo if unsigned => IRInstruction::Modu(size, vreg, left, right),
+            Modulo => IRInstruction::Mod(size, vreg, left, right),
+
+            BinOr => IRInstruction::Or(size, vreg, left, right),
+            BinAnd => IRInstruction::And(size, vreg, left, right),
+            BinXor => IRInstruction::Xor(size, vreg, left, right),
+
+            LeftShift => IRInstruction::Shl(size, vreg, left, right),
+            RightShift if unsigned => IRInstruction::Shr(size, vreg, left, right),
+            RightShift => IRInstruction::Sar(size, vreg, left, right),
+
+            Equal => IRInstruction::Eq(size, vreg, left, right),
+            Inequal => IRInstruction::Ne(size, vreg, left, right),
+            Less if unsigned => IRInstruction::Ltu(size, vreg, left, right),
+            Less => IRInstruction::Lt(size, vreg, left, right),
+            LessEqual if unsigned => IRInstruction::Leu(size, vreg, left, right),
+            LessEqual => IRInstruction::Le(size, vreg, left, right),
+            Greater if unsigned => IRInstruction::Gtu(size, vreg, left, right),
+            Greater => IRInstruction::Gt(size, vreg, left, right),
+            GreaterEqual if unsigned => IRInstruction::Geu(size, vreg, left, right),
+            GreaterEqual => IRInstruction::Ge(size, vreg, left, right),
+
+            Comma | LogOr | LogAnd | Index => unreachable!(),
+        }
+    }
+}
@@ -1,192 +1,278 @@
-use super::{evaluation_context::EvaluateSize, Evaluate, EvaluationContext};
-use crate::backend::{Backend, TypeInfo};
-use crate::ir::*;
-use crate::options::OptimizationSettings;
-use crate::parser::r#type::DeclarationType;
-use crate::parser::{ast::*, Type};
-use crate::table::Symbol;
-use std::collections::{HashMap, HashSet};
-
-impl ExternalDeclaration {
-    pub fn eval(
-        &self,
-        struct_size_table: &Vec<TypeInfo>,
-        struct_offset_table: &Vec<Vec<usize>>,
-        backend: &mut dyn Backend,
-        optimization_settings: &OptimizationSettings,
-    ) -> Option<IRFunction> {
-        match &self.function_body {
-            Some(statements) => {
-                let mut instructions = Vec::<IRInstruction>::new();
-                let mut context = EvaluationContext {
-                    vreg_counter: 0,
-                    label_counter: 1,
-                    variables: Vec::new(),
-                    strings: Vec::new(),
-                    unfixed_break: Vec::new(),
-                    unfixed_continue: Vec::new(),
-                    loop_depth: 0,
-                    backend,
-                    struct_size_table,
-                    struct_offset_table,
-                    type_info: backend.get_type_info_table(),
-                    optimization_settings,
-                };
-                instructions.push(IRInstruction::Label(None, 0));
-
-                let arguments = self.eval_function_arguments(&mut instructions, &mut context);
-                for statement in statements {
-                    statement.eval(&mut instructions, &mut context);
-                }
-
-                Some(IRFunction {
-                    name: self.name.clone().unwrap(),
-                    return_size: IRSize::S32,
-                    instructions,
-                    arguments,
-                    variables: context.variables,
-                    strings: context.strings,
-                    vreg_count: context.vreg_counter,
-                })
-            }
-            None => {
-                log::info!("Empty function body");
-                None
-            }
-        }
-    }
-
-    fn eval_function_arguments(
-        &self,
-        result: &mut Vec<IRInstruction>,
-        context: &mut EvaluationContext,
-    ) -> IRArguments {
-        let arguments: Vec<_> = self
-            .decl_type
-            .get_function_arguments()
-            .unwrap()
-            .iter()
-            .filter(|&t| !t.is_void())
-            .cloned()
-            .collect();
-
-        let count = arguments.len();
-        let ir_arguments = arguments
-            .iter()
-            .map(|arg| context.get_size(&arg.clone().remove_name().array_promotion()))
-            .collect();
-
-        let in_register = context.backend.get_arguments_in_registers(&ir_arguments);
-        let vreg_count = in_register.iter().filter(|&&in_reg| in_reg).count() as u32;
-        context.vreg_counter += vreg_count * 2;
-        let mut vregs = Vec::new();
-        //let mut variable_location; //= Vec::new();
-        for arg in 0..arguments.len() {
-            let variable = IRVariable {
-                number: arg as u32,
-                size: ir_arguments[arg].clone(),
-                count: 1,
-            };
-            context.variables.push(variable);
-            if in_register[arg] {
-                let argument = arg as u32;
-                let addr = vreg_count + argument;
-                result.push(IRInstruction::AddrL(IRSize::P, addr, arg));
-                result.push(IRInstruction::Store(
-                    ir_arguments[arg].clone(),
-                    arg as u32,
-                    addr,
-                ));
-                vregs.push(Some(argument));
-            } else {
-                vregs.push(None);
-            }
-        }
-
-        IRArguments {
-            sizes: ir_arguments,
-            arguments: vregs,
-            variables: (0..arguments.len() as u32).map(|i| Some(i)).collect(),
-            count,
-        }
-    }
-
-    pub fn eval_global(
-        &self,
-        map: &HashMap<String, Symbol>,
-        defined: &mut HashSet<String>,
-        context: &mut EvaluationContext,
-    ) -> Option<IRGlobal> {
-        if self.name.is_none() {
-            return None;
-        }
-
-        let name = self.name.as_ref().unwrap();
-        if defined.contains(name) {
-            None
-        } else if let Some(_) = self.function_body {
-            defined.insert(name.clone());
-            None
-        } else if self.decl_type.is_function() {
-            log::trace!(
-                "Found non defined function {} with type {:?}",
-                name,
-                map[name].declaration_type
-            );
-            defined.insert(name.clone());
-            if map[name].declaration_type == DeclarationType::Definition {
-                None
-            } else {
-                let array_type: Type = map[name].symbol_type.get_return_type().unwrap().into();
-                let size = context
-                    .type_info
-                    .get_irsize(&array_type.get_element(), &context.struct_size_table);
-
-                Some(IRGlobal {
-                    name: name.clone(),
-                    size: size,
-                    count: 0,
-                    value: None,
-                    function: true,
-                })
-            }
-        } else {
-            if let Some(expression) = &self.expression {
-                defined.insert(name.clone());
-                if let ExpressionVariant::ConstI(value) = expression.variant {
-                    let (array_type, array_count) = map[name].symbol_type.deconstruct();
-                    let size = context
-                        .type_info
-                        .get_irsize(&array_type, &context.struct_size_table);
-
-                    Some(IRGlobal {
-                        name: name.clone(),
-                        size,
-                        count: array_count,
-                        value: Some(value),
-                        function: false,
-                    })
-                } else {
-                    unreachable!();
-                }
-            } else if map[name].declaration_type == DeclarationType::Declaration {
-                defined.insert(name.clone());
-
-                let (array_type, array_count) = map[name].symbol_type.deconstruct();
-                let size = context
-                    .type_info
-                    .get_irsize(&array_type, &context.struct_size_table);
-
-                Some(IRGlobal {
-                    name: name.clone(),
-                    size,
-                    count: array_count,
-                    value: None,
-                    function: false,
-                })
-            } else {
-                None
-            }
-        }
-    }
-}
+use super::{evaluation_context::EvaluateSize, Evaluate, EvaluationContext};
+use crate::backend::{Backend, TypeInfo};
+use crate::ir::*;
+use crate::options::OptimizationSettings;
+use crate::parser::r#type::{DeclarationType, StorageClass, StructType};
+use crate::parser::{ast::*, Type};
+use crate::table::Symbol;
+use std::collections::{HashMap, HashSet};
+
+impl ExternalDeclaration {
+    pub fn eval(
+        &self,
+        map: &HashMap<String, Symbol>,
+        globals: &mut Vec<IRGlobal>,
+        struct_type_table: &Vec<StructType>,
+        struct_size_table: &Vec<TypeInfo>,
+        struct_offset_table: &Vec<Vec<usize>>,
+        backend: &mut dyn Backend,
+        optimization_settings: &OptimizationSettings,
+    ) -> Option<IRFunction> {
+        match &self.function_body {
+            Some(statements) => {
+                let mut instructions = Vec::<IRInstruction>::new();
+                let mut context = EvaluationContext {
+                    vreg_counter: 0,
+                    label_counter: 1,
+                    variables: Vec::new(),
+                    strings: Vec::new(),
+                    unfixed_break: Vec::new(),
+                    unfixed_continue: Vec::new(),
+                    loop_depth: 0,
+                    switch_labels: Vec::new(),
+                    labels: HashMap::new(),
+                    unfixed_goto: Vec::new(),
+                    globals: Vec::new(),
+                    temporaries: Vec::new(),
+                    unfixed_temporaries: Vec::new(),
+                    return_pointer: None,
+                    backend,
+                    struct_type_table,
+                    struct_size_table,
+                    struct_offset_table,
+                    type_info: backend.get_type_info_table(),
+                    optimization_settings,
+                };
+                instructions.push(IRInstruction::Label(None, 0));
+
+                let arguments = self.eval_function_arguments(&mut instructions, &mut context);
+                let return_size = self.eval_return_size(&context);
+                for statement in statements {
+                    statement.eval(&mut instructions, &mut context);
+                }
+                context.fix_temporaries(&mut instructions);
+                globals.append(&mut context.globals);
+
+                Some(IRFunction {
+                    name: self.name.clone().unwrap(),
+                    return_size,
+                    instructions,
+                    arguments,
+                    variables: context.variables,
+                    strings: context.strings,
+                    vreg_count: context.vreg_counter,
+                    linkage: get_linkage(&map[self.name.as_ref().unwrap()]),
+                })
+            }
+            None => {
+                log::info!("Empty function body");
+                None
+            }
+        }
+    }
+
+    // Aggregates are returned as a pointer to their value, like in the Ret instruction
+    fn eval_return_size(&self, context: &EvaluationContext) -> IRSize {
+        let return_type: Type = self.decl_type.get_return_type().unwrap().into();
+        if return_type.is_struct() {
+            return IRSize::P;
+        }
+        // Values smaller than an int are returned extended to 32 bits, as in the return statements
+        match context.get_size(&return_type) {
+            IRSize::S8 | IRSize::S16 => IRSize::S32,
+            size => size,
+        }
+    }
+
+    fn eval_function_arguments(
+        &self,
+        result: &mut Vec<IRInstruction>,
+        context: &mut EvaluationContext,
+    ) -> IRArguments {
+        let arguments: Vec<_> = self
+            .decl_type
+            .get_function_arguments()
+            .unwrap()
+            .iter()
+            .filter(|&t| !t.is_void())
+            .cloned()
+            .collect();
+
+        let count = arguments.len();
+        let arguments: Vec<Type> = arguments
+            .iter()
+            .map(|arg| arg.clone().remove_name().array_promotion())
+            .collect();
+        let return_type: Type = self.decl_type.get_return_type().unwrap().into();
+        let return_sizes = match return_type.is_struct() {
+            true => context.get_aggregate_registers(&return_type),
+            false => None,
+        };
+        let return_pointer = return_type.is_struct() && return_sizes.is_none();
+        let sizes = context.get_argument_sizes(&arguments, return_pointer);
+        let ir_arguments: Vec<IRSize> = sizes.iter().flatten().cloned().collect();
+
+        // Arguments in registers are not necessarily the first arguments
+        // so every part of an argument reserves a vreg for its value and its address
+        let in_register = context.backend.get_arguments_in_registers(&ir_arguments);
+        let vreg_count = ir_arguments.len() as u32;
+        context.vreg_counter += vreg_count * 2;
+        let mut vregs = Vec::new();
+        let mut variables = Vec::new();
+
+        // The pointer to an aggregate returned in memory is kept until the function returns
+        if return_pointer {
+            let temporary = context.add_temporary(IRSize::P);
+            let addr = context.insert_temporary_address(result, temporary);
+            result.push(IRInstruction::Store(IRSize::P, 0, addr, false));
+            context.return_pointer = Some(temporary);
+            vregs.push(Some(0));
+            variables.push(None);
+        }
+
+        let mut part = return_pointer as u32;
+        for (arg, parts) in sizes.iter().skip(return_pointer as usize).enumerate() {
+            let in_register = in_register[part as usize];
+            let size = if in_register && arguments[arg].is_struct() {
+                context.get_aggregate_size(parts)
+            } else {
+                context.get_size(&arguments[arg])
+            };
+            let variable = IRVariable {
+                number: arg as u32,
+                size,
+                count: 1,
+            };
+            context.variables.push(variable);
+            let argument_vregs: Vec<u32> = (part..part + parts.len() as u32).collect();
+            if in_register {
+                let addr = vreg_count + part;
+                result.push(IRInstruction::AddrL(IRSize::P, addr, arg));
+                if arguments[arg].is_struct() {
+                    context.store_parts(result, parts, &argument_vregs, addr);
+                } else {
+                    result.push(IRInstruction::Store(size, part, addr, false));
+                }
+            }
+            for vreg in argument_vregs {
+                vregs.push(if in_register { Some(vreg) } else { None });
+                variables.push(Some(arg as u32));
+            }
+            part += parts.len() as u32;
+        }
+
+        IRArguments {
+            sizes: ir_arguments,
+            arguments: vregs,
+            variables,
+            count,
+            return_sizes: return_sizes.unwrap_or_default(),
+            return_variable: None,
+            variadic: self.decl_type.is_variadic(),
+        }
+    }
+
+    pub fn eval_global(
+        &self,
+        map: &HashMap<String, Symbol>,
+        defined: &mut HashSet<String>,
+        context: &mut EvaluationContext,
+    ) -> Option<IRGlobal> {
+        if self.name.is_none() || self.ast_type.is_typedef() {
+            return None;
+        }
+
+        let name = self.name.as_ref().unwrap();
+        let symbol = &map[name];
+        if defined.contains(name) {
+            None
+        } else if let Some(_) = self.function_body {
+            defined.insert(name.clone());
+            None
+        } else if self.decl_type.is_function() {
+            log::trace!(
+                "Found non defined function {} with type {:?}",
+                name,
+                symbol.declaration_type
+            );
+            defined.insert(name.clone());
+            if symbol.declaration_type == DeclarationType::Definition {
+                None
+            } else {
+                Some(context.eval_function_declaration(name, &symbol.symbol_type))
+            }
+        } else if let Some(initializer) = &self.expression {
+            defined.insert(name.clone());
+            Some(context.eval_global_variable(
+                name,
+                &symbol.symbol_type,
+                Some(initializer),
+                get_linkage(symbol),
+            ))
+        } else if symbol.declaration_type == DeclarationType::Tentative {
+            // Tentative definitions without a later definition are zero initialized
+            defined.insert(name.clone());
+            Some(context.eval_global_variable(name, &symbol.symbol_type, None, get_linkage(symbol)))
+        } else if symbol.declaration_type == DeclarationType::Declaration {
+            defined.insert(name.clone());
+            Some(context.eval_global_variable(name, &symbol.symbol_type, None, IRLinkage::Import))
+        } else {
+            None
+        }
+    }
+}
+
+// Only globals declared static are hidden from other modules
+pub(super) fn get_linkage(symbol: &Symbol) -> IRLinkage {
+    match symbol.storage_class {
+        StorageClass::Static => IRLinkage::Local,
+        StorageClass::Default | StorageClass::Extern => IRLinkage::Export,
+    }
+}
+
+impl EvaluationContext<'_> {
+    // Declares a function that is defined in another module
+    pub(super) fn eval_function_declaration(&self, name: &String, typ: &Type) -> IRGlobal {
+        let array_type: Type = typ.get_return_type().unwrap().into();
+        let size = self
+            .type_info
+            .get_irsize(&array_type.get_element(), &self.struct_size_table);
+
+        IRGlobal {
+            name: name.clone(),
+            size: size,
+            count: 0,
+            value: None,
+            function: true,
+            linkage: IRLinkage::Import,
+            read_only: false,
+        }
+    }
+
+    // Globals without a value are zero initialized or defined in another module
+    // Const globals are placed in read only memory
+    pub(super) fn eval_global_variable(
+        &mut self,
+        name: &String,
+        typ: &Type,
+        initializer: Option<&Initializer>,
+        linkage: IRLinkage,
+    ) -> IRGlobal {
+        let (array_type, array_count) = typ.deconstruct();
+        let size = self
+            .type_info
+            .get_irsize(&array_type, &self.struct_size_table);
+        let value =
+            initializer.map(|initializer| self.eval_global_initializer(name, typ, initializer));
+
+        IRGlobal {
+            name: name.clone(),
+            size,
+            count: array_count,
+            value,
+            function: false,
+            linkage,
+            read_only: typ.get_element_qualifiers().is_const,
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use super::{evaluation_context::EvaluateSize, Evaluate, EvaluationContext};
+use crate::ir::*;
+use crate::parser::{ast::*, r#type::BitField, Type, TypeNode};
+use crate::semantic_analysis::type_promotion::TypePromotion;
+
+impl EvaluationContext<'_> {
+    // Locals initialized by a braced list are zeroed where the list gives no value
+    pub(super) fn eval_initializer_list(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        list: &InitializerList,
+        typ: &Type,
+        variable: usize,
+    ) {
+        let addr = self.next_vreg();
+        result.push(IRInstruction::AddrL(IRSize::P, addr, variable));
+        let volatile = typ.get_element_qualifiers().is_volatile;
+        let mut end = 0;
+        for (offset, value_type, bit_field, expression) in self.get_initialized_values(typ, list) {
+            self.zero_fill(result, addr, end, offset, volatile);
+            let size = self.get_size(&value_type);
+            let vreg = expression.eval(result, self);
+            let target = self.offset_address(result, addr, offset);
+            let value_end = offset + self.sizeof(&value_type) as usize;
+            if let Some(bit_field) = bit_field {
+                // The storage unit is zeroed first, as only the bits of the bit-field are set
+                let start = std::cmp::max(end, offset);
+                self.zero_fill(result, addr, start, value_end, volatile);
+                let size = self.get_size(&value_type.promote());
+                let vreg = self.convert(result, size, &expression.ast_type, vreg);
+                self.store_bit_field(result, &value_type, bit_field, target, vreg, volatile);
+            } else if value_type.is_struct() {
+                result.push(IRInstruction::Copy(size, vreg, target));
+            } else {
+                let vreg = self.convert(result, size, &expression.ast_type, vreg);
+                result.push(IRInstruction::Store(size, vreg, target, volatile));
+            }
+            // Members can be placed in the storage unit of an earlier bit-field
+            end = std::cmp::max(end, value_end);
+        }
+        let size = self.sizeof(typ) as usize;
+        self.zero_fill(result, addr, end, size, volatile);
+    }
+
+    // Gives the initial value of a global, with zeroes where no value is given
+    pub(super) fn eval_global_initializer(
+        &mut self,
+        name: &String,
+        typ: &Type,
+        initializer: &Initializer,
+    ) -> Vec<IRGlobalValue> {
+        let values = match initializer {
+            Initializer::Expression(expression) => vec![(0, typ.clone(), None, expression)],
+            Initializer::List(list) => self.get_initialized_values(typ, list),
+        };
+
+        let mut result = Vec::new();
+        let mut end = 0;
+        for (offset, value_type, bit_field, expression) in values {
+            let size = self.get_size(&value_type);
+            let value = match (expression.is_constant(), bit_field) {
+                (true, Some(bit_field)) => {
+                    let mask = (1 << bit_field.width) - 1;
+                    let value = get_global_value(size, expression) & mask;
+                    IRGlobalValue::Value(size, value << bit_field.offset)
+                }
+                (true, None) => IRGlobalValue::Value(size, get_global_value(size, expression)),
+                (false, _) => {
+                    let (symbol, offset) = self.eval_address_constant(name, expression);
+                    IRGlobalValue::Address(symbol, offset)
+                }
+            };
+            if offset < end {
+                merge_global_value(&mut result, end, offset, value);
+            } else {
+                if offset > end {
+                    result.push(IRGlobalValue::Zero(offset - end));
+                }
+                result.push(value);
+            }
+            end = std::cmp::max(end, offset + self.sizeof(&value_type) as usize);
+        }
+        let size = self.sizeof(typ) as usize;
+        if size > end {
+            result.push(IRGlobalValue::Zero(size - end));
+        }
+        result
+    }
+
+    // Gives the symbol an address constant points into and the offset from that symbol
+    fn eval_address_constant(&mut self, name: &String, expression: &Expression) -> (String, i128) {
+        use ExpressionVariant::*;
+        if expression.ast_type.is_array() || expression.ast_type.is_function() {
+            return self.eval_lvalue_address_constant(name, expression);
+        }
+        match &expression.variant {
+            CString(string) => (self.eval_global_string(name, string), 0),
+            Unary(UnaryExpressionType::Address, exp) => {
+                self.eval_lvalue_address_constant(name, exp)
+            }
+            Cast(exp, _) => self.eval_address_constant(name, exp),
+            Binary(op, left, right) => {
+                let (pointer, index) = match right.is_constant() {
+                    true => (left, right),
+                    false => (right, left),
+                };
+                let (symbol, offset) = self.eval_address_constant(name, pointer);
+                let element = self.sizeof(&expression.ast_type.clone().deref()) as i128;
+                let index = index.get_const_value() * element;
+                match op {
+                    BinaryExpressionType::Subtract => (symbol, offset - index),
+                    _ => (symbol, offset + index),
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    fn eval_lvalue_address_constant(
+        &mut self,
+        name: &String,
+        expression: &Expression,
+    ) -> (String, i128) {
+        use ExpressionVariant::*;
+        match &expression.variant {
+            Ident(ident, ..) => (ident.clone(), 0),
+            CString(string) => (self.eval_global_string(name, string), 0),
+            Member(exp, _, indirect, index) => {
+                let (symbol, offset) = match indirect {
+                    true => self.eval_address_constant(name, exp),
+                    false => self.eval_lvalue_address_constant(name, exp),
+                };
+                let struct_index = exp.ast_type.get_struct_index();
+                let member = self.struct_offset_table[struct_index][*index as usize];
+                (symbol, offset + member as i128)
+            }
+            Binary(BinaryExpressionType::Index, left, right) => {
+                let (pointer, index) = match right.is_constant() {
+                    true => (left, right),
+                    false => (right, left),
+                };
+                let (symbol, offset) = self.eval_address_constant(name, pointer);
+                let element = self.sizeof(&expression.ast_type) as i128;
+                (symbol, offset + index.get_const_value() * element)
+            }
+            Unary(UnaryExpressionType::Deref, exp) => self.eval_address_constant(name, exp),
+            _ => unreachable!(),
+        }
+    }
+
+    // String literals in the initializer of a global are stored in a global of their own
+    fn eval_global_string(&mut self, name: &String, string: &Vec<u8>) -> String {
+        let name = format!("{}.string{}", name, self.globals.len());
+        let mut value: Vec<_> = string
+            .iter()
+            .map(|&c| IRGlobalValue::Value(IRSize::S8, c as i128))
+            .collect();
+        value.push(IRGlobalValue::Zero(1));
+        self.globals.push(IRGlobal {
+            name: name.clone(),
+            size: IRSize::S8,
+            count: value.len(),
+            value: Some(value),
+            function: false,
+            linkage: IRLinkage::Local,
+            read_only: false,
+        });
+        name
+    }
+
+    // Gives the values set by a list with their offset and type, ordered by their offset
+    // Values of bit-fields also give their position in the storage unit at the offset
+    fn get_initialized_values<'b>(
+        &self,
+        typ: &Type,
+        list: &'b InitializerList,
+    ) -> Vec<(usize, Type, Option<BitField>, &'b Expression)> {
+        let mut values: Vec<_> = list
+            .values
+            .iter()
+            .map(|(path, expression)| {
+                let (offset, value_type, bit_field) = self.get_initialized_object(typ, path);
+                (offset, value_type, bit_field, expression)
+            })
+            .collect();
+        values.sort_by_key(|&(offset, ..)| offset);
+        values
+    }
+
+    // Follows the indices of the members and elements containing a value
+    fn get_initialized_object(
+        &self,
+        typ: &Type,
+        path: &[usize],
+    ) -> (usize, Type, Option<BitField>) {
+        let mut offset = 0;
+        let mut typ = typ.clone();
+        let mut bit_field = None;
+        for &member in path {
+            match typ.nodes[0] {
+                TypeNode::Array(_) => {
+                    typ = typ.deref();
+                    offset += member * self.sizeof(&typ) as usize;
+                }
+                TypeNode::Struct(index) => {
+                    let struct_type = &self.struct_type_table[index];
+                    offset += self.struct_offset_table[index][member];
+                    typ = struct_type.members.as_ref().unwrap()[member].1.clone();
+                    bit_field = struct_type.bit_fields[member];
+                }
+                _ => unreachable!(),
+            }
+        }
+        (offset, typ, bit_field)
+    }
+
+    // Zeroes the memory between the offsets using the largest stores that stay aligned
+    fn zero_fill(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        addr: u32,
+        from: usize,
+        to: usize,
+        volatile: bool,
+    ) {
+        let mut offset = from;
+        while offset < to {
+            let size = [IRSize::S64, IRSize::S32, IRSize::S16, IRSize::S8]
+                .iter()
+                .map(|&size| (size, size.to_bit_width() / 8))
+                .find(|&(_, bytes)| offset % bytes == 0 && offset + bytes <= to);
+            let (size, bytes) = size.unwrap();
+            let zero = self.next_vreg();
+            result.push(IRInstruction::Imm(size, zero, 0));
+            let target = self.offset_address(result, addr, offset);
+            result.push(IRInstruction::Store(size, zero, target, volatile));
+            offset += bytes;
+        }
+    }
+}
+
+// Members that share the storage unit of a bit-field use different bits of it
+// The values from the offset up to the end are split into bytes, in which the value is merged
+fn merge_global_value(
+    values: &mut Vec<IRGlobalValue>,
+    end: usize,
+    offset: usize,
+    value: IRGlobalValue,
+) {
+    let mut start = end;
+    let mut bytes = Vec::new();
+    while start > offset {
+        let split = match values.pop().unwrap() {
+            IRGlobalValue::Zero(count) if start - count < offset => {
+                values.push(IRGlobalValue::Zero(count - (start - offset)));
+                vec![0; start - offset]
+            }
+            value => get_global_bytes(value),
+        };
+        start -= split.len();
+        bytes = [split, bytes].concat();
+    }
+
+    for (i, byte) in get_global_bytes(value).into_iter().enumerate() {
+        match bytes.get_mut(offset - start + i) {
+            Some(merged) => *merged |= byte,
+            None => bytes.push(byte),
+        }
+    }
+    let bytes = bytes.into_iter();
+    values.extend(bytes.map(|byte| IRGlobalValue::Value(IRSize::S8, byte as i128)));
+}
+
+// Values are stored in little endian order
+// Addresses are never in the storage unit of a bit-field, as they are aligned to the next unit
+fn get_global_bytes(value: IRGlobalValue) -> Vec<u8> {
+    match value {
+        IRGlobalValue::Zero(count) => vec![0; count],
+        IRGlobalValue::Value(size, value) => (0..size.to_bit_width() / 8)
+            .map(|i| (value >> (8 * i)) as u8)
+            .collect(),
+        IRGlobalValue::Address(..) => unreachable!(),
+    }
+}
+
+// Floating point values are stored as their bit pattern
+fn get_global_value(size: IRSize, expression: &Expression) -> i128 {
+    match (&expression.variant, size.is_float()) {
+        (&ExpressionVariant::ConstI(value), false) => value,
+        (&ExpressionVariant::ConstI(value), true) => size.float_bits(value as f64),
+        (&ExpressionVariant::ConstF(value), false) => value as i128,
+        (&ExpressionVariant::ConstF(value), true) => size.float_bits(value),
+        _ => unreachable!(),
+    }
+}
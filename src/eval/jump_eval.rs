@@ -7,6 +7,7 @@ use crate::eval::Evaluate;
 use crate::ir::*;
 use crate::parser::ast::Expression;
 use crate::parser::ast::{BinaryExpressionType, UnaryExpressionType};
+use crate::parser::Type;
 use crate::semantic_analysis::type_promotion::TypePromotion;
 
 use super::evaluation_context::EvaluationContext;
@@ -101,51 +102,196 @@ impl Expression {
                 _,
             ) => {
                 // Duplication of normal code, might not be wanted
-                let size = context
-                    .get_size(&(left.ast_type.promote(), right.ast_type.promote()).promote());
-                let left_size = context.get_size(&left.ast_type);
-                let right_size = context.get_size(&right.ast_type);
+                let op_type = (left.ast_type.promote(), right.ast_type.promote()).promote();
+                let size = context.get_size(&op_type);
+                let left_type = &left.ast_type;
+                let right_type = &right.ast_type;
 
                 let left = left.eval(result, context);
                 let right = right.eval(result, context);
-                let left = context.promote(result, size, left_size, left);
-                let right = context.promote(result, size, right_size, right);
+                let left = context.convert(result, size, left_type, left);
+                let right = context.convert(result, size, right_type, right);
                 let vreg = context.next_vreg();
 
-                result.push(match (op, jump_type) {
-                    (Equal, JumpType::Jcc) | (Inequal, JumpType::Jnc) => {
-                        IRInstruction::Eq(size, vreg, left, right)
-                    }
-                    (Inequal, JumpType::Jcc) | (Equal, JumpType::Jnc) => {
-                        IRInstruction::Ne(size, vreg, left, right)
-                    }
-                    (Less, JumpType::Jcc) | (GreaterEqual, JumpType::Jnc) => {
-                        IRInstruction::Lt(size, vreg, left, right)
-                    }
-                    (LessEqual, JumpType::Jcc) | (Greater, JumpType::Jnc) => {
-                        IRInstruction::Le(size, vreg, left, right)
-                    }
-                    (Greater, JumpType::Jcc) | (LessEqual, JumpType::Jnc) => {
-                        IRInstruction::Gt(size, vreg, left, right)
-                    }
-                    (GreaterEqual, JumpType::Jcc) | (Less, JumpType::Jnc) => {
-                        IRInstruction::Ge(size, vreg, left, right)
-                    }
+                // Inverting a floating point comparison is incorrect for unordered values
+                let (op, jump_type) = match (op, jump_type) {
+                    (op, JumpType::Jcc) => (op.clone(), JumpType::Jcc),
+                    (op, JumpType::Jnc) if size.is_float() => (op.clone(), JumpType::Jnc),
+                    (Equal, JumpType::Jnc) => (Inequal, JumpType::Jcc),
+                    (Inequal, JumpType::Jnc) => (Equal, JumpType::Jcc),
+                    (Less, JumpType::Jnc) => (GreaterEqual, JumpType::Jcc),
+                    (LessEqual, JumpType::Jnc) => (Greater, JumpType::Jcc),
+                    (Greater, JumpType::Jnc) => (LessEqual, JumpType::Jcc),
+                    (GreaterEqual, JumpType::Jnc) => (Less, JumpType::Jcc),
                     _ => unreachable!(),
-                });
+                };
+                result.push(op.to_instruction(&op_type, size, vreg, left, right));
 
                 let (index, _) = context.insert_place_holder_jump(result);
-                list.push((index, vreg, context.type_info.int.irsize, JumpType::Jcc));
+                list.push((index, vreg, context.type_info.int.irsize, jump_type));
             }
 
             _ => {
                 let vreg = self.eval(result, context);
-                let from = context.get_size(&self.ast_type);
                 let size = context.get_size(&self.ast_type.promote());
-                let cond = context.promote(result, size, from, vreg);
+                let cond = context.convert(result, size, &self.ast_type, vreg);
+                let (size, cond) = context.condition(result, size, cond);
                 let (index, _label) = context.insert_place_holder_jump(result);
                 list.push((index, cond, size, jump_type));
             }
         }
     }
 }
+
+// The labels of the cases in a switch statement, which are collected while evaluating the body
+#[derive(Debug, Clone, Default)]
+pub struct SwitchLabels {
+    pub cases: Vec<(i128, IRLabel)>,
+    pub default: Option<IRLabel>,
+}
+
+impl<'a> EvaluationContext<'a> {
+    // Evaluates the jump to the correct case of a switch statement
+    // Dense cases use a jump table, otherwise a binary search over the cases is done
+    // Returns the jumps that should go to the end of the switch when there is no default
+    pub fn switch_eval(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        typ: &Type,
+        vreg: IRReg,
+        mut labels: SwitchLabels,
+    ) -> Vec<usize> {
+        labels.cases.sort_by_key(|&(value, _)| value);
+        let cases = &labels.cases;
+        let count = cases.len() as i128;
+        let mut end_jumps = Vec::new();
+
+        if count >= 4 && cases[cases.len() - 1].0 - cases[0].0 < 3 * count {
+            self.jump_table_eval(result, typ, vreg, cases, labels.default, &mut end_jumps);
+        } else {
+            self.binary_search_eval(result, typ, vreg, cases, labels.default, &mut end_jumps);
+        }
+        end_jumps
+    }
+
+    // Points the jumps without a default label to the end of the switch statement
+    pub fn fix_switch_end(&mut self, result: &mut Vec<IRInstruction>, jumps: &[usize], label: u32) {
+        for &index in jumps {
+            match &mut result[index] {
+                IRInstruction::Jmp(target) | IRInstruction::Jcc(.., target) => *target = label,
+                IRInstruction::JmpTable(.., targets) => {
+                    for target in targets.iter_mut().filter(|target| **target == 0) {
+                        *target = label;
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    // Compares against the middle case until at most three cases are left
+    // These are then compared one by one
+    fn binary_search_eval(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        typ: &Type,
+        vreg: IRReg,
+        cases: &[(i128, IRLabel)],
+        default: Option<IRLabel>,
+        end_jumps: &mut Vec<usize>,
+    ) {
+        let size = self.get_size(typ);
+        let int_size = self.type_info.int.irsize;
+
+        if cases.len() <= 3 {
+            for &(value, label) in cases {
+                let constant = self.next_vreg();
+                let cond = self.next_vreg();
+                result.push(IRInstruction::Imm(size, constant, value));
+                let equal = BinaryExpressionType::Equal;
+                result.push(equal.to_instruction(typ, size, cond, vreg, constant));
+                result.push(IRInstruction::Jcc(int_size, cond, label));
+                self.insert_label(result);
+            }
+            self.default_jump(result, default, end_jumps);
+            return;
+        }
+
+        let middle = cases.len() / 2;
+        let constant = self.next_vreg();
+        let cond = self.next_vreg();
+        result.push(IRInstruction::Imm(size, constant, cases[middle].0));
+        let less = BinaryExpressionType::Less;
+        result.push(less.to_instruction(typ, size, cond, vreg, constant));
+        let (index, _) = self.insert_place_holder_jump(result);
+
+        self.binary_search_eval(result, typ, vreg, &cases[middle..], default, end_jumps);
+        let label = self.get_current_label();
+        result[index] = IRInstruction::Jcc(int_size, cond, label);
+        self.binary_search_eval(result, typ, vreg, &cases[..middle], default, end_jumps);
+    }
+
+    // Subtracting the lowest case turns the value into an index in the table
+    // Values outside of the table are caught by a single unsigned comparison
+    fn jump_table_eval(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        typ: &Type,
+        vreg: IRReg,
+        cases: &[(i128, IRLabel)],
+        default: Option<IRLabel>,
+        end_jumps: &mut Vec<usize>,
+    ) {
+        let size = self.get_size(typ);
+        let int_size = self.type_info.int.irsize;
+        let table_size = self.int_ptr(false);
+        let min = cases[0].0;
+        let max = cases[cases.len() - 1].0;
+
+        let constant = self.next_vreg();
+        let index = self.next_vreg();
+        let limit = self.next_vreg();
+        let cond = self.next_vreg();
+        result.push(IRInstruction::Imm(size, constant, min));
+        result.push(IRInstruction::Sub(size, index, vreg, constant));
+        result.push(IRInstruction::Imm(size, limit, max - min));
+        result.push(IRInstruction::Gtu(size, cond, index, limit));
+        if default.is_none() {
+            end_jumps.push(result.len());
+        }
+        result.push(IRInstruction::Jcc(int_size, cond, default.unwrap_or(0)));
+        self.insert_label(result);
+
+        let index = if size != table_size {
+            let wide = self.next_vreg();
+            result.push(IRInstruction::Cvu(table_size, wide, size, index));
+            wide
+        } else {
+            index
+        };
+
+        let mut table = vec![default.unwrap_or(0); (max - min + 1) as usize];
+        for &(value, label) in cases {
+            table[(value - min) as usize] = label;
+        }
+        if table.contains(&0) {
+            end_jumps.push(result.len());
+        }
+        result.push(IRInstruction::JmpTable(table_size, index, table));
+        self.insert_label(result);
+    }
+
+    // Jumps to the default case, or to the end of the switch if there is none
+    fn default_jump(
+        &mut self,
+        result: &mut Vec<IRInstruction>,
+        default: Option<IRLabel>,
+        end_jumps: &mut Vec<usize>,
+    ) {
+        if default.is_none() {
+            end_jumps.push(result.len());
+        }
+        result.push(IRInstruction::Jmp(default.unwrap_or(0)));
+        self.insert_label(result);
+    }
+}
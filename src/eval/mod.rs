@@ -1,123 +1,179 @@
-use std::collections::{HashMap, HashSet};
-
-use crate::backend::{Backend, TypeInfo, TypeInfoTable};
-use crate::ir::*;
-use crate::options::OptimizationSettings;
-use crate::parser::ast::*;
-use crate::parser::r#type::StructType;
-use crate::table::struct_table::StructTable;
-use crate::table::Symbol;
-use crate::utility::padding;
-
-use self::evaluation_context::EvaluationContext;
-
-pub mod evaluation_context;
-mod expression_eval;
-mod global_eval;
-mod jump_eval;
-mod optimize;
-mod statement_eval;
-
-// This module is used to evaluate the AST into an IR
-
-// The trait Evaluate is used by statements and expressions
-// The vreg counter should be updated every use
-// The function returns the virtual register representing its result
-trait Evaluate {
-    fn eval(&self, result: &mut Vec<IRInstruction>, context: &mut EvaluationContext) -> u32;
-}
-
-// The public function used to evaluate the ast
-pub fn evaluate(
-    ast: &mut TranslationUnit,
-    map: &HashMap<String, Symbol>,
-    backend: &mut dyn Backend,
-    struct_table: StructTable,
-    optimization_settings: &OptimizationSettings,
-) -> IRModule {
-    self::optimize::optimize(ast, backend, &struct_table, optimization_settings);
-
-    let mut functions = Vec::<IRFunction>::new();
-    let mut function_names = HashSet::<String>::new();
-    for global in &ast.global_declarations {
-        if let Some(declaration) = global.eval(
-            &struct_table.info,
-            &struct_table.offsets,
-            backend,
-            optimization_settings,
-        ) {
-            function_names.insert(declaration.name.clone());
-            functions.push(declaration);
-        }
-    }
-
-    let mut globals = Vec::new();
-    let mut defined = HashSet::new();
-
-    let mut context = EvaluationContext {
-        vreg_counter: 0,
-        label_counter: 1,
-        variables: Vec::new(),
-        strings: Vec::new(),
-        unfixed_break: Vec::new(),
-        unfixed_continue: Vec::new(),
-        loop_depth: 0,
-        backend,
-        struct_size_table: &struct_table.info,
-        struct_offset_table: &struct_table.offsets,
-        type_info: backend.get_type_info_table(),
-        optimization_settings,
-    };
-
-    for global in &ast.global_declarations {
-        log::trace!("Evaluating individual global");
-        if let Some(declaration) = global.eval_global(map, &mut defined, &mut context) {
-            globals.push(declaration);
-        }
-    }
-
-    IRModule {
-        functions,
-        globals,
-        function_names,
-    }
-}
-
-impl StructTable {
-    /*pub fn to_info(&self, backend: &dyn Backend) -> (Vec<TypeInfo>, Vec<Vec<usize>>) {
-        let mut size_list: Vec<TypeInfo> = Vec::new();
-        let mut offset_list: Vec<Vec<usize>> = Vec::new();
-        for object in &self.structs {
-            let (info, offsets) = object.to_info(backend, &size_list);
-            size_list.push(info);
-            offset_list.push(offsets);
-        }
-        (size_list, offset_list)
-    }*/
-}
-
-impl StructType {
-    pub fn to_info(
-        &self,
-        type_info: &TypeInfoTable,
-        struct_size: &Vec<TypeInfo>,
-    ) -> (TypeInfo, Vec<usize>) {
-        let (size, align, offsets) = match &self.members {
-            Some(members) => members.iter().fold(
-                (0, 1, Vec::<usize>::new()),
-                |(offset, alignment, mut offset_list), (_, typ)| {
-                    let element_alignment = type_info.sizeof_element(typ, struct_size) as usize;
-                    let alignment = std::cmp::max(alignment, element_alignment);
-                    let offset = offset + padding(offset, element_alignment);
-                    let sizeof = type_info.sizeof(typ, struct_size) as usize;
-                    offset_list.push(offset);
-                    (offset + sizeof, alignment, offset_list)
-                },
-            ),
-            None => (0, 1, Vec::new()),
-        };
-
-        let size = size + padding(size, align);
-        (TypeInfo::new(size, align, align), offsets)
-    }
-}
+use std::collections::{HashMap, HashSet};
+
+use crate::backend::{Backend, TypeInfo, TypeInfoTable};
+use crate::ir::*;
+use crate::options::OptimizationSettings;
+use crate::parser::ast::*;
+use crate::parser::r#type::{BitField, StructType};
+use crate::table::struct_table::StructTable;
+use crate::table::Symbol;
+use crate::utility::padding;
+
+use self::evaluation_context::EvaluationContext;
+
+pub mod evaluation_context;
+mod expression_eval;
+mod global_eval;
+mod initializer_eval;
+mod jump_eval;
+mod optimize;
+mod statement_eval;
+
+// This module is used to evaluate the AST into an IR
+
+// The trait Evaluate is used by statements and expressions
+// The vreg counter should be updated every use
+// The function returns the virtual register representing its result
+trait Evaluate {
+    fn eval(&self, result: &mut Vec<IRInstruction>, context: &mut EvaluationContext) -> u32;
+}
+
+// The public function used to evaluate the ast
+pub fn evaluate(
+    ast: &mut TranslationUnit,
+    map: &HashMap<String, Symbol>,
+    backend: &mut dyn Backend,
+    struct_table: StructTable,
+    optimization_settings: &OptimizationSettings,
+) -> IRModule {
+    self::optimize::optimize(ast, backend, &struct_table, optimization_settings);
+
+    let mut functions = Vec::<IRFunction>::new();
+    let mut function_names = HashSet::<String>::new();
+    let mut block_globals = Vec::new();
+    for global in &ast.global_declarations {
+        if let Some(declaration) = global.eval(
+            map,
+            &mut block_globals,
+            &struct_table.structs,
+            &struct_table.info,
+            &struct_table.offsets,
+            backend,
+            optimization_settings,
+        ) {
+            function_names.insert(declaration.name.clone());
+            functions.push(declaration);
+        }
+    }
+
+    let mut globals = Vec::new();
+    let mut defined = HashSet::new();
+
+    let mut context = EvaluationContext {
+        vreg_counter: 0,
+        label_counter: 1,
+        variables: Vec::new(),
+        strings: Vec::new(),
+        unfixed_break: Vec::new(),
+        unfixed_continue: Vec::new(),
+        loop_depth: 0,
+        switch_labels: Vec::new(),
+        labels: HashMap::new(),
+        unfixed_goto: Vec::new(),
+        globals: Vec::new(),
+        temporaries: Vec::new(),
+        unfixed_temporaries: Vec::new(),
+        return_pointer: None,
+        backend,
+        struct_type_table: &struct_table.structs,
+        struct_size_table: &struct_table.info,
+        struct_offset_table: &struct_table.offsets,
+        type_info: backend.get_type_info_table(),
+        optimization_settings,
+    };
+
+    for global in &ast.global_declarations {
+        log::trace!("Evaluating individual global");
+        if let Some(declaration) = global.eval_global(map, &mut defined, &mut context) {
+            globals.push(declaration);
+        }
+    }
+    globals.append(&mut context.globals);
+
+    // Block scope externs are only declared if the global is not already known
+    for global in block_globals {
+        if global.linkage != IRLinkage::Import || defined.insert(global.name.clone()) {
+            globals.push(global);
+        }
+    }
+
+    IRModule {
+        functions,
+        globals,
+        function_names,
+    }
+}
+
+impl StructTable {
+    /*pub fn to_info(&self, backend: &dyn Backend) -> (Vec<TypeInfo>, Vec<Vec<usize>>) {
+        let mut size_list: Vec<TypeInfo> = Vec::new();
+        let mut offset_list: Vec<Vec<usize>> = Vec::new();
+        for object in &self.structs {
+            let (info, offsets) = object.to_info(backend, &size_list);
+            size_list.push(info);
+            offset_list.push(offsets);
+        }
+        (size_list, offset_list)
+    }*/
+}
+
+impl StructType {
+    // Bit-fields are packed into storage units of their declared type, like the System V ABI does
+    // A bit-field that would cross the boundary of its unit starts in the next unit instead
+    // Unnamed bit-fields do not affect the alignment of the struct
+    pub fn to_info(
+        &self,
+        type_info: &TypeInfoTable,
+        struct_size: &Vec<TypeInfo>,
+    ) -> (TypeInfo, Vec<usize>, Vec<Option<BitField>>) {
+        let members = match &self.members {
+            Some(members) => members,
+            None => return (TypeInfo::new(0, 1, 1), Vec::new(), Vec::new()),
+        };
+
+        // The number of bits used by the members, which all start at the beginning of a union
+        let mut end = 0;
+        let mut align = 1;
+        let mut offsets = Vec::new();
+        let mut bit_fields = Vec::new();
+        for ((name, typ), bit_field) in members.iter().zip(&self.bit_fields) {
+            let alignment = type_info.alignof(typ, struct_size) as usize;
+            let sizeof = type_info.sizeof(typ, struct_size) as usize;
+            let start = if self.is_union { 0 } else { end };
+            match bit_field {
+                Some(field) => {
+                    let unit = 8 * sizeof;
+                    let width = field.width as usize;
+                    // A bit-field of width zero moves the next bit-field to a new unit
+                    let start = if width == 0 || start / unit != (start + width - 1) / unit {
+                        start + padding(start, unit)
+                    } else {
+                        start
+                    };
+                    let offset = start / unit * sizeof;
+                    offsets.push(offset);
+                    bit_fields.push(Some(BitField {
+                        width: field.width,
+                        offset: (start - 8 * offset) as u32,
+                    }));
+                    end = std::cmp::max(end, start + width);
+                    if !name.is_empty() {
+                        align = std::cmp::max(align, alignment);
+                    }
+                }
+                None => {
+                    let offset = (start + 7) / 8;
+                    let offset = offset + padding(offset, alignment);
+                    offsets.push(offset);
+                    bit_fields.push(None);
+                    end = std::cmp::max(end, 8 * (offset + sizeof));
+                    align = std::cmp::max(align, alignment);
+                }
+            }
+        }
+
+        let size = (end + 7) / 8;
+        let size = size + padding(size, align);
+        (TypeInfo::new(size, align, align), offsets, bit_fields)
+    }
+}
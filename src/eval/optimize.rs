@@ -109,7 +109,7 @@ impl Optimize for Statement {
                 expression,
                 statement: _,
             } => {
-                if let Some(init) = init {
+                for init in init {
                     init.constant_eval(optimizer);
                 }
                 if let Some(expression) = expression {
@@ -120,11 +120,12 @@ impl Optimize for Statement {
                 }
             }
 
+            Statement::Declaration {
+                init: Some(init), ..
+            } => init.constant_eval(optimizer),
+
             Statement::While { expression, .. }
-            | Statement::Declaration {
-                init: Some(expression),
-                ..
-            }
+            | Statement::Switch { expression, .. }
             | Statement::Return {
                 expression: Some(expression),
                 ..
@@ -138,11 +139,28 @@ impl Optimize for Statement {
             | Statement::Return { .. }
             | Statement::Continue { span: _ }
             | Statement::Break { span: _ }
+            | Statement::Case { .. }
+            | Statement::Default { .. }
+            | Statement::Goto { .. }
+            | Statement::Label { .. }
             | Statement::Empty(_) => (),
         }
     }
 }
 
+impl Optimize for Initializer {
+    fn constant_eval(&mut self, optimizer: &Optimizer) {
+        match self {
+            Initializer::Expression(expression) => expression.constant_eval(optimizer),
+            Initializer::List(list) => {
+                for (_, expression) in &mut list.values {
+                    expression.constant_eval(optimizer);
+                }
+            }
+        }
+    }
+}
+
 impl Optimize for Expression {
     fn constant_eval(&mut self, optimizer: &Optimizer) {
         let span = Span::empty();
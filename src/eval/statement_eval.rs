@@ -1,193 +1,324 @@
-use super::{Evaluate, EvaluationContext};
-use crate::eval::evaluation_context::EvaluateSize;
-use crate::eval::jump_eval::JumpType;
-use crate::ir::*;
-use crate::parser::ast::*;
-
-impl Evaluate for Statement {
-    fn eval(&self, result: &mut Vec<IRInstruction>, context: &mut EvaluationContext) -> u32 {
-        use Statement::*;
-        match self {
-            Break { .. } => {
-                let (index, _) = context.insert_place_holder_jump(result);
-                context.add_break(index);
-            }
-
-            Continue { .. } => {
-                let (index, _) = context.insert_place_holder_jump(result);
-                context.add_continue(index);
-            }
-
-            Compound {
-                span: _,
-                statements,
-            } => {
-                for stmt in statements {
-                    stmt.eval(result, context);
-                }
-            }
-
-            Declaration {
-                span: _,
-                ident: _,
-                decl_type,
-                ast_type: _,
-                init,
-            } => {
-                let index = context.variables.len();
-                let (array_type, array_count) = decl_type.deconstruct();
-                let size = context
-                    .type_info
-                    .get_irsize(&array_type, &context.struct_size_table);
-                let variable = IRVariable {
-                    size,
-                    count: array_count,
-                    number: index as u32,
-                };
-                context.variables.push(variable);
-                if let Some(exp) = init {
-                    let exp_size = context.get_size(&exp.ast_type.array_promotion());
-                    let vreg = exp.eval(result, context);
-                    let vreg = context.promote(result, size, exp_size, vreg);
-                    let addr = context.next_vreg();
-                    result.push(IRInstruction::AddrL(IRSize::P, addr, index));
-                    result.push(IRInstruction::Store(size, vreg, addr));
-                }
-            }
-
-            Empty(_) => (),
-
-            Expression {
-                span: _,
-                expression,
-            } => {
-                expression.eval(result, context);
-            }
-
-            If {
-                span: _,
-                expression,
-                statement,
-                else_statement,
-            } => {
-                /*let cond = expression.eval(result, context);
-                let size = context.get_size(&expression.ast_type);
-
-                let (index, _) = context.insert_place_holder_jump(result);*/
-                let jumps = expression.condition_eval(result, context, JumpType::Jnc);
-
-                statement.eval(result, context);
-
-                if let Some(statement) = else_statement {
-                    let (else_index, else_label) = context.insert_place_holder_jump(result);
-                    statement.eval(result, context);
-
-                    let (last_index, label) = context.insert_place_holder_jump(result);
-                    //result[index] = IRInstruction::Jnc(size, cond, else_label);
-                    context.fix_jumps(result, &jumps, else_label);
-                    result[else_index] = IRInstruction::Jmp(label);
-                    result[last_index] = IRInstruction::Jmp(label);
-                } else {
-                    let (last_index, label) = context.insert_place_holder_jump(result);
-                    //result[index] = IRInstruction::Jnc(size, cond, label);
-                    context.fix_jumps(result, &jumps, label);
-                    result[last_index] = IRInstruction::Jmp(label);
-                }
-            }
-
-            For {
-                span: _,
-                init,
-                condition,
-                expression,
-                statement,
-            } => {
-                /*let size = condition
-                .as_ref()
-                .map(|exp| context.get_size(&exp.ast_type))
-                .unwrap_or(IRSize::S32);*/
-
-                context.enter_loop();
-                if let Some(init) = init {
-                    init.eval(result, context);
-                }
-
-                let (jmp_index, loop_label) = context.insert_place_holder_jump(result);
-
-                statement.eval(result, context);
-                let continue_label = context.insert_fall_through(result);
-
-                expression.as_ref().map(|exp| exp.eval(result, context));
-
-                let check_label = context.insert_fall_through(result);
-
-                let jumps = condition
-                    .as_ref()
-                    .map(|cond| cond.condition_eval(result, context, JumpType::Jcc));
-                let (last_index, label_after) = if jumps.is_none() {
-                    context.insert_place_holder_jump(result)
-                } else {
-                    (0, context.get_current_label())
-                };
-
-                context.fix_break_continue(result, label_after, continue_label);
-                result[jmp_index] = IRInstruction::Jmp(check_label);
-                match jumps {
-                    Some(jumps) => context.fix_jumps(result, &jumps, loop_label), //IRInstruction::Jcc(size, comparison, loop_label),
-                    None => result[last_index] = IRInstruction::Jmp(loop_label),
-                };
-            }
-
-            // The check is done last, therefore an extra jump is inserted at the front
-            // In most cases this should lead to a speedup as most loops are entered
-            While {
-                span: _,
-                expression,
-                statement,
-                do_while,
-            } => {
-                //let size = context.get_size(&expression.ast_type);
-                context.enter_loop();
-                let (jmp_index, loop_label) = context.insert_place_holder_jump(result);
-
-                statement.eval(result, context);
-                let check_label = context.insert_fall_through(result);
-
-                //let expression = expression.eval(result, context);
-                //let (last_index, label_after) = context.insert_place_holder_jump(result);
-                let jumps = expression.condition_eval(result, context, JumpType::Jcc);
-                let label_after = context.get_current_label();
-                context.fix_break_continue(result, label_after, check_label);
-
-                //result[last_index] = IRInstruction::Jcc(size, expression, loop_label);
-                context.fix_jumps(result, &jumps, loop_label);
-                result[jmp_index] = IRInstruction::Jmp(match do_while {
-                    true => loop_label,
-                    false => check_label,
-                });
-            }
-
-            Return {
-                span: _,
-                ast_type,
-                expression,
-            } => {
-                if let Some(exp) = expression {
-                    let size = context.get_size(&ast_type);
-                    let exp_size = context.get_size(&exp.ast_type);
-                    let vreg = exp.eval(result, context);
-                    let vreg = context.promote(result, size, exp_size, vreg);
-                    result.push(IRInstruction::Ret(size, vreg))
-                } else {
-                    use crate::parser::Type;
-                    // Creates a temporary that isn't used.
-                    let vreg = context.next_vreg();
-                    result.push(IRInstruction::Imm(context.get_size(&Type::int()), vreg, 0));
-                    result.push(IRInstruction::Ret(IRSize::V, vreg))
-                }
-                context.insert_label(result);
-            }
-        }
-        0
-    }
-}
+use super::{Evaluate, EvaluationContext};
+use crate::eval::evaluation_context::EvaluateSize;
+use crate::eval::jump_eval::{JumpType, SwitchLabels};
+use crate::ir::*;
+use crate::parser::ast::*;
+use crate::parser::r#type::StorageClass;
+use crate::semantic_analysis::type_promotion::TypePromotion;
+
+impl Evaluate for Statement {
+    fn eval(&self, result: &mut Vec<IRInstruction>, context: &mut EvaluationContext) -> u32 {
+        use Statement::*;
+        match self {
+            Break { .. } => {
+                let (index, _) = context.insert_place_holder_jump(result);
+                context.add_break(index);
+            }
+
+            Continue { .. } => {
+                let (index, _) = context.insert_place_holder_jump(result);
+                context.add_continue(index);
+            }
+
+            Compound {
+                span: _,
+                statements,
+            } => {
+                for stmt in statements {
+                    stmt.eval(result, context);
+                }
+            }
+
+            // The body is evaluated first to find all cases
+            // The controlling expression and the jump to the right case are placed after it
+            Switch {
+                span: _,
+                expression,
+                statement,
+            } => {
+                let typ = expression.ast_type.promote();
+                let size = context.get_size(&typ);
+                context.enter_loop();
+                context.switch_labels.push(SwitchLabels::default());
+                let (jmp_index, _) = context.insert_place_holder_jump(result);
+
+                statement.eval(result, context);
+                let (last_index, switch_label) = context.insert_place_holder_jump(result);
+                let labels = context.switch_labels.pop().unwrap();
+
+                let vreg = expression.eval(result, context);
+                let vreg = context.convert(result, size, &expression.ast_type, vreg);
+                let end_jumps = context.switch_eval(result, &typ, vreg, labels);
+                let label_after = context.get_current_label();
+
+                context.fix_switch_end(result, &end_jumps, label_after);
+                context.fix_break(result, label_after);
+                result[jmp_index] = IRInstruction::Jmp(switch_label);
+                result[last_index] = IRInstruction::Jmp(label_after);
+            }
+
+            Case {
+                span: _,
+                expression,
+                statement,
+            } => {
+                let label = context.insert_fall_through(result);
+                let value = expression.get_const_value();
+                let labels = context.switch_labels.last_mut().unwrap();
+                labels.cases.push((value, label));
+                statement.eval(result, context);
+            }
+
+            Default { span: _, statement } => {
+                let label = context.insert_fall_through(result);
+                context.switch_labels.last_mut().unwrap().default = Some(label);
+                statement.eval(result, context);
+            }
+
+            Goto { span: _, label } => {
+                let (index, _) = context.insert_place_holder_jump(result);
+                context.add_goto(result, index, label);
+            }
+
+            Label {
+                span: _,
+                label,
+                statement,
+            } => {
+                let target = context.insert_fall_through(result);
+                context.add_label(result, label, target);
+                statement.eval(result, context);
+            }
+
+            Declaration {
+                span: _,
+                ident,
+                decl_type,
+                ast_type,
+                init,
+            } => {
+                // Type declarations do not create a variable
+                if ident.is_none() || ast_type.is_typedef() {
+                    return 0;
+                }
+
+                // Statics and externs in a block are stored in globals instead of variables
+                let name = ident.as_ref().unwrap();
+                if decl_type.is_function() {
+                    let global = context.eval_function_declaration(name, decl_type);
+                    context.globals.push(global);
+                    return 0;
+                }
+                match ast_type.get_storage_class() {
+                    StorageClass::Static => {
+                        let global = context.eval_global_variable(
+                            name,
+                            decl_type,
+                            init.as_ref(),
+                            IRLinkage::Local,
+                        );
+                        context.globals.push(global);
+                        return 0;
+                    }
+                    StorageClass::Extern => {
+                        let global =
+                            context.eval_global_variable(name, decl_type, None, IRLinkage::Import);
+                        context.globals.push(global);
+                        return 0;
+                    }
+                    StorageClass::Default => (),
+                }
+
+                let index = context.variables.len();
+                let (array_type, array_count) = decl_type.deconstruct();
+                let size = context
+                    .type_info
+                    .get_irsize(&array_type, &context.struct_size_table);
+                let variable = IRVariable {
+                    size,
+                    count: array_count,
+                    number: index as u32,
+                };
+                context.variables.push(variable);
+                match init {
+                    Some(Initializer::List(list)) => {
+                        context.eval_initializer_list(result, list, decl_type, index);
+                    }
+                    Some(Initializer::Expression(exp)) if decl_type.is_struct() => {
+                        let from = exp.eval(result, context);
+                        let addr = context.next_vreg();
+                        result.push(IRInstruction::AddrL(IRSize::P, addr, index));
+                        result.push(IRInstruction::Copy(size, from, addr));
+                    }
+                    Some(Initializer::Expression(exp)) => {
+                        let vreg = exp.eval(result, context);
+                        let vreg = context.convert(result, size, &exp.ast_type, vreg);
+                        let addr = context.next_vreg();
+                        result.push(IRInstruction::AddrL(IRSize::P, addr, index));
+                        let volatile = decl_type.is_volatile();
+                        result.push(IRInstruction::Store(size, vreg, addr, volatile));
+                    }
+                    None => (),
+                }
+            }
+
+            Empty(_) => (),
+
+            Expression {
+                span: _,
+                expression,
+            } => {
+                expression.eval(result, context);
+            }
+
+            If {
+                span: _,
+                expression,
+                statement,
+                else_statement,
+            } => {
+                /*let cond = expression.eval(result, context);
+                let size = context.get_size(&expression.ast_type);
+
+                let (index, _) = context.insert_place_holder_jump(result);*/
+                let jumps = expression.condition_eval(result, context, JumpType::Jnc);
+
+                statement.eval(result, context);
+
+                if let Some(statement) = else_statement {
+                    let (else_index, else_label) = context.insert_place_holder_jump(result);
+                    statement.eval(result, context);
+
+                    let (last_index, label) = context.insert_place_holder_jump(result);
+                    //result[index] = IRInstruction::Jnc(size, cond, else_label);
+                    context.fix_jumps(result, &jumps, else_label);
+                    result[else_index] = IRInstruction::Jmp(label);
+                    result[last_index] = IRInstruction::Jmp(label);
+                } else {
+                    let (last_index, label) = context.insert_place_holder_jump(result);
+                    //result[index] = IRInstruction::Jnc(size, cond, label);
+                    context.fix_jumps(result, &jumps, label);
+                    result[last_index] = IRInstruction::Jmp(label);
+                }
+            }
+
+            For {
+                span: _,
+                init,
+                condition,
+                expression,
+                statement,
+            } => {
+                /*let size = condition
+                .as_ref()
+                .map(|exp| context.get_size(&exp.ast_type))
+                .unwrap_or(IRSize::S32);*/
+
+                context.enter_loop();
+                for init in init {
+                    init.eval(result, context);
+                }
+
+                let (jmp_index, loop_label) = context.insert_place_holder_jump(result);
+
+                statement.eval(result, context);
+                let continue_label = context.insert_fall_through(result);
+
+                expression.as_ref().map(|exp| exp.eval(result, context));
+
+                let check_label = context.insert_fall_through(result);
+
+                let jumps = condition
+                    .as_ref()
+                    .map(|cond| cond.condition_eval(result, context, JumpType::Jcc));
+                let (last_index, label_after) = if jumps.is_none() {
+                    context.insert_place_holder_jump(result)
+                } else {
+                    (0, context.get_current_label())
+                };
+
+                context.fix_break_continue(result, label_after, continue_label);
+                result[jmp_index] = IRInstruction::Jmp(check_label);
+                match jumps {
+                    Some(jumps) => context.fix_jumps(result, &jumps, loop_label), //IRInstruction::Jcc(size, comparison, loop_label),
+                    None => result[last_index] = IRInstruction::Jmp(loop_label),
+                };
+            }
+
+            // The check is done last, therefore an extra jump is inserted at the front
+            // In most cases this should lead to a speedup as most loops are entered
+            While {
+                span: _,
+                expression,
+                statement,
+                do_while,
+            } => {
+                //let size = context.get_size(&expression.ast_type);
+                context.enter_loop();
+                let (jmp_index, loop_label) = context.insert_place_holder_jump(result);
+
+                statement.eval(result, context);
+                let check_label = context.insert_fall_through(result);
+
+                //let expression = expression.eval(result, context);
+                //let (last_index, label_after) = context.insert_place_holder_jump(result);
+                let jumps = expression.condition_eval(result, context, JumpType::Jcc);
+                let label_after = context.get_current_label();
+                context.fix_break_continue(result, label_after, check_label);
+
+                //result[last_index] = IRInstruction::Jcc(size, expression, loop_label);
+                context.fix_jumps(result, &jumps, loop_label);
+                result[jmp_index] = IRInstruction::Jmp(match do_while {
+                    true => loop_label,
+                    false => check_label,
+                });
+            }
+
+            Return {
+                span: _,
+                ast_type,
+                expression,
+            } => {
+                if let (Some(exp), true) = (expression, ast_type.is_struct()) {
+                    let size = context.get_size(&ast_type);
+                    let addr = exp.eval(result, context);
+                    let addr = match context.return_pointer {
+                        // Aggregates returned in memory are copied to the caller's location
+                        Some(temporary) => {
+                            let pointer = context.insert_temporary_address(result, temporary);
+                            let vreg = context.next_vreg();
+                            result.push(IRInstruction::Load(IRSize::P, vreg, pointer, false));
+                            result.push(IRInstruction::Copy(size, addr, vreg));
+                            vreg
+                        }
+                        None => {
+                            let parts = context.get_aggregate_registers(&ast_type).unwrap();
+                            context.pad_aggregate(result, &parts, size, addr)
+                        }
+                    };
+                    result.push(IRInstruction::Ret(IRSize::P, addr))
+                } else if let Some(exp) = expression {
+                    let size = context.get_size(&ast_type);
+                    let vreg = exp.eval(result, context);
+                    let vreg = context.convert(result, size, &exp.ast_type, vreg);
+                    // Values smaller than an int are sign or zero extended to 32 bits,
+                    // as callers compiled by gcc and clang rely on it
+                    if matches!(size, IRSize::S8 | IRSize::S16) {
+                        let vreg = context.convert(result, IRSize::S32, &ast_type, vreg);
+                        result.push(IRInstruction::Ret(IRSize::S32, vreg))
+                    } else {
+                        result.push(IRInstruction::Ret(size, vreg))
+                    }
+                } else {
+                    use crate::parser::Type;
+                    // Creates a temporary that isn't used.
+                    let vreg = context.next_vreg();
+                    result.push(IRInstruction::Imm(context.get_size(&Type::int()), vreg, 0));
+                    result.push(IRInstruction::Ret(IRSize::V, vreg))
+                }
+                context.insert_label(result);
+            }
+        }
+        0
+    }
+}
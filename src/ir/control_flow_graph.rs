@@ -84,6 +84,14 @@ impl ControlFlowGraph {
                     block.successors.push(next);
                     block.successors.push(i + 1)
                 }
+                // Multiple entries in a table can point to the same block
+                JmpTable(_, _, ref labels) => {
+                    for &next in labels {
+                        if !block.successors.contains(&next) {
+                            block.successors.push(next);
+                        }
+                    }
+                }
                 Ret(..) => (),
                 _ if (i + 1) < length => block.successors.push(i + 1), // The last instruction in the last block has no successors
                 _ => (),
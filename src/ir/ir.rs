@@ -19,8 +19,9 @@ pub struct IRFunction {
     pub instructions: Vec<IRInstruction>,
     pub arguments: IRArguments,
     pub variables: Vec<IRVariable>,
-    pub strings: Vec<String>,
+    pub strings: Vec<Vec<u8>>,
     pub vreg_count: u32,
+    pub linkage: IRLinkage,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -34,9 +35,33 @@ pub struct IRVariable {
 pub struct IRGlobal {
     pub name: String,
     pub size: IRSize,
-    pub value: Option<i128>,
+    pub value: Option<Vec<IRGlobalValue>>,
     pub count: usize,
     pub function: bool,
+    pub linkage: IRLinkage,
+    pub read_only: bool,
+}
+
+/// A part of the initial value of a global, the parts are stored in order
+#[derive(Clone, Debug, PartialEq)]
+pub enum IRGlobalValue {
+    /// A value of the given size, floating point values are stored as their bit pattern
+    Value(IRSize, i128),
+    /// The given number of zero bytes
+    Zero(usize),
+    /// The address of a symbol plus an offset, which is resolved by the linker
+    Address(String, i128),
+}
+
+/// Describes where a global symbol is defined and whether other modules can see it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum IRLinkage {
+    /// Defined in this module and visible to other modules
+    Export,
+    /// Defined in this module and only visible within it
+    Local,
+    /// Defined in another module
+    Import,
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -45,6 +70,9 @@ pub struct IRArguments {
     pub variables: Vec<Option<u32>>,
     pub arguments: Vec<Option<IRReg>>,
     pub count: usize,
+    pub return_sizes: Vec<IRSize>, // Registers in which an aggregate is returned
+    pub return_variable: Option<u32>, // Variable that receives an aggregate returned in registers
+    pub variadic: bool,            // Unnamed arguments follow the given arguments
 }
 
 #[allow(dead_code)]
@@ -56,39 +84,61 @@ pub enum IRInstruction {
     AddrG(IRSize, IRReg, String),
     Arg(IRSize, IRReg, Option<usize>),
 
-    Load(IRSize, IRReg, IRReg),  // Result address
-    Store(IRSize, IRReg, IRReg), // From address
+    Load(IRSize, IRReg, IRReg, bool),  // Result address volatile
+    Store(IRSize, IRReg, IRReg, bool), // From address volatile
+    Copy(IRSize, IRReg, IRReg),        // From address to address, size is always B(n)
 
     Add(IRSize, IRReg, IRReg, IRReg),
     Sub(IRSize, IRReg, IRReg, IRReg),
     Mul(IRSize, IRReg, IRReg, IRReg),
     Div(IRSize, IRReg, IRReg, IRReg),
+    Mod(IRSize, IRReg, IRReg, IRReg),
+    Divu(IRSize, IRReg, IRReg, IRReg),
+    Modu(IRSize, IRReg, IRReg, IRReg),
 
     Xor(IRSize, IRReg, IRReg, IRReg),
     Or(IRSize, IRReg, IRReg, IRReg),
     And(IRSize, IRReg, IRReg, IRReg),
 
+    Shl(IRSize, IRReg, IRReg, IRReg),
+    Sar(IRSize, IRReg, IRReg, IRReg),
+    Shr(IRSize, IRReg, IRReg, IRReg),
+
     Eq(IRSize, IRReg, IRReg, IRReg),
     Ne(IRSize, IRReg, IRReg, IRReg),
     Lt(IRSize, IRReg, IRReg, IRReg),
     Le(IRSize, IRReg, IRReg, IRReg),
     Gt(IRSize, IRReg, IRReg, IRReg),
     Ge(IRSize, IRReg, IRReg, IRReg),
+    Ltu(IRSize, IRReg, IRReg, IRReg),
+    Leu(IRSize, IRReg, IRReg, IRReg),
+    Gtu(IRSize, IRReg, IRReg, IRReg),
+    Geu(IRSize, IRReg, IRReg, IRReg),
 
     Jcc(IRSize, IRReg, IRLabel),
     Jnc(IRSize, IRReg, IRLabel),
     Jmp(IRLabel),
+    JmpTable(IRSize, IRReg, Vec<IRLabel>), // Jumps to the label at the index in the table
     CallV(IRSize, IRReg, IRReg, Box<IRArguments>),
     Call(IRSize, IRReg, String, Box<IRArguments>),
     Label(Option<Box<IRPhi>>, IRLabel),
 
-    Cvp(IRSize, IRReg, IRSize, IRReg), // (to,from) to:IRSize=p
-    Cvs(IRSize, IRReg, IRSize, IRReg), // (to,from) to:IRSize in {S8,S16,S32,S64}
-    Cvu(IRSize, IRReg, IRSize, IRReg), // (to,from) //Unsupported
+    Cvp(IRSize, IRReg, IRSize, IRReg),  // (to,from) to:IRSize=p
+    Cvs(IRSize, IRReg, IRSize, IRReg),  // (to,from) to:IRSize in {S8,S16,S32,S64}
+    Cvu(IRSize, IRReg, IRSize, IRReg),  // (to,from)
+    Cvf(IRSize, IRReg, IRSize, IRReg),  // (to,from) to,from:IRSize in {F32,F64}
+    Cvif(IRSize, IRReg, IRSize, IRReg), // (to,from) signed integer to floating point
+    Cvuf(IRSize, IRReg, IRSize, IRReg), // (to,from) from:IRSize=S64 unsigned to floating point
+    Cvfi(IRSize, IRReg, IRSize, IRReg), // (to,from) to:IRSize=S64 floating point to integer
 
     Phi(Box<IRPhi>),
     PhiSrc(IRLabel),
 
+    VaStart(IRReg), // Initializes the va_list at the address
+    // Takes the next argument from the va_list at the address, an aggregate gives its address
+    // The parts of an aggregate are the registers it is passed in, or its size if it is in memory
+    VaArg(IRSize, IRReg, IRReg, Vec<IRSize>),
+
     Nop,
 
     Ret(IRSize, IRReg),
@@ -105,26 +155,39 @@ pub enum IRType {
 
     Load,
     Store,
+    Copy,
 
     Add,
     Sub,
     Mul,
     Div,
+    Mod,
+    Divu,
+    Modu,
 
     Xor,
     Or,
     And,
 
+    Shl,
+    Sar,
+    Shr,
+
     Eq,
     Ne,
     Lt,
     Le,
     Gt,
     Ge,
+    Ltu,
+    Leu,
+    Gtu,
+    Geu,
 
     Jcc,
     Jnc,
     Jmp,
+    JmpTable,
     Call,
     CallV,
     Label,
@@ -132,10 +195,17 @@ pub enum IRType {
     Cvp,
     Cvs,
     Cvu,
+    Cvf,
+    Cvif,
+    Cvuf,
+    Cvfi,
 
     Phi,
     PhiSrc,
 
+    VaStart,
+    VaArg,
+
     Nop,
 
     Ret,
@@ -151,6 +221,8 @@ pub enum IRSize {
     S16,
     S32,
     S64,
+    F32,
+    F64,
     P,
     V,
     B(u16),
@@ -162,11 +234,25 @@ impl IRSize {
             IRSize::S8 => 8,
             IRSize::S16 => 16,
             IRSize::S32 => 32,
-            IRSize::S64 => 64,
+            IRSize::S64 | IRSize::F64 => 64,
+            IRSize::F32 => 32,
             IRSize::B(size) => (*size as usize) * 8,
             IRSize::P | IRSize::V => unreachable!(),
         }
     }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, IRSize::F32 | IRSize::F64)
+    }
+
+    // Gives the bit pattern of a floating point constant as it is stored in an Imm
+    pub fn float_bits(&self, value: f64) -> i128 {
+        match self {
+            IRSize::F32 => (value as f32).to_bits() as i128,
+            IRSize::F64 => value.to_bits() as i128,
+            _ => unreachable!(),
+        }
+    }
 }
 
 impl IRInstruction {
@@ -180,15 +266,22 @@ impl IRInstruction {
 
             &Self::Load(..) => IRType::Load,
             &Self::Store(..) => IRType::Store,
+            &Self::Copy(..) => IRType::Copy,
 
             &Self::Add(..) => IRType::Add,
             &Self::Sub(..) => IRType::Sub,
             &Self::Mul(..) => IRType::Mul,
             &Self::Div(..) => IRType::Div,
+            &Self::Mod(..) => IRType::Mod,
+            &Self::Divu(..) => IRType::Divu,
+            &Self::Modu(..) => IRType::Modu,
 
             &Self::Xor(..) => IRType::Xor,
             &Self::Or(..) => IRType::Or,
             &Self::And(..) => IRType::And,
+            &Self::Shl(..) => IRType::Shl,
+            &Self::Sar(..) => IRType::Sar,
+            &Self::Shr(..) => IRType::Shr,
 
             &Self::Eq(..) => IRType::Eq,
             &Self::Ne(..) => IRType::Ne,
@@ -196,10 +289,15 @@ impl IRInstruction {
             &Self::Le(..) => IRType::Le,
             &Self::Gt(..) => IRType::Gt,
             &Self::Ge(..) => IRType::Ge,
+            &Self::Ltu(..) => IRType::Ltu,
+            &Self::Leu(..) => IRType::Leu,
+            &Self::Gtu(..) => IRType::Gtu,
+            &Self::Geu(..) => IRType::Geu,
 
             &Self::Jcc(..) => IRType::Jcc,
             &Self::Jnc(..) => IRType::Jnc,
             &Self::Jmp(..) => IRType::Jmp,
+            &Self::JmpTable(..) => IRType::JmpTable,
             &Self::Call(..) => IRType::Call,
             &Self::CallV(..) => IRType::CallV,
             &Self::Label(..) => IRType::Label,
@@ -207,10 +305,17 @@ impl IRInstruction {
             &Self::Cvp(..) => IRType::Cvp,
             &Self::Cvs(..) => IRType::Cvs,
             &Self::Cvu(..) => IRType::Cvu,
+            &Self::Cvf(..) => IRType::Cvf,
+            &Self::Cvif(..) => IRType::Cvif,
+            &Self::Cvuf(..) => IRType::Cvuf,
+            &Self::Cvfi(..) => IRType::Cvfi,
 
             &Self::Phi(..) => IRType::Phi,
             &Self::PhiSrc(..) => IRType::PhiSrc,
 
+            &Self::VaStart(..) => IRType::VaStart,
+            &Self::VaArg(..) => IRType::VaArg,
+
             &Self::Nop => IRType::Nop,
 
             &Self::Ret(..) => IRType::Ret,
@@ -222,27 +327,45 @@ impl IRInstruction {
         match self {
             &Self::Ret(_, left)
             | &Self::Arg(_, left, _)
-            | &Self::Load(_, _, left)
-            | &Self::Store(_, left, _)
+            | &Self::Load(_, _, left, _)
+            | &Self::Store(_, left, _, _)
+            | &Self::Copy(_, left, _)
             | &Self::Add(_, _, left, _)
             | &Self::Sub(_, _, left, _)
             | &Self::Mul(_, _, left, _)
             | &Self::Div(_, _, left, _)
+            | &Self::Mod(_, _, left, _)
+            | &Self::Divu(_, _, left, _)
+            | &Self::Modu(_, _, left, _)
             | &Self::Xor(_, _, left, _)
             | &Self::Or(_, _, left, _)
             | &Self::And(_, _, left, _)
+            | &Self::Shl(_, _, left, _)
+            | &Self::Sar(_, _, left, _)
+            | &Self::Shr(_, _, left, _)
             | &Self::Eq(_, _, left, _)
             | &Self::Ne(_, _, left, _)
             | &Self::Lt(_, _, left, _)
             | &Self::Le(_, _, left, _)
             | &Self::Gt(_, _, left, _)
             | &Self::Ge(_, _, left, _)
+            | &Self::Ltu(_, _, left, _)
+            | &Self::Leu(_, _, left, _)
+            | &Self::Gtu(_, _, left, _)
+            | &Self::Geu(_, _, left, _)
             | &Self::Jcc(_, left, _)
             | &Self::Jnc(_, left, _)
+            | &Self::JmpTable(_, left, _)
             | &Self::Cvp(.., left)
             | &Self::Cvs(.., left)
             | &Self::Cvu(.., left)
-            | &Self::CallV(_, _, left, _) => Some(left),
+            | &Self::Cvf(.., left)
+            | &Self::Cvif(.., left)
+            | &Self::Cvuf(.., left)
+            | &Self::Cvfi(.., left)
+            | &Self::CallV(_, _, left, _)
+            | &Self::VaStart(left)
+            | &Self::VaArg(_, _, left, _) => Some(left),
             _ => None,
         }
     }
@@ -251,27 +374,45 @@ impl IRInstruction {
         match self {
             Self::Ret(_, left)
             | Self::Arg(_, left, _)
-            | Self::Load(_, _, left)
-            | Self::Store(_, left, _)
+            | Self::Load(_, _, left, _)
+            | Self::Store(_, left, _, _)
+            | Self::Copy(_, left, _)
             | Self::Add(_, _, left, _)
             | Self::Sub(_, _, left, _)
             | Self::Mul(_, _, left, _)
             | Self::Div(_, _, left, _)
+            | Self::Mod(_, _, left, _)
+            | Self::Divu(_, _, left, _)
+            | Self::Modu(_, _, left, _)
             | Self::Xor(_, _, left, _)
             | Self::Or(_, _, left, _)
             | Self::And(_, _, left, _)
+            | Self::Shl(_, _, left, _)
+            | Self::Sar(_, _, left, _)
+            | Self::Shr(_, _, left, _)
             | Self::Eq(_, _, left, _)
             | Self::Ne(_, _, left, _)
             | Self::Lt(_, _, left, _)
             | Self::Le(_, _, left, _)
             | Self::Gt(_, _, left, _)
             | Self::Ge(_, _, left, _)
+            | Self::Ltu(_, _, left, _)
+            | Self::Leu(_, _, left, _)
+            | Self::Gtu(_, _, left, _)
+            | Self::Geu(_, _, left, _)
             | Self::Jcc(_, left, _)
             | Self::Jnc(_, left, _)
+            | Self::JmpTable(_, left, _)
             | Self::Cvp(.., left)
             | Self::Cvs(.., left)
             | Self::Cvu(.., left)
-            | Self::CallV(_, _, left, _) => Some(left),
+            | Self::Cvf(.., left)
+            | Self::Cvif(.., left)
+            | Self::Cvuf(.., left)
+            | Self::Cvfi(.., left)
+            | Self::CallV(_, _, left, _)
+            | Self::VaStart(left)
+            | Self::VaArg(_, _, left, _) => Some(left),
             _ => None,
         }
     }
@@ -279,40 +420,62 @@ impl IRInstruction {
     // Returns the right vregister if it exists
     pub fn get_right(&self) -> Option<IRReg> {
         match self {
-            &Self::Store(.., right)
+            &Self::Store(_, _, right, _)
+            | &Self::Copy(_, _, right)
             | &Self::Add(.., right)
             | &Self::Sub(.., right)
             | &Self::Mul(.., right)
             | &Self::Div(.., right)
+            | &Self::Mod(.., right)
+            | &Self::Divu(.., right)
+            | &Self::Modu(.., right)
             | &Self::Xor(.., right)
             | &Self::Or(.., right)
             | &Self::And(.., right)
+            | &Self::Shl(.., right)
+            | &Self::Sar(.., right)
+            | &Self::Shr(.., right)
             | &Self::Eq(.., right)
             | &Self::Ne(.., right)
             | &Self::Lt(.., right)
             | &Self::Le(.., right)
             | &Self::Gt(.., right)
-            | &Self::Ge(.., right) => Some(right),
+            | &Self::Ge(.., right)
+            | &Self::Ltu(.., right)
+            | &Self::Leu(.., right)
+            | &Self::Gtu(.., right)
+            | &Self::Geu(.., right) => Some(right),
             _ => None,
         }
     }
 
     pub fn get_right_mut<'a>(&'a mut self) -> Option<&'a mut IRReg> {
         match self {
-            Self::Store(.., right)
+            Self::Store(_, _, right, _)
+            | Self::Copy(_, _, right)
             | Self::Add(.., right)
             | Self::Sub(.., right)
             | Self::Mul(.., right)
             | Self::Div(.., right)
+            | Self::Mod(.., right)
+            | Self::Divu(.., right)
+            | Self::Modu(.., right)
             | Self::Xor(.., right)
             | Self::Or(.., right)
             | Self::And(.., right)
+            | Self::Shl(.., right)
+            | Self::Sar(.., right)
+            | Self::Shr(.., right)
             | Self::Eq(.., right)
             | Self::Ne(.., right)
             | Self::Lt(.., right)
             | Self::Le(.., right)
             | Self::Gt(.., right)
-            | Self::Ge(.., right) => Some(right),
+            | Self::Ge(.., right)
+            | Self::Ltu(.., right)
+            | Self::Leu(.., right)
+            | Self::Gtu(.., right)
+            | Self::Geu(.., right) => Some(right),
             _ => None,
         }
     }
@@ -329,20 +492,35 @@ impl IRInstruction {
             | &Self::Sub(_, result, ..)
             | &Self::Mul(_, result, ..)
             | &Self::Div(_, result, ..)
+            | &Self::Mod(_, result, ..)
+            | &Self::Divu(_, result, ..)
+            | &Self::Modu(_, result, ..)
             | &Self::Xor(_, result, ..)
             | &Self::Or(_, result, ..)
             | &Self::And(_, result, ..)
+            | &Self::Shl(_, result, ..)
+            | &Self::Sar(_, result, ..)
+            | &Self::Shr(_, result, ..)
             | &Self::Eq(_, result, ..)
             | &Self::Ne(_, result, ..)
             | &Self::Lt(_, result, ..)
             | &Self::Le(_, result, ..)
             | &Self::Gt(_, result, ..)
             | &Self::Ge(_, result, ..)
+            | &Self::Ltu(_, result, ..)
+            | &Self::Leu(_, result, ..)
+            | &Self::Gtu(_, result, ..)
+            | &Self::Geu(_, result, ..)
             | &Self::Call(_, result, ..)
             | &Self::CallV(_, result, ..)
             | &Self::Cvp(_, result, ..)
             | &Self::Cvs(_, result, ..)
-            | &Self::Cvu(_, result, ..) => Some(result),
+            | &Self::Cvu(_, result, ..)
+            | &Self::Cvf(_, result, ..)
+            | &Self::Cvif(_, result, ..)
+            | &Self::Cvuf(_, result, ..)
+            | &Self::Cvfi(_, result, ..)
+            | &Self::VaArg(_, result, ..) => Some(result),
             _ => None,
         }
     }
@@ -355,33 +533,55 @@ impl IRInstruction {
             | Self::Arg(size, ..)
             | Self::Load(size, ..)
             | Self::Store(size, ..)
+            | Self::Copy(size, ..)
             | Self::Add(size, ..)
             | Self::Sub(size, ..)
             | Self::Mul(size, ..)
             | Self::Div(size, ..)
+            | Self::Mod(size, ..)
+            | Self::Divu(size, ..)
+            | Self::Modu(size, ..)
             | Self::Xor(size, ..)
             | Self::Or(size, ..)
             | Self::And(size, ..)
+            | Self::Shl(size, ..)
+            | Self::Sar(size, ..)
+            | Self::Shr(size, ..)
             | Self::Eq(size, ..)
             | Self::Ne(size, ..)
             | Self::Lt(size, ..)
             | Self::Le(size, ..)
             | Self::Gt(size, ..)
             | Self::Ge(size, ..)
+            | Self::Ltu(size, ..)
+            | Self::Leu(size, ..)
+            | Self::Gtu(size, ..)
+            | Self::Geu(size, ..)
             | Self::Jnc(size, ..)
             | Self::Jcc(size, ..)
+            | Self::JmpTable(size, ..)
             | Self::Ret(size, ..)
             | Self::Call(size, ..)
-            | Self::CallV(size, ..) => size.clone(),
-
-            Self::Cvs(to, _, from, _) | Self::Cvu(to, _, from, _) | Self::Cvp(to, _, from, _) => {
+            | Self::CallV(size, ..)
+            | Self::VaArg(size, ..) => size.clone(),
+
+            Self::Cvs(to, _, from, _)
+            | Self::Cvu(to, _, from, _)
+            | Self::Cvp(to, _, from, _)
+            | Self::Cvf(to, _, from, _)
+            | Self::Cvif(to, _, from, _)
+            | Self::Cvuf(to, _, from, _)
+            | Self::Cvfi(to, _, from, _) => {
                 let _ = from;
                 *to
             }
 
-            Self::Nop | Self::Jmp(_) | Self::Label(..) | Self::PhiSrc(..) | Self::Phi(..) => {
-                IRSize::P
-            }
+            Self::Nop
+            | Self::Jmp(_)
+            | Self::Label(..)
+            | Self::PhiSrc(..)
+            | Self::Phi(..)
+            | Self::VaStart(..) => IRSize::P,
         }
     }
 
@@ -394,7 +594,11 @@ impl IRInstruction {
             | Self::Lt(..)
             | Self::Le(..)
             | Self::Gt(..)
-            | Self::Ge(..) => int_size,
+            | Self::Ge(..)
+            | Self::Ltu(..)
+            | Self::Leu(..)
+            | Self::Gtu(..)
+            | Self::Geu(..) => int_size,
 
             ins => {
                 return ins.get_size(); //std::cmp::max(ins.get_size(), int_size);
@@ -455,28 +659,46 @@ impl IRInstruction {
                 .collect(),
 
             _ => match self {
-                Self::Load(_, right, left)
-                | Self::Store(_, left, right)
+                Self::Load(_, right, left, _)
+                | Self::Store(_, left, right, _)
+                | Self::Copy(_, left, right)
                 | Self::Add(_, _, left, right)
                 | Self::Sub(_, _, left, right)
                 | Self::Mul(_, _, left, right)
                 | Self::Div(_, _, left, right)
+                | Self::Mod(_, _, left, right)
+                | Self::Divu(_, _, left, right)
+                | Self::Modu(_, _, left, right)
                 | Self::Xor(_, _, left, right)
                 | Self::Or(_, _, left, right)
                 | Self::And(_, _, left, right)
+                | Self::Shl(_, _, left, right)
+                | Self::Sar(_, _, left, right)
+                | Self::Shr(_, _, left, right)
                 | Self::Eq(_, _, left, right)
                 | Self::Ne(_, _, left, right)
                 | Self::Lt(_, _, left, right)
                 | Self::Le(_, _, left, right)
                 | Self::Gt(_, _, left, right)
-                | Self::Ge(_, _, left, right) => smallvec![left, right],
+                | Self::Ge(_, _, left, right)
+                | Self::Ltu(_, _, left, right)
+                | Self::Leu(_, _, left, right)
+                | Self::Gtu(_, _, left, right)
+                | Self::Geu(_, _, left, right) => smallvec![left, right],
                 Self::Ret(_, left)
                 | Self::Arg(_, left, _)
                 | Self::Jcc(_, left, _)
                 | Self::Jnc(_, left, _)
+                | Self::JmpTable(_, left, _)
                 | Self::Cvp(.., left)
                 | Self::Cvs(.., left)
-                | Self::Cvu(.., left) => smallvec![left],
+                | Self::Cvu(.., left)
+                | Self::Cvf(.., left)
+                | Self::Cvif(.., left)
+                | Self::Cvuf(.., left)
+                | Self::Cvfi(.., left)
+                | Self::VaStart(left)
+                | Self::VaArg(_, _, left, _) => smallvec![left],
                 _ => SmallVec::new(),
             },
         }
@@ -488,6 +710,17 @@ impl IRInstruction {
 
     pub fn has_side_effect(&self) -> bool {
         use IRInstruction::*;
-        matches!(self, Store(..) | Call(..) | CallV(..))
+        matches!(
+            self,
+            Store(..) | Copy(..) | Call(..) | CallV(..) | VaStart(..) | VaArg(..)
+        )
+    }
+
+    // Volatile memory accesses must never be removed, merged or reordered
+    pub fn is_volatile(&self) -> bool {
+        matches!(
+            self,
+            IRInstruction::Load(.., true) | IRInstruction::Store(.., true)
+        )
     }
 }
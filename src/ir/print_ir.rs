@@ -26,6 +26,14 @@ fn fmt_argument(arguments: &IRArguments, f: &mut fmt::Formatter) -> fmt::Result
     Ok(())
 }
 
+// Aggregates returned in registers are stored in a variable after the call
+fn fmt_return_variable(arguments: &IRArguments, f: &mut fmt::Formatter) -> fmt::Result {
+    match arguments.return_variable {
+        Some(variable) => write!(f, " -> ${}", variable),
+        None => Ok(()),
+    }
+}
+
 impl Display for IRModule {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         for function in &self.functions {
@@ -40,8 +48,15 @@ impl Display for IRModule {
 
 impl Display for IRFunction {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "define {} @{}(", self.return_size, self.name)?;
+        write!(
+            f,
+            "define {}{} @{}(",
+            self.linkage, self.return_size, self.name
+        )?;
         fmt_argument(&self.arguments, f)?;
+        if self.arguments.variadic {
+            write!(f, ", ...")?;
+        }
         writeln!(f, ") [")?;
         for local in &self.variables {
             if local.count == 1 {
@@ -64,65 +79,147 @@ impl Display for IRGlobal {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         if self.function {
             writeln!(f, "declaration {} @{}()", self.size, self.name)?;
-        } else if let Some(value) = self.value {
-            writeln!(f, "@{} = {} {}", self.name, self.size, value)?;
+        } else if let Some(values) = &self.value {
+            write!(
+                f,
+                "{}{}@{} = {} {{",
+                self.linkage,
+                read_only(self.read_only),
+                self.name,
+                self.size,
+            )?;
+            for (i, value) in values.iter().enumerate() {
+                if i != 0 {
+                    write!(f, ", ")?;
+                }
+                match value {
+                    IRGlobalValue::Value(size, value) => write!(f, "{} {}", size, value)?,
+                    IRGlobalValue::Zero(size) => write!(f, "zero {}", size)?,
+                    IRGlobalValue::Address(name, offset) => {
+                        write!(f, "{} @{}{:+}", IRSize::P, name, offset)?
+                    }
+                }
+            }
+            writeln!(f, "}}")?;
         } else {
-            writeln!(f, "declaration {} @{}", self.size, self.name)?;
+            writeln!(
+                f,
+                "declaration {}{}{} @{}",
+                self.linkage,
+                read_only(self.read_only),
+                self.size,
+                self.name
+            )?;
         }
         Ok(())
     }
 }
 
+fn read_only(read_only: bool) -> &'static str {
+    if read_only {
+        "readonly "
+    } else {
+        ""
+    }
+}
+
 impl Display for IRInstruction {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use IRInstruction::*;
         let ins = self.to_type();
         match self {
+            Imm(size, reg, value) if size.is_float() => {
+                write!(f, "\t%{} = {} {} #{:#x}", reg, ins, size, value)
+            }
             Imm(size, reg, value) => write!(f, "\t%{} = {} {} #{}", reg, ins, size, value),
             AddrL(size, reg, value) => write!(f, "\t%{} = {} {} ${}", reg, ins, size, value),
             AddrG(size, reg, name) => write!(f, "\t%{} = {} {} @{}", reg, ins, size, name),
             Arg(size, reg, Some(index)) => write!(f, "\t%{} {} %{} for {}", ins, size, reg, index),
             Arg(size, reg, None) => write!(f, "\t%{} {} %{}", ins, size, reg),
 
-            Load(size, reg, addr) => write!(f, "\t%{} = {} {} [%{}]", reg, ins, size, addr),
-            Store(size, reg, addr) => write!(f, "\t{} {} %{}, [%{}]\n", ins, size, reg, addr),
+            Load(size, reg, addr, false) => write!(f, "\t%{} = {} {} [%{}]", reg, ins, size, addr),
+            Load(size, reg, addr, true) => {
+                write!(f, "\t%{} = {} volatile {} [%{}]", reg, ins, size, addr)
+            }
+            Store(size, reg, addr, false) => {
+                write!(f, "\t{} {} %{}, [%{}]\n", ins, size, reg, addr)
+            }
+            Store(size, reg, addr, true) => {
+                write!(f, "\t{} volatile {} %{}, [%{}]\n", ins, size, reg, addr)
+            }
+            Copy(size, from, to) => write!(f, "\t{} {} [%{}], [%{}]", ins, size, from, to),
 
             Add(size, result, left, right)
             | Sub(size, result, left, right)
             | Mul(size, result, left, right)
             | Div(size, result, left, right)
+            | Mod(size, result, left, right)
+            | Divu(size, result, left, right)
+            | Modu(size, result, left, right)
             | Xor(size, result, left, right)
             | Or(size, result, left, right)
             | And(size, result, left, right)
+            | Shl(size, result, left, right)
+            | Sar(size, result, left, right)
+            | Shr(size, result, left, right)
             | Eq(size, result, left, right)
             | Ne(size, result, left, right)
             | Lt(size, result, left, right)
             | Le(size, result, left, right)
             | Gt(size, result, left, right)
-            | Ge(size, result, left, right) => {
+            | Ge(size, result, left, right)
+            | Ltu(size, result, left, right)
+            | Leu(size, result, left, right)
+            | Gtu(size, result, left, right)
+            | Geu(size, result, left, right) => {
                 write!(f, "\t%{} = {} {} %{}, %{}", result, ins, size, left, right)
             }
 
             Jcc(size, left, label) => write!(f, "\tjcc {} %{} L{}", size, left, label),
             Jnc(size, left, label) => write!(f, "\tjnc {} %{} L{}", size, left, label),
             Jmp(label) => write!(f, "\tjmp L{}", label),
+            JmpTable(size, left, labels) => {
+                write!(f, "\tjmptable {} %{} [", size, left)?;
+                for (i, label) in labels.iter().enumerate() {
+                    match i {
+                        0 => write!(f, "L{}", label)?,
+                        _ => write!(f, ", L{}", label)?,
+                    }
+                }
+                write!(f, "]")
+            }
             Call(size, result, name, arguments) => {
-                write!(f, "\t%{} = {} call @{}({})", result, size, name, arguments)
+                write!(f, "\t%{} = {} call @{}({})", result, size, name, arguments)?;
+                fmt_return_variable(arguments, f)
             }
             CallV(size, result, addr, arguments) => {
-                write!(f, "\t%{} = {} call %{}({})", result, size, addr, arguments)
+                write!(f, "\t%{} = {} call %{}({})", result, size, addr, arguments)?;
+                fmt_return_variable(arguments, f)
             }
             Label(Some(phi), label) => write!(f, "L{}:\n{}", label, phi),
             Label(None, label) => write!(f, "L{}:", label),
 
             Cvs(to_s, to_r, from_s, from_r)
             | Self::Cvu(to_s, to_r, from_s, from_r)
-            | Self::Cvp(to_s, to_r, from_s, from_r) => {
+            | Self::Cvp(to_s, to_r, from_s, from_r)
+            | Self::Cvf(to_s, to_r, from_s, from_r)
+            | Self::Cvif(to_s, to_r, from_s, from_r)
+            | Self::Cvuf(to_s, to_r, from_s, from_r)
+            | Self::Cvfi(to_s, to_r, from_s, from_r) => {
                 write!(f, "\t%{} = {} {} {} %{}", to_r, to_s, ins, from_s, from_r)
             }
             PhiSrc(label) => write!(f, "\tphisrc L{}:", label),
             Phi(phi) => write!(f, "{}", phi),
 
+            VaStart(addr) => write!(f, "\t{} [%{}]", ins, addr),
+            VaArg(size, result, addr, parts) => {
+                write!(f, "\t%{} = {} {} [%{}]", result, ins, size, addr)?;
+                for part in parts {
+                    write!(f, " {}", part)?;
+                }
+                Ok(())
+            }
+
             Nop => write!(f, "\tnop"),
 
             Ret(size, reg) => write!(f, "\t{} {} %{}", ins, size, reg),
@@ -140,23 +237,40 @@ impl Display for IRType {
             Arg => write!(f, "arg"),
             Load => write!(f, "load"),
             Store => write!(f, "store"),
+            Copy => write!(f, "copy"),
             Add => write!(f, "add"),
             Sub => write!(f, "sub"),
             Mul => write!(f, "mul"),
             Div => write!(f, "div"),
+            Mod => write!(f, "mod"),
+            Divu => write!(f, "divu"),
+            Modu => write!(f, "modu"),
             Xor => write!(f, "xor"),
             Or => write!(f, "or"),
             And => write!(f, "and"),
+            Shl => write!(f, "shl"),
+            Sar => write!(f, "sar"),
+            Shr => write!(f, "shr"),
             Eq => write!(f, "eq"),
             Ne => write!(f, "ne"),
             Lt => write!(f, "lt"),
             Le => write!(f, "le"),
             Gt => write!(f, "gt"),
             Ge => write!(f, "ge"),
+            Ltu => write!(f, "ltu"),
+            Leu => write!(f, "leu"),
+            Gtu => write!(f, "gtu"),
+            Geu => write!(f, "geu"),
             Ret => write!(f, "ret"),
             Cvp => write!(f, "cvp"),
             Cvs => write!(f, "cvs"),
             Cvu => write!(f, "cvu"),
+            Cvf => write!(f, "cvf"),
+            Cvif => write!(f, "cvif"),
+            Cvuf => write!(f, "cvuf"),
+            Cvfi => write!(f, "cvfi"),
+            VaStart => write!(f, "va_start"),
+            VaArg => write!(f, "va_arg"),
             Nop => write!(f, "nop"),
 
             _ => unreachable!(),
@@ -171,6 +285,8 @@ impl Display for IRSize {
             Self::S16 => write!(f, "s16"),
             Self::S32 => write!(f, "s32"),
             Self::S64 => write!(f, "s64"),
+            Self::F32 => write!(f, "f32"),
+            Self::F64 => write!(f, "f64"),
             Self::P => write!(f, "p"),
             Self::V => write!(f, "v"),
             Self::B(s) => write!(f, "b({})", s),
@@ -179,6 +295,16 @@ impl Display for IRSize {
     }
 }
 
+impl Display for IRLinkage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Export => Ok(()),
+            Self::Local => write!(f, "internal "),
+            Self::Import => write!(f, "external "),
+        }
+    }
+}
+
 impl Display for IRPhi {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         for i in 0..self.targets.len() {
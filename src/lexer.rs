@@ -1,379 +1,884 @@
-use crate::error;
-use crate::file_table;
-use crate::span::Span;
-use crate::token;
-use crate::token::Token;
-use crate::token::TokenType;
-
-// The Lexer is a mutuable structure keeping track of the current location in the source
-pub struct Lexer {
-    file_index: u32,
-    line: u32,
-    column: u32,
-    offset: u32,
-    last_char: Option<char>,
-}
-
-impl Lexer {
-    pub fn new(filename: &String) -> Lexer {
-        let file_index = file_table::add_sourcefile(filename) as u32;
-        Lexer {
-            file_index,
-            line: 1,
-            column: 1,
-            offset: 0,
-            last_char: None,
-        }
-    }
-}
-
-impl Lexer {
-    // Returns the current location of the lexer
-    fn here(&mut self) -> Span {
-        Span::new(self.file_index, self.line, self.column, self.offset, 1)
-    }
-
-    pub fn peek<T: Iterator<Item = char>>(&mut self, it: &mut T) -> Option<char> {
-        if self.last_char.is_none() {
-            self.last_char = it.next();
-            // Keeping track of current character location and offset
-            self.offset += 1;
-            self.column += 1;
-            if let Some('\n') = self.last_char {
-                self.line += 1;
-                self.column = 0;
-            }
-        }
-        self.last_char
-    }
-    pub fn next<T: Iterator<Item = char>>(&mut self, it: &mut T) -> Option<char> {
-        let result = self.peek(it);
-        self.last_char = None;
-        result
-    }
-
-    // General lexing function
-    // More complex analysis like identifiers and numbers are split out
-    // The lexer can work on any arbitrary iterator that returns characters
-    pub fn lex<T: Iterator<Item = char>>(
-        &mut self,
-        input: &mut T,
-    ) -> (Vec<Token>, Result<(), Vec<String>>) {
-        let mut output = Vec::<Token>::new();
-        let mut errors = Vec::<String>::new();
-        while let Some(c) = self.peek(input).clone() {
-            match c {
-                'a'..='z' | 'A'..='Z' | '_' => output.push(self.lex_identifier(input)),
-                '1'..='9' | '0' => match self.lex_number(input) {
-                    (token, Ok(_)) => output.push(token),
-                    (token, Err(err)) => {
-                        output.push(token);
-                        errors.push(err);
-                    }
-                },
-                '#' => match self.line_command(input) {
-                    Ok(()) => (),
-                    Err(err) => errors.push(err),
-                },
-                '\'' => match self.lex_char(input) {
-                    (token, Ok(_)) => output.push(token),
-                    (token, Err(err)) => {
-                        output.push(token);
-                        errors.push(err);
-                    }
-                },
-                '"' => match self.lex_string(input) {
-                    (token, Ok(_)) => output.push(token),
-                    (token, Err(err)) => {
-                        output.push(token);
-                        errors.push(err);
-                    }
-                },
-                ';' | '{' | '}' | '(' | ')' | '[' | ']' | '+' | '*' | '/' | '~' | '?' | ':'
-                | ',' | '.' => {
-                    self.next(input);
-                    output.push(Token::new(token::punct(c), self.here()));
-                }
-                '=' | '!' | '<' | '>' => {
-                    let begin = self.here();
-                    self.next(input);
-                    if let Some('=') = self.peek(input) {
-                        self.next(input);
-                        output.push(Token::new(
-                            match c {
-                                '=' => TokenType::Equal,
-                                '!' => TokenType::Inequal,
-                                '<' => TokenType::LessEqual,
-                                '>' => TokenType::GreaterEqual,
-                                _ => unreachable!(),
-                            },
-                            begin.to(&self.here()),
-                        ));
-                    } else {
-                        output.push(Token::new(token::punct(c), begin));
-                    }
-                }
-                '|' | '&' => {
-                    let first_char = self.peek(input).unwrap();
-                    let begin = self.here();
-                    self.next(input);
-                    let c = self.peek(input);
-                    let span = begin.to(&self.here());
-                    match (c, first_char) {
-                        (Some('|'), '|') => {
-                            output.push(Token::new(TokenType::LogicalOr, span));
-                            self.next(input);
-                        }
-                        (Some('&'), '&') => {
-                            output.push(Token::new(TokenType::LogicalAnd, span));
-                            self.next(input);
-                        }
-                        _ => output.push(Token::new(token::punct(first_char), begin)),
-                    }
-                }
-                '-' => {
-                    let begin = self.here();
-                    self.next(input);
-                    if let Some('>') = self.peek(input) {
-                        self.next(input);
-                        output.push(Token::new(TokenType::Arrow, begin.to(&self.here())));
-                    } else {
-                        output.push(Token::new(token::punct(c), begin));
-                    }
-                }
-
-                ' ' | '\t' | '\n' | '\r' => {
-                    self.next(input);
-                }
-                _ => {
-                    self.next(input);
-                    errors.push(crate::error!(self.here(), "Unknown character {}", c));
-                }
-            }
-        }
-        match errors.is_empty() {
-            true => (output, Ok(())),
-            false => (output, Err(errors)),
-        }
-    }
-
-    // Lex an identifier or keyword
-    pub fn lex_identifier<T: Iterator<Item = char>>(&mut self, input: &mut T) -> Token {
-        let start = self.here();
-        let mut identifier = String::new();
-        while let Some(c) = self.peek(input) {
-            match c {
-                'a'..='z' | 'A'..='Z' | '_' | '0' | '1'..='9' => {
-                    self.next(input);
-                    identifier.push(c);
-                }
-                _ => {
-                    break;
-                }
-            }
-        }
-        let span = start.to(&self.here());
-        use TokenType::*;
-        match identifier.as_str() {
-            "char" => Token::new(Char, span),
-            "int" => Token::new(Int, span),
-            "long" => Token::new(Long, span),
-            "short" => Token::new(Short, span),
-            "struct" => Token::new(Struct, span),
-            "void" => Token::new(Void, span),
-            "if" => Token::new(If, span),
-            "else" => Token::new(Else, span),
-            "while" => Token::new(While, span),
-            "for" => Token::new(For, span),
-            "do" => Token::new(Do, span),
-            "break" => Token::new(Break, span),
-            "continue" => Token::new(Continue, span),
-            "return" => Token::new(Return, span),
-            "sizeof" => Token::new(Sizeof, span),
-
-            _ => Token::new(Ident(identifier), span),
-        }
-    }
-
-    // Lex a number
-    // Currently only decimal numbers are implemented
-    pub fn lex_number<T: Iterator<Item = char>>(
-        &mut self,
-        input: &mut T,
-    ) -> (Token, Result<(), String>) {
-        let start = self.here();
-        let mut number = String::new();
-        while let Some(c) = self.peek(input) {
-            match c {
-                '1'..='9' | '0' => {
-                    self.next(input);
-                    number.push(c);
-                }
-                _ => {
-                    break;
-                }
-            }
-        }
-        let end = self.here();
-        let span: Span = start.to(&end);
-        match number.parse::<u64>() {
-            Ok(number) => (Token::new(TokenType::ConstI(number), span), Ok(())),
-            Err(_) => (
-                Token::new(TokenType::ConstI(0), span.clone()),
-                Err(error!(
-                    span,
-                    "number {} to big too fit in any integer", number
-                )),
-            ),
-        }
-    }
-
-    fn lex_string<T: Iterator<Item = char>>(
-        &mut self,
-        input: &mut T,
-    ) -> (Token, Result<(), String>) {
-        let start = self.here();
-        let mut errors = String::new();
-        let mut string = String::new();
-        self.next(input);
-
-        while let Some(c) = self.peek(input) {
-            if c == '"' {
-                self.next(input);
-                break;
-            }
-            let (c, err) = self.lex_single_char(input);
-            string.push(c);
-            if let Err(err) = err {
-                errors.push_str(&err)
-            }
-        }
-
-        let err = if !errors.is_empty() {
-            Err(errors)
-        } else {
-            Ok(())
-        };
-
-        let span = start.to(&self.here());
-        let token = Token::new(TokenType::CString(string), span.clone());
-        match self.peek(input) {
-            Some(_) => (token, err),
-            None => (token, Err(error!(span, "Unexpected end of file"))),
-        }
-    }
-
-    fn lex_char<T: Iterator<Item = char>>(&mut self, input: &mut T) -> (Token, Result<(), String>) {
-        let start = self.here();
-        self.next(input);
-        let (c, err) = self.lex_single_char(input);
-        let span = start.to(&self.here());
-        let token = Token::new(TokenType::ConstI(c as u64), span.clone());
-        match self.peek(input) {
-            Some('\'') => {
-                self.next(input);
-                (token, err)
-            }
-            Some(c) => (
-                token,
-                Err(error!(
-                    span,
-                    "Expected ' after character constant, but found {}", c
-                )),
-            ),
-            None => (
-                token,
-                Err(error!(
-                    span,
-                    "Expected ' after character constant, but found end of file"
-                )),
-            ),
-        }
-    }
-
-    fn lex_single_char<T: Iterator<Item = char>>(
-        &mut self,
-        input: &mut T,
-    ) -> (char, Result<(), String>) {
-        let start = self.here();
-        match self.next(input) {
-            Some('\\') => match self.next(input) {
-                Some('\'') => ('\'', Ok(())),
-                Some('\\') => ('\\', Ok(())),
-                Some('"') => ('"', Ok(())),
-                Some('a') => ('\x07', Ok(())),
-                Some('b') => ('\x08', Ok(())),
-                Some('f') => ('\x0e', Ok(())),
-                Some('n') => ('\n', Ok(())),
-                Some('r') => ('\r', Ok(())),
-                Some('t') => ('\t', Ok(())),
-                Some('v') => ('\x0b', Ok(())),
-                Some(c) if c != '\n' => (
-                    '_',
-                    Err(error!(
-                        start,
-                        "Expected an escape sequence in string/character, but found '\\{}'", c
-                    )),
-                ),
-                _ => (
-                    '_',
-                    Err(error!(
-                        start,
-                        "Expected an escape sequence in string/character, but found end of file"
-                    )),
-                ),
-            },
-            //   \' \" \? \\ \a \b \f \n \r \t \v
-            Some(c) if c.is_ascii() && c != '\n' => (c, Ok(())),
-            Some(c) => (
-                '_',
-                Err(error!(
-                    start,
-                    "Expected an ascii character in string/character, but found {:?}", c
-                )),
-            ),
-            None => (
-                '_',
-                Err(error!(
-                    start,
-                    "Expected an ascii character in string/character, but found end of file"
-                )),
-            ),
-        }
-    }
-
-    fn line_command<T: Iterator<Item = char>>(&mut self, input: &mut T) -> Result<(), String> {
-        let mut line = String::new();
-        while let Some(c) = self.peek(input) {
-            self.next(input);
-            if c == '\n' {
-                break;
-            }
-            line.push(c);
-        }
-
-        let split = line.split(' ').collect::<Vec<_>>();
-
-        if split[0] != "#" {
-            return Err(String::from("Bad line command"));
-        }
-
-        let line = match u32::from_str_radix(split[1], 10) {
-            Ok(i) => i,
-            Err(e) => {
-                return Err(e.to_string());
-            }
-        };
-
-        // Removes both the first and last character of a utf-8 string
-        //let file = split[2].chars().skip(1);
-        //let filename: String = file.clone().zip(file.skip(1)).map(|(c, _)| c).collect();
-        let filename = String::from(split[2]);
-
-        self.column = 1;
-        self.line = line;
-        self.file_index = file_table::add_sourcefile(&filename) as u32;
-
-        Ok(())
-    }
-}
+use crate::file_table;
+use crate::parser::TypeNode;
+use crate::span::Span;
+use crate::token;
+use crate::token::Token;
+use crate::token::TokenType;
+use crate::{error, warning};
+
+// The Lexer is a mutuable structure keeping track of the current location in the source
+pub struct Lexer {
+    file_index: u32,
+    line: u32,
+    column: u32,
+    offset: u32,
+    last_char: Option<char>,
+    preprocessing: bool,
+    // The characters read while preprocessing and where the last token ended in them
+    source: String,
+    token_end: usize,
+}
+
+impl Lexer {
+    pub fn new(filename: &String) -> Lexer {
+        let file_index = file_table::add_sourcefile(filename) as u32;
+        Lexer {
+            file_index,
+            line: 1,
+            column: 1,
+            offset: 0,
+            last_char: None,
+            preprocessing: false,
+            source: String::new(),
+            token_end: 0,
+        }
+    }
+
+    // Creates a lexer for the preprocessor
+    // This lexer also returns newlines and '#' as tokens
+    pub fn new_preprocessing(filename: &String) -> Lexer {
+        Lexer {
+            preprocessing: true,
+            ..Lexer::new(filename)
+        }
+    }
+
+    // Creates a lexer starting at the location of span
+    // Used to relex source that has been created by the preprocessor
+    pub fn from_span(span: &Span) -> Lexer {
+        Lexer {
+            file_index: span.file_index(),
+            line: span.line(),
+            column: span.column(),
+            offset: span.offset(),
+            last_char: None,
+            preprocessing: false,
+            source: String::new(),
+            token_end: 0,
+        }
+    }
+}
+
+impl Lexer {
+    // Returns the current location of the lexer
+    fn here(&mut self) -> Span {
+        Span::new(self.file_index, self.line, self.column, self.offset, 1)
+    }
+
+    pub fn peek<T: Iterator<Item = char>>(&mut self, it: &mut T) -> Option<char> {
+        if self.last_char.is_none() {
+            self.last_char = it.next();
+            if let (true, Some(c)) = (self.preprocessing, self.last_char) {
+                self.source.push(c);
+            }
+            // Keeping track of current character location and offset
+            self.offset += 1;
+            self.column += 1;
+            if let Some('\n') = self.last_char {
+                self.line += 1;
+                self.column = 0;
+            }
+        }
+        self.last_char
+    }
+    pub fn next<T: Iterator<Item = char>>(&mut self, it: &mut T) -> Option<char> {
+        let result = self.peek(it);
+        self.last_char = None;
+        result
+    }
+
+    // General lexing function
+    // More complex analysis like identifiers and numbers are split out
+    // The lexer can work on any arbitrary iterator that returns characters
+    pub fn lex<T: Iterator<Item = char>>(
+        &mut self,
+        input: &mut T,
+    ) -> (Vec<Token>, Result<(), Vec<String>>) {
+        let mut output = Vec::<Token>::new();
+        let mut errors = Vec::<String>::new();
+        while let Some(c) = self.peek(input).clone() {
+            let start = self.source.len().saturating_sub(c.len_utf8());
+            let count = output.len();
+            match c {
+                'a'..='z' | 'A'..='Z' | '_' => output.push(self.lex_identifier(input)),
+                '1'..='9' | '0' => match self.lex_number(input) {
+                    (token, Ok(_)) => output.push(token),
+                    (token, Err(err)) => {
+                        output.push(token);
+                        errors.push(err);
+                    }
+                },
+                '#' if self.preprocessing => {
+                    let begin = self.here();
+                    self.next(input);
+                    if let Some('#') = self.peek(input) {
+                        self.next(input);
+                        output.push(Token::new(TokenType::HashHash, begin.to(&self.here())));
+                    } else {
+                        output.push(Token::new(TokenType::Hash, begin));
+                    }
+                }
+                '#' => match self.line_command(input) {
+                    Ok(()) => (),
+                    Err(err) => errors.push(err),
+                },
+                '\'' => match self.lex_char(input) {
+                    (token, Ok(_)) => output.push(token),
+                    (token, Err(err)) => {
+                        output.push(token);
+                        errors.push(err);
+                    }
+                },
+                '"' => match self.lex_string(input) {
+                    (token, Ok(_)) => output.push(token),
+                    (token, Err(err)) => {
+                        output.push(token);
+                        errors.push(err);
+                    }
+                },
+                ';' | '{' | '}' | '(' | ')' | '[' | ']' | '~' | '?' | ':' | ',' => {
+                    self.next(input);
+                    output.push(Token::new(token::punct(c), self.here()));
+                }
+                // A period directly followed by a digit starts a floating point constant
+                '.' => {
+                    let begin = self.here();
+                    self.next(input);
+                    match self.peek(input) {
+                        Some('0'..='9') => match self.lex_float(input, begin, String::from(".")) {
+                            (token, Ok(_)) => output.push(token),
+                            (token, Err(err)) => {
+                                output.push(token);
+                                errors.push(err);
+                            }
+                        },
+                        // Two periods that are not followed by a third are two separate tokens
+                        Some('.') => {
+                            let second = self.here();
+                            self.next(input);
+                            if let Some('.') = self.peek(input) {
+                                self.next(input);
+                                output
+                                    .push(Token::new(TokenType::Ellipsis, begin.to(&self.here())));
+                            } else {
+                                output.push(Token::new(token::punct(c), begin));
+                                output.push(Token::new(token::punct(c), second));
+                            }
+                        }
+                        _ => output.push(Token::new(token::punct(c), self.here())),
+                    }
+                }
+                '+' | '*' | '%' | '^' => {
+                    let begin = self.here();
+                    self.next(input);
+                    match self.peek(input) {
+                        Some('=') => {
+                            self.next(input);
+                            output.push(self.compound_assign(c, &begin));
+                        }
+                        Some('+') if c == '+' => {
+                            self.next(input);
+                            output.push(Token::new(TokenType::Increment, begin.to(&self.here())));
+                        }
+                        _ => output.push(Token::new(token::punct(c), begin)),
+                    }
+                }
+                '=' | '!' | '<' | '>' => {
+                    let begin = self.here();
+                    self.next(input);
+                    if let Some('=') = self.peek(input) {
+                        self.next(input);
+                        output.push(Token::new(
+                            match c {
+                                '=' => TokenType::Equal,
+                                '!' => TokenType::Inequal,
+                                '<' => TokenType::LessEqual,
+                                '>' => TokenType::GreaterEqual,
+                                _ => unreachable!(),
+                            },
+                            begin.to(&self.here()),
+                        ));
+                    } else if (c == '<' || c == '>') && self.peek(input) == Some(c) {
+                        self.next(input);
+                        if let Some('=') = self.peek(input) {
+                            self.next(input);
+                            let spelling = format!("{}{}=", c, c);
+                            output.push(Token::new(
+                                TokenType::from(spelling.as_str()),
+                                begin.to(&self.here()),
+                            ));
+                        } else {
+                            output.push(Token::new(
+                                match c {
+                                    '<' => TokenType::LeftShift,
+                                    '>' => TokenType::RightShift,
+                                    _ => unreachable!(),
+                                },
+                                begin.to(&self.here()),
+                            ));
+                        }
+                    } else {
+                        output.push(Token::new(token::punct(c), begin));
+                    }
+                }
+                '|' | '&' => {
+                    let first_char = self.peek(input).unwrap();
+                    let begin = self.here();
+                    self.next(input);
+                    let c = self.peek(input);
+                    let span = begin.to(&self.here());
+                    match (c, first_char) {
+                        (Some('|'), '|') => {
+                            output.push(Token::new(TokenType::LogicalOr, span));
+                            self.next(input);
+                        }
+                        (Some('&'), '&') => {
+                            output.push(Token::new(TokenType::LogicalAnd, span));
+                            self.next(input);
+                        }
+                        (Some('='), _) => {
+                            self.next(input);
+                            output.push(self.compound_assign(first_char, &begin));
+                        }
+                        _ => output.push(Token::new(token::punct(first_char), begin)),
+                    }
+                }
+                '-' => {
+                    let begin = self.here();
+                    self.next(input);
+                    match self.peek(input) {
+                        Some('>') => {
+                            self.next(input);
+                            output.push(Token::new(TokenType::Arrow, begin.to(&self.here())));
+                        }
+                        Some('=') => {
+                            self.next(input);
+                            output.push(self.compound_assign(c, &begin));
+                        }
+                        Some('-') => {
+                            self.next(input);
+                            output.push(Token::new(TokenType::Decrement, begin.to(&self.here())));
+                        }
+                        _ => output.push(Token::new(token::punct(c), begin)),
+                    }
+                }
+                '/' => {
+                    let begin = self.here();
+                    self.next(input);
+                    match self.peek(input) {
+                        Some('/') => self.skip_line_comment(input),
+                        Some('*') => {
+                            if let Err(err) = self.skip_block_comment(input, &begin) {
+                                errors.push(err);
+                            }
+                        }
+                        Some('=') => {
+                            self.next(input);
+                            output.push(self.compound_assign(c, &begin));
+                        }
+                        _ => output.push(Token::new(TokenType::Divide, begin)),
+                    }
+                }
+
+                // A backslash directly followed by a newline joins two lines
+                '\\' => {
+                    self.next(input);
+                    if let Some('\r') = self.peek(input) {
+                        self.next(input);
+                    }
+                    match self.peek(input) {
+                        Some('\n') => {
+                            self.next(input);
+                        }
+                        _ => errors.push(crate::error!(self.here(), "Unknown character {}", c)),
+                    }
+                }
+
+                '\n' if self.preprocessing => {
+                    let span = self.here();
+                    self.next(input);
+                    output.push(Token::new(TokenType::Newline, span));
+                }
+
+                ' ' | '\t' | '\n' | '\r' => {
+                    self.next(input);
+                }
+                _ => {
+                    self.next(input);
+                    errors.push(crate::error!(self.here(), "Unknown character {}", c));
+                }
+            }
+            if self.preprocessing && output.len() > count {
+                self.keep_source(&mut output[count..], start);
+            }
+        }
+        match errors.is_empty() {
+            true => (output, Ok(())),
+            false => (output, Err(errors)),
+        }
+    }
+
+    // Gives the tokens lexed from one start position the text they were lexed from
+    // Two tokens are only lexed at once from '..', which are spelled as in the source
+    // A newline counts as whitespace before the next token
+    fn keep_source(&mut self, tokens: &mut [Token], start: usize) {
+        let end = self.source.len() - self.last_char.map_or(0, char::len_utf8);
+        let leading_space = start != self.token_end;
+        match tokens {
+            [token] if token.token() == TokenType::Newline => (),
+            [token] => {
+                let source = self.source[start..end]
+                    .replace("\\\r\n", "")
+                    .replace("\\\n", "");
+                *token = token.clone().with_source(source, leading_space);
+                self.token_end = end;
+            }
+            _ => self.token_end = end,
+        }
+    }
+
+    // Lex an identifier or keyword
+    pub fn lex_identifier<T: Iterator<Item = char>>(&mut self, input: &mut T) -> Token {
+        let start = self.here();
+        let mut identifier = String::new();
+        while let Some(c) = self.peek(input) {
+            match c {
+                'a'..='z' | 'A'..='Z' | '_' | '0' | '1'..='9' => {
+                    self.next(input);
+                    identifier.push(c);
+                }
+                _ => {
+                    break;
+                }
+            }
+        }
+        let span = start.to(&self.here());
+        use TokenType::*;
+        match identifier.as_str() {
+            "char" => Token::new(Char, span),
+            "int" => Token::new(Int, span),
+            "long" => Token::new(Long, span),
+            "short" => Token::new(Short, span),
+            "float" => Token::new(Float, span),
+            "double" => Token::new(Double, span),
+            "signed" => Token::new(Signed, span),
+            "unsigned" => Token::new(Unsigned, span),
+            "struct" => Token::new(Struct, span),
+            "union" => Token::new(Union, span),
+            "enum" => Token::new(Enum, span),
+            "void" => Token::new(Void, span),
+            "typedef" => Token::new(Typedef, span),
+            "static" => Token::new(Static, span),
+            "extern" => Token::new(Extern, span),
+            "const" => Token::new(Const, span),
+            "volatile" => Token::new(Volatile, span),
+            "if" => Token::new(If, span),
+            "else" => Token::new(Else, span),
+            "while" => Token::new(While, span),
+            "for" => Token::new(For, span),
+            "do" => Token::new(Do, span),
+            "break" => Token::new(Break, span),
+            "continue" => Token::new(Continue, span),
+            "switch" => Token::new(Switch, span),
+            "case" => Token::new(Case, span),
+            "default" => Token::new(Default, span),
+            "goto" => Token::new(Goto, span),
+            "return" => Token::new(Return, span),
+            "sizeof" => Token::new(Sizeof, span),
+            "__builtin_va_start" => Token::new(VaStart, span),
+            "__builtin_va_arg" => Token::new(VaArg, span),
+            "__builtin_va_end" => Token::new(VaEnd, span),
+            "__builtin_va_copy" => Token::new(VaCopy, span),
+
+            _ => Token::new(Ident(identifier), span),
+        }
+    }
+
+    // Lex a number
+    // Integer constants are decimal, octal with a leading 0, hexadecimal with 0x or binary with 0b
+    pub fn lex_number<T: Iterator<Item = char>>(
+        &mut self,
+        input: &mut T,
+    ) -> (Token, Result<(), String>) {
+        let start = self.here();
+        let mut number = String::new();
+        self.lex_digits(input, &mut number);
+        if let Some('.' | 'e' | 'E') = self.peek(input) {
+            return self.lex_float(input, start, number);
+        }
+        let (radix, name) = match (number.as_str(), self.peek(input)) {
+            ("0", Some('x' | 'X')) => (16, "hexadecimal"),
+            ("0", Some('b' | 'B')) => (2, "binary"),
+            _ if number.starts_with('0') => (8, "octal"),
+            _ => (10, "decimal"),
+        };
+
+        let mut errors = String::new();
+        if radix == 16 || radix == 2 {
+            self.next(input);
+            number.clear();
+            while let Some(c) = self.peek(input).filter(|c| c.is_digit(radix)) {
+                self.next(input);
+                number.push(c);
+            }
+            if number.is_empty() {
+                let span = start.to(&self.here());
+                errors.push_str(&error!(span, "Expected digits in {} constant", name));
+            }
+        }
+        // Octal constants can contain the digits 8 and 9, as they are lexed as decimal digits
+        for (i, c) in number.char_indices().filter(|(_, c)| !c.is_digit(radix)) {
+            let span = Lexer::character_span(&start, i);
+            errors.push_str(&error!(span, "Invalid digit '{}' in {} constant", c, name));
+        }
+
+        let suffix_start = self.here();
+        let mut suffix = String::new();
+        while let Some(c @ ('a'..='z' | 'A'..='Z' | '_' | '0'..='9')) = self.peek(input) {
+            self.next(input);
+            suffix.push(c);
+        }
+        let span = start.to(&self.here());
+        // A decimal digit directly after the digits of a binary constant is not a suffix
+        let (unsigned, long) = match Lexer::integer_suffix(&suffix) {
+            Some(suffix) => suffix,
+            None if suffix.starts_with(|c: char| c.is_digit(10)) => {
+                let span = Lexer::character_span(&suffix_start, 0);
+                let c = suffix.chars().next().unwrap();
+                errors.push_str(&error!(span, "Invalid digit '{}' in {} constant", c, name));
+                (false, false)
+            }
+            None => {
+                let span = suffix_start.to(&self.here());
+                errors.push_str(&error!(
+                    span,
+                    "Invalid suffix '{}' on integer constant", suffix
+                ));
+                (false, false)
+            }
+        };
+
+        let value = match u64::from_str_radix(&number, radix) {
+            Ok(value) => value,
+            Err(_) if !errors.is_empty() || number.is_empty() => 0,
+            Err(_) => {
+                errors.push_str(&error!(span, "Integer constant {} is too large", number));
+                0
+            }
+        };
+
+        // The type is the first type in the list of the constant that can represent the value
+        use TypeNode::{Int, Long, UInt, ULong};
+        let types: &[TypeNode] = match (unsigned, long, radix == 10) {
+            (false, false, true) => &[Int, Long],
+            (false, false, false) => &[Int, UInt, Long, ULong],
+            (false, true, true) => &[Long],
+            (false, true, false) => &[Long, ULong],
+            (true, false, _) => &[UInt, ULong],
+            (true, true, _) => &[ULong],
+        };
+        let maximum = |typ: &TypeNode| match typ {
+            Int => i32::MAX as u64,
+            UInt => u32::MAX as u64,
+            Long => i64::MAX as u64,
+            _ => u64::MAX,
+        };
+        let typ = match types.iter().find(|typ| value <= maximum(*typ)) {
+            Some(typ) => typ.clone(),
+            None => {
+                warning!(
+                    span,
+                    "Integer constant {} is so large that it is unsigned",
+                    value
+                );
+                ULong
+            }
+        };
+
+        let token = Token::new(TokenType::ConstI(value, typ), span);
+        match errors.is_empty() {
+            true => (token, Ok(())),
+            false => (token, Err(errors)),
+        }
+    }
+
+    // Lex the remainder of a floating point constant
+    // number contains the digits and period that have already been consumed
+    // The suffix f makes the constant a float, otherwise it is a double
+    fn lex_float<T: Iterator<Item = char>>(
+        &mut self,
+        input: &mut T,
+        start: Span,
+        mut number: String,
+    ) -> (Token, Result<(), String>) {
+        if !number.contains('.') && self.peek(input) == Some('.') {
+            self.next(input);
+            number.push('.');
+        }
+        self.lex_digits(input, &mut number);
+
+        let mut result = Ok(());
+        if let Some(c @ ('e' | 'E')) = self.peek(input) {
+            self.next(input);
+            number.push(c);
+            if let Some(c @ ('+' | '-')) = self.peek(input) {
+                self.next(input);
+                number.push(c);
+            }
+            if !self.lex_digits(input, &mut number) {
+                result = Err(error!(
+                    start.to(&self.here()),
+                    "Exponent of floating point constant {} has no digits", number
+                ));
+            }
+        }
+
+        let suffix = match self.peek(input) {
+            Some(c @ ('f' | 'F' | 'l' | 'L')) => {
+                self.next(input);
+                Some(c.to_ascii_lowercase())
+            }
+            _ => None,
+        };
+
+        let span = start.to(&self.here());
+        let value = number.parse::<f64>().unwrap_or(0.0);
+        let value = match suffix {
+            Some('f') => value as f32 as f64,
+            _ => value,
+        };
+        if result.is_ok() && value.is_infinite() {
+            result = Err(error!(
+                span,
+                "floating point constant {} is too large for its type", number
+            ));
+        }
+        match suffix {
+            Some('f') => (Token::new(TokenType::ConstF(value), span), result),
+            Some(_) => (
+                Token::new(TokenType::ConstD(value), span.clone()),
+                result.and(Err(error!(span, "long double is not supported"))),
+            ),
+            None => (Token::new(TokenType::ConstD(value), span), result),
+        }
+    }
+
+    // The span of the character at index in the token starting at start
+    fn character_span(start: &Span, index: usize) -> Span {
+        let index = index as u32;
+        Span::new(
+            start.file_index(),
+            start.line(),
+            start.column() + index,
+            start.offset() + index,
+            1,
+        )
+    }
+
+    // Returns whether the suffix of an integer constant makes it unsigned and long
+    // Both letters of ll have the same case, while u can be either before or after it
+    fn integer_suffix(suffix: &str) -> Option<(bool, bool)> {
+        if suffix.contains("lL") || suffix.contains("Ll") {
+            return None;
+        }
+        match suffix.to_ascii_lowercase().as_str() {
+            "" => Some((false, false)),
+            "u" => Some((true, false)),
+            "l" | "ll" => Some((false, true)),
+            "ul" | "lu" | "ull" | "llu" => Some((true, true)),
+            _ => None,
+        }
+    }
+
+    // Lex a sequence of decimal digits into number
+    // Returns whether any digits were found
+    fn lex_digits<T: Iterator<Item = char>>(&mut self, input: &mut T, number: &mut String) -> bool {
+        let length = number.len();
+        while let Some(c) = self.peek(input) {
+            match c {
+                '1'..='9' | '0' => {
+                    self.next(input);
+                    number.push(c);
+                }
+                _ => {
+                    break;
+                }
+            }
+        }
+        number.len() != length
+    }
+
+    fn lex_string<T: Iterator<Item = char>>(
+        &mut self,
+        input: &mut T,
+    ) -> (Token, Result<(), String>) {
+        let start = self.here();
+        let mut errors = String::new();
+        let mut string = Vec::new();
+        self.next(input);
+
+        while let Some(c) = self.peek(input) {
+            if c == '"' {
+                self.next(input);
+                break;
+            } else if !c.is_ascii() {
+                // Characters outside of ascii are stored in their UTF-8 encoding
+                self.next(input);
+                let mut buffer = [0; 4];
+                string.extend_from_slice(c.encode_utf8(&mut buffer).as_bytes());
+                continue;
+            }
+            let (c, err) = self.lex_single_char(input);
+            string.push(c);
+            if let Err(err) = err {
+                errors.push_str(&err)
+            }
+        }
+
+        let err = if !errors.is_empty() {
+            Err(errors)
+        } else {
+            Ok(())
+        };
+
+        let span = start.to(&self.here());
+        let token = Token::new(TokenType::CString(string), span.clone());
+        match self.peek(input) {
+            Some(_) => (token, err),
+            None => (token, Err(error!(span, "Unexpected end of file"))),
+        }
+    }
+
+    // A character constant has type int and the value of its char
+    // Multiple characters are combined from most to least significant byte, like gcc does
+    fn lex_char<T: Iterator<Item = char>>(&mut self, input: &mut T) -> (Token, Result<(), String>) {
+        let start = self.here();
+        let mut errors = String::new();
+        let mut characters = Vec::new();
+        self.next(input);
+
+        while let Some(c) = self.peek(input) {
+            if c == '\'' || c == '\n' {
+                break;
+            }
+            let (c, err) = self.lex_single_char(input);
+            characters.push(c);
+            if let Err(err) = err {
+                errors.push_str(&err)
+            }
+        }
+
+        let span = start.to(&self.here());
+        let value = match characters.as_slice() {
+            [] => {
+                errors.push_str(&error!(span, "Empty character constant"));
+                0
+            }
+            [c] => *c as i8 as i32,
+            _ => {
+                warning!(span, "Multi-character character constant");
+                characters
+                    .iter()
+                    .fold(0i32, |value, &c| value.wrapping_shl(8) | c as i32)
+            }
+        };
+        let err = if !errors.is_empty() {
+            Err(errors)
+        } else {
+            Ok(())
+        };
+
+        let token = Token::new(
+            TokenType::ConstI(value as u32 as u64, TypeNode::Int),
+            span.clone(),
+        );
+        match self.peek(input) {
+            Some('\'') => {
+                self.next(input);
+                (token, err)
+            }
+            Some(c) => (
+                token,
+                Err(error!(
+                    span,
+                    "Expected ' after character constant, but found {:?}", c
+                )),
+            ),
+            None => (
+                token,
+                Err(error!(
+                    span,
+                    "Expected ' after character constant, but found end of file"
+                )),
+            ),
+        }
+    }
+
+    //   \' \" \? \\ \a \b \f \n \r \t \v \ooo \xhh
+    fn lex_single_char<T: Iterator<Item = char>>(
+        &mut self,
+        input: &mut T,
+    ) -> (u8, Result<(), String>) {
+        let start = self.here();
+        match self.next(input) {
+            Some('\\') => match self.next(input) {
+                Some('\'') => (b'\'', Ok(())),
+                Some('\\') => (b'\\', Ok(())),
+                Some('"') => (b'"', Ok(())),
+                Some('?') => (b'?', Ok(())),
+                Some('a') => (b'\x07', Ok(())),
+                Some('b') => (b'\x08', Ok(())),
+                Some('f') => (b'\x0c', Ok(())),
+                Some('n') => (b'\n', Ok(())),
+                Some('r') => (b'\r', Ok(())),
+                Some('t') => (b'\t', Ok(())),
+                Some('v') => (b'\x0b', Ok(())),
+                Some(c @ '0'..='7') => {
+                    let mut value = c.to_digit(8).unwrap();
+                    for _ in 0..2 {
+                        match self.peek(input).and_then(|c| c.to_digit(8)) {
+                            Some(digit) => {
+                                self.next(input);
+                                value = value * 8 + digit;
+                            }
+                            None => break,
+                        }
+                    }
+                    self.escape_value(value, start, "Octal")
+                }
+                Some('x') => {
+                    let mut value: u32 = 0;
+                    let mut digits = 0;
+                    while let Some(digit) = self.peek(input).and_then(|c| c.to_digit(16)) {
+                        self.next(input);
+                        value = value.saturating_mul(16).saturating_add(digit);
+                        digits += 1;
+                    }
+                    match digits {
+                        0 => (
+                            0,
+                            Err(error!(
+                                start.to(&self.here()),
+                                "Expected hexadecimal digits after '\\x'"
+                            )),
+                        ),
+                        _ => self.escape_value(value, start, "Hexadecimal"),
+                    }
+                }
+                Some(c) if c != '\n' => (
+                    b'_',
+                    Err(error!(
+                        start,
+                        "Expected an escape sequence in string/character, but found '\\{}'", c
+                    )),
+                ),
+                _ => (
+                    b'_',
+                    Err(error!(
+                        start,
+                        "Expected an escape sequence in string/character, but found end of file"
+                    )),
+                ),
+            },
+            Some(c) if c.is_ascii() && c != '\n' => (c as u8, Ok(())),
+            Some(c) => (
+                b'_',
+                Err(error!(
+                    start,
+                    "Expected an ascii character in string/character, but found {:?}", c
+                )),
+            ),
+            None => (
+                b'_',
+                Err(error!(
+                    start,
+                    "Expected an ascii character in string/character, but found end of file"
+                )),
+            ),
+        }
+    }
+
+    // The value of a numeric escape sequence has to fit in a single char
+    fn escape_value(&mut self, value: u32, start: Span, kind: &str) -> (u8, Result<(), String>) {
+        match value {
+            0..=255 => (value as u8, Ok(())),
+            _ => (
+                0,
+                Err(error!(
+                    start.to(&self.here()),
+                    "{} escape sequence out of range", kind
+                )),
+            ),
+        }
+    }
+
+    // Creates the compound assignment token for c followed by '='
+    // The '=' should already have been consumed
+    fn compound_assign(&mut self, c: char, begin: &Span) -> Token {
+        let spelling = format!("{}=", c);
+        Token::new(TokenType::from(spelling.as_str()), begin.to(&self.here()))
+    }
+
+    // Skips a comment starting with '//' until the end of the line
+    // The newline itself is not consumed
+    fn skip_line_comment<T: Iterator<Item = char>>(&mut self, input: &mut T) {
+        while let Some(c) = self.peek(input) {
+            if c == '\n' {
+                break;
+            }
+            self.next(input);
+        }
+    }
+
+    // Skips a comment starting with '/*' until the closing '*/'
+    fn skip_block_comment<T: Iterator<Item = char>>(
+        &mut self,
+        input: &mut T,
+        begin: &Span,
+    ) -> Result<(), String> {
+        self.next(input);
+        let mut last = None;
+        while let Some(c) = self.next(input) {
+            if let (Some('*'), '/') = (last, c) {
+                return Ok(());
+            }
+            last = Some(c);
+        }
+        Err(error!(begin, "Unterminated comment"))
+    }
+
+    fn line_command<T: Iterator<Item = char>>(&mut self, input: &mut T) -> Result<(), String> {
+        let mut line = String::new();
+        while let Some(c) = self.peek(input) {
+            self.next(input);
+            if c == '\n' {
+                break;
+            }
+            line.push(c);
+        }
+
+        let split = line.split(' ').collect::<Vec<_>>();
+
+        if split[0] != "#" {
+            return Err(String::from("Bad line command"));
+        }
+
+        let line = match u32::from_str_radix(split[1], 10) {
+            Ok(i) => i,
+            Err(e) => {
+                return Err(e.to_string());
+            }
+        };
+
+        // Removes both the first and last character of a utf-8 string
+        //let file = split[2].chars().skip(1);
+        //let filename: String = file.clone().zip(file.skip(1)).map(|(c, _)| c).collect();
+        let filename = String::from(split[2]);
+
+        self.column = 1;
+        self.line = line;
+        self.file_index = file_table::add_sourcefile(&filename) as u32;
+
+        Ok(())
+    }
+}
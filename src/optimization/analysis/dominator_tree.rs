@@ -44,43 +44,42 @@ impl DominatorTree {
         let mut doms = vec![None; cfg.len()];
         let mut changed = true;
         let post_order = cfg.rev_post();
-        let mut look_up = vec![0; cfg.len()];
+        // Blocks that are not reachable from a block without predecessors have no post order
+        let mut look_up = vec![None; cfg.len()];
         for (i, &b) in post_order.iter().enumerate() {
-            look_up[b as usize] = i;
+            look_up[b as usize] = Some(i);
         }
-        doms[look_up[0]] = Some(look_up[0] as u32);
+        let root = look_up[0].unwrap();
+        doms[root] = Some(root as u32);
         log::trace!("post_order:{:?}", post_order);
         log::trace!("look_up:{:?}", look_up);
         while changed {
             changed = false;
             log::debug!("new iteration: {:?}", doms);
 
-            for (i, &block) in post_order.iter().enumerate().filter(|(_, &b)| b != 0) {
+            // Blocks are visited in reverse post order, so the parent in the search is always first
+            // This keeps the algorithm correct for irreducible control flow created by goto
+            for (i, &block) in post_order
+                .iter()
+                .enumerate()
+                .rev()
+                .filter(|(_, &b)| b != 0)
+            {
                 let node = &cfg[block];
 
-                let pred = node
+                let mut preds = node
                     .predecessors
                     .iter()
-                    .map(|&p| look_up[p as usize])
-                    .find(|&p| doms[p].is_some());
+                    .filter_map(|&p| look_up[p as usize])
+                    .filter(|&p| doms[p].is_some());
 
-                let pred = if pred.is_some() {
-                    pred.unwrap()
-                } else {
-                    continue;
+                let mut new_idom = match preds.next() {
+                    Some(pred) => pred,
+                    None => continue,
                 };
 
-                let mut new_idom = pred; //look_up[pred as usize];
-
-                for p in node
-                    .predecessors
-                    .iter()
-                    .map(|&p| look_up[p as usize])
-                    .filter(|&p| p != pred)
-                {
-                    if doms[p].is_some() {
-                        new_idom = DominatorTree::intersect(new_idom, p, &doms);
-                    }
+                for p in preds {
+                    new_idom = DominatorTree::intersect(new_idom, p, &doms);
                 }
                 if doms[i] != Some(new_idom as u32) {
                     doms[i] = Some(new_idom as u32);
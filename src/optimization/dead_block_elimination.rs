@@ -7,34 +7,27 @@ use std::mem;
 /// Only removes dead blocks, not any unused variables.
 // It first finds all blocks that need to be modified, removes them and renumbers them
 pub fn eliminate_dead_blocks(function: &mut IRFunction) {
-    let mut cfg = ControlFlowGraph::construct(&function.instructions);
-    let dead_blocks = find_dead_blocks(&mut cfg);
+    let cfg = ControlFlowGraph::construct(&function.instructions);
+    let dead_blocks = find_dead_blocks(&cfg);
     remove_blocks(function, &dead_blocks);
     renumber_blocks(function, &dead_blocks)
 }
 
-/// Finds all blocks of code without predecessors using a worklist.
-/// Never removes block 0 and cannot remove loops.
-/// Disconnects the block from the cfg.
-fn find_dead_blocks(cfg: &mut ControlFlowGraph) -> HashSet<u32> {
-    let mut work_list: VecDeque<_> = (1..cfg.len() as u32).collect();
-    let mut dead_code = HashSet::new();
+/// Finds all blocks of code that can not be reached from block 0 using a worklist.
+/// Never removes block 0. Unreachable loops, which can be created using goto, are also removed.
+fn find_dead_blocks(cfg: &ControlFlowGraph) -> HashSet<u32> {
+    let mut work_list = VecDeque::from(vec![0]);
+    let mut reachable = HashSet::new();
 
     while let Some(block) = work_list.pop_front() {
-        if block == 0 {
-            continue;
-        }
-        if cfg[block].predecessors.is_empty() {
-            dead_code.insert(block);
-            let successors = mem::take(&mut cfg[block].successors);
-            for succ in successors {
-                work_list.push_back(succ);
-                cfg[succ].predecessors.retain(|b| *b != block);
-            }
+        if reachable.insert(block) {
+            work_list.extend(cfg[block].successors.iter().cloned());
         }
     }
 
-    dead_code
+    (0..cfg.len() as u32)
+        .filter(|block| !reachable.contains(block))
+        .collect()
 }
 
 /// Removes block by only keeping instructions outside the loops
@@ -80,6 +73,12 @@ fn renumber_blocks(function: &mut IRFunction, dead_blocks: &HashSet<u32>) {
             | IRInstruction::Label(None, label)
             | IRInstruction::Jmp(label) => *label = label_map[label],
 
+            IRInstruction::JmpTable(_, _, labels) => {
+                for label in labels {
+                    *label = label_map[label];
+                }
+            }
+
             IRInstruction::Label(Some(phi), label) => {
                 *label = label_map[label];
                 for sources in &mut phi.sources {
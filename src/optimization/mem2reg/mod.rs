@@ -139,7 +139,7 @@ fn replace_mem(
     use IRInstruction::{AddrL, Load, Store};
     for (this, next) in range_1.zip(range_2) {
         match (&instructions[this], &instructions[next]) {
-            (&AddrL(_, a, var), &Store(_size, store, address))
+            (&AddrL(_, a, var), &Store(_size, store, address, _))
                 if promotions.contains(&(var as u32)) && a == address =>
             {
                 instructions[this] = IRInstruction::Nop;
@@ -147,7 +147,7 @@ fn replace_mem(
                 stack[var].push(store);
                 stack_size[var] += 1;
             }
-            (&AddrL(_, a, var), &Load(size, load, address))
+            (&AddrL(_, a, var), &Load(size, load, address, _))
                 if promotions.contains(&(var as u32)) && a == address =>
             {
                 instructions[this] = IRInstruction::Nop;
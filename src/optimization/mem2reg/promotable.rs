@@ -5,6 +5,7 @@ impl IRSize {
     fn is_promotable(&self) -> bool {
         match self {
             IRSize::S8 | IRSize::S16 | IRSize::S32 | IRSize::S64 | IRSize::P => true,
+            IRSize::F32 | IRSize::F64 => true,
             IRSize::V | IRSize::B(_) => false,
         }
     }
@@ -53,9 +54,13 @@ pub(super) fn find_promotable_variables(
         }
 
         // Only loads or stores immediately following the load are accepted(this is significantly more severe then technically necessary,but sufficient for most needs)
+        // Volatile accesses must stay in memory, so these variables are never promoted
         match instructions.get(index + 1) {
-            Some(&IRInstruction::Load(_, _, address)) if address == vreg => (),
-            Some(&IRInstruction::Store(_, src, address)) if address == vreg && src != vreg => (),
+            Some(instruction) if instruction.is_volatile() => {
+                candidates.remove(&variable);
+            }
+            Some(&IRInstruction::Load(_, _, address, _)) if address == vreg => (),
+            Some(&IRInstruction::Store(_, src, address, _)) if address == vreg && src != vreg => (),
             None => (),
             _ => {
                 candidates.remove(&variable);
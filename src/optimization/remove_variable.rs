@@ -6,8 +6,15 @@ impl IRFunction {
     pub fn remove_variables(&mut self) {
         let mut used_variables = HashSet::new();
         for instruction in &self.instructions {
-            if let &IRInstruction::AddrL(_, _, variable) = instruction {
-                used_variables.insert(variable as u32);
+            match instruction {
+                &IRInstruction::AddrL(_, _, variable) => {
+                    used_variables.insert(variable as u32);
+                }
+                // Aggregates returned in registers are stored directly into a variable
+                IRInstruction::Call(.., arguments) | IRInstruction::CallV(.., arguments) => {
+                    used_variables.extend(arguments.return_variable);
+                }
+                _ => (),
             }
         }
         self.variables
@@ -1,217 +1,343 @@
-use super::{Type, TypeNode};
-use crate::{span::Span, token::Token};
-
-// This module declares all the AST members that are used
-
-// Represents the entire TranslationUnit
-#[derive(Debug, Clone)]
-pub struct TranslationUnit {
-    pub global_declarations: Vec<ExternalDeclaration>,
-}
-
-// Represent a function or global variable declaration
-#[derive(Debug, Clone)]
-pub struct ExternalDeclaration {
-    pub span: Span,
-    pub decl_type: Type,
-    pub ast_type: ASTType,
-    pub name: Option<String>,
-    pub function_body: Option<Vec<Statement>>,
-    pub expression: Option<Expression>,
-}
-
-// Represents all possible statements
-#[derive(Debug, Clone)]
-pub enum Statement {
-    Return {
-        span: Span,
-        ast_type: Type,
-        expression: Option<Expression>,
-    },
-
-    If {
-        span: Span,
-        expression: Expression,
-        statement: Box<Statement>,
-        else_statement: Option<Box<Statement>>,
-    },
-
-    While {
-        span: Span,
-        expression: Expression,
-        statement: Box<Statement>,
-        do_while: bool,
-    },
-
-    For {
-        span: Span,
-        init: Option<Box<Statement>>,
-        condition: Option<Box<Expression>>,
-        expression: Option<Box<Expression>>,
-        statement: Box<Statement>,
-    },
-
-    Break {
-        span: Span,
-    },
-
-    Continue {
-        span: Span,
-    },
-
-    Expression {
-        span: Span,
-        expression: Expression,
-    },
-
-    Empty(Span),
-
-    Declaration {
-        span: Span,
-        ident: Option<String>,
-        decl_type: Type,
-        ast_type: ASTType,
-        init: Option<Expression>,
-    },
-
-    Compound {
-        span: Span,
-        statements: Vec<Statement>,
-    },
-}
-
-// Expression has a seperate expression variant
-// This is used to seperate the shared components
-#[derive(Debug, Clone)]
-pub struct Expression {
-    pub span: Span,
-    pub ast_type: Type,
-    pub variant: ExpressionVariant,
-}
-
-impl Expression {
-    pub fn default(span: &Span) -> Expression {
-        Expression {
-            span: span.clone(),
-            ast_type: Type::empty(),
-            variant: ExpressionVariant::ConstI(0),
-        }
-    }
-}
-
-#[derive(Debug, Clone)]
-pub enum ExpressionVariant {
-    Assign(Box<Expression>, Box<Expression>),
-
-    Ternary(Box<Expression>, Box<Expression>, Box<Expression>),
-    Binary(BinaryExpressionType, Box<Expression>, Box<Expression>),
-    Unary(UnaryExpressionType, Box<Expression>),
-    Member(Box<Expression>, String, bool, u16),
-    Cast(Box<Expression>, ASTType),
-
-    Function(Box<Expression>, Vec<Expression>),
-
-    Sizeof(SizeofType),
-    ConstI(i128),
-    CString(String),
-    Ident(String, u32, bool),
-}
-
-#[derive(Debug, Clone)]
-pub enum SizeofType {
-    Type(ASTType, Type),
-    Expression(Box<Expression>),
-}
-
-#[derive(Debug, Clone)]
-pub enum BinaryExpressionType {
-    Index,
-    Add,
-    Subtract,
-    Multiply,
-    Divide,
-    Equal,
-    Inequal,
-    Less,
-    LessEqual,
-    Greater,
-    GreaterEqual,
-    BinOr,
-    BinAnd,
-    LogOr,
-    LogAnd,
-    Comma,
-}
-
-#[derive(Debug, Clone)]
-pub enum UnaryExpressionType {
-    Identity,
-    Negate,
-    BinNot,
-    LogNot,
-    Deref,
-    Address,
-}
-
-#[derive(Debug, Clone)]
-pub struct ASTType {
-    pub span: Span,
-    pub list: Vec<ASTTypeNode>,
-}
-#[derive(Debug, Clone)]
-pub enum ASTTypeNode {
-    Simple(TypeNode),
-    Array(Box<Expression>),
-    Struct(Box<ASTStruct>),
-    Name(String),
-    Function(Vec<ASTType>),
-}
-
-#[derive(Debug, Clone)]
-pub struct ASTStruct {
-    pub name: Option<String>,
-    pub members: Option<Vec<ASTType>>,
-}
-
-impl ASTType {
-    pub fn combine(mut self, mut rhs: ASTType) -> ASTType {
-        let span = self.span.to(&rhs.span);
-        rhs.list.append(&mut self.list);
-        ASTType {
-            span,
-            list: rhs.list,
-        }
-    }
-    pub fn from_slice(slice: &[ASTTypeNode], span: Span) -> ASTType {
-        ASTType {
-            span,
-            list: slice.into(),
-        }
-    }
-    pub fn get_name(&self) -> Option<String> {
-        match self.list.get(0) {
-            Some(ASTTypeNode::Name(name)) => Some(name.clone()),
-            _ => None,
-        }
-    }
-    pub fn has_name(&self) -> bool {
-        self.get_name().is_some()
-    }
-    pub fn is_type_declaration(&self) -> bool {
-        use ASTTypeNode::*;
-        for entry in &self.list {
-            match entry {
-                Struct(s) => return s.members.is_some() && s.name.is_some(),
-                Name(_) => continue,
-                _ => break,
-            }
-        }
-        false
-    }
-}
-
-impl From<Token> for ASTTypeNode {
-    fn from(t: Token) -> Self {
-        ASTTypeNode::Simple(t.into())
-    }
-}
+use super::{r#type::StorageClass, Type, TypeNode};
+use crate::{span::Span, token::Token};
+use std::{cell::Cell, rc::Rc};
+
+// This module declares all the AST members that are used
+
+// Represents the entire TranslationUnit
+#[derive(Debug, Clone)]
+pub struct TranslationUnit {
+    pub global_declarations: Vec<ExternalDeclaration>,
+}
+
+// Represent a function or global variable declaration
+#[derive(Debug, Clone)]
+pub struct ExternalDeclaration {
+    pub span: Span,
+    pub decl_type: Type,
+    pub ast_type: ASTType,
+    pub name: Option<String>,
+    pub function_body: Option<Vec<Statement>>,
+    pub expression: Option<Initializer>,
+}
+
+// Represents all possible statements
+#[derive(Debug, Clone)]
+pub enum Statement {
+    Return {
+        span: Span,
+        ast_type: Type,
+        expression: Option<Expression>,
+    },
+
+    If {
+        span: Span,
+        expression: Expression,
+        statement: Box<Statement>,
+        else_statement: Option<Box<Statement>>,
+    },
+
+    While {
+        span: Span,
+        expression: Expression,
+        statement: Box<Statement>,
+        do_while: bool,
+    },
+
+    For {
+        span: Span,
+        init: Vec<Statement>,
+        condition: Option<Box<Expression>>,
+        expression: Option<Box<Expression>>,
+        statement: Box<Statement>,
+    },
+
+    Break {
+        span: Span,
+    },
+
+    Continue {
+        span: Span,
+    },
+
+    Switch {
+        span: Span,
+        expression: Expression,
+        statement: Box<Statement>,
+    },
+
+    Case {
+        span: Span,
+        expression: Expression,
+        statement: Box<Statement>,
+    },
+
+    Default {
+        span: Span,
+        statement: Box<Statement>,
+    },
+
+    Goto {
+        span: Span,
+        label: String,
+    },
+
+    Label {
+        span: Span,
+        label: String,
+        statement: Box<Statement>,
+    },
+
+    Expression {
+        span: Span,
+        expression: Expression,
+    },
+
+    Empty(Span),
+
+    Declaration {
+        span: Span,
+        ident: Option<String>,
+        decl_type: Type,
+        ast_type: ASTType,
+        init: Option<Initializer>,
+    },
+
+    Compound {
+        span: Span,
+        statements: Vec<Statement>,
+    },
+}
+
+// The value an object is initialized with
+#[derive(Debug, Clone)]
+pub enum Initializer {
+    Expression(Expression),
+    List(InitializerList),
+}
+
+// The semantic analysis flattens the entries into the scalar values that are set
+// Every value is found through the indices of the members and elements that contain it
+#[derive(Debug, Clone)]
+pub struct InitializerList {
+    pub span: Span,
+    pub entries: Vec<(Vec<Designator>, Initializer)>,
+    pub values: Vec<(Vec<usize>, Expression)>,
+}
+
+#[derive(Debug, Clone)]
+pub enum Designator {
+    Member(String),
+    Index(Expression),
+}
+
+// Expression has a seperate expression variant
+// This is used to seperate the shared components
+#[derive(Debug, Clone)]
+pub struct Expression {
+    pub span: Span,
+    pub ast_type: Type,
+    pub variant: ExpressionVariant,
+}
+
+impl Expression {
+    pub fn default(span: &Span) -> Expression {
+        Expression {
+            span: span.clone(),
+            ast_type: Type::empty(),
+            variant: ExpressionVariant::ConstI(0),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ExpressionVariant {
+    Assign(Box<Expression>, Box<Expression>),
+    CompoundAssign(BinaryExpressionType, Box<Expression>, Box<Expression>),
+
+    Ternary(Box<Expression>, Box<Expression>, Box<Expression>),
+    Binary(BinaryExpressionType, Box<Expression>, Box<Expression>),
+    Unary(UnaryExpressionType, Box<Expression>),
+    Member(Box<Expression>, String, bool, u16),
+    Cast(Box<Expression>, ASTType),
+
+    Function(Box<Expression>, Vec<Expression>),
+
+    // Builtins operating on the va_list given as the first expression
+    VaStart(Box<Expression>, Box<Expression>),
+    VaArg(Box<Expression>, ASTType),
+    VaEnd(Box<Expression>),
+    VaCopy(Box<Expression>, Box<Expression>),
+
+    Sizeof(SizeofType),
+    ConstI(i128),
+    ConstF(f64),
+    CString(Vec<u8>),
+    Ident(String, u32, bool),
+}
+
+#[derive(Debug, Clone)]
+pub enum SizeofType {
+    Type(ASTType, Type),
+    Expression(Box<Expression>),
+}
+
+#[derive(Debug, Clone)]
+pub enum BinaryExpressionType {
+    Index,
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Modulo,
+    Equal,
+    Inequal,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    LeftShift,
+    RightShift,
+    BinOr,
+    BinAnd,
+    BinXor,
+    LogOr,
+    LogAnd,
+    Comma,
+}
+
+#[derive(Debug, Clone)]
+pub enum UnaryExpressionType {
+    Identity,
+    Negate,
+    BinNot,
+    LogNot,
+    Deref,
+    Address,
+    PreIncrement,
+    PreDecrement,
+    PostIncrement,
+    PostDecrement,
+}
+
+#[derive(Debug, Clone)]
+pub struct ASTType {
+    pub span: Span,
+    pub list: Vec<ASTTypeNode>,
+}
+#[derive(Debug, Clone)]
+pub enum ASTTypeNode {
+    Simple(TypeNode),
+    Array(Box<Expression>),
+    Struct(Box<ASTStruct>),
+    Enum(Box<ASTEnum>),
+    Name(String),
+    Function(Vec<ASTType>, bool),
+    Typedef,
+    Static,
+    Extern,
+    Const,
+    Volatile,
+    TypedefName(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ASTStruct {
+    pub name: Option<String>,
+    pub members: Option<Vec<(ASTType, Option<Expression>)>>,
+    pub is_union: bool,
+    // The index of the defined struct, shared with the later declarators of the declaration
+    pub index: Rc<Cell<Option<usize>>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ASTEnum {
+    pub name: Option<String>,
+    pub members: Option<Vec<(String, Option<Expression>)>>,
+}
+
+impl ASTType {
+    pub fn combine(mut self, mut rhs: ASTType) -> ASTType {
+        let span = self.span.to(&rhs.span);
+        rhs.list.append(&mut self.list);
+        ASTType {
+            span,
+            list: rhs.list,
+        }
+    }
+    pub fn from_slice(slice: &[ASTTypeNode], span: Span) -> ASTType {
+        ASTType {
+            span,
+            list: slice.into(),
+        }
+    }
+    pub fn get_name(&self) -> Option<String> {
+        match self.list.get(0) {
+            Some(ASTTypeNode::Name(name)) => Some(name.clone()),
+            _ => None,
+        }
+    }
+    pub fn has_name(&self) -> bool {
+        self.get_name().is_some()
+    }
+    pub fn is_typedef(&self) -> bool {
+        self.list
+            .iter()
+            .any(|entry| matches!(entry, ASTTypeNode::Typedef))
+    }
+    pub fn get_storage_class(&self) -> StorageClass {
+        for entry in &self.list {
+            match entry {
+                ASTTypeNode::Static => return StorageClass::Static,
+                ASTTypeNode::Extern => return StorageClass::Extern,
+                _ => (),
+            }
+        }
+        StorageClass::Default
+    }
+    pub fn has_storage_class(&self) -> bool {
+        self.is_typedef() || self.get_storage_class() != StorageClass::Default
+    }
+    // The specifiers shared by the later declarators of a declaration
+    // Struct and enum definitions only belong to the first declarator, the others refer to them
+    // An anonymous struct is referred to without a name and members
+    pub fn without_definitions(&self) -> ASTType {
+        use ASTTypeNode::*;
+        let list = self
+            .list
+            .iter()
+            .map(|entry| match entry {
+                Struct(s) if s.members.is_some() => Struct(Box::new(ASTStruct {
+                    name: s.name.clone(),
+                    members: None,
+                    is_union: s.is_union,
+                    index: s.index.clone(),
+                })),
+                Enum(e) if e.members.is_some() => Simple(TypeNode::Int),
+                entry => entry.clone(),
+            })
+            .collect();
+        ASTType {
+            span: self.span.clone(),
+            list,
+        }
+    }
+    pub fn is_type_declaration(&self) -> bool {
+        use ASTTypeNode::*;
+        for entry in &self.list {
+            match entry {
+                Struct(s) => return s.members.is_some() && s.name.is_some(),
+                Enum(e) => return e.members.is_some(),
+                Name(_) | Const | Volatile => continue,
+                _ => break,
+            }
+        }
+        false
+    }
+}
+
+impl From<Token> for ASTTypeNode {
+    fn from(t: Token) -> Self {
+        ASTTypeNode::Simple(t.into())
+    }
+}
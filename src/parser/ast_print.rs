@@ -1,393 +1,532 @@
-use super::ast::*;
-use std::fmt;
-use std::fmt::Display;
-
-// This module implements the Display trait for the AST
-// The print-out should be valid c code to allow for relexing and reparsing
-
-// Allows the conversion of a type into a String representing the type as used in C
-
-impl Display for TranslationUnit {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for declaration in &self.global_declarations {
-            writeln!(f, "{}", declaration)?;
-        }
-        Ok(())
-    }
-}
-
-impl Display for ExternalDeclaration {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        write!(f, "{}", self.ast_type)?;
-        match &self.function_body {
-            None => writeln!(f, ";")?,
-            Some(body) => {
-                writeln!(f, "{{")?;
-                for statement in body {
-                    write!(f, "{}", statement)?;
-                }
-                writeln!(f, "}}")?;
-            }
-        }
-        Ok(())
-    }
-}
-
-impl Display for Statement {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        use Statement::*;
-        match self {
-            Break { .. } => writeln!(f, "break;")?,
-
-            Continue { .. } => writeln!(f, "continue;")?,
-
-            Compound {
-                span: _,
-                statements,
-            } => {
-                writeln!(f, "{{")?;
-                for stmt in statements {
-                    write!(f, "{}", stmt)?;
-                }
-                writeln!(f, "}}")?;
-            }
-
-            Declaration {
-                span: _,
-                ident: _,
-                decl_type: _,
-                ast_type,
-                init,
-            } => {
-                write!(f, "{}", ast_type)?;
-                if let Some(exp) = init {
-                    writeln!(f, " = {};", exp)?;
-                } else {
-                    writeln!(f, ";")?;
-                }
-            }
-
-            Empty(_) => write!(f, ";")?,
-
-            Expression {
-                span: _,
-                expression,
-            } => writeln!(f, "{};", expression)?,
-
-            If {
-                span: _,
-                expression,
-                statement,
-                else_statement,
-            } => {
-                writeln!(f, "if ({})", expression)?;
-                writeln!(f, "{}", statement)?;
-                if let Some(statement) = else_statement {
-                    writeln!(f, "else \n{}", statement)?;
-                }
-            }
-
-            For {
-                span: _,
-                init,
-                condition,
-                expression,
-                statement,
-            } => {
-                write!(f, "for (")?;
-                match init {
-                    Some(init) => write!(f, "{}", init)?,
-                    None => write!(f, ";")?,
-                }
-
-                if let Some(init) = condition {
-                    write!(f, "{}", init)?;
-                }
-                write!(f, ";")?;
-
-                match expression {
-                    Some(init) => writeln!(f, "{})", init)?,
-                    None => writeln!(f, ")")?,
-                }
-
-                writeln!(f, "{}", statement)?;
-            }
-
-            Return {
-                span: _,
-                ast_type: _,
-                expression,
-            } => match expression {
-                Some(expression) => writeln!(f, "return {};", expression)?,
-                None => writeln!(f, "return;")?,
-            },
-
-            While {
-                span: _,
-                expression,
-                statement,
-                do_while: false,
-            } => {
-                writeln!(f, "while ({})", expression)?;
-                writeln!(f, "{}", statement)?;
-            }
-
-            While {
-                span: _,
-                expression,
-                statement,
-                do_while: true,
-            } => {
-                write!(f, "do\n{}", statement)?;
-                writeln!(f, "while ({});", expression)?;
-            }
-        }
-        Ok(())
-    }
-}
-
-impl Display for Expression {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        use ExpressionVariant::*;
-        match &self.variant {
-            ConstI(value) => write!(f, "{}", value)?,
-            CString(value) => write!(f, "\"{}\"", print_c_string::format_c_string(value))?,
-            Ident(name, ..) => write!(f, "{}", name)?,
-            Sizeof(typ) => write!(f, "sizeof {}", typ)?,
-
-            Function(func, arguments) => {
-                write!(f, "({}(", func)?;
-                if let Some(arg) = arguments.get(0) {
-                    write!(f, "{}", arg)?;
-                }
-                for arg in arguments.iter().skip(1) {
-                    write!(f, ",{}", arg)?;
-                }
-                write!(f, "))")?;
-            }
-
-            Member(exp, id, indirect, _) => {
-                write!(f, "({}){}{}", exp, if *indirect { "->" } else { "." }, id)?;
-            }
-
-            Cast(exp, typ) => {
-                write!(f, "(({}){})", typ, exp)?;
-            }
-
-            Unary(op, exp) => {
-                write!(f, "({} {})", op, exp)?;
-            }
-
-            Binary(BinaryExpressionType::Index, left, right) => {
-                write!(f, "({}[{}])", left, right)?;
-            }
-
-            Binary(op, left, right) => {
-                write!(f, "({} {} {})", left, op, right)?;
-            }
-
-            Ternary(cond, left, right) => {
-                write!(f, "({} ? {} : {})", cond, left, right)?;
-            }
-
-            Assign(left, right) => {
-                write!(f, "({} = {})", left, right)?;
-            }
-        }
-        Ok(())
-    }
-}
-
-impl Display for BinaryExpressionType {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use BinaryExpressionType::*;
-        write!(
-            f,
-            "{}",
-            match self {
-                Index => "[]",
-                Add => "+",
-                Subtract => "-",
-                Multiply => "*",
-                Divide => "/",
-                Equal => "==",
-                Inequal => "!=",
-                Less => "<",
-                LessEqual => "<=",
-                Greater => ">",
-                GreaterEqual => ">=",
-                BinOr => "|",
-                BinAnd => "&",
-                LogOr => "||",
-                LogAnd => "&&",
-                Comma => ",",
-            }
-        )
-    }
-}
-
-impl Display for UnaryExpressionType {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        use UnaryExpressionType::*;
-        write!(
-            f,
-            "{}",
-            match self {
-                Identity => "+",
-                Negate => "-",
-                BinNot => "~",
-                LogNot => "!",
-                Deref => "*",
-                Address => "&",
-            }
-        )
-    }
-}
-
-impl Display for SizeofType {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            SizeofType::Type(typ, _) => write!(f, "({})", typ),
-            SizeofType::Expression(exp) => write!(f, "{}", exp),
-        }
-    }
-}
-
-impl Display for ASTType {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        format_prefix_type(&self.list, f)?;
-        format_type(&self.list, f)
-    }
-}
-
-fn format_prefix_type(typ: &[ASTTypeNode], f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    for i in 0..typ.len() {
-        use super::TypeNode::*;
-        use ASTTypeNode::*;
-        type AST = ASTTypeNode;
-        match &typ[i] {
-            Simple(Char) => write!(f, "char ")?,
-            Simple(Int) => write!(f, "int ")?,
-            Simple(Long) => write!(f, "long ")?,
-            Simple(Short) => write!(f, "short ")?,
-            Simple(Void) => write!(f, "void ")?,
-            AST::Struct(s) => {
-                write!(f, "struct {} ", s.name.clone().unwrap_or_default())?;
-                if let Some(members) = &s.members {
-                    writeln!(f, "{{")?;
-                    for member in members {
-                        writeln!(f, "{};", member)?;
-                    }
-                    writeln!(f, "}}")?;
-                }
-            }
-            _ => (),
-        }
-    }
-    Ok(())
-}
-
-fn format_type(typ: &[ASTTypeNode], f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-    if typ.is_empty() {
-        return Ok(());
-    }
-
-    for i in (0..typ.len()).rev() {
-        use super::TypeNode::*;
-        use ASTTypeNode::*;
-        type AST = ASTTypeNode;
-        match &typ[i] {
-            Simple(Char | Int | Long | Short | Void) => (),
-
-            Simple(Pointer) => write!(f, "* ")?,
-            Simple(t) => {
-                log::error!("unexpected type node {:?}", t);
-                unreachable!()
-            }
-            AST::Name(name) => write!(f, "{} ", name)?,
-            AST::Function(arguments) => {
-                //Extend later when functions are fully implemented
-                write!(f, "(")?;
-                format_type(&typ[0..i], f)?;
-                write!(f, ")")?;
-                write!(f, "(")?;
-                if let Some(arg) = arguments.get(0) {
-                    write!(f, "{}", arg)?;
-                }
-                for arg in arguments.iter().skip(1) {
-                    write!(f, ", {}", arg)?;
-                }
-                write!(f, ")")?;
-                break;
-            }
-            AST::Array(size) => {
-                //Extend later when functions are fully implemented
-                write!(f, "(")?;
-                format_type(&typ[0..i], f)?;
-                write!(f, ")")?;
-                write!(f, "[{}]", size)?;
-                break;
-            }
-            AST::Struct(..) => (),
-        };
-    }
-    Ok(())
-}
-
-// This module mimic the functionality in the crate either, which might have been better to use
-// EitherIterator is used to flatten something that will either be a string or a char to an iterator of char
-mod print_c_string {
-    enum Either {
-        Char(char),
-        String(&'static str),
-    }
-
-    enum EitherIterator {
-        Char(std::iter::Once<char>),
-        String(std::str::Chars<'static>),
-    }
-
-    impl Iterator for EitherIterator {
-        type Item = char;
-        fn next(&mut self) -> Option<Self::Item> {
-            match self {
-                EitherIterator::Char(it) => it.next(),
-                EitherIterator::String(it) => it.next(),
-            }
-        }
-    }
-
-    impl IntoIterator for Either {
-        type IntoIter = EitherIterator;
-        type Item = char;
-        fn into_iter(self) -> Self::IntoIter {
-            match self {
-                Either::Char(c) => EitherIterator::Char(std::iter::once(c)),
-                Either::String(str) => EitherIterator::String(str.chars()),
-            }
-        }
-    }
-
-    pub fn format_c_string(string: &String) -> String {
-        use Either::{Char, String};
-        string
-            .chars()
-            .flat_map(|c| {
-                match c {
-                    '\x07' => String("\\a"),
-                    '\x08' => String("\\b"),
-                    '\x0b' => String("\\v"),
-                    '\x0e' => String("\\f"),
-                    '\\' => String("\\\\"),
-                    '\'' => String("\\'"),
-                    '\"' => String("\\\""),
-                    '\n' => String("\\n"),
-                    '\r' => String("\\r"),
-                    '\t' => String("\\t"),
-                    c => Char(c),
-                }
-                .into_iter()
-            })
-            .collect()
-    }
-}
+use super::ast::*;
+use std::fmt;
+use std::fmt::Display;
+
+// This module implements the Display trait for the AST
+// The print-out should be valid c code to allow for relexing and reparsing
+
+// Allows the conversion of a type into a String representing the type as used in C
+
+impl Display for TranslationUnit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for declaration in &self.global_declarations {
+            writeln!(f, "{}", declaration)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for ExternalDeclaration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.ast_type)?;
+        match &self.function_body {
+            None => writeln!(f, ";")?,
+            Some(body) => {
+                writeln!(f, "{{")?;
+                for statement in body {
+                    write!(f, "{}", statement)?;
+                }
+                writeln!(f, "}}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Statement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use Statement::*;
+        match self {
+            Break { .. } => writeln!(f, "break;")?,
+
+            Continue { .. } => writeln!(f, "continue;")?,
+
+            Switch {
+                span: _,
+                expression,
+                statement,
+            } => {
+                writeln!(f, "switch ({})", expression)?;
+                writeln!(f, "{}", statement)?;
+            }
+
+            Case {
+                span: _,
+                expression,
+                statement,
+            } => {
+                writeln!(f, "case {}:", expression)?;
+                write!(f, "{}", statement)?;
+            }
+
+            Default { span: _, statement } => {
+                writeln!(f, "default:")?;
+                write!(f, "{}", statement)?;
+            }
+
+            Goto { span: _, label } => writeln!(f, "goto {};", label)?,
+
+            Label {
+                span: _,
+                label,
+                statement,
+            } => {
+                writeln!(f, "{}:", label)?;
+                write!(f, "{}", statement)?;
+            }
+
+            Compound {
+                span: _,
+                statements,
+            } => {
+                writeln!(f, "{{")?;
+                for stmt in statements {
+                    write!(f, "{}", stmt)?;
+                }
+                writeln!(f, "}}")?;
+            }
+
+            Declaration {
+                span: _,
+                ident: _,
+                decl_type: _,
+                ast_type,
+                init,
+            } => {
+                write!(f, "{}", ast_type)?;
+                if let Some(exp) = init {
+                    writeln!(f, " = {};", exp)?;
+                } else {
+                    writeln!(f, ";")?;
+                }
+            }
+
+            Empty(_) => write!(f, ";")?,
+
+            Expression {
+                span: _,
+                expression,
+            } => writeln!(f, "{};", expression)?,
+
+            If {
+                span: _,
+                expression,
+                statement,
+                else_statement,
+            } => {
+                writeln!(f, "if ({})", expression)?;
+                writeln!(f, "{}", statement)?;
+                if let Some(statement) = else_statement {
+                    writeln!(f, "else \n{}", statement)?;
+                }
+            }
+
+            For {
+                span: _,
+                init,
+                condition,
+                expression,
+                statement,
+            } => {
+                write!(f, "for (")?;
+                for init in init {
+                    write!(f, "{}", init)?;
+                }
+                if init.is_empty() {
+                    write!(f, ";")?;
+                }
+
+                if let Some(init) = condition {
+                    write!(f, "{}", init)?;
+                }
+                write!(f, ";")?;
+
+                match expression {
+                    Some(init) => writeln!(f, "{})", init)?,
+                    None => writeln!(f, ")")?,
+                }
+
+                writeln!(f, "{}", statement)?;
+            }
+
+            Return {
+                span: _,
+                ast_type: _,
+                expression,
+            } => match expression {
+                Some(expression) => writeln!(f, "return {};", expression)?,
+                None => writeln!(f, "return;")?,
+            },
+
+            While {
+                span: _,
+                expression,
+                statement,
+                do_while: false,
+            } => {
+                writeln!(f, "while ({})", expression)?;
+                writeln!(f, "{}", statement)?;
+            }
+
+            While {
+                span: _,
+                expression,
+                statement,
+                do_while: true,
+            } => {
+                write!(f, "do\n{}", statement)?;
+                writeln!(f, "while ({});", expression)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Initializer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Initializer::Expression(expression) => write!(f, "{}", expression)?,
+            Initializer::List(list) => {
+                write!(f, "{{")?;
+                for (designators, initializer) in &list.entries {
+                    for designator in designators {
+                        match designator {
+                            Designator::Member(name) => write!(f, ".{}", name)?,
+                            Designator::Index(index) => write!(f, "[{}]", index)?,
+                        }
+                    }
+                    if !designators.is_empty() {
+                        write!(f, " = ")?;
+                    }
+                    write!(f, "{},", initializer)?;
+                }
+                write!(f, "}}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use ExpressionVariant::*;
+        match &self.variant {
+            ConstI(value) => write!(f, "{}", value)?,
+            ConstF(value) if self.ast_type.nodes.get(0) == Some(&super::TypeNode::Float) => {
+                write!(f, "{:?}f", value)?
+            }
+            ConstF(value) => write!(f, "{:?}", value)?,
+            CString(value) => write!(f, "\"{}\"", print_c_string::format_c_string(value))?,
+            Ident(name, ..) => write!(f, "{}", name)?,
+            Sizeof(typ) => write!(f, "sizeof {}", typ)?,
+            VaStart(list, last) => write!(f, "__builtin_va_start({},{})", list, last)?,
+            VaArg(list, typ) => write!(f, "__builtin_va_arg({},{})", list, typ)?,
+            VaEnd(list) => write!(f, "__builtin_va_end({})", list)?,
+            VaCopy(dest, src) => write!(f, "__builtin_va_copy({},{})", dest, src)?,
+
+            Function(func, arguments) => {
+                write!(f, "({}(", func)?;
+                if let Some(arg) = arguments.get(0) {
+                    write!(f, "{}", arg)?;
+                }
+                for arg in arguments.iter().skip(1) {
+                    write!(f, ",{}", arg)?;
+                }
+                write!(f, "))")?;
+            }
+
+            Member(exp, id, indirect, _) => {
+                write!(f, "({}){}{}", exp, if *indirect { "->" } else { "." }, id)?;
+            }
+
+            Cast(exp, typ) => {
+                write!(f, "(({}){})", typ, exp)?;
+            }
+
+            Unary(
+                op @ (UnaryExpressionType::PostIncrement | UnaryExpressionType::PostDecrement),
+                exp,
+            ) => {
+                write!(f, "({} {})", exp, op)?;
+            }
+
+            Unary(op, exp) => {
+                write!(f, "({} {})", op, exp)?;
+            }
+
+            Binary(BinaryExpressionType::Index, left, right) => {
+                write!(f, "({}[{}])", left, right)?;
+            }
+
+            Binary(op, left, right) => {
+                write!(f, "({} {} {})", left, op, right)?;
+            }
+
+            Ternary(cond, left, right) => {
+                write!(f, "({} ? {} : {})", cond, left, right)?;
+            }
+
+            Assign(left, right) => {
+                write!(f, "({} = {})", left, right)?;
+            }
+
+            CompoundAssign(op, left, right) => {
+                write!(f, "({} {}= {})", left, op, right)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for BinaryExpressionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use BinaryExpressionType::*;
+        write!(
+            f,
+            "{}",
+            match self {
+                Index => "[]",
+                Add => "+",
+                Subtract => "-",
+                Multiply => "*",
+                Divide => "/",
+                Modulo => "%",
+                Equal => "==",
+                Inequal => "!=",
+                Less => "<",
+                LessEqual => "<=",
+                Greater => ">",
+                GreaterEqual => ">=",
+                LeftShift => "<<",
+                RightShift => ">>",
+                BinOr => "|",
+                BinAnd => "&",
+                BinXor => "^",
+                LogOr => "||",
+                LogAnd => "&&",
+                Comma => ",",
+            }
+        )
+    }
+}
+
+impl Display for UnaryExpressionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use UnaryExpressionType::*;
+        write!(
+            f,
+            "{}",
+            match self {
+                Identity => "+",
+                Negate => "-",
+                BinNot => "~",
+                LogNot => "!",
+                Deref => "*",
+                Address => "&",
+                PreIncrement | PostIncrement => "++",
+                PreDecrement | PostDecrement => "--",
+            }
+        )
+    }
+}
+
+impl Display for SizeofType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SizeofType::Type(typ, _) => write!(f, "({})", typ),
+            SizeofType::Expression(exp) => write!(f, "{}", exp),
+        }
+    }
+}
+
+impl Display for ASTType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        format_prefix_type(&self.list, f)?;
+        format_type(&self.list, f)
+    }
+}
+
+fn format_prefix_type(typ: &[ASTTypeNode], f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    for i in 0..typ.len() {
+        use super::TypeNode::*;
+        use ASTTypeNode::*;
+        type AST = ASTTypeNode;
+        match &typ[i] {
+            AST::Typedef => write!(f, "typedef ")?,
+            AST::Static => write!(f, "static ")?,
+            AST::Extern => write!(f, "extern ")?,
+            AST::Const if !is_pointer_qualifier(typ, i) => write!(f, "const ")?,
+            AST::Volatile if !is_pointer_qualifier(typ, i) => write!(f, "volatile ")?,
+            AST::TypedefName(name) => write!(f, "{} ", name)?,
+            Simple(Char) => write!(f, "char ")?,
+            Simple(Int) => write!(f, "int ")?,
+            Simple(Long) => write!(f, "long ")?,
+            Simple(Short) => write!(f, "short ")?,
+            Simple(Float) => write!(f, "float ")?,
+            Simple(Double) => write!(f, "double ")?,
+            Simple(Signed) => write!(f, "signed ")?,
+            Simple(Unsigned) => write!(f, "unsigned ")?,
+            Simple(Void) => write!(f, "void ")?,
+            AST::Struct(s) => {
+                let keyword = if s.is_union { "union" } else { "struct" };
+                write!(f, "{} {} ", keyword, s.name.clone().unwrap_or_default())?;
+                if let Some(members) = &s.members {
+                    writeln!(f, "{{")?;
+                    for (member, width) in members {
+                        match width {
+                            Some(width) => writeln!(f, "{} : {};", member, width)?,
+                            None => writeln!(f, "{};", member)?,
+                        }
+                    }
+                    writeln!(f, "}}")?;
+                }
+            }
+            AST::Enum(e) => {
+                write!(f, "enum {} ", e.name.clone().unwrap_or_default())?;
+                if let Some(members) = &e.members {
+                    writeln!(f, "{{")?;
+                    for (name, value) in members {
+                        match value {
+                            Some(value) => writeln!(f, "{} = {},", name, value)?,
+                            None => writeln!(f, "{},", name)?,
+                        }
+                    }
+                    writeln!(f, "}}")?;
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(())
+}
+
+fn format_type(typ: &[ASTTypeNode], f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    if typ.is_empty() {
+        return Ok(());
+    }
+
+    for i in (0..typ.len()).rev() {
+        use super::TypeNode::*;
+        use ASTTypeNode::*;
+        type AST = ASTTypeNode;
+        match &typ[i] {
+            Simple(Char | Int | Long | Short | Float | Double | Signed | Unsigned | Void) => (),
+            AST::Typedef | AST::Static | AST::Extern | AST::TypedefName(_) => (),
+            AST::Const if is_pointer_qualifier(typ, i) => write!(f, "const ")?,
+            AST::Volatile if is_pointer_qualifier(typ, i) => write!(f, "volatile ")?,
+            AST::Const | AST::Volatile => (),
+
+            Simple(Pointer) => write!(f, "* ")?,
+            Simple(t) => {
+                log::error!("unexpected type node {:?}", t);
+                unreachable!()
+            }
+            AST::Name(name) => write!(f, "{} ", name)?,
+            AST::Function(arguments, variadic) => {
+                //Extend later when functions are fully implemented
+                write!(f, "(")?;
+                format_type(&typ[0..i], f)?;
+                write!(f, ")")?;
+                write!(f, "(")?;
+                if let Some(arg) = arguments.get(0) {
+                    write!(f, "{}", arg)?;
+                }
+                for arg in arguments.iter().skip(1) {
+                    write!(f, ", {}", arg)?;
+                }
+                if *variadic {
+                    write!(f, ", ...")?;
+                }
+                write!(f, ")")?;
+                break;
+            }
+            AST::Array(size) => {
+                //Extend later when functions are fully implemented
+                write!(f, "(")?;
+                format_type(&typ[0..i], f)?;
+                write!(f, ")")?;
+                write!(f, "[{}]", size)?;
+                break;
+            }
+            AST::Struct(..) | AST::Enum(..) => (),
+        };
+    }
+    Ok(())
+}
+
+// Qualifiers directly before a pointer apply to the pointer, others to the base type
+fn is_pointer_qualifier(typ: &[ASTTypeNode], index: usize) -> bool {
+    typ[index + 1..]
+        .iter()
+        .find(|node| !matches!(node, ASTTypeNode::Const | ASTTypeNode::Volatile))
+        .map_or(false, |node| {
+            matches!(node, ASTTypeNode::Simple(super::TypeNode::Pointer))
+        })
+}
+
+// This module mimic the functionality in the crate either, which might have been better to use
+// EitherIterator is used to flatten something that will either be a string or a char to an iterator of char
+pub(crate) mod print_c_string {
+    enum Either {
+        Char(char),
+        String(&'static str),
+        Octal(u8),
+    }
+
+    enum EitherIterator {
+        Char(std::iter::Once<char>),
+        String(std::str::Chars<'static>),
+        Octal(std::vec::IntoIter<char>),
+    }
+
+    impl Iterator for EitherIterator {
+        type Item = char;
+        fn next(&mut self) -> Option<Self::Item> {
+            match self {
+                EitherIterator::Char(it) => it.next(),
+                EitherIterator::String(it) => it.next(),
+                EitherIterator::Octal(it) => it.next(),
+            }
+        }
+    }
+
+    impl IntoIterator for Either {
+        type IntoIter = EitherIterator;
+        type Item = char;
+        fn into_iter(self) -> Self::IntoIter {
+            match self {
+                Either::Char(c) => EitherIterator::Char(std::iter::once(c)),
+                Either::String(str) => EitherIterator::String(str.chars()),
+                Either::Octal(c) => {
+                    let escape: Vec<char> = format!("\\{:03o}", c).chars().collect();
+                    EitherIterator::Octal(escape.into_iter())
+                }
+            }
+        }
+    }
+
+    // Bytes without a printable ascii representation are written as three digit octal escapes
+    pub fn format_c_string(string: &[u8]) -> String {
+        use Either::{Char, Octal, String};
+        string
+            .iter()
+            .flat_map(|&c| {
+                match c {
+                    b'\x07' => String("\\a"),
+                    b'\x08' => String("\\b"),
+                    b'\x0b' => String("\\v"),
+                    b'\x0c' => String("\\f"),
+                    b'\\' => String("\\\\"),
+                    b'\'' => String("\\'"),
+                    b'\"' => String("\\\""),
+                    b'\n' => String("\\n"),
+                    b'\r' => String("\\r"),
+                    b'\t' => String("\\t"),
+                    c if c == b' ' || c.is_ascii_graphic() => Char(c as char),
+                    c => Octal(c),
+                }
+                .into_iter()
+            })
+            .collect()
+    }
+}
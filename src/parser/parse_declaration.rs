@@ -1,99 +1,166 @@
-use super::ast::{ASTType, ASTTypeNode};
-use super::r#type::TypeNode;
-use super::{recovery::RecoveryStrategy, Parser};
-use crate::expect;
-use crate::token::TokenType;
-
-impl<'a> Parser<'a> {
-    pub(super) fn parse_declaration(&mut self) -> Result<ASTType, ()> {
-        let base_type = self.parse_declaration_specifiers()?;
-        //let base_type = self.check_declaration_specifiers(base_type);
-        let declarator = self.parse_declarator()?;
-        Ok(ASTType::combine(base_type, declarator))
-    }
-
-    // Parses all type qualifiers (const, int, void)
-    // <declaration-specifiers> ::= <type-qualifier>+
-    fn parse_declaration_specifiers(&mut self) -> Result<ASTType, ()> {
-        let begin = self.peek_span();
-        if self.peek().filter(Parser::is_type_qualifier) == None {
-            self.expect_some()?;
-            let span = self.peek_span();
-            let token = self.peek_type().unwrap();
-            self.errors.push(crate::error!(
-                span,
-                "Expected type qualifier in declaration, found {}",
-                token
-            ));
-            self.recover(&RecoveryStrategy::Next);
-            return Err(());
-        }
-        let mut result = Vec::<ASTTypeNode>::new();
-        while let Some(token) = self.peek().filter(Parser::is_type_qualifier) {
-            if let TokenType::Struct = self.peek_type().unwrap() {
-                result.push(self.parse_struct()?);
-            } else {
-                self.next();
-                result.push(token.into());
-            }
-        }
-        let span = begin.to(&self.peek_span());
-        Ok(ASTType::from_slice(&result, span))
-    }
-
-    // Parse a declarator optionally containing pointers and function
-    // <declarator> ::= ('*')* name ( '(' <parameter-type-list>? ')' )?
-    fn parse_declarator(&mut self) -> Result<ASTType, ()> {
-        let begin = self.peek_span();
-        let mut pointers = Vec::new();
-        while let Some(TokenType::Asterisk) = self.peek_type() {
-            self.next();
-            pointers.push(ASTTypeNode::Simple(TypeNode::Pointer));
-        }
-
-        let mut result = Vec::<ASTTypeNode>::new();
-        match self.peek_type() {
-            Some(TokenType::LParenthesis) => {
-                let inner = self.parse_braced('(', Parser::parse_declarator)?;
-                result = inner.list
-            }
-            Some(TokenType::Ident(name)) => {
-                self.next();
-                result.push(ASTTypeNode::Name(name));
-            }
-            _ => (),
-        }
-
-        loop {
-            match self.peek_type() {
-                Some(TokenType::LParenthesis) => {
-                    let arguments = self.parse_braced('(', Parser::parse_parameter_type_list)?;
-                    result.push(ASTTypeNode::Function(arguments))
-                }
-                Some(TokenType::LSquare) => {
-                    let expression = self.parse_braced('[', Parser::parse_conditional)?;
-                    result.push(ASTTypeNode::Array(Box::new(expression)));
-                }
-                _ => break,
-            }
-        }
-
-        result.append(&mut pointers);
-        let span = begin.to(&self.peek_span());
-        Ok(ASTType::from_slice(&result, span))
-    }
-
-    // Parse a list of paremeter declarations seperated by comma's
-    // <parameter-type-list> ::= <declaration> ( ,<declaration> )*
-    fn parse_parameter_type_list(&mut self) -> Result<Vec<ASTType>, ()> {
-        let mut arguments = Vec::new();
-        while let Some(true) = self.peek().as_ref().map(Parser::is_type_qualifier) {
-            arguments.push(self.parse_declaration()?);
-            if let Some(TokenType::RParenthesis) = self.peek_type() {
-            } else {
-                let _ = expect!(self, TokenType::Comma, RecoveryStrategy::Nothing);
-            }
-        }
-        Ok(arguments)
-    }
-}
+use super::ast::{ASTType, ASTTypeNode};
+use super::r#type::TypeNode;
+use super::{recovery::RecoveryStrategy, Parser};
+use crate::token::TokenType;
+use crate::{error, expect};
+
+impl<'a> Parser<'a> {
+    pub(super) fn parse_declaration(&mut self) -> Result<ASTType, ()> {
+        let base_type = self.parse_declaration_specifiers()?;
+        //let base_type = self.check_declaration_specifiers(base_type);
+        let declarator = self.parse_declarator()?;
+        Ok(ASTType::combine(base_type, declarator))
+    }
+
+    // Parses all type qualifiers (const, int, void)
+    // A typedef name is only part of the type if no other type specifier was found before it
+    // <declaration-specifiers> ::= <type-qualifier>+
+    pub(super) fn parse_declaration_specifiers(&mut self) -> Result<ASTType, ()> {
+        let begin = self.peek_span();
+        if self.peek().filter(|token| self.is_type_qualifier(token)) == None {
+            self.expect_some()?;
+            let span = self.peek_span();
+            let token = self.peek_type().unwrap();
+            self.errors.push(crate::error!(
+                span,
+                "Expected type qualifier in declaration, found {}",
+                token
+            ));
+            self.recover(&RecoveryStrategy::Next);
+            return Err(());
+        }
+        let mut result = Vec::<ASTTypeNode>::new();
+        let mut type_seen = false;
+        while let Some(token) = self.peek().filter(|token| self.is_type_qualifier(token)) {
+            match token.token() {
+                TokenType::Struct | TokenType::Union => {
+                    result.push(self.parse_struct()?);
+                    type_seen = true;
+                }
+                TokenType::Enum => {
+                    result.push(self.parse_enum()?);
+                    type_seen = true;
+                }
+                TokenType::Typedef => {
+                    self.next();
+                    result.push(ASTTypeNode::Typedef);
+                }
+                TokenType::Static => {
+                    self.next();
+                    result.push(ASTTypeNode::Static);
+                }
+                TokenType::Extern => {
+                    self.next();
+                    result.push(ASTTypeNode::Extern);
+                }
+                TokenType::Const | TokenType::Volatile => {
+                    result.append(&mut self.parse_type_qualifiers());
+                }
+                TokenType::Ident(_) if type_seen => break,
+                TokenType::Ident(name) => {
+                    self.next();
+                    result.push(ASTTypeNode::TypedefName(name));
+                    type_seen = true;
+                }
+                _ => {
+                    self.next();
+                    result.push(token.into());
+                    type_seen = true;
+                }
+            }
+        }
+        let span = begin.to(&self.peek_span());
+        Ok(ASTType::from_slice(&result, span))
+    }
+
+    // Parse a possibly abstract declarator containing pointers, arrays and functions
+    // The qualifiers after a '*' are placed before the pointer they apply to
+    // A '(' followed by a type or ')' starts a parameter list instead of a nested declarator
+    // <declarator> ::= ('*' <type-qualifier>*)* <direct-declarator>
+    // <direct-declarator> ::= ( name | '(' <declarator> ')' )? <declarator-suffix>*
+    // <declarator-suffix> ::= '(' <parameter-type-list>? ')' | '[' <conditional-expression> ']'
+    pub(super) fn parse_declarator(&mut self) -> Result<ASTType, ()> {
+        let begin = self.peek_span();
+        let mut pointers = Vec::new();
+        while let Some(TokenType::Asterisk) = self.peek_type() {
+            self.next();
+            let mut pointer = self.parse_type_qualifiers();
+            pointer.push(ASTTypeNode::Simple(TypeNode::Pointer));
+            pointers.splice(0..0, pointer);
+        }
+
+        let mut result = Vec::<ASTTypeNode>::new();
+        let parameters = self.peek2().map_or(false, |token| {
+            token.token() == TokenType::RParenthesis || self.is_type_qualifier(&token)
+        });
+        match self.peek_type() {
+            Some(TokenType::LParenthesis) if !parameters => {
+                let inner = self.parse_braced('(', Parser::parse_declarator)?;
+                result = inner.list
+            }
+            Some(TokenType::Ident(name)) => {
+                self.next();
+                result.push(ASTTypeNode::Name(name));
+            }
+            _ => (),
+        }
+
+        loop {
+            match self.peek_type() {
+                Some(TokenType::LParenthesis) => {
+                    let (arguments, variadic) =
+                        self.parse_braced('(', Parser::parse_parameter_type_list)?;
+                    result.push(ASTTypeNode::Function(arguments, variadic))
+                }
+                Some(TokenType::LSquare) => {
+                    let expression = self.parse_braced('[', Parser::parse_conditional)?;
+                    result.push(ASTTypeNode::Array(Box::new(expression)));
+                }
+                _ => break,
+            }
+        }
+
+        result.append(&mut pointers);
+        let span = begin.to(&self.peek_span());
+        Ok(ASTType::from_slice(&result, span))
+    }
+
+    // Parses a possibly empty list of type qualifiers
+    // <type-qualifier> ::= 'const' | 'volatile'
+    fn parse_type_qualifiers(&mut self) -> Vec<ASTTypeNode> {
+        let mut result = Vec::new();
+        loop {
+            match self.peek_type() {
+                Some(TokenType::Const) => result.push(ASTTypeNode::Const),
+                Some(TokenType::Volatile) => result.push(ASTTypeNode::Volatile),
+                _ => break,
+            }
+            self.next();
+        }
+        result
+    }
+
+    // Parse a list of paremeter declarations seperated by comma's
+    // Returns whether the list ends in an ellipsis, which cannot be the only entry
+    // <parameter-type-list> ::= <declaration> ( ,<declaration> )* ( , '...' )?
+    fn parse_parameter_type_list(&mut self) -> Result<(Vec<ASTType>, bool), ()> {
+        let mut arguments = Vec::new();
+        while let Some(true) = self.peek().as_ref().map(|token| self.is_type_qualifier(token)) {
+            arguments.push(self.parse_declaration()?);
+            if let Some(TokenType::RParenthesis) = self.peek_type() {
+            } else {
+                let _ = expect!(self, TokenType::Comma, RecoveryStrategy::Nothing);
+            }
+        }
+        if let Some(TokenType::Ellipsis) = self.peek_type() {
+            let span = self.peek_span();
+            self.next();
+            if arguments.is_empty() {
+                self.errors
+                    .push(error!(span, "Expected a named argument before '...'"));
+            }
+            return Ok((arguments, true));
+        }
+        Ok((arguments, false))
+    }
+}
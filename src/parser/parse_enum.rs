@@ -0,0 +1,82 @@
+use super::ast::{ASTEnum, ASTTypeNode, Expression};
+use super::{recovery::RecoveryStrategy, Parser};
+use crate::error;
+use crate::token::TokenType;
+
+impl<'a> Parser<'a> {
+    // Parses an enum specifier
+    // <enum-specifier> ::= 'enum' <identifier>? ( '{' <enumerator-list> '}' )?
+    pub fn parse_enum(&mut self) -> Result<ASTTypeNode, ()> {
+        let begin = self.peek_span();
+        self.next();
+
+        let name = match self.peek_type() {
+            Some(TokenType::Ident(name)) => {
+                self.next();
+                Some(name)
+            }
+            Some(TokenType::LBrace) => None,
+            _ => {
+                self.errors
+                    .push(error!(begin, "Expected identifier or '{{'"));
+                self.recover(&RecoveryStrategy::or(
+                    RecoveryStrategy::UpTo(';'),
+                    RecoveryStrategy::Until('{'),
+                ));
+                return Err(());
+            }
+        };
+
+        let members = if let Some(TokenType::LBrace) = self.peek_type() {
+            Some(self.parse_braced('{', Parser::parse_enumerator_list)?)
+        } else {
+            None
+        };
+
+        let ast_enum = Box::new(ASTEnum { name, members });
+
+        Ok(ASTTypeNode::Enum(ast_enum))
+    }
+
+    // Parses the enumerators, which may end in a trailing comma
+    // <enumerator-list> ::= <enumerator> ( ',' <enumerator> )* ','?
+    // <enumerator> ::= <identifier> ( '=' <conditional-expression> )?
+    fn parse_enumerator_list(&mut self) -> Result<Vec<(String, Option<Expression>)>, ()> {
+        let mut result = Vec::new();
+        loop {
+            let span = self.peek_span();
+            let name = match self.peek_type() {
+                Some(TokenType::Ident(name)) => {
+                    self.next();
+                    name
+                }
+                _ => {
+                    self.errors.push(error!(span, "Expected enumerator name"));
+                    self.recover(&RecoveryStrategy::UpTo('}'));
+                    return Err(());
+                }
+            };
+
+            let value = if let Some(TokenType::Assign) = self.peek_type() {
+                self.next();
+                Some(self.parse_conditional()?)
+            } else {
+                None
+            };
+
+            // Enumerators are ordinary identifiers and shadow typedef names
+            self.declare_name(name.clone(), false);
+            result.push((name, value));
+
+            if let Some(TokenType::Comma) = self.peek_type() {
+                self.next();
+            } else {
+                break;
+            }
+            if let Some(TokenType::RBrace) = self.peek_type() {
+                break;
+            }
+        }
+        Ok(result)
+    }
+}
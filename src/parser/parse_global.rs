@@ -1,71 +1,112 @@
-use super::ast::*;
-use super::{Parser, Type};
-
-use crate::token::{Token, TokenType};
-
-impl<'a> Parser<'a> {
-    // The public parser function used by the compiler
-    // Parses global declarations until the end of the file
-    // <translation-unit> ::= <external-declaration>*
-    pub fn parse(&mut self, tokens: Vec<Token>) -> (TranslationUnit, Result<(), Vec<String>>) {
-        self.tokens = tokens;
-        let mut global_declarations = Vec::<ExternalDeclaration>::new();
-        while !self.empty() {
-            match self.parse_external_declaration() {
-                Ok(declaration) => global_declarations.push(declaration),
-                Err(_) => (),
-            }
-        }
-        let result = match self.errors.is_empty() {
-            true => Ok(()),
-            false => Err(self.errors.clone()),
-        };
-        (
-            TranslationUnit {
-                global_declarations,
-            },
-            result,
-        )
-    }
-
-    // Parses a single extarnal declaration, which is either a function or a global
-    // <external-declaration> ::= <declaration> (';'|<compound-statement>|'=' <const-expression> ';')
-    pub fn parse_external_declaration(&mut self) -> Result<ExternalDeclaration, ()> {
-        let begin = self.peek_span();
-        let declaration = self.parse_declaration()?;
-        let name = declaration.get_name();
-        //let decl_type = declaration.remove_name();
-        let function_body = if let Some(TokenType::LBrace) = self.peek_type() {
-            let compound_statement = self.parse_compound_statement()?;
-            Some(compound_statement)
-        } else {
-            None
-        };
-        let expression = if function_body.is_none() {
-            if let Some(TokenType::Assign) = self.peek_type() {
-                self.next();
-                let expression = self.parse_conditional().unwrap_or_else(|_| Expression {
-                    span: begin.clone(),
-                    ast_type: Type::int(),
-                    variant: ExpressionVariant::ConstI(0),
-                });
-                self.expect_semicolon();
-                Some(expression)
-            } else {
-                self.expect_semicolon();
-                None
-            }
-        } else {
-            None
-        };
-
-        Ok(ExternalDeclaration {
-            span: begin.to(&self.peek_span()),
-            ast_type: declaration,
-            decl_type: Type::int(),
-            name,
-            function_body,
-            expression,
-        })
-    }
-}
+use super::ast::*;
+use super::{Parser, Type};
+
+use crate::token::{Token, TokenType};
+
+impl<'a> Parser<'a> {
+    // The public parser function used by the compiler
+    // Parses global declarations until the end of the file
+    // <translation-unit> ::= <external-declaration>*
+    pub fn parse(&mut self, tokens: Vec<Token>) -> (TranslationUnit, Result<(), Vec<String>>) {
+        self.tokens = tokens;
+        let mut global_declarations = Vec::<ExternalDeclaration>::new();
+        while !self.empty() {
+            match self.parse_external_declaration() {
+                Ok(mut declarations) => global_declarations.append(&mut declarations),
+                Err(_) => (),
+            }
+        }
+        let result = match self.errors.is_empty() {
+            true => Ok(()),
+            false => Err(self.errors.clone()),
+        };
+        (
+            TranslationUnit {
+                global_declarations,
+            },
+            result,
+        )
+    }
+
+    // Parses a single extarnal declaration, which is either a function or globals
+    // All declarators share the specifiers and each gives a declaration of its own
+    // <external-declaration> ::= <declaration-specifiers> <declarator> <compound-statement>
+    //                          | <declaration-specifiers> <init-declarator-list> ';'
+    // <init-declarator-list> ::= <declarator> ('=' <initializer>)? (',' <init-declarator-list>)?
+    pub fn parse_external_declaration(&mut self) -> Result<Vec<ExternalDeclaration>, ()> {
+        let mut begin = self.peek_span();
+        let mut specifiers = self.parse_declaration_specifiers()?;
+        let mut result = Vec::new();
+        loop {
+            let declaration = specifiers.clone().combine(self.parse_declarator()?);
+            let name = declaration.get_name();
+            if let Some(name) = &name {
+                self.declare_name(name.clone(), declaration.is_typedef());
+            }
+            //let decl_type = declaration.remove_name();
+            let function_body = match self.peek_type() {
+                Some(TokenType::LBrace) if result.is_empty() => {
+                    // The parameters can shadow typedef names within the function body
+                    self.enter_scope();
+                    self.declare_parameters(&declaration);
+                    let compound_statement = self.parse_compound_statement();
+                    self.leave_scope();
+                    Some(compound_statement?)
+                }
+                _ => None,
+            };
+            let expression = match (&function_body, self.peek_type()) {
+                (None, Some(TokenType::Assign)) => {
+                    self.next();
+                    let expression = self.parse_initializer().unwrap_or_else(|_| {
+                        Initializer::Expression(Expression {
+                            span: begin.clone(),
+                            ast_type: Type::int(),
+                            variant: ExpressionVariant::ConstI(0),
+                        })
+                    });
+                    Some(expression)
+                }
+                _ => None,
+            };
+            let function = function_body.is_some();
+
+            result.push(ExternalDeclaration {
+                span: begin.to(&self.peek_span()),
+                ast_type: declaration,
+                decl_type: Type::int(),
+                name,
+                function_body,
+                expression,
+            });
+
+            match self.peek_type() {
+                _ if function => return Ok(result),
+                Some(TokenType::Comma) => {
+                    self.next();
+                    begin = self.peek_span();
+                    specifiers = specifiers.without_definitions();
+                }
+                _ => {
+                    self.expect_semicolon();
+                    return Ok(result);
+                }
+            }
+        }
+    }
+
+    fn declare_parameters(&mut self, declaration: &ASTType) {
+        for entry in &declaration.list {
+            match entry {
+                ASTTypeNode::Name(_) => continue,
+                ASTTypeNode::Function(arguments, _) => {
+                    for name in arguments.iter().filter_map(ASTType::get_name) {
+                        self.declare_name(name, false);
+                    }
+                    break;
+                }
+                _ => break,
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use super::ast::{Designator, Initializer, InitializerList};
+use super::{recovery::RecoveryStrategy, Parser};
+use crate::token::TokenType;
+use crate::{error, expect};
+
+impl<'a> Parser<'a> {
+    // Parses the value of a declaration, which is either an expression or a braced list
+    // <initializer> ::= <assignment-expression> | '{' <initializer-list>? '}'
+    pub(super) fn parse_initializer(&mut self) -> Result<Initializer, ()> {
+        if let Some(TokenType::LBrace) = self.peek_type() {
+            let begin = self.peek_span();
+            let entries = self.parse_braced('{', Parser::parse_initializer_list)?;
+            Ok(Initializer::List(InitializerList {
+                span: begin.to(&self.peek_span()),
+                entries,
+                values: Vec::new(),
+            }))
+        } else {
+            Ok(Initializer::Expression(self.parse_assignment()?))
+        }
+    }
+
+    // Parses the entries of a braced list, which may end in a trailing comma
+    // <initializer-list> ::= <designated-initializer> ( ',' <designated-initializer> )* ','?
+    // <designated-initializer> ::= <designation>? <initializer>
+    // <designation> ::= ( '[' <conditional-expression> ']' | '.' <identifier> )+ '='
+    fn parse_initializer_list(&mut self) -> Result<Vec<(Vec<Designator>, Initializer)>, ()> {
+        let mut result = Vec::new();
+        while !matches!(self.peek_type(), Some(TokenType::RBrace) | None) {
+            let designators = self.parse_designation()?;
+            let initializer = self.parse_initializer()?;
+            result.push((designators, initializer));
+
+            if let Some(TokenType::Comma) = self.peek_type() {
+                self.next();
+            } else {
+                break;
+            }
+        }
+        Ok(result)
+    }
+
+    fn parse_designation(&mut self) -> Result<Vec<Designator>, ()> {
+        let mut designators = Vec::new();
+        loop {
+            match self.peek_type() {
+                Some(TokenType::LSquare) => {
+                    let index = self.parse_braced('[', Parser::parse_conditional)?;
+                    designators.push(Designator::Index(index));
+                }
+                Some(TokenType::Period) => {
+                    self.next();
+                    let span = self.peek_span();
+                    match self.peek_type() {
+                        Some(TokenType::Ident(name)) => {
+                            self.next();
+                            designators.push(Designator::Member(name));
+                        }
+                        _ => {
+                            self.errors.push(error!(span, "Expected member name"));
+                            self.recover(&RecoveryStrategy::UpTo('}'));
+                            return Err(());
+                        }
+                    }
+                }
+                _ => break,
+            }
+        }
+        if !designators.is_empty() {
+            expect!(self, TokenType::Assign, RecoveryStrategy::UpTo('}'))?;
+        }
+        Ok(designators)
+    }
+}
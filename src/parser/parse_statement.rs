@@ -1,271 +1,388 @@
-use super::ast::*;
-use super::r#type::*;
-use super::recovery::RecoveryStrategy;
-use super::Parser;
-use crate::error;
-use crate::expect;
-use crate::token::TokenType;
-
-impl<'a> Parser<'a> {
-    // Compound statements can contain a lot of other statements.
-    // All statements within the brace are parsed
-    // <compound-statement> ::= '{' <statement>* '}'
-    pub(super) fn parse_compound_statement(&mut self) -> Result<Vec<Statement>, ()> {
-        let _ = expect!(
-            self,
-            TokenType::LBrace,
-            RecoveryStrategy::or(RecoveryStrategy::Until(';'), RecoveryStrategy::Until('{'))
-        );
-        self.enter_scope();
-        let mut result = Vec::<Statement>::new();
-        loop {
-            if let Some(TokenType::RBrace) = self.peek_type() {
-                self.next();
-                self.leave_scope();
-                break;
-            }
-            if self.peek() == None {
-                let loc = self.peek_span();
-                self.errors
-                    .push(error!(loc, "Expected }} before end of file"));
-                return Err(());
-            }
-            let statement = self.parse_statement();
-            if let Ok(statement) = statement {
-                result.push(statement);
-            }
-        }
-        Ok(result)
-    }
-
-    // Parsing statements
-    // <statement> ::= return <expression> ';'
-    //               | ';'
-    //               | <expression> ';'
-    //               | <declaration> ('=' <expression>)? ';'
-    //               | break ';'
-    //               | continue ';'
-    //               | if '(' <expression< ')' <statement> (else <statement)?
-    //               | do <statement> while '(' <expression> ')' ';'
-    //               | while '(' <expression> ')' <statement>
-    //               | for '( <statement> <expression>? ';' <expression>? ')'
-    //               | | '{' <statement>* '}'
-    fn parse_statement(&mut self) -> Result<Statement, ()> {
-        let begin = self.peek_span();
-        use TokenType::*;
-        match self.peek_type() {
-            Some(_) if Parser::is_type_qualifier(&self.peek().unwrap()) => {
-                self.parse_local_declaration()
-            }
-
-            Some(Break) => {
-                self.next();
-                self.expect_semicolon();
-                Ok(Statement::Break { span: begin })
-            }
-
-            Some(Continue) => {
-                self.next();
-                self.expect_semicolon();
-                Ok(Statement::Continue { span: begin })
-            }
-
-            Some(Do) => {
-                self.next();
-                let statement = Box::new(self.parse_statement()?);
-                expect!(self, TokenType::While, RecoveryStrategy::Nothing)?;
-                let expression = self.parse_braced('(', Parser::parse_expression)?;
-                self.expect_semicolon();
-
-                let span = begin.to(&self.peek_span());
-                Ok(Statement::While {
-                    span,
-                    expression,
-                    statement,
-                    do_while: true,
-                })
-            }
-
-            Some(If) => {
-                self.next();
-                let expression = self.parse_braced('(', Parser::parse_expression)?;
-                let statement = Box::new(self.parse_statement()?);
-                let else_statement = if let Some(Else) = self.peek_type() {
-                    self.next();
-                    Some(Box::new(self.parse_statement()?))
-                } else {
-                    None
-                };
-
-                let span = begin.to(&self.peek_span());
-                Ok(Statement::If {
-                    span,
-                    expression,
-                    statement,
-                    else_statement,
-                })
-            }
-
-            Some(For) => {
-                self.next();
-                let (init, condition, expression) =
-                    self.parse_braced('(', Parser::parse_for_clause)?;
-
-                self.enter_scope();
-                let statement = self.parse_statement();
-                self.leave_scope();
-                let statement = Box::new(statement?);
-
-                let span = begin.to(&self.peek_span());
-                Ok(Statement::For {
-                    span,
-                    init,
-                    condition,
-                    expression,
-                    statement,
-                })
-            }
-
-            Some(Return) => {
-                self.next();
-                let expression = if let Some(TokenType::Semicolon) = self.peek_type() {
-                    self.next();
-                    None
-                } else {
-                    let expression = self.parse_expression();
-                    self.expect_semicolon();
-                    //Expression is unwrapped here to first parse semicolon first
-                    Some(expression?)
-                };
-
-                let span = begin.to(&self.peek_span());
-                Ok(Statement::Return {
-                    span,
-                    expression,
-                    ast_type: Type::empty(),
-                })
-            }
-
-            Some(Semicolon) => {
-                self.next();
-                Ok(Statement::Empty(begin))
-            }
-
-            Some(While) => {
-                self.next();
-                let expression = self.parse_braced('(', Parser::parse_expression)?;
-                let statement = Box::new(self.parse_statement()?);
-
-                let span = begin.to(&self.peek_span());
-                Ok(Statement::While {
-                    span,
-                    expression,
-                    statement,
-                    do_while: false,
-                })
-            }
-
-            Some(LBrace) => {
-                let statements = self.parse_compound_statement()?;
-                let span = begin.to(&self.peek_span());
-                Ok(Statement::Compound { span, statements })
-            }
-
-            Some(_) => {
-                let expression = self.parse_expression();
-                let _ = expect!(
-                    self,
-                    TokenType::Semicolon,
-                    RecoveryStrategy::or(RecoveryStrategy::UpTo('}'), RecoveryStrategy::Until(';'))
-                );
-
-                //Expression is unwrapped here to first parse semicolon first
-                let expression = expression?;
-                let span = begin.to(&self.peek_span());
-                Ok(Statement::Expression { span, expression })
-            }
-
-            None => Err(()),
-        }
-    }
-
-    fn parse_local_declaration(&mut self) -> Result<Statement, ()> {
-        let begin = self.peek_span();
-        let ast_type = self.parse_declaration()?;
-        let ident = ast_type.get_name();
-        //let decl_type = decl_type.remove_name();
-
-        let init = if let Some(TokenType::Assign) = self.peek_type() {
-            self.next();
-            match self.parse_expression() {
-                Ok(init) => Some(init),
-                Err(_) => None,
-            }
-        } else {
-            None
-        };
-
-        let _ = expect!(self, TokenType::Semicolon, RecoveryStrategy::Nothing);
-
-        Ok(Statement::Declaration {
-            span: begin.to(&self.peek_span()),
-            ident,
-            decl_type: Type::empty(),
-            ast_type,
-            init,
-        })
-    }
-
-    fn parse_for_clause(
-        &mut self,
-    ) -> Result<
-        (
-            Option<Box<Statement>>,
-            Option<Box<Expression>>,
-            Option<Box<Expression>>,
-        ),
-        (),
-    > {
-        let init = match self.peek_type() {
-            Some(TokenType::Semicolon) => {
-                self.next();
-                None
-            }
-            Some(_) => Some(Box::new(self.parse_statement()?)),
-
-            None => {
-                self.error_unexpected_eof();
-                return Err(());
-            }
-        };
-
-        let condition = match self.peek_type() {
-            Some(TokenType::Semicolon) => {
-                self.next();
-                None
-            }
-            Some(_) => {
-                let condition = self.parse_expression();
-                self.expect_semicolon();
-                Some(Box::new(condition?))
-            }
-
-            None => {
-                self.error_unexpected_eof();
-                return Err(());
-            }
-        };
-
-        let expression = match self.peek_type() {
-            Some(TokenType::RParenthesis) => None,
-            Some(_) => Some(Box::new(self.parse_expression()?)),
-
-            None => {
-                self.error_unexpected_eof();
-                return Err(());
-            }
-        };
-
-        Ok((init, condition, expression))
-    }
-}
+use super::ast::*;
+use super::r#type::*;
+use super::recovery::RecoveryStrategy;
+use super::Parser;
+use crate::error;
+use crate::expect;
+use crate::token::TokenType;
+
+impl<'a> Parser<'a> {
+    // Compound statements can contain a lot of other statements.
+    // All statements within the brace are parsed
+    // <compound-statement> ::= '{' <block-item>* '}'
+    pub(super) fn parse_compound_statement(&mut self) -> Result<Vec<Statement>, ()> {
+        let _ = expect!(
+            self,
+            TokenType::LBrace,
+            RecoveryStrategy::or(RecoveryStrategy::Until(';'), RecoveryStrategy::Until('{'))
+        );
+        self.enter_scope();
+        let mut result = Vec::<Statement>::new();
+        loop {
+            if let Some(TokenType::RBrace) = self.peek_type() {
+                self.next();
+                self.leave_scope();
+                break;
+            }
+            if self.peek() == None {
+                let loc = self.peek_span();
+                self.errors
+                    .push(error!(loc, "Expected }} before end of file"));
+                return Err(());
+            }
+            let statements = self.parse_block_item();
+            if let Ok(mut statements) = statements {
+                result.append(&mut statements);
+            }
+        }
+        Ok(result)
+    }
+
+    // A declaration gives a statement for each of its declarators
+    // <block-item> ::= <declaration> | <statement>
+    fn parse_block_item(&mut self) -> Result<Vec<Statement>, ()> {
+        match self.peek() {
+            Some(token)
+                if self.is_type_qualifier(&token)
+                    && !matches!(self.peek2().map(|t| t.token()), Some(TokenType::Colon)) =>
+            {
+                self.parse_local_declaration()
+            }
+            _ => Ok(vec![self.parse_statement()?]),
+        }
+    }
+
+    // Parsing statements
+    // <statement> ::= return <expression> ';'
+    //               | ';'
+    //               | <expression> ';'
+    //               | <declaration> ('=' <initializer>)? ';'
+    //               | break ';'
+    //               | continue ';'
+    //               | if '(' <expression< ')' <statement> (else <statement)?
+    //               | do <statement> while '(' <expression> ')' ';'
+    //               | while '(' <expression> ')' <statement>
+    //               | for '( <statement> <expression>? ';' <expression>? ')'
+    //               | switch '(' <expression> ')' <statement>
+    //               | case <conditional-expression> ':' <statement>
+    //               | default ':' <statement>
+    //               | goto <identifier> ';'
+    //               | <identifier> ':' <statement>
+    //               | | '{' <statement>* '}'
+    fn parse_statement(&mut self) -> Result<Statement, ()> {
+        let begin = self.peek_span();
+        use TokenType::*;
+        match self.peek_type() {
+            // Labels have their own namespace, so typedef names can also be used as a label
+            Some(Ident(label)) if matches!(self.peek2().map(|t| t.token()), Some(Colon)) => {
+                self.next();
+                self.next();
+                let statement = Box::new(self.parse_statement()?);
+
+                let span = begin.to(&self.peek_span());
+                Ok(Statement::Label {
+                    span,
+                    label,
+                    statement,
+                })
+            }
+
+            // A declaration is not allowed here, but all of its declarators are kept for analysis
+            Some(_) if self.is_type_qualifier(&self.peek().unwrap()) => {
+                let statements = self.parse_local_declaration()?;
+                let span = begin.to(&self.peek_span());
+                self.errors.push(error!(
+                    span,
+                    "A declaration can not be used as the body of a control flow statement"
+                ));
+                Ok(Statement::Compound { span, statements })
+            }
+
+            Some(Break) => {
+                self.next();
+                self.expect_semicolon();
+                Ok(Statement::Break { span: begin })
+            }
+
+            Some(Continue) => {
+                self.next();
+                self.expect_semicolon();
+                Ok(Statement::Continue { span: begin })
+            }
+
+            Some(Switch) => {
+                self.next();
+                let expression = self.parse_braced('(', Parser::parse_expression)?;
+                let statement = Box::new(self.parse_statement()?);
+
+                let span = begin.to(&self.peek_span());
+                Ok(Statement::Switch {
+                    span,
+                    expression,
+                    statement,
+                })
+            }
+
+            Some(Case) => {
+                self.next();
+                let expression = self.parse_conditional()?;
+                expect!(self, TokenType::Colon, RecoveryStrategy::Nothing)?;
+                let statement = Box::new(self.parse_statement()?);
+
+                let span = begin.to(&self.peek_span());
+                Ok(Statement::Case {
+                    span,
+                    expression,
+                    statement,
+                })
+            }
+
+            Some(Default) => {
+                self.next();
+                expect!(self, TokenType::Colon, RecoveryStrategy::Nothing)?;
+                let statement = Box::new(self.parse_statement()?);
+
+                let span = begin.to(&self.peek_span());
+                Ok(Statement::Default { span, statement })
+            }
+
+            Some(Goto) => {
+                self.next();
+                let label = match self.peek_type() {
+                    Some(Ident(label)) => {
+                        self.next();
+                        label
+                    }
+                    _ => {
+                        let span = self.peek_span();
+                        self.errors.push(error!(span, "Expected label after 'goto'"));
+                        self.recover(&RecoveryStrategy::or(
+                            RecoveryStrategy::UpTo('}'),
+                            RecoveryStrategy::Until(';'),
+                        ));
+                        return Err(());
+                    }
+                };
+                self.expect_semicolon();
+                Ok(Statement::Goto { span: begin, label })
+            }
+
+            Some(Do) => {
+                self.next();
+                let statement = Box::new(self.parse_statement()?);
+                expect!(self, TokenType::While, RecoveryStrategy::Nothing)?;
+                let expression = self.parse_braced('(', Parser::parse_expression)?;
+                self.expect_semicolon();
+
+                let span = begin.to(&self.peek_span());
+                Ok(Statement::While {
+                    span,
+                    expression,
+                    statement,
+                    do_while: true,
+                })
+            }
+
+            Some(If) => {
+                self.next();
+                let expression = self.parse_braced('(', Parser::parse_expression)?;
+                let statement = Box::new(self.parse_statement()?);
+                let else_statement = if let Some(Else) = self.peek_type() {
+                    self.next();
+                    Some(Box::new(self.parse_statement()?))
+                } else {
+                    None
+                };
+
+                let span = begin.to(&self.peek_span());
+                Ok(Statement::If {
+                    span,
+                    expression,
+                    statement,
+                    else_statement,
+                })
+            }
+
+            Some(For) => {
+                self.next();
+                // Declarations in the for clause are only visible within the loop
+                self.enter_scope();
+                let for_clause = self.parse_braced('(', Parser::parse_for_clause);
+                let statement = match for_clause {
+                    Ok(_) => self.parse_statement(),
+                    Err(()) => Err(()),
+                };
+                self.leave_scope();
+                let (init, condition, expression) = for_clause?;
+                let statement = Box::new(statement?);
+
+                let span = begin.to(&self.peek_span());
+                Ok(Statement::For {
+                    span,
+                    init,
+                    condition,
+                    expression,
+                    statement,
+                })
+            }
+
+            Some(Return) => {
+                self.next();
+                let expression = if let Some(TokenType::Semicolon) = self.peek_type() {
+                    self.next();
+                    None
+                } else {
+                    let expression = self.parse_expression();
+                    self.expect_semicolon();
+                    //Expression is unwrapped here to first parse semicolon first
+                    Some(expression?)
+                };
+
+                let span = begin.to(&self.peek_span());
+                Ok(Statement::Return {
+                    span,
+                    expression,
+                    ast_type: Type::empty(),
+                })
+            }
+
+            Some(Semicolon) => {
+                self.next();
+                Ok(Statement::Empty(begin))
+            }
+
+            Some(While) => {
+                self.next();
+                let expression = self.parse_braced('(', Parser::parse_expression)?;
+                let statement = Box::new(self.parse_statement()?);
+
+                let span = begin.to(&self.peek_span());
+                Ok(Statement::While {
+                    span,
+                    expression,
+                    statement,
+                    do_while: false,
+                })
+            }
+
+            Some(LBrace) => {
+                let statements = self.parse_compound_statement()?;
+                let span = begin.to(&self.peek_span());
+                Ok(Statement::Compound { span, statements })
+            }
+
+            Some(_) => {
+                let expression = self.parse_expression();
+                let _ = expect!(
+                    self,
+                    TokenType::Semicolon,
+                    RecoveryStrategy::or(RecoveryStrategy::UpTo('}'), RecoveryStrategy::Until(';'))
+                );
+
+                //Expression is unwrapped here to first parse semicolon first
+                let expression = expression?;
+                let span = begin.to(&self.peek_span());
+                Ok(Statement::Expression { span, expression })
+            }
+
+            None => Err(()),
+        }
+    }
+
+    // All declarators share the specifiers and each gives a declaration of its own
+    // <declaration> ::= <declaration-specifiers> <init-declarator-list> ';'
+    // <init-declarator-list> ::= <declarator> ('=' <initializer>)? (',' <init-declarator-list>)?
+    fn parse_local_declaration(&mut self) -> Result<Vec<Statement>, ()> {
+        let mut begin = self.peek_span();
+        let mut specifiers = self.parse_declaration_specifiers()?;
+        let mut result = Vec::new();
+        loop {
+            let ast_type = specifiers.clone().combine(self.parse_declarator()?);
+            let ident = ast_type.get_name();
+            if let Some(name) = &ident {
+                self.declare_name(name.clone(), ast_type.is_typedef());
+            }
+            //let decl_type = decl_type.remove_name();
+
+            let init = if let Some(TokenType::Assign) = self.peek_type() {
+                self.next();
+                match self.parse_initializer() {
+                    Ok(init) => Some(init),
+                    Err(_) => None,
+                }
+            } else {
+                None
+            };
+
+            result.push(Statement::Declaration {
+                span: begin.to(&self.peek_span()),
+                ident,
+                decl_type: Type::empty(),
+                ast_type,
+                init,
+            });
+
+            if let Some(TokenType::Comma) = self.peek_type() {
+                self.next();
+                begin = self.peek_span();
+                specifiers = specifiers.without_definitions();
+            } else {
+                let _ = expect!(self, TokenType::Semicolon, RecoveryStrategy::Nothing);
+                return Ok(result);
+            }
+        }
+    }
+
+    fn parse_for_clause(
+        &mut self,
+    ) -> Result<
+        (
+            Vec<Statement>,
+            Option<Box<Expression>>,
+            Option<Box<Expression>>,
+        ),
+        (),
+    > {
+        let init = match self.peek_type() {
+            Some(TokenType::Semicolon) => {
+                self.next();
+                Vec::new()
+            }
+            Some(_) => self.parse_block_item()?,
+
+            None => {
+                self.error_unexpected_eof();
+                return Err(());
+            }
+        };
+
+        let condition = match self.peek_type() {
+            Some(TokenType::Semicolon) => {
+                self.next();
+                None
+            }
+            Some(_) => {
+                let condition = self.parse_expression();
+                self.expect_semicolon();
+                Some(Box::new(condition?))
+            }
+
+            None => {
+                self.error_unexpected_eof();
+                return Err(());
+            }
+        };
+
+        let expression = match self.peek_type() {
+            Some(TokenType::RParenthesis) => None,
+            Some(_) => Some(Box::new(self.parse_expression()?)),
+
+            None => {
+                self.error_unexpected_eof();
+                return Err(());
+            }
+        };
+
+        Ok((init, condition, expression))
+    }
+}
@@ -1,11 +1,14 @@
-use super::ast::{ASTStruct, ASTType, ASTTypeNode};
+use super::ast::{ASTStruct, ASTType, ASTTypeNode, Expression};
 use super::{recovery::RecoveryStrategy, Parser};
 use crate::error;
 use crate::token::TokenType;
+use std::{cell::Cell, rc::Rc};
 
 impl<'a> Parser<'a> {
+    // Parses both structs and unions, which only differ in their layout
     pub fn parse_struct(&mut self) -> Result<ASTTypeNode, ()> {
         let begin = self.peek_span();
+        let is_union = self.peek_type() == Some(TokenType::Union);
         self.next();
 
         let name = match self.peek_type() {
@@ -35,20 +38,49 @@ impl<'a> Parser<'a> {
         let ast_struct = Box::new(ASTStruct {
             name,
             members: struct_definition,
+            is_union,
+            index: Rc::new(Cell::new(None)),
         });
 
         Ok(ASTTypeNode::Struct(ast_struct))
     }
 
-    fn parse_struct_declaration(&mut self) -> Result<Vec<ASTType>, ()> {
-        let mut result = Vec::<ASTType>::new();
+    // Members declared together share their specifiers
+    // A bit-field gives its width after the declarator, which can be left out for padding
+    // <struct-declaration> ::= <declaration-specifiers> <field> (',' <field>)*
+    // <field> ::= <declarator> | <declarator>? ':' <conditional-expression>
+    fn parse_struct_declaration(&mut self) -> Result<Vec<(ASTType, Option<Expression>)>, ()> {
+        let mut result = Vec::new();
         loop {
             if let Some(TokenType::RBrace) = self.peek_type() {
                 break;
             }
-            let decl = self.parse_declaration();
-            if decl.is_ok() {
-                result.push(decl.unwrap());
+            if let Ok(mut specifiers) = self.parse_declaration_specifiers() {
+                loop {
+                    let member = match self.peek_type() {
+                        Some(TokenType::Colon) => specifiers.clone(),
+                        _ => match self.parse_declarator() {
+                            Ok(declarator) => specifiers.clone().combine(declarator),
+                            Err(()) => break,
+                        },
+                    };
+                    let width = if let Some(TokenType::Colon) = self.peek_type() {
+                        self.next();
+                        match self.parse_conditional() {
+                            Ok(width) => Some(width),
+                            Err(()) => break,
+                        }
+                    } else {
+                        None
+                    };
+                    result.push((member, width));
+                    if let Some(TokenType::Comma) = self.peek_type() {
+                        self.next();
+                        specifiers = specifiers.without_definitions();
+                    } else {
+                        break;
+                    }
+                }
             }
             if let Some(TokenType::RBrace) = self.peek_type() {
                 break;
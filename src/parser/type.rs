@@ -1,329 +1,528 @@
-use smallvec::{smallvec, SmallVec};
-
-use crate::{
-    table::StructTable,
-    token::{Token, TokenType},
-};
-use std::fmt::Display;
-
-#[derive(Debug, Clone, PartialEq, Eq, Copy)]
-pub enum DeclarationType {
-    Declaration,
-    Prototype,
-    Definition,
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct StructType {
-    pub name: Option<String>,
-    pub members: Option<Vec<(String, Type)>>,
-}
-
-impl StructType {
-    pub fn is_qualified(&self) -> bool {
-        matches!(self.members, Some(..))
-    }
-}
-
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum TypeNode {
-    Char,
-    Int,
-    Long,
-    Short,
-    Void,
-    Pointer,
-    Struct(usize),
-    Array(usize),
-    Function(Box<Vec<Type>>),
-}
-
-impl TypeNode {
-    pub fn error() -> TypeNode {
-        TypeNode::Int
-    }
-}
-
-// Type contains  C Type used by something
-// The Name if any should be the highest
-// This is followed in order of dereferencing/calling
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Type {
-    pub nodes: SmallVec<[TypeNode; 2]>,
-}
-
-impl Type {
-    pub fn empty() -> Type {
-        Type {
-            nodes: SmallVec::new(),
-        }
-    }
-    pub fn int() -> Type {
-        Type {
-            nodes: smallvec![TypeNode::Int],
-        }
-    }
-    pub fn pointer() -> Type {
-        Type {
-            nodes: smallvec![TypeNode::Pointer],
-        }
-    }
-    pub fn error() -> Type {
-        Type::int()
-    }
-
-    pub fn is_qualified(&self, struct_table: &StructTable) -> bool {
-        Type::is_qualified2(&self.nodes, struct_table)
-    }
-    fn is_qualified2(nodes: &[TypeNode], struct_table: &StructTable) -> bool {
-        match nodes.get(0) {
-            Some(TypeNode::Struct(index)) => struct_table[*index].is_qualified(),
-            Some(TypeNode::Array(..)) => Type::is_qualified2(&nodes[1..], struct_table),
-            Some(TypeNode::Void) => false,
-            _ => true,
-        }
-    }
-
-    pub fn is_declaration(&self) -> bool {
-        self.get_function_arguments()
-            .map(|args| args.len() == 0)
-            .unwrap_or(false)
-    }
-
-    pub fn is_char(&self) -> bool {
-        match self.nodes.get(0) {
-            Some(TypeNode::Char) => true,
-            _ => false,
-        }
-    }
-    pub fn is_void(&self) -> bool {
-        matches!(self.nodes.get(0), Some(TypeNode::Void))
-    }
-
-    pub fn is_void_pointer(&self) -> bool {
-        matches!(
-            (self.nodes.get(0), self.nodes.get(1)),
-            (Some(TypeNode::Pointer), Some(TypeNode::Void))
-        )
-    }
-
-    pub fn is_pointer(&self) -> bool {
-        match self.nodes.get(0) {
-            Some(TypeNode::Pointer) => true,
-            _ => false,
-        }
-    }
-
-    pub fn is_array(&self) -> bool {
-        match self.nodes.get(0) {
-            Some(TypeNode::Array(..)) => true,
-            _ => false,
-        }
-    }
-
-    pub fn is_struct(&self) -> bool {
-        match self.nodes.get(0) {
-            Some(TypeNode::Struct(..)) => true,
-            _ => false,
-        }
-    }
-
-    pub fn is_callable(&self) -> bool {
-        self.is_function() || self.is_function_pointer()
-    }
-
-    pub fn is_function_pointer(&self) -> bool {
-        matches!(self.nodes.get(0), Some(TypeNode::Pointer)) && Type::is_function2(&self.nodes[1..])
-    }
-
-    pub fn is_function(&self) -> bool {
-        Type::is_function2(&self.nodes)
-    }
-
-    fn is_function2(input: &[TypeNode]) -> bool {
-        match input.get(0) {
-            Some(TypeNode::Function(_)) => true,
-            _ => false,
-        }
-    }
-
-    pub fn get_function_arguments<'a>(&'a self) -> Option<&'a Vec<Type>> {
-        Type::get_function_arguments2(&self.nodes)
-            .or_else(|| Type::get_function_arguments2(&self.nodes[1..]))
-    }
-
-    fn get_function_arguments2<'a>(input: &'a [TypeNode]) -> Option<&'a Vec<Type>> {
-        match input.get(0) {
-            Some(TypeNode::Function(arguments)) => Some(arguments),
-            _ => None,
-        }
-    }
-
-    pub fn get_element(&self) -> TypeNode {
-        Type::get_element2(&self.nodes)
-    }
-    fn get_element2(nodes: &[TypeNode]) -> TypeNode {
-        match nodes.get(0) {
-            Some(TypeNode::Array(..)) => Type::get_element2(&nodes[1..]),
-            Some(t) => t.clone(),
-            None => unreachable!(),
-        }
-    }
-
-    pub fn remove_name(self) -> Type {
-        self
-    }
-
-    pub fn get_return_type<'a>(&'a self) -> Option<&'a [TypeNode]> {
-        if self.is_function_pointer() {
-            Type::get_return_type2(&self.nodes[1..])
-        } else {
-            Type::get_return_type2(&self.nodes)
-        }
-    }
-
-    fn get_return_type2<'a>(input: &'a [TypeNode]) -> Option<&'a [TypeNode]> {
-        match input.get(0) {
-            Some(TypeNode::Function(_)) => Some(&input[1..]),
-            Some(_) => Some(&input[0..]),
-            _ => None,
-        }
-    }
-
-    pub fn get_struct_index(&self) -> usize {
-        Type::get_struct_index2(&self.nodes, true)
-    }
-
-    pub fn get_struct_index2(nodes: &[TypeNode], first: bool) -> usize {
-        match (nodes.get(0), first) {
-            (Some(TypeNode::Struct(index)), _) => *index,
-            (Some(TypeNode::Pointer), true) => Type::get_struct_index2(&nodes[1..], false),
-            _ => unreachable!(),
-        }
-    }
-
-    // Function works under current definition of the types, but might need to be processed further when more types are introduced
-    pub fn combine(mut base_type: Type, mut declarator: Type) -> Type {
-        declarator.nodes.append(&mut base_type.nodes);
-        declarator
-    }
-
-    pub fn append(mut self, other: &Type) -> Type {
-        self.nodes.extend(other.nodes.clone());
-        self
-    }
-
-    pub fn deref(self) -> Type {
-        if let Some(TypeNode::Pointer | TypeNode::Array(..)) = self.nodes.get(0) {
-            self.nodes[1..].into()
-        } else {
-            self
-        }
-    }
-
-    pub fn deconstruct(&self) -> (TypeNode, usize) {
-        Type::deconstruct2(&self.nodes)
-    }
-
-    fn deconstruct2(typ: &[TypeNode]) -> (TypeNode, usize) {
-        match typ.get(0) {
-            Some(TypeNode::Array(size)) => {
-                let (t, s) = Type::deconstruct2(&typ[1..]);
-                (t, s * size)
-            }
-            Some(t) => (t.clone(), 1),
-            None => unreachable!(),
-        }
-    }
-}
-
-impl From<Token> for TypeNode {
-    fn from(token: Token) -> TypeNode {
-        use TokenType::*;
-        match token.token() {
-            Char => TypeNode::Char,
-            Int => TypeNode::Int,
-            Long => TypeNode::Long,
-            Short => TypeNode::Short,
-            Asterisk => TypeNode::Pointer,
-            Void => TypeNode::Void,
-            _ => {
-                log::error!("From<Token> for Type called on unqualified type");
-                std::process::exit(1);
-            }
-        }
-    }
-}
-
-impl From<Vec<TypeNode>> for Type {
-    fn from(nodes: Vec<TypeNode>) -> Type {
-        Type {
-            nodes: nodes.into_iter().collect(),
-        }
-    }
-}
-
-impl From<&[TypeNode]> for Type {
-    fn from(nodes: &[TypeNode]) -> Self {
-        Type {
-            nodes: nodes.iter().map(|t| t.clone()).collect(),
-        }
-    }
-}
-
-impl Display for Type {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        format_type(&self.nodes, f, None)
-    }
-}
-
-fn format_type(
-    typ: &[TypeNode],
-    f: &mut std::fmt::Formatter<'_>,
-    table: Option<&StructTable>,
-) -> std::fmt::Result {
-    if typ.is_empty() {
-        return Ok(());
-    }
-    for i in (0..=(typ.len() - 1)).rev() {
-        use TypeNode::*;
-        match &typ[i] {
-            Char => write!(f, "char ")?,
-            Int => write!(f, "int ")?,
-            Long => write!(f, "long ")?,
-            Short => write!(f, "short ")?,
-            Void => write!(f, "void ")?,
-            Pointer => write!(f, "* ")?,
-            Function(arguments) => {
-                format_type(&typ[0..i], f, table)?;
-                write!(f, "(")?;
-                if let Some(arg) = arguments.get(0) {
-                    write!(f, "{}", arg)?;
-                }
-                for arg in arguments.iter().skip(1) {
-                    write!(f, ", {}", arg)?;
-                }
-                write!(f, ")")?;
-                break;
-            }
-            Array(size) => {
-                format_type(&typ[0..i], f, table)?;
-                write!(f, "[{}]", size)?;
-                break;
-            }
-
-            Struct(index) => {
-                if let Some(table) = table {
-                    if let Some(name) = &table[*index].name {
-                        write!(f, "struct {}__{} ", name, index)?;
-                    } else {
-                        write!(f, "__anonymous_struct__{} ", index)?;
-                    }
-                } else {
-                    write!(f, "struct ")?
-                }
-            }
-        };
-    }
-    Ok(())
-}
+use smallvec::{smallvec, SmallVec};
+
+use crate::{
+    table::StructTable,
+    token::{Token, TokenType},
+};
+use std::fmt::Display;
+
+#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+pub enum DeclarationType {
+    Declaration,
+    Prototype,
+    Definition,
+    Tentative,
+    Typedef,
+    Enumerator(i128),
+}
+
+// The storage class specifier given in a declaration
+// Default is extern at file scope and automatic storage in a block
+#[derive(Debug, Clone, PartialEq, Eq, Copy)]
+pub enum StorageClass {
+    Default,
+    Static,
+    Extern,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StructType {
+    pub name: Option<String>,
+    pub members: Option<Vec<(String, Type)>>,
+    pub bit_fields: Vec<Option<BitField>>,
+    pub is_union: bool,
+}
+
+// A bit-field is accessed through a storage unit of its declared type
+// Its offset is the position of its lowest bit in that unit, which is set by the layout
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitField {
+    pub width: u32,
+    pub offset: u32,
+}
+
+impl StructType {
+    pub fn is_qualified(&self) -> bool {
+        matches!(self.members, Some(..))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnumType {
+    pub name: Option<String>,
+    pub members: Vec<(String, i128)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TypeNode {
+    Char,
+    Int,
+    Long,
+    Short,
+    UChar,
+    UInt,
+    ULong,
+    UShort,
+    Float,
+    Double,
+    Signed,
+    Unsigned,
+    Void,
+    Pointer,
+    Struct(usize),
+    Array(usize),
+    Function(Box<Vec<Type>>, bool),
+}
+
+impl TypeNode {
+    pub fn error() -> TypeNode {
+        TypeNode::Int
+    }
+}
+
+// The type qualifiers applied to a single level of a type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Qualifiers {
+    pub is_const: bool,
+    pub is_volatile: bool,
+}
+
+impl Qualifiers {
+    pub fn union(self, other: Qualifiers) -> Qualifiers {
+        Qualifiers {
+            is_const: self.is_const || other.is_const,
+            is_volatile: self.is_volatile || other.is_volatile,
+        }
+    }
+
+    // Checks if all qualifiers in other are also present in self
+    pub fn contains(&self, other: &Qualifiers) -> bool {
+        (self.is_const || !other.is_const) && (self.is_volatile || !other.is_volatile)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        !self.is_const && !self.is_volatile
+    }
+}
+
+// Type contains  C Type used by something
+// The Name if any should be the highest
+// This is followed in order of dereferencing/calling
+// The qualifiers are stored per level of nodes, missing levels are unqualified
+#[derive(Debug, Clone, Eq)]
+pub struct Type {
+    pub nodes: SmallVec<[TypeNode; 2]>,
+    pub qualifiers: SmallVec<[Qualifiers; 2]>,
+}
+
+// Qualifiers do not change the representation of a type, so they are not compared
+impl PartialEq for Type {
+    fn eq(&self, other: &Self) -> bool {
+        self.nodes == other.nodes
+    }
+}
+
+impl Type {
+    pub fn empty() -> Type {
+        Type {
+            nodes: SmallVec::new(),
+            qualifiers: SmallVec::new(),
+        }
+    }
+    pub fn int() -> Type {
+        Type {
+            nodes: smallvec![TypeNode::Int],
+            qualifiers: SmallVec::new(),
+        }
+    }
+    pub fn pointer() -> Type {
+        Type {
+            nodes: smallvec![TypeNode::Pointer],
+            qualifiers: SmallVec::new(),
+        }
+    }
+    pub fn error() -> Type {
+        Type::int()
+    }
+
+    pub fn is_qualified(&self, struct_table: &StructTable) -> bool {
+        Type::is_qualified2(&self.nodes, struct_table)
+    }
+    fn is_qualified2(nodes: &[TypeNode], struct_table: &StructTable) -> bool {
+        match nodes.get(0) {
+            Some(TypeNode::Struct(index)) => struct_table[*index].is_qualified(),
+            Some(TypeNode::Array(..)) => Type::is_qualified2(&nodes[1..], struct_table),
+            Some(TypeNode::Void) => false,
+            _ => true,
+        }
+    }
+
+    pub fn is_declaration(&self) -> bool {
+        self.get_function_arguments()
+            .map(|args| args.len() == 0)
+            .unwrap_or(false)
+    }
+
+    pub fn is_char(&self) -> bool {
+        match self.nodes.get(0) {
+            Some(TypeNode::Char) => true,
+            _ => false,
+        }
+    }
+    pub fn is_unsigned(&self) -> bool {
+        use TypeNode::*;
+        matches!(self.nodes.get(0), Some(UChar | UInt | ULong | UShort))
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self.nodes.get(0), Some(TypeNode::Float | TypeNode::Double))
+    }
+
+    pub fn is_void(&self) -> bool {
+        matches!(self.nodes.get(0), Some(TypeNode::Void))
+    }
+
+    pub fn is_void_pointer(&self) -> bool {
+        matches!(
+            (self.nodes.get(0), self.nodes.get(1)),
+            (Some(TypeNode::Pointer), Some(TypeNode::Void))
+        )
+    }
+
+    pub fn is_pointer(&self) -> bool {
+        match self.nodes.get(0) {
+            Some(TypeNode::Pointer) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_array(&self) -> bool {
+        match self.nodes.get(0) {
+            Some(TypeNode::Array(..)) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_struct(&self) -> bool {
+        match self.nodes.get(0) {
+            Some(TypeNode::Struct(..)) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_callable(&self) -> bool {
+        self.is_function() || self.is_function_pointer()
+    }
+
+    pub fn is_function_pointer(&self) -> bool {
+        matches!(self.nodes.get(0), Some(TypeNode::Pointer)) && Type::is_function2(&self.nodes[1..])
+    }
+
+    pub fn is_function(&self) -> bool {
+        Type::is_function2(&self.nodes)
+    }
+
+    fn is_function2(input: &[TypeNode]) -> bool {
+        match input.get(0) {
+            Some(TypeNode::Function(..)) => true,
+            _ => false,
+        }
+    }
+
+    pub fn get_function_arguments<'a>(&'a self) -> Option<&'a Vec<Type>> {
+        Type::get_function_arguments2(&self.nodes)
+            .or_else(|| Type::get_function_arguments2(&self.nodes[1..]))
+    }
+
+    fn get_function_arguments2<'a>(input: &'a [TypeNode]) -> Option<&'a Vec<Type>> {
+        match input.get(0) {
+            Some(TypeNode::Function(arguments, _)) => Some(arguments),
+            _ => None,
+        }
+    }
+
+    // Returns whether a function or function pointer takes unnamed arguments after its arguments
+    pub fn is_variadic(&self) -> bool {
+        let nodes = match self.is_function_pointer() {
+            true => &self.nodes[1..],
+            false => &self.nodes[..],
+        };
+        matches!(nodes.get(0), Some(TypeNode::Function(_, true)))
+    }
+
+    pub fn get_element(&self) -> TypeNode {
+        Type::get_element2(&self.nodes)
+    }
+    fn get_element2(nodes: &[TypeNode]) -> TypeNode {
+        match nodes.get(0) {
+            Some(TypeNode::Array(..)) => Type::get_element2(&nodes[1..]),
+            Some(t) => t.clone(),
+            None => unreachable!(),
+        }
+    }
+
+    pub fn remove_name(self) -> Type {
+        self
+    }
+
+    pub fn get_return_type<'a>(&'a self) -> Option<&'a [TypeNode]> {
+        if self.is_function_pointer() {
+            Type::get_return_type2(&self.nodes[1..])
+        } else {
+            Type::get_return_type2(&self.nodes)
+        }
+    }
+
+    pub fn get_qualified_return_type(&self) -> Option<Type> {
+        let length = self.get_return_type()?.len();
+        Some(self.slice(self.nodes.len() - length))
+    }
+
+    fn get_return_type2<'a>(input: &'a [TypeNode]) -> Option<&'a [TypeNode]> {
+        match input.get(0) {
+            Some(TypeNode::Function(..)) => Some(&input[1..]),
+            Some(_) => Some(&input[0..]),
+            _ => None,
+        }
+    }
+
+    pub fn get_struct_index(&self) -> usize {
+        Type::get_struct_index2(&self.nodes, true)
+    }
+
+    pub fn get_struct_index2(nodes: &[TypeNode], first: bool) -> usize {
+        match (nodes.get(0), first) {
+            (Some(TypeNode::Struct(index)), _) => *index,
+            (Some(TypeNode::Pointer), true) => Type::get_struct_index2(&nodes[1..], false),
+            _ => unreachable!(),
+        }
+    }
+
+    // Function works under current definition of the types, but might need to be processed further when more types are introduced
+    pub fn combine(mut base_type: Type, mut declarator: Type) -> Type {
+        if !base_type.qualifiers.is_empty() {
+            let length = declarator.nodes.len();
+            declarator.qualifiers.resize(length, Qualifiers::default());
+            declarator.qualifiers.append(&mut base_type.qualifiers);
+        }
+        declarator.nodes.append(&mut base_type.nodes);
+        declarator
+    }
+
+    pub fn append(mut self, other: &Type) -> Type {
+        if !other.qualifiers.is_empty() {
+            let length = self.nodes.len();
+            self.qualifiers.resize(length, Qualifiers::default());
+            self.qualifiers.extend(other.qualifiers.iter().copied());
+        }
+        self.nodes.extend(other.nodes.clone());
+        self
+    }
+
+    pub fn deref(self) -> Type {
+        if let Some(TypeNode::Pointer | TypeNode::Array(..)) = self.nodes.get(0) {
+            self.slice(1)
+        } else {
+            self
+        }
+    }
+
+    // Gets the type starting at the given level, keeping the qualifiers of every level
+    fn slice(&self, start: usize) -> Type {
+        Type {
+            nodes: self.nodes[start..].into(),
+            qualifiers: self.qualifiers.iter().skip(start).copied().collect(),
+        }
+    }
+
+    pub fn get_qualifiers(&self) -> Qualifiers {
+        self.get_qualifiers_at(0)
+    }
+
+    pub fn get_qualifiers_at(&self, level: usize) -> Qualifiers {
+        self.qualifiers.get(level).copied().unwrap_or_default()
+    }
+
+    // Arrays are qualified through the qualifiers of their elements
+    pub fn get_element_qualifiers(&self) -> Qualifiers {
+        let level = self
+            .nodes
+            .iter()
+            .take_while(|node| matches!(node, TypeNode::Array(..)))
+            .count();
+        self.get_qualifiers_at(level)
+    }
+
+    pub fn is_const(&self) -> bool {
+        self.get_qualifiers().is_const
+    }
+
+    pub fn is_volatile(&self) -> bool {
+        self.get_qualifiers().is_volatile
+    }
+
+    // Adds qualifiers to the given level of the type
+    pub fn qualify(mut self, level: usize, qualifiers: Qualifiers) -> Type {
+        if qualifiers.is_empty() {
+            return self;
+        }
+        if self.qualifiers.len() <= level {
+            self.qualifiers.resize(level + 1, Qualifiers::default());
+        }
+        self.qualifiers[level] = self.qualifiers[level].union(qualifiers);
+        self
+    }
+
+    // Removes the qualifiers of the outermost level, as values are never qualified
+    pub fn unqualified(mut self) -> Type {
+        if let Some(qualifiers) = self.qualifiers.get_mut(0) {
+            *qualifiers = Qualifiers::default();
+        }
+        self
+    }
+
+    pub fn deconstruct(&self) -> (TypeNode, usize) {
+        Type::deconstruct2(&self.nodes)
+    }
+
+    fn deconstruct2(typ: &[TypeNode]) -> (TypeNode, usize) {
+        match typ.get(0) {
+            Some(TypeNode::Array(size)) => {
+                let (t, s) = Type::deconstruct2(&typ[1..]);
+                (t, s * size)
+            }
+            Some(t) => (t.clone(), 1),
+            None => unreachable!(),
+        }
+    }
+}
+
+impl From<Token> for TypeNode {
+    fn from(token: Token) -> TypeNode {
+        use TokenType::*;
+        match token.token() {
+            Char => TypeNode::Char,
+            Int => TypeNode::Int,
+            Long => TypeNode::Long,
+            Short => TypeNode::Short,
+            Float => TypeNode::Float,
+            Double => TypeNode::Double,
+            Signed => TypeNode::Signed,
+            Unsigned => TypeNode::Unsigned,
+            Asterisk => TypeNode::Pointer,
+            Void => TypeNode::Void,
+            _ => {
+                log::error!("From<Token> for Type called on unqualified type");
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+impl From<Vec<TypeNode>> for Type {
+    fn from(nodes: Vec<TypeNode>) -> Type {
+        Type {
+            nodes: nodes.into_iter().collect(),
+            qualifiers: SmallVec::new(),
+        }
+    }
+}
+
+impl From<&[TypeNode]> for Type {
+    fn from(nodes: &[TypeNode]) -> Self {
+        Type {
+            nodes: nodes.iter().map(|t| t.clone()).collect(),
+            qualifiers: SmallVec::new(),
+        }
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        format_type(&self.nodes, &self.qualifiers, f, None)
+    }
+}
+
+impl Display for Qualifiers {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_const {
+            write!(f, "const ")?;
+        }
+        if self.is_volatile {
+            write!(f, "volatile ")?;
+        }
+        Ok(())
+    }
+}
+
+fn format_type(
+    typ: &[TypeNode],
+    qualifiers: &[Qualifiers],
+    f: &mut std::fmt::Formatter<'_>,
+    table: Option<&StructTable>,
+) -> std::fmt::Result {
+    if typ.is_empty() {
+        return Ok(());
+    }
+    for i in (0..=(typ.len() - 1)).rev() {
+        use TypeNode::*;
+        let qualifier = qualifiers.get(i).copied().unwrap_or_default();
+        if !matches!(typ[i], Pointer | Function(..) | Array(..)) {
+            write!(f, "{}", qualifier)?;
+        }
+        match &typ[i] {
+            Char => write!(f, "char ")?,
+            Int => write!(f, "int ")?,
+            Long => write!(f, "long ")?,
+            Short => write!(f, "short ")?,
+            UChar => write!(f, "unsigned char ")?,
+            UInt => write!(f, "unsigned int ")?,
+            ULong => write!(f, "unsigned long ")?,
+            UShort => write!(f, "unsigned short ")?,
+            Float => write!(f, "float ")?,
+            Double => write!(f, "double ")?,
+            Signed => write!(f, "signed ")?,
+            Unsigned => write!(f, "unsigned ")?,
+            Void => write!(f, "void ")?,
+            Pointer => write!(f, "* {}", qualifier)?,
+            Function(arguments, variadic) => {
+                format_type(&typ[0..i], qualifiers, f, table)?;
+                write!(f, "(")?;
+                if let Some(arg) = arguments.get(0) {
+                    write!(f, "{}", arg)?;
+                }
+                for arg in arguments.iter().skip(1) {
+                    write!(f, ", {}", arg)?;
+                }
+                if *variadic {
+                    write!(f, ", ...")?;
+                }
+                write!(f, ")")?;
+                break;
+            }
+            Array(size) => {
+                format_type(&typ[0..i], qualifiers, f, table)?;
+                write!(f, "[{}]", size)?;
+                break;
+            }
+
+            Struct(index) => {
+                if let Some(table) = table {
+                    let keyword = if table[*index].is_union {
+                        "union"
+                    } else {
+                        "struct"
+                    };
+                    if let Some(name) = &table[*index].name {
+                        write!(f, "{} {}__{} ", keyword, name, index)?;
+                    } else {
+                        write!(f, "__anonymous_{}__{} ", keyword, index)?;
+                    }
+                } else {
+                    write!(f, "struct ")?
+                }
+            }
+        };
+    }
+    Ok(())
+}
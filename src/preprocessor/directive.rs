@@ -0,0 +1,419 @@
+use std::path::Path;
+
+use super::expression::evaluate;
+use super::macros::{identifier_name, Macro, PPToken};
+use super::{Conditional, Preprocessor, MAX_INCLUDE_DEPTH};
+use crate::parser::TypeNode;
+use crate::span::Span;
+use crate::token::{Token, TokenType};
+use crate::{error, warning};
+
+impl Preprocessor {
+    // Executes a single directive
+    // hash is the '#' starting the line and line contains all tokens after it
+    pub(super) fn directive(
+        &mut self,
+        hash: &Token,
+        line: Vec<PPToken>,
+        filename: &String,
+        output: &mut Vec<Token>,
+    ) {
+        let name = match line.first() {
+            Some(token) => match identifier_name(&token.token) {
+                Some(name) => name,
+                // Line markers in the form of '# 1 "file"' are ignored
+                None if self.is_active() && matches!(token.token.token(), TokenType::ConstI(..)) => {
+                    return
+                }
+                None if self.is_active() => {
+                    self.errors.push(error!(
+                        token.token.span(),
+                        "Invalid preprocessing directive {}", token.token
+                    ));
+                    return;
+                }
+                None => return,
+            },
+            // The null directive has no effect
+            None => return,
+        };
+        let span = line[0].token.span().clone();
+        let arguments = &line[1..];
+
+        // Only conditional directives are handled in skipped groups
+        match name.as_str() {
+            "if" | "ifdef" | "ifndef" if !self.is_active() => {
+                self.conditions.push(Conditional {
+                    span,
+                    active: false,
+                    taken: true,
+                    seen_else: false,
+                });
+                return;
+            }
+            "if" | "ifdef" | "ifndef" | "elif" | "else" | "endif" => (),
+            _ if !self.is_active() => return,
+            _ => (),
+        }
+
+        match name.as_str() {
+            "include" => self.include(&span, arguments, filename, output),
+            "define" => self.define(&span, arguments),
+            "undef" => match arguments.first().and_then(|token| identifier_name(&token.token)) {
+                Some(name) => {
+                    self.macros.remove(&name);
+                }
+                None => self
+                    .errors
+                    .push(error!(span, "Expected a macro name after #undef")),
+            },
+
+            "if" => {
+                let value = self.condition(&span, arguments);
+                self.conditions.push(Conditional {
+                    span,
+                    active: value,
+                    taken: value,
+                    seen_else: false,
+                });
+            }
+            "ifdef" | "ifndef" => {
+                let defined = match arguments.first().and_then(|token| identifier_name(&token.token))
+                {
+                    Some(name) => self.is_defined(&name),
+                    None => {
+                        self.errors
+                            .push(error!(span, "Expected a macro name after #{}", name));
+                        false
+                    }
+                };
+                let value = defined == (name == "ifdef");
+                self.conditions.push(Conditional {
+                    span,
+                    active: value,
+                    taken: value,
+                    seen_else: false,
+                });
+            }
+            "elif" => {
+                let taken = match self.conditions.last() {
+                    Some(conditional) if conditional.seen_else => {
+                        self.errors.push(error!(span, "#elif after #else"));
+                        true
+                    }
+                    Some(conditional) => conditional.taken,
+                    None => {
+                        self.errors.push(error!(span, "#elif without #if"));
+                        return;
+                    }
+                };
+                // The condition is only evaluated if no earlier group was taken
+                let value = !taken && self.is_outer_active() && self.condition(&span, arguments);
+                let conditional = self.conditions.last_mut().unwrap();
+                conditional.active = value;
+                conditional.taken |= value;
+            }
+            "else" => match self.conditions.last_mut() {
+                Some(conditional) if conditional.seen_else => {
+                    self.errors.push(error!(span, "#else after #else"));
+                }
+                Some(conditional) => {
+                    conditional.active = !conditional.taken;
+                    conditional.taken = true;
+                    conditional.seen_else = true;
+                }
+                None => self.errors.push(error!(span, "#else without #if")),
+            },
+            "endif" => {
+                if self.conditions.pop().is_none() {
+                    self.errors.push(error!(span, "#endif without #if"));
+                }
+            }
+
+            "pragma" => {
+                if let Some("once") = arguments
+                    .first()
+                    .and_then(|token| identifier_name(&token.token))
+                    .as_deref()
+                {
+                    self.once.insert(canonical_name(filename));
+                }
+                // Unknown pragmas are ignored
+            }
+            "error" => self.errors.push(error!(
+                hash.span(),
+                "#error {}",
+                spell_tokens(arguments)
+            )),
+            "warning" => {
+                warning!(hash.span(), "#warning {}", spell_tokens(arguments));
+            }
+            // Line directives are accepted, but do not modify the source location
+            "line" => (),
+            _ => self.errors.push(error!(
+                span,
+                "Invalid preprocessing directive #{}", name
+            )),
+        }
+    }
+
+    // Checks if the groups surrounding the innermost conditional are being processed
+    fn is_outer_active(&self) -> bool {
+        let length = self.conditions.len();
+        self.conditions[..length - 1]
+            .iter()
+            .all(|conditional| conditional.active)
+    }
+
+    fn is_defined(&self, name: &str) -> bool {
+        self.macros.contains_key(name) || name == "__LINE__" || name == "__FILE__"
+    }
+
+    // Parses a macro definition
+    // #define <identifier> <replacement-list>
+    // #define <identifier>'(' <identifier-list>? ')' <replacement-list>
+    fn define(&mut self, span: &Span, arguments: &[PPToken]) {
+        let name = match arguments.first().and_then(|token| identifier_name(&token.token)) {
+            Some(name) => name,
+            None => {
+                self.errors
+                    .push(error!(span, "Expected a macro name after #define"));
+                return;
+            }
+        };
+        if name == "defined" {
+            self.errors
+                .push(error!(span, "'defined' cannot be used as a macro name"));
+            return;
+        }
+        let name_token = &arguments[0].token;
+
+        // A function-like macro requires the '(' to directly follow the name
+        let (parameters, body) = match arguments.get(1) {
+            Some(token)
+                if token.token.token() == TokenType::LParenthesis
+                    && name_token.span().offset() + name.len() as u32
+                        == token.token.span().offset() =>
+            {
+                match self.parameters(span, &arguments[2..]) {
+                    Some((parameters, length)) => {
+                        (Some(parameters), &arguments[2 + length..])
+                    }
+                    None => return,
+                }
+            }
+            _ => (None, &arguments[1..]),
+        };
+
+        let body: Vec<Token> = body.iter().map(|token| token.token.clone()).collect();
+        let definition = Macro::new(name.clone(), parameters, body, span.clone());
+        if let Some(old) = self.macros.get(&name) {
+            if !old.is_equal(&definition) {
+                warning!(span, "Macro {} redefined", name);
+                warning!(old.span, "Previous definition of {} is here", name);
+            }
+        }
+        self.macros.insert(name, definition);
+    }
+
+    // Parses the parameter list of a function-like macro
+    // Returns the parameters and the amount of tokens used, including the ')'
+    fn parameters(&mut self, span: &Span, tokens: &[PPToken]) -> Option<(Vec<String>, usize)> {
+        let mut parameters = Vec::new();
+        let mut index = 0;
+        if let Some(TokenType::RParenthesis) = tokens.get(0).map(|token| token.token.token()) {
+            return Some((parameters, 1));
+        }
+        loop {
+            match tokens.get(index).and_then(|token| identifier_name(&token.token)) {
+                Some(name) if parameters.contains(&name) => {
+                    self.errors
+                        .push(error!(span, "Duplicate macro parameter {}", name));
+                    return None;
+                }
+                Some(name) => parameters.push(name),
+                None => {
+                    self.errors
+                        .push(error!(span, "Expected a macro parameter name"));
+                    return None;
+                }
+            }
+            match tokens.get(index + 1).map(|token| token.token.token()) {
+                Some(TokenType::Comma) => index += 2,
+                Some(TokenType::RParenthesis) => return Some((parameters, index + 2)),
+                _ => {
+                    self.errors
+                        .push(error!(span, "Expected ',' or ')' in macro parameter list"));
+                    return None;
+                }
+            }
+        }
+    }
+
+    // Evaluates the condition of an #if or #elif directive
+    // 'defined' is handled before macro expansion and all remaining identifiers are replaced by 0
+    fn condition(&mut self, span: &Span, arguments: &[PPToken]) -> bool {
+        let mut tokens = Vec::new();
+        let mut i = 0;
+        while i < arguments.len() {
+            let token = &arguments[i];
+            if identifier_name(&token.token).as_deref() != Some("defined") {
+                tokens.push(token.clone());
+                i += 1;
+                continue;
+            }
+
+            let (name, length) = match (arguments.get(i + 1), arguments.get(i + 2)) {
+                (Some(open), Some(name))
+                    if open.token.token() == TokenType::LParenthesis
+                        && matches!(
+                            arguments.get(i + 3).map(|token| token.token.token()),
+                            Some(TokenType::RParenthesis)
+                        ) =>
+                {
+                    (identifier_name(&name.token), 4)
+                }
+                (Some(name), _) => (identifier_name(&name.token), 2),
+                (None, _) => (None, 1),
+            };
+            let value = match name {
+                Some(name) => self.is_defined(&name) as u64,
+                None => {
+                    self.errors
+                        .push(error!(token.token.span(), "Expected a macro name after defined"));
+                    0
+                }
+            };
+            tokens.push(PPToken::new(Token::new(
+                TokenType::ConstI(value, TypeNode::Int),
+                token.token.span().clone(),
+            )));
+            i += length;
+        }
+
+        let tokens: Vec<Token> = self
+            .expand_all(tokens)
+            .into_iter()
+            .map(|token| match token.token.token() {
+                TokenType::Ident(_) => {
+                    Token::new(TokenType::ConstI(0, TypeNode::Int), token.token.span().clone())
+                }
+                _ => token.token,
+            })
+            .collect();
+
+        match evaluate(&tokens, span) {
+            Ok(value) => value,
+            Err(err) => {
+                self.errors.push(err);
+                false
+            }
+        }
+    }
+
+    // Includes a file
+    // #include "file" searches the directory of the current file first
+    // #include <file> only searches the include directories
+    fn include(
+        &mut self,
+        span: &Span,
+        arguments: &[PPToken],
+        filename: &String,
+        output: &mut Vec<Token>,
+    ) {
+        let tokens: Vec<Token> = match arguments.first().map(|token| token.token.token()) {
+            Some(TokenType::CString(_) | TokenType::Less) => {
+                arguments.iter().map(|token| token.token.clone()).collect()
+            }
+            _ => self
+                .expand_all(arguments.to_vec())
+                .into_iter()
+                .map(|token| token.token)
+                .collect(),
+        };
+
+        let (name, local) = match tokens.first().map(Token::token) {
+            Some(TokenType::CString(name)) if tokens.len() == 1 => {
+                (String::from_utf8_lossy(&name).into_owned(), true)
+            }
+            Some(TokenType::Less)
+                if matches!(tokens.last().map(Token::token), Some(TokenType::Greater)) =>
+            {
+                let name = tokens[1..tokens.len() - 1]
+                    .iter()
+                    .map(Token::source)
+                    .collect::<String>();
+                (name, false)
+            }
+            _ => {
+                self.errors
+                    .push(error!(span, "Expected \"file\" or <file> after #include"));
+                return;
+            }
+        };
+
+        let path = match self.find_include(&name, filename, local) {
+            Some(path) => path,
+            None => {
+                self.errors
+                    .push(error!(span, "Could not find included file {}", name));
+                return;
+            }
+        };
+
+        if self.once.contains(&canonical_name(&path)) {
+            return;
+        }
+
+        if self.include_depth >= MAX_INCLUDE_DEPTH {
+            self.errors.push(error!(
+                span,
+                "Include depth exceeds {} while including {}", MAX_INCLUDE_DEPTH, name
+            ));
+            return;
+        }
+
+        self.include_depth += 1;
+        let mut tokens = self.preprocess_file(&path);
+        self.include_depth -= 1;
+        output.append(&mut tokens);
+    }
+
+    // Searches for an included file and returns its path
+    fn find_include(&self, name: &String, filename: &String, local: bool) -> Option<String> {
+        if Path::new(name).is_absolute() {
+            return Path::new(name).is_file().then(|| name.clone());
+        }
+
+        let current_directory = Path::new(filename)
+            .parent()
+            .and_then(Path::to_str)
+            .unwrap_or("")
+            .to_string();
+        let local_directory = local.then(|| current_directory);
+
+        local_directory
+            .iter()
+            .chain(self.include_directories.iter())
+            .map(|directory| Path::new(directory).join(name))
+            .find(|path| path.is_file())
+            .and_then(|path| path.to_str().map(String::from))
+    }
+}
+
+// Gets a unique name for a file, which is used for #pragma once
+fn canonical_name(filename: &String) -> String {
+    std::fs::canonicalize(filename)
+        .ok()
+        .and_then(|path| path.to_str().map(String::from))
+        .unwrap_or_else(|| filename.clone())
+}
+
+// Turns the tokens of a line back into text for #error and #warning
+fn spell_tokens(tokens: &[PPToken]) -> String {
+    tokens
+        .iter()
+        .map(|token| token.token.source())
+        .collect::<Vec<String>>()
+        .join(" ")
+}
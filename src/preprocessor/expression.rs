@@ -0,0 +1,235 @@
+use crate::error;
+use crate::parser::{binding_power, is_binary_operator, TypeNode};
+use crate::span::Span;
+use crate::token::{Token, TokenType};
+
+// Evaluates the constant expression of an #if or #elif directive
+// Uses the same precedence as the parser, but directly computes the value
+pub(super) fn evaluate(tokens: &[Token], span: &Span) -> Result<bool, String> {
+    let mut evaluator = Evaluator {
+        tokens,
+        index: 0,
+        span: span.clone(),
+        unevaluated: 0,
+    };
+    let value = evaluator.pratt_evaluate(0)?;
+    match evaluator.peek() {
+        None => Ok(value.is_true()),
+        Some(token) => Err(error!(
+            token.span(),
+            "Unexpected {} in preprocessor expression", token
+        )),
+    }
+}
+
+// All values in preprocessor expressions have the type intmax_t or uintmax_t
+// The bits are stored the same way for both types
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Value {
+    bits: u64,
+    unsigned: bool,
+}
+
+impl Value {
+    fn signed(value: i64) -> Value {
+        Value {
+            bits: value as u64,
+            unsigned: false,
+        }
+    }
+
+    fn boolean(value: bool) -> Value {
+        Value::signed(value as i64)
+    }
+
+    fn is_true(&self) -> bool {
+        self.bits != 0
+    }
+}
+
+struct Evaluator<'a> {
+    tokens: &'a [Token],
+    index: usize,
+    span: Span,
+    // The depth of operands that are skipped by short-circuiting, which cannot raise errors
+    unevaluated: u32,
+}
+
+impl Evaluator<'_> {
+    fn peek(&self) -> Option<Token> {
+        self.tokens.get(self.index).cloned()
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.peek();
+        self.index += 1;
+        token
+    }
+
+    fn expect(&mut self, expected: TokenType) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token.token() == expected => Ok(()),
+            Some(token) => Err(error!(
+                token.span(),
+                "Expected {} in preprocessor expression, but found {}", expected, token
+            )),
+            None => Err(error!(
+                self.span,
+                "Expected {} in preprocessor expression", expected
+            )),
+        }
+    }
+
+    // Evaluates an operand, which is only parsed if skip is set
+    fn operand(&mut self, min_bp: u8, skip: bool) -> Result<Value, String> {
+        self.unevaluated += skip as u32;
+        let value = self.pratt_evaluate(min_bp);
+        self.unevaluated -= skip as u32;
+        value
+    }
+
+    fn pratt_evaluate(&mut self, min_bp: u8) -> Result<Value, String> {
+        use TokenType::*;
+        let mut left = self.evaluate_unary()?;
+        while let Some(token) = is_binary_operator(self.peek()) {
+            let (l_bp, r_bp) = binding_power(&token);
+            if l_bp < min_bp {
+                break;
+            }
+            self.next();
+
+            left = match token.token() {
+                Question => {
+                    let middle = self.operand(0, !left.is_true())?;
+                    self.expect(Colon)?;
+                    let right = self.operand(r_bp, left.is_true())?;
+                    match left.is_true() {
+                        true => middle,
+                        false => right,
+                    }
+                }
+                // The right operand of '&&' and '||' is skipped if the left decides the result
+                LogicalAnd => {
+                    let right = self.operand(r_bp, !left.is_true())?;
+                    Value::boolean(left.is_true() && right.is_true())
+                }
+                LogicalOr => {
+                    let right = self.operand(r_bp, left.is_true())?;
+                    Value::boolean(left.is_true() || right.is_true())
+                }
+                _ => {
+                    let right = self.pratt_evaluate(r_bp)?;
+                    self.binary(&token, left, right)?
+                }
+            };
+        }
+        Ok(left)
+    }
+
+    fn evaluate_unary(&mut self) -> Result<Value, String> {
+        use TokenType::*;
+        let token = match self.next() {
+            Some(token) => token,
+            None => {
+                return Err(error!(
+                    self.span,
+                    "Expected expression in preprocessor expression"
+                ))
+            }
+        };
+        match token.token() {
+            Plus => self.evaluate_unary(),
+            Minus => {
+                let value = self.evaluate_unary()?;
+                Ok(Value {
+                    bits: value.bits.wrapping_neg(),
+                    ..value
+                })
+            }
+            Tilde => {
+                let value = self.evaluate_unary()?;
+                Ok(Value {
+                    bits: !value.bits,
+                    ..value
+                })
+            }
+            Exclamation => Ok(Value::boolean(!self.evaluate_unary()?.is_true())),
+            LParenthesis => {
+                let value = self.pratt_evaluate(0)?;
+                self.expect(RParenthesis)?;
+                Ok(value)
+            }
+            // Character constants with a negative value are stored as the bits of an int
+            ConstI(value, TypeNode::Int) => Ok(Value::signed(value as i32 as i64)),
+            ConstI(value, TypeNode::UInt | TypeNode::ULong) => Ok(Value {
+                bits: value,
+                unsigned: true,
+            }),
+            ConstI(value, _) => Ok(Value::signed(value as i64)),
+            _ => Err(error!(
+                token.span(),
+                "Unexpected {} in preprocessor expression", token
+            )),
+        }
+    }
+
+    // Both operands are converted to uintmax_t if either of them is unsigned
+    fn binary(&self, token: &Token, left: Value, right: Value) -> Result<Value, String> {
+        use TokenType::*;
+        let unsigned = left.unsigned || right.unsigned;
+        let (l, r) = (left.bits, right.bits);
+        let (sl, sr) = (l as i64, r as i64);
+        let bits = match token.token() {
+            Plus => l.wrapping_add(r),
+            Minus => l.wrapping_sub(r),
+            Asterisk => l.wrapping_mul(r),
+            Divide | Percent if r == 0 && self.unevaluated > 0 => 0,
+            Divide | Percent if r == 0 => {
+                return Err(error!(
+                    token.span(),
+                    "Division by zero in preprocessor expression"
+                ))
+            }
+            Divide if unsigned => l / r,
+            Divide => sl.wrapping_div(sr) as u64,
+            Percent if unsigned => l % r,
+            Percent => sl.wrapping_rem(sr) as u64,
+            Equal => return Ok(Value::boolean(l == r)),
+            Inequal => return Ok(Value::boolean(l != r)),
+            Less if unsigned => return Ok(Value::boolean(l < r)),
+            LessEqual if unsigned => return Ok(Value::boolean(l <= r)),
+            Greater if unsigned => return Ok(Value::boolean(l > r)),
+            GreaterEqual if unsigned => return Ok(Value::boolean(l >= r)),
+            Less => return Ok(Value::boolean(sl < sr)),
+            LessEqual => return Ok(Value::boolean(sl <= sr)),
+            Greater => return Ok(Value::boolean(sl > sr)),
+            GreaterEqual => return Ok(Value::boolean(sl >= sr)),
+            Or => l | r,
+            And => l & r,
+            Caret => l ^ r,
+            LeftShift | RightShift => return Ok(Evaluator::shift(token, left, r)),
+            Comma => return Ok(right),
+            _ => {
+                return Err(error!(
+                    token.span(),
+                    "Unexpected {} in preprocessor expression", token
+                ))
+            }
+        };
+        Ok(Value { bits, unsigned })
+    }
+
+    // Shifts have the type of their left operand
+    fn shift(token: &Token, left: Value, right: u64) -> Value {
+        let bits = if right >= 64 {
+            0
+        } else if token.token() == TokenType::LeftShift {
+            left.bits << right
+        } else if left.unsigned {
+            left.bits >> right
+        } else {
+            ((left.bits as i64) >> right) as u64
+        };
+        Value { bits, ..left }
+    }
+}
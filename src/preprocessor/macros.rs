@@ -0,0 +1,377 @@
+use std::collections::VecDeque;
+
+use super::Preprocessor;
+use crate::error;
+use crate::file_table;
+use crate::lexer::Lexer;
+use crate::parser::TypeNode;
+use crate::span::Span;
+use crate::token::{Token, TokenType};
+
+// A macro definition
+// Object-like macros have no parameters, function-like macros always have a list of parameters
+#[derive(Clone, Debug)]
+pub(super) struct Macro {
+    pub name: String,
+    pub parameters: Option<Vec<String>>,
+    pub body: Vec<Token>,
+    pub span: Span,
+}
+
+impl Macro {
+    pub fn new(
+        name: String,
+        parameters: Option<Vec<String>>,
+        body: Vec<Token>,
+        span: Span,
+    ) -> Macro {
+        Macro {
+            name,
+            parameters,
+            body,
+            span,
+        }
+    }
+
+    // Checks if two definitions of a macro are the same, which allows redefinition
+    pub fn is_equal(&self, other: &Macro) -> bool {
+        self.parameters == other.parameters
+            && self.body.len() == other.body.len()
+            && self
+                .body
+                .iter()
+                .zip(other.body.iter())
+                .all(|(left, right)| left.token() == right.token())
+    }
+
+    // Gets the index of a parameter of a function-like macro
+    fn parameter_index(&self, token: &Token) -> Option<usize> {
+        let name = identifier_name(token)?;
+        self.parameters
+            .as_ref()?
+            .iter()
+            .position(|parameter| *parameter == name)
+    }
+}
+
+// A token with the set of macros it may no longer be expanded by
+// This prevents infinite recursion during macro expansion
+#[derive(Clone, Debug)]
+pub(super) struct PPToken {
+    pub token: Token,
+    pub hide_set: Vec<String>,
+}
+
+impl PPToken {
+    pub fn new(token: Token) -> PPToken {
+        PPToken {
+            token,
+            hide_set: Vec::new(),
+        }
+    }
+}
+
+// Returns the name of an identifier or keyword, as keywords can also be macro names
+pub(super) fn identifier_name(token: &Token) -> Option<String> {
+    match token.token() {
+        TokenType::Ident(name) => Some(name),
+        TokenType::ConstI(..)
+        | TokenType::ConstF(_)
+        | TokenType::ConstD(_)
+        | TokenType::CString(_) => None,
+        token => {
+            let spelling = token.spelling();
+            if spelling.chars().all(|c| c.is_ascii_alphabetic()) {
+                Some(spelling)
+            } else {
+                None
+            }
+        }
+    }
+}
+
+impl Preprocessor {
+    // Tries to expand a macro invocation starting at token
+    // The expansion is placed in front of the input, such that it will be rescanned
+    // Returns false if the token does not start a macro invocation
+    pub(super) fn expand(&mut self, token: &PPToken, input: &mut VecDeque<PPToken>) -> bool {
+        let name = match identifier_name(&token.token) {
+            Some(name) => name,
+            None => return false,
+        };
+        if token.hide_set.contains(&name) {
+            return false;
+        }
+
+        let span = token.token.span().clone();
+        if let Some(result) = self.expand_builtin(&name, &span) {
+            input.push_front(PPToken::new(result));
+            return true;
+        }
+
+        let definition = match self.macros.get(&name) {
+            Some(definition) => definition.clone(),
+            None => return false,
+        };
+
+        let result = match &definition.parameters {
+            None => {
+                let mut hide_set = token.hide_set.clone();
+                hide_set.push(name);
+                self.substitute(&definition, &[], &hide_set, &span)
+            }
+            Some(parameters) => {
+                // A function-like macro name not followed by '(' is not an invocation
+                let index = input
+                    .iter()
+                    .position(|token| token.token.token() != TokenType::Newline);
+                let index = match index {
+                    Some(index) if input[index].token.token() == TokenType::LParenthesis => index,
+                    _ => return false,
+                };
+                input.drain(0..=index);
+
+                let (arguments, end) = match self.collect_arguments(&span, &name, input) {
+                    Some(result) => result,
+                    None => return true,
+                };
+
+                // A macro without parameters is invoked with a single empty argument
+                let arguments = if parameters.is_empty()
+                    && arguments.len() == 1
+                    && arguments[0].is_empty()
+                {
+                    Vec::new()
+                } else {
+                    arguments
+                };
+
+                if arguments.len() != parameters.len() {
+                    self.errors.push(error!(
+                        span,
+                        "Macro {} expects {} arguments, but {} were given",
+                        name,
+                        parameters.len(),
+                        arguments.len()
+                    ));
+                    return true;
+                }
+
+                let mut hide_set: Vec<String> = token
+                    .hide_set
+                    .iter()
+                    .filter(|name| end.hide_set.contains(name))
+                    .cloned()
+                    .collect();
+                hide_set.push(name);
+                self.substitute(&definition, &arguments, &hide_set, &span)
+            }
+        };
+
+        for token in result.into_iter().rev() {
+            input.push_front(token);
+        }
+        true
+    }
+
+    // Expands the macros that change depending on their location
+    fn expand_builtin(&self, name: &str, span: &Span) -> Option<Token> {
+        match name {
+            "__LINE__" => Some(Token::new(
+                TokenType::ConstI(span.line() as u64, TypeNode::Int),
+                span.clone(),
+            )),
+            "__FILE__" => Some(Token::new(
+                TokenType::CString(file_table::get_sourcefile(span.file_index()).into_bytes()),
+                span.clone(),
+            )),
+            _ => None,
+        }
+    }
+
+    // Collects the arguments of a function-like macro invocation up to the closing ')'
+    // Commas inside nested parentheses do not separate arguments
+    // Returns the arguments and the closing ')'
+    fn collect_arguments(
+        &mut self,
+        span: &Span,
+        name: &String,
+        input: &mut VecDeque<PPToken>,
+    ) -> Option<(Vec<Vec<PPToken>>, PPToken)> {
+        let mut arguments = Vec::new();
+        let mut argument = Vec::new();
+        let mut depth = 0;
+        while let Some(token) = input.pop_front() {
+            match token.token.token() {
+                TokenType::Newline => continue,
+                TokenType::LParenthesis => depth += 1,
+                TokenType::RParenthesis if depth == 0 => {
+                    arguments.push(argument);
+                    return Some((arguments, token));
+                }
+                TokenType::RParenthesis => depth -= 1,
+                TokenType::Comma if depth == 0 => {
+                    arguments.push(std::mem::take(&mut argument));
+                    continue;
+                }
+                _ => (),
+            }
+            argument.push(token);
+        }
+        self.errors.push(error!(
+            span,
+            "Unterminated invocation of macro {}", name
+        ));
+        None
+    }
+
+    // Substitutes the arguments into the body of a macro
+    // Handles stringification using '#' and token pasting using '##'
+    // Arguments are fully expanded before substitution, except when used with '#' or '##'
+    fn substitute(
+        &mut self,
+        definition: &Macro,
+        arguments: &[Vec<PPToken>],
+        hide_set: &[String],
+        span: &Span,
+    ) -> Vec<PPToken> {
+        let body = &definition.body;
+        let function_like = definition.parameters.is_some();
+        let mut result: Vec<PPToken> = Vec::new();
+        // The start of the last operand in the result, which is empty for an empty argument
+        let mut operand_start = 0;
+        let mut i = 0;
+        while i < body.len() {
+            let token = &body[i];
+            let next = body.get(i + 1);
+            if token.token() != TokenType::HashHash {
+                operand_start = result.len();
+            }
+            let next_parameter = next.and_then(|next| definition.parameter_index(next));
+
+            match token.token() {
+                TokenType::Hash if function_like => match next_parameter {
+                    Some(index) => {
+                        result.push(PPToken::new(stringify(&arguments[index], span)));
+                        i += 2;
+                    }
+                    None => {
+                        self.errors.push(error!(
+                            span,
+                            "'#' is not followed by a macro parameter in {}", definition.name
+                        ));
+                        i += 1;
+                    }
+                },
+
+                TokenType::HashHash => {
+                    let right: Vec<PPToken> = match (next, next_parameter) {
+                        (Some(_), Some(index)) => arguments[index].clone(),
+                        (Some(next), None) => {
+                            vec![PPToken::new(next.relocate(span))]
+                        }
+                        (None, _) => {
+                            self.errors.push(error!(
+                                span,
+                                "'##' cannot appear at either end of macro {}", definition.name
+                            ));
+                            Vec::new()
+                        }
+                    };
+                    i += 2;
+
+                    let left = match result.len() > operand_start {
+                        true => result.pop(),
+                        false => None,
+                    };
+                    let mut right = right.into_iter();
+                    match (left, right.next()) {
+                        (Some(left), Some(first)) => {
+                            let pasted = self.paste(&left, &first);
+                            result.push(pasted);
+                        }
+                        (Some(left), None) => result.push(left),
+                        (None, Some(first)) => result.push(first),
+                        (None, None) => (),
+                    }
+                    result.extend(right);
+                    // The last token of the result of pasting is the left operand of the next '##'
+                    operand_start = std::cmp::max(operand_start, result.len().saturating_sub(1));
+                }
+
+                _ => match definition.parameter_index(token) {
+                    // Operands of '##' are not expanded
+                    Some(index) if matches!(next.map(Token::token), Some(TokenType::HashHash)) => {
+                        result.extend(arguments[index].iter().cloned());
+                        i += 1;
+                    }
+                    Some(index) => {
+                        let expanded = self.expand_all(arguments[index].clone());
+                        result.extend(expanded);
+                        i += 1;
+                    }
+                    None => {
+                        result.push(PPToken::new(token.relocate(span)));
+                        i += 1;
+                    }
+                },
+            }
+        }
+
+        for token in &mut result {
+            for name in hide_set {
+                if !token.hide_set.contains(name) {
+                    token.hide_set.push(name.clone());
+                }
+            }
+        }
+        result
+    }
+
+    // Fully expands a list of tokens on its own, as is done with macro arguments
+    pub(super) fn expand_all(&mut self, tokens: Vec<PPToken>) -> Vec<PPToken> {
+        let mut input: VecDeque<PPToken> = tokens.into_iter().collect();
+        let mut output = Vec::new();
+        while let Some(token) = input.pop_front() {
+            if !self.expand(&token, &mut input) {
+                output.push(token);
+            }
+        }
+        output
+    }
+
+    // Pastes two tokens together and relexes the result as a single token
+    fn paste(&mut self, left: &PPToken, right: &PPToken) -> PPToken {
+        let span = left.token.span();
+        let string = left.token.source() + &right.token.source();
+        let mut lexer = Lexer::from_span(span);
+        let (tokens, errors) = lexer.lex(&mut string.chars());
+        if errors.is_err() || tokens.len() != 1 {
+            self.errors.push(error!(
+                span,
+                "Pasting {} and {} does not give a valid preprocessing token",
+                left.token,
+                right.token
+            ));
+            return left.clone();
+        }
+        let leading_space = left.token.has_leading_space();
+        let token = Token::new(tokens[0].token(), span.clone()).with_source(string, leading_space);
+        PPToken {
+            token,
+            hide_set: left.hide_set.clone(),
+        }
+    }
+}
+
+// Turns the tokens of a macro argument into a string literal
+fn stringify(argument: &[PPToken], span: &Span) -> Token {
+    let mut string = String::new();
+    for (i, token) in argument.iter().enumerate() {
+        if i != 0 && token.token.has_leading_space() {
+            string.push(' ');
+        }
+        string.push_str(&token.token.source());
+    }
+    Token::new(TokenType::CString(string.into_bytes()), span.clone())
+}
@@ -0,0 +1,190 @@
+mod directive;
+mod expression;
+mod macros;
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use self::macros::{Macro, PPToken};
+use crate::compiler::open;
+use crate::error;
+use crate::file_table;
+use crate::lexer::Lexer;
+use crate::parser::TypeNode;
+use crate::span::Span;
+use crate::token::{Token, TokenType};
+
+// Maximum depth of nested includes before we assume an include loop
+const MAX_INCLUDE_DEPTH: usize = 200;
+
+// The preprocessor lexes every (included) file with its own lexer, so every token keeps a span into its own file
+// Directives are executed and macros are expanded on the token stream
+// The result is a list of tokens that can be directly given to the parser
+pub struct Preprocessor {
+    errors: Vec<String>,
+    include_directories: Vec<String>,
+    macros: HashMap<String, Macro>,
+    once: HashSet<String>,
+    conditions: Vec<Conditional>,
+    include_depth: usize,
+}
+
+// Keeps track of a single #if/#ifdef/#ifndef group
+#[derive(Clone, Debug)]
+struct Conditional {
+    span: Span,
+    // The current group is being processed
+    active: bool,
+    // One of the groups has been taken, so all following groups are skipped
+    taken: bool,
+    // The #else group has been seen
+    seen_else: bool,
+}
+
+impl Preprocessor {
+    pub fn new(include_directories: Vec<String>) -> Preprocessor {
+        let mut preprocessor = Preprocessor {
+            errors: Vec::new(),
+            include_directories,
+            macros: HashMap::new(),
+            once: HashSet::new(),
+            conditions: Vec::new(),
+            include_depth: 0,
+        };
+        preprocessor.define_builtins();
+        preprocessor
+    }
+
+    // Preprocesses a file and all files it includes
+    pub fn preprocess(&mut self, filename: &String) -> (Vec<Token>, Result<(), Vec<String>>) {
+        log::info!("Preprocessing {}", filename);
+        let tokens = self.preprocess_file(filename);
+        log::trace!(target: "preprocessor", "Preprocessed tokens: {:?}", tokens);
+        let errors = std::mem::take(&mut self.errors);
+        match errors.is_empty() {
+            true => (tokens, Ok(())),
+            false => (tokens, Err(errors)),
+        }
+    }
+
+    // Lexes a single file and processes all lines in it
+    fn preprocess_file(&mut self, filename: &String) -> Vec<Token> {
+        let file = match open(filename.clone()) {
+            Ok(file) => file,
+            Err(err) => {
+                self.errors.push(err);
+                return Vec::new();
+            }
+        };
+
+        let mut lexer = Lexer::new_preprocessing(filename);
+        let (tokens, lexer_errors) = lexer.lex(&mut file.chars());
+        if let Err(mut errors) = lexer_errors {
+            self.errors.append(&mut errors);
+        }
+
+        let mut input: VecDeque<PPToken> = tokens.into_iter().map(PPToken::new).collect();
+        let mut output = Vec::new();
+        let depth = self.conditions.len();
+        let mut line_start = true;
+
+        while let Some(token) = input.pop_front() {
+            match token.token.token() {
+                TokenType::Newline => {
+                    line_start = true;
+                    continue;
+                }
+                TokenType::Hash if line_start => {
+                    let line = take_line(&mut input);
+                    self.directive(&token.token, line, filename, &mut output);
+                    continue;
+                }
+                _ => line_start = false,
+            }
+
+            if !self.is_active() {
+                continue;
+            }
+
+            if !self.expand(&token, &mut input) {
+                output.push(token.token);
+            }
+        }
+
+        // All conditionals opened in this file should be closed in this file
+        while self.conditions.len() > depth {
+            let conditional = self.conditions.pop().unwrap();
+            self.errors
+                .push(error!(conditional.span, "Unterminated conditional directive"));
+        }
+        output
+    }
+
+    // Checks if all surrounding conditional groups are being processed
+    fn is_active(&self) -> bool {
+        self.conditions.iter().all(|conditional| conditional.active)
+    }
+
+    // Predefined macros
+    // __FILE__ and __LINE__ are handled during expansion
+    fn define_builtins(&mut self) {
+        let index = file_table::add_sourcefile(&String::from("<built-in>")) as u32;
+        let span = Span::new(index, 1, 1, 0, 1);
+        for &(name, value) in [
+            ("__STDC__", 1),
+            ("__STDC_HOSTED__", 1),
+            ("__x86_64__", 1),
+            ("__utcc__", 1),
+        ]
+        .iter()
+        {
+            let body = vec![Token::new(TokenType::ConstI(value, TypeNode::Int), span.clone())];
+            self.macros
+                .insert(name.to_string(), Macro::new(name.to_string(), None, body, span.clone()));
+        }
+    }
+}
+
+// Removes all tokens up to and including the next newline from input
+fn take_line(input: &mut VecDeque<PPToken>) -> Vec<PPToken> {
+    let mut line = Vec::new();
+    while let Some(token) = input.pop_front() {
+        if token.token.token() == TokenType::Newline {
+            break;
+        }
+        line.push(token);
+    }
+    line
+}
+
+// Converts a list of preprocessed tokens back to source text for -E
+// Line markers are inserted whenever the file changes or lines are skipped
+pub fn to_text(tokens: &[Token]) -> String {
+    let mut result = String::new();
+    let mut file_index = None;
+    let mut line = 0;
+    for token in tokens {
+        let span = token.span();
+        if file_index != Some(span.file_index()) || span.line() > line + 8 || span.line() < line
+        {
+            if !result.is_empty() {
+                result.push('\n');
+            }
+            result.push_str(&format!(
+                "# {} \"{}\"\n",
+                span.line(),
+                file_table::get_sourcefile(span.file_index())
+            ));
+            file_index = Some(span.file_index());
+            line = span.line();
+        } else {
+            while line < span.line() {
+                result.push('\n');
+                line += 1;
+            }
+        }
+        result.push_str(&token.token().spelling());
+        result.push(' ');
+    }
+    result.push('\n');
+    result
+}
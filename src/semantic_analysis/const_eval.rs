@@ -1,183 +1,427 @@
-use crate::{
-    error,
-    eval::evaluation_context::EvaluateSize,
-    parser::{ast::*, Type},
-};
-
-use super::SemanticAnalyzer;
-
-impl Expression {
-    pub fn is_constant(&self) -> bool {
-        match self.variant {
-            ExpressionVariant::ConstI(_) => true,
-            _ => false,
-        }
-    }
-
-    pub fn get_const_value(&self) -> i128 {
-        match self.variant {
-            ExpressionVariant::ConstI(val) => val,
-            _ => 0,
-        }
-    }
-
-    pub fn force_const_eval(&mut self, analyzer: &mut SemanticAnalyzer) {
-        let expression = std::mem::replace(self, Expression::default(&self.span));
-        let expression = expression.const_eval(analyzer);
-        if !expression.is_constant() {
-            analyzer.errors.push(error!(
-                self.span,
-                "Initialization of global variable must be constant"
-            ));
-        }
-        *self = expression;
-    }
-
-    pub fn const_eval(self, evaluation: &dyn EvaluateSize) -> Expression {
-        use ExpressionVariant::*;
-        match self.variant {
-            ConstI(_) => self,
-            Assign(..)
-            | Function(..)
-            | Ident(..)
-            | CString(..)
-            | Member(..)
-            | Binary(BinaryExpressionType::Index, ..)
-            | Unary(UnaryExpressionType::Deref | UnaryExpressionType::Address, ..) => self,
-
-            Sizeof(typ) => {
-                let size = evaluation.eval_sizeof(&typ);
-                Expression {
-                    span: self.span,
-                    ast_type: self.ast_type,
-                    variant: ConstI(size as i128),
-                }
-            }
-
-            Ternary(cond, left, right) => {
-                let cond = cond.const_eval(evaluation);
-                let left = left.const_eval(evaluation);
-                let right = right.const_eval(evaluation);
-
-                match (&cond.variant, &left.variant, &right.variant) {
-                    (ConstI(cond), ConstI(left), ConstI(right)) => Expression {
-                        span: self.span,
-                        ast_type: self.ast_type,
-
-                        variant: ConstI(if *cond != 0 { *left } else { *right }),
-                    },
-                    _ => Expression {
-                        span: self.span,
-                        ast_type: self.ast_type,
-
-                        variant: ExpressionVariant::Ternary(
-                            Box::new(cond),
-                            Box::new(left),
-                            Box::new(right),
-                        ),
-                    },
-                }
-            }
-            Binary(op, left, right) => {
-                let left = left.const_eval(evaluation);
-                let right = right.const_eval(evaluation);
-
-                match (&left.variant, &right.variant) {
-                    (ConstI(left), ConstI(right)) => Expression {
-                        span: self.span,
-                        ast_type: self.ast_type,
-
-                        variant: ConstI(op.const_eval(left, right)),
-                    },
-                    _ => Expression {
-                        span: self.span,
-                        ast_type: self.ast_type,
-                        variant: ExpressionVariant::Binary(op, Box::new(left), Box::new(right)),
-                    },
-                }
-            }
-
-            Cast(exp, typ) => {
-                let exp = exp.const_eval(evaluation);
-
-                match &exp.variant {
-                    ConstI(exp) => {
-                        let size = (evaluation.sizeof(&self.ast_type) * 8) as i128;
-                        let value = exp % (1 << size);
-                        Expression {
-                            span: self.span,
-                            ast_type: self.ast_type.clone(),
-                            variant: ConstI(value),
-                        }
-                    }
-                    _ => Expression {
-                        span: self.span,
-                        ast_type: self.ast_type,
-                        variant: ExpressionVariant::Cast(Box::new(exp), typ),
-                    },
-                }
-            }
-
-            Unary(op, exp) => {
-                let exp = exp.const_eval(evaluation);
-
-                match &exp.variant {
-                    ConstI(exp) => Expression {
-                        span: self.span,
-                        ast_type: self.ast_type.clone(),
-                        variant: ConstI(op.const_eval(exp, &self.ast_type)),
-                    },
-                    _ => Expression {
-                        span: self.span,
-                        ast_type: self.ast_type,
-                        variant: ExpressionVariant::Unary(op, Box::new(exp)),
-                    },
-                }
-            }
-        }
-    }
-}
-
-impl BinaryExpressionType {
-    fn const_eval(&self, &left: &i128, &right: &i128) -> i128 {
-        use BinaryExpressionType::*;
-        match self {
-            Add => left + right,
-            Subtract => left - right,
-            Multiply => left * right,
-            Equal => (left == right) as i128,
-            Inequal => (left != right) as i128,
-            Less => (left < right) as i128,
-            LessEqual => (left <= right) as i128,
-            Greater => (left > right) as i128,
-            GreaterEqual => (left >= right) as i128,
-            BinOr => left | right,
-            BinAnd => left & right,
-            LogOr => (left != 0 || right != 0) as i128,
-            LogAnd => (left != 0 && right != 0) as i128,
-            Comma => right,
-            Divide => {
-                if right != 0 {
-                    left / right
-                } else {
-                    0
-                }
-            }
-            Index => unreachable!(),
-        }
-    }
-}
-
-impl UnaryExpressionType {
-    fn const_eval(&self, &exp: &i128, ast_type: &Type) -> i128 {
-        use UnaryExpressionType::*;
-        let _ = ast_type;
-        match self {
-            Identity => exp,
-            Negate => -exp,
-            BinNot => !exp,
-            LogNot => (exp == 0) as i128,
-            Deref | Address => unreachable!(),
-        }
-    }
-}
+use crate::{
+    error,
+    eval::evaluation_context::EvaluateSize,
+    ir::IRSize,
+    parser::{ast::*, Type, TypeNode},
+};
+
+use super::{type_class::TypeClass, type_promotion::TypePromotion, SemanticAnalyzer};
+
+impl Expression {
+    pub fn is_constant(&self) -> bool {
+        match self.variant {
+            ExpressionVariant::ConstI(_) | ExpressionVariant::ConstF(_) => true,
+            _ => false,
+        }
+    }
+
+    fn get_float_value(&self) -> f64 {
+        match self.variant {
+            ExpressionVariant::ConstI(val) => val as f64,
+            ExpressionVariant::ConstF(val) => val,
+            _ => 0.0,
+        }
+    }
+
+    fn is_true(&self) -> bool {
+        match self.variant {
+            ExpressionVariant::ConstF(val) => val != 0.0,
+            _ => self.get_const_value() != 0,
+        }
+    }
+
+    // Converts a constant expression to a constant of another type
+    fn convert_constant(self, typ: Type, evaluation: &dyn EvaluateSize) -> Expression {
+        use ExpressionVariant::*;
+        let variant = match (self.variant, typ.is_float()) {
+            (ConstI(value), false) => ConstI(wrap(value, &typ, evaluation)),
+            (ConstI(value), true) => ConstF(round(value as f64, &typ)),
+            (ConstF(value), false) => ConstI(wrap(value as i128, &typ, evaluation)),
+            (ConstF(value), true) => ConstF(round(value, &typ)),
+            _ => unreachable!(),
+        };
+        Expression {
+            span: self.span,
+            ast_type: typ,
+            variant,
+        }
+    }
+
+    pub fn get_const_value(&self) -> i128 {
+        match self.variant {
+            ExpressionVariant::ConstI(val) => val,
+            _ => 0,
+        }
+    }
+
+    pub fn force_const_eval(&mut self, analyzer: &mut SemanticAnalyzer) {
+        let expression = std::mem::replace(self, Expression::default(&self.span));
+        let expression = expression.const_eval(analyzer);
+        if !expression.is_constant() {
+            analyzer.errors.push(error!(
+                self.span,
+                "Initialization of global variable must be constant"
+            ));
+        }
+        *self = expression;
+    }
+
+    // Globals can also be initialized by an address constant, which is resolved by the linker
+    // Such a value is the address of a global or a string literal plus a constant offset
+    pub fn force_initializer_eval(&mut self, analyzer: &mut SemanticAnalyzer, typ: &Type) {
+        let expression = std::mem::replace(self, Expression::default(&self.span));
+        let mut expression = expression.const_eval(analyzer);
+        let address = is_address_size(typ, analyzer) && expression.address_const_eval(analyzer);
+        if !expression.is_constant() && !address {
+            analyzer.errors.push(error!(
+                self.span,
+                "Initialization of global variable must be constant"
+            ));
+        }
+        *self = expression;
+    }
+
+    // Checks for an address constant, the integer operands in it are folded into constants
+    fn address_const_eval(&mut self, evaluation: &dyn EvaluateSize) -> bool {
+        use ExpressionVariant::*;
+        // Arrays and functions are converted to their address
+        if self.ast_type.is_array() || self.ast_type.is_function() {
+            return self.lvalue_address_const_eval(evaluation);
+        }
+        match &mut self.variant {
+            CString(_) => true,
+            Unary(UnaryExpressionType::Address, exp) => exp.lvalue_address_const_eval(evaluation),
+            Cast(exp, _) if is_address_size(&self.ast_type, evaluation) => {
+                exp.address_const_eval(evaluation)
+            }
+            Binary(BinaryExpressionType::Add, left, right) if self.ast_type.is_pointer() => {
+                if right.integer_const_eval(evaluation) {
+                    left.address_const_eval(evaluation)
+                } else {
+                    left.integer_const_eval(evaluation) && right.address_const_eval(evaluation)
+                }
+            }
+            Binary(BinaryExpressionType::Subtract, left, right) if self.ast_type.is_pointer() => {
+                right.integer_const_eval(evaluation) && left.address_const_eval(evaluation)
+            }
+            _ => false,
+        }
+    }
+
+    // Checks that an lvalue is an object with a constant address
+    fn lvalue_address_const_eval(&mut self, evaluation: &dyn EvaluateSize) -> bool {
+        use ExpressionVariant::*;
+        match &mut self.variant {
+            Ident(_, _, global) => *global,
+            CString(_) => true,
+            Member(exp, _, false, _) => exp.lvalue_address_const_eval(evaluation),
+            Member(exp, _, true, _) => exp.address_const_eval(evaluation),
+            Binary(BinaryExpressionType::Index, left, right) => {
+                if right.integer_const_eval(evaluation) {
+                    left.address_const_eval(evaluation)
+                } else {
+                    left.integer_const_eval(evaluation) && right.address_const_eval(evaluation)
+                }
+            }
+            Unary(UnaryExpressionType::Deref, exp) => exp.address_const_eval(evaluation),
+            _ => false,
+        }
+    }
+
+    fn integer_const_eval(&mut self, evaluation: &dyn EvaluateSize) -> bool {
+        let expression = std::mem::replace(self, Expression::default(&self.span));
+        *self = expression.const_eval(evaluation);
+        self.is_constant() && self.ast_type.is_in(TypeClass::Integer)
+    }
+
+    pub fn const_eval(self, evaluation: &dyn EvaluateSize) -> Expression {
+        use ExpressionVariant::*;
+        match self.variant {
+            ConstI(_) | ConstF(_) => self,
+            Assign(..)
+            | CompoundAssign(..)
+            | Function(..)
+            | VaStart(..)
+            | VaArg(..)
+            | VaEnd(..)
+            | VaCopy(..)
+            | Ident(..)
+            | CString(..)
+            | Member(..)
+            | Binary(BinaryExpressionType::Index, ..)
+            | Unary(
+                UnaryExpressionType::Deref
+                | UnaryExpressionType::Address
+                | UnaryExpressionType::PreIncrement
+                | UnaryExpressionType::PreDecrement
+                | UnaryExpressionType::PostIncrement
+                | UnaryExpressionType::PostDecrement,
+                ..,
+            ) => self,
+
+            Sizeof(typ) => {
+                let size = evaluation.eval_sizeof(&typ);
+                Expression {
+                    span: self.span,
+                    ast_type: self.ast_type,
+                    variant: ConstI(size as i128),
+                }
+            }
+
+            Ternary(cond, left, right) => {
+                let cond = cond.const_eval(evaluation);
+                let left = left.const_eval(evaluation);
+                let right = right.const_eval(evaluation);
+
+                match (&cond.variant, &left.variant, &right.variant) {
+                    (ConstI(cond), ConstI(left), ConstI(right)) => {
+                        let value = if *cond != 0 { *left } else { *right };
+                        Expression {
+                            span: self.span,
+                            variant: ConstI(wrap(value, &self.ast_type, evaluation)),
+                            ast_type: self.ast_type,
+                        }
+                    }
+                    _ if cond.is_constant() && left.is_constant() && right.is_constant() => {
+                        let value = if cond.is_true() { left } else { right };
+                        let value = Expression {
+                            span: self.span,
+                            ..value
+                        };
+                        value.convert_constant(self.ast_type, evaluation)
+                    }
+                    _ => Expression {
+                        span: self.span,
+                        ast_type: self.ast_type,
+
+                        variant: ExpressionVariant::Ternary(
+                            Box::new(cond),
+                            Box::new(left),
+                            Box::new(right),
+                        ),
+                    },
+                }
+            }
+            Binary(op, left, right) => {
+                let left = left.const_eval(evaluation);
+                let right = right.const_eval(evaluation);
+
+                match (&left.variant, &right.variant) {
+                    (ConstI(left_value), ConstI(right_value)) => {
+                        // The operands are first converted to the type the operation is done in
+                        use BinaryExpressionType::*;
+                        let (left_value, right_value) = match op {
+                            LeftShift | RightShift => {
+                                let op_type = left.ast_type.promote();
+                                (wrap(*left_value, &op_type, evaluation), *right_value)
+                            }
+                            LogOr | LogAnd | Comma => (*left_value, *right_value),
+                            _ => {
+                                let op_type =
+                                    (left.ast_type.promote(), right.ast_type.promote()).promote();
+                                (
+                                    wrap(*left_value, &op_type, evaluation),
+                                    wrap(*right_value, &op_type, evaluation),
+                                )
+                            }
+                        };
+                        let value = op.const_eval(&left_value, &right_value);
+                        Expression {
+                            span: self.span,
+                            variant: ConstI(wrap(value, &self.ast_type, evaluation)),
+                            ast_type: self.ast_type,
+                        }
+                    }
+                    // At least one of the operands is floating, so the operation is as well
+                    (ConstI(_) | ConstF(_), ConstI(_) | ConstF(_)) => {
+                        let op_type = (left.ast_type.promote(), right.ast_type.promote()).promote();
+                        let left_value = round(left.get_float_value(), &op_type);
+                        let right_value = round(right.get_float_value(), &op_type);
+                        let value = op.const_eval_float(left_value, right_value);
+                        let variant = if self.ast_type.is_float() {
+                            ConstF(round(value, &self.ast_type))
+                        } else {
+                            ConstI(wrap(value as i128, &self.ast_type, evaluation))
+                        };
+                        Expression {
+                            span: self.span,
+                            ast_type: self.ast_type,
+                            variant,
+                        }
+                    }
+                    _ => Expression {
+                        span: self.span,
+                        ast_type: self.ast_type,
+                        variant: ExpressionVariant::Binary(op, Box::new(left), Box::new(right)),
+                    },
+                }
+            }
+
+            Cast(exp, typ) => {
+                let exp = exp.const_eval(evaluation);
+
+                match &exp.variant {
+                    ConstI(_) | ConstF(_) => Expression {
+                        span: self.span,
+                        ..exp
+                    }
+                    .convert_constant(self.ast_type, evaluation),
+                    _ => Expression {
+                        span: self.span,
+                        ast_type: self.ast_type,
+                        variant: ExpressionVariant::Cast(Box::new(exp), typ),
+                    },
+                }
+            }
+
+            Unary(op, exp) => {
+                let exp = exp.const_eval(evaluation);
+
+                match &exp.variant {
+                    ConstI(value) => {
+                        let value = match op {
+                            UnaryExpressionType::LogNot => *value,
+                            _ => wrap(*value, &self.ast_type, evaluation),
+                        };
+                        let value = op.const_eval(&value, &self.ast_type);
+                        Expression {
+                            span: self.span,
+                            ast_type: self.ast_type.clone(),
+                            variant: ConstI(wrap(value, &self.ast_type, evaluation)),
+                        }
+                    }
+                    ConstF(value) => {
+                        let variant = match op {
+                            UnaryExpressionType::Identity => ConstF(*value),
+                            UnaryExpressionType::Negate => ConstF(-*value),
+                            UnaryExpressionType::LogNot => ConstI((*value == 0.0) as i128),
+                            // Other operators on floats are already reported as errors
+                            _ => ConstF(*value),
+                        };
+                        Expression {
+                            span: self.span,
+                            ast_type: self.ast_type,
+                            variant,
+                        }
+                    }
+                    _ => Expression {
+                        span: self.span,
+                        ast_type: self.ast_type,
+                        variant: ExpressionVariant::Unary(op, Box::new(exp)),
+                    },
+                }
+            }
+        }
+    }
+}
+
+// Wraps a value around to the range of values of an integer or pointer type
+pub(super) fn wrap(value: i128, typ: &Type, evaluation: &dyn EvaluateSize) -> i128 {
+    let unsigned = typ.is_unsigned() || typ.is_pointer();
+    if !unsigned && !typ.is_in(TypeClass::Integer) {
+        return value;
+    }
+    let bits = evaluation.sizeof(typ) * 8;
+    let modulus = 1i128 << bits;
+    if unsigned {
+        value.rem_euclid(modulus)
+    } else {
+        let half = modulus >> 1;
+        value.wrapping_add(half).rem_euclid(modulus) - half
+    }
+}
+
+// Only types as large as a pointer can hold an address constant
+fn is_address_size(typ: &Type, evaluation: &dyn EvaluateSize) -> bool {
+    matches!(evaluation.get_size(typ), IRSize::P | IRSize::S64)
+}
+
+// Rounds a value to the precision of a float when required
+fn round(value: f64, typ: &Type) -> f64 {
+    match typ.nodes.get(0) {
+        Some(TypeNode::Float) => value as f32 as f64,
+        _ => value,
+    }
+}
+
+impl BinaryExpressionType {
+    // Comparisons and logical operations give 1.0 or 0.0, which is converted to the int result
+    fn const_eval_float(&self, left: f64, right: f64) -> f64 {
+        use BinaryExpressionType::*;
+        let value = match self {
+            Add => return left + right,
+            Subtract => return left - right,
+            Multiply => return left * right,
+            Divide => return left / right,
+            Comma => return right,
+            Equal => left == right,
+            Inequal => left != right,
+            Less => left < right,
+            LessEqual => left <= right,
+            Greater => left > right,
+            GreaterEqual => left >= right,
+            LogOr => left != 0.0 || right != 0.0,
+            LogAnd => left != 0.0 && right != 0.0,
+            // Integer operators on floats are already reported as errors
+            Modulo | BinOr | BinAnd | BinXor | LeftShift | RightShift | Index => return 0.0,
+        };
+        value as i32 as f64
+    }
+
+    fn const_eval(&self, &left: &i128, &right: &i128) -> i128 {
+        use BinaryExpressionType::*;
+        match self {
+            Add => left.wrapping_add(right),
+            Subtract => left.wrapping_sub(right),
+            Multiply => left.wrapping_mul(right),
+            Equal => (left == right) as i128,
+            Inequal => (left != right) as i128,
+            Less => (left < right) as i128,
+            LessEqual => (left <= right) as i128,
+            Greater => (left > right) as i128,
+            GreaterEqual => (left >= right) as i128,
+            BinOr => left | right,
+            BinAnd => left & right,
+            BinXor => left ^ right,
+            LeftShift if (0..128).contains(&right) => left << right,
+            LeftShift => 0,
+            RightShift if (0..128).contains(&right) => left >> right,
+            RightShift => -((left < 0) as i128),
+            LogOr => (left != 0 || right != 0) as i128,
+            LogAnd => (left != 0 && right != 0) as i128,
+            Comma => right,
+            Divide => {
+                if right != 0 {
+                    left / right
+                } else {
+                    0
+                }
+            }
+            // The remainder in Rust is truncated towards zero, just like in C
+            Modulo => {
+                if right != 0 {
+                    left % right
+                } else {
+                    0
+                }
+            }
+            Index => unreachable!(),
+        }
+    }
+}
+
+impl UnaryExpressionType {
+    fn const_eval(&self, &exp: &i128, ast_type: &Type) -> i128 {
+        use UnaryExpressionType::*;
+        let _ = ast_type;
+        match self {
+            Identity => exp,
+            Negate => -exp,
+            BinNot => !exp,
+            LogNot => (exp == 0) as i128,
+            Deref | Address | PreIncrement | PreDecrement | PostIncrement | PostDecrement => {
+                unreachable!()
+            }
+        }
+    }
+}
@@ -1,324 +1,495 @@
-use super::analysis::Analysis;
-use super::type_class::TypeClass;
-use super::SemanticAnalyzer;
-use crate::error;
-use crate::parser::{ast::*, Type};
-use crate::semantic_analysis::type_checking::{check_arguments_function, check_member_type};
-use crate::semantic_analysis::type_promotion::TypePromotion;
-
-// The analysis for expressions
-impl Analysis for Expression {
-    fn analyze(&mut self, analyzer: &mut SemanticAnalyzer) -> () {
-        use ExpressionVariant::*;
-        match &mut self.variant {
-            CString(_) | ConstI(_) => {
-                return;
-            }
-
-            Sizeof(typ) => {
-                match typ {
-                    SizeofType::Type(ast_type, typ) => {
-                        analyzer.assert_no_name(&self.span, ast_type);
-                        *typ = ast_type.to_type(analyzer);
-                    }
-                    SizeofType::Expression(exp) => {
-                        exp.analyze(analyzer);
-                    }
-                }
-                self.ast_type = analyzer.type_info.size_t();
-                return;
-            }
-
-            Member(exp, ..) => exp.analyze(analyzer),
-
-            Ident(name, symbol_number, global) => {
-                if let Some(symbol) = analyzer.symbol_table.get(name) {
-                    self.ast_type = symbol.symbol_type.clone();
-                    *symbol_number = symbol.number;
-                    *global = symbol.global;
-                } else {
-                    analyzer
-                        .errors
-                        .push(error!(self.span, "Identifier {} is not defined", name))
-                }
-                return;
-            }
-
-            Function(func, arguments) => {
-                func.analyze_lvalue(analyzer);
-                for arg in arguments.iter_mut() {
-                    arg.analyze(analyzer);
-                }
-                check_arguments_function(analyzer, &self.span, &func.ast_type, arguments);
-            }
-
-            Unary(UnaryExpressionType::Address, exp) => {
-                exp.analyze_lvalue(analyzer);
-            }
-
-            Cast(exp, _) => {
-                exp.analyze(analyzer);
-            }
-
-            Unary(_op, exp) => {
-                exp.analyze(analyzer);
-            }
-
-            Binary(_op, left, right) => {
-                left.analyze(analyzer);
-                right.analyze(analyzer);
-            }
-
-            Ternary(cond, left, right) => {
-                cond.analyze(analyzer);
-                left.analyze(analyzer);
-                right.analyze(analyzer);
-            }
-
-            Assign(left, right) => {
-                left.analyze_lvalue(analyzer);
-                right.analyze(analyzer);
-            }
-        }
-        self.ast_type = self.get_type(analyzer);
-    }
-}
-
-impl Expression {
-    fn analyze_lvalue(&mut self, analyzer: &mut SemanticAnalyzer) -> () {
-        use ExpressionVariant::*;
-        match &mut self.variant {
-            Ident(..) => {
-                self.analyze(analyzer);
-            }
-            Unary(UnaryExpressionType::Deref, _) => self.analyze(analyzer),
-            Binary(BinaryExpressionType::Index, ..) => self.analyze(analyzer),
-            Member(..) => self.analyze(analyzer),
-            _ => {
-                analyzer.errors.push(error!(self.span, "Expected lvalue"));
-            }
-        }
-        if self.ast_type.is_array() {
-            analyzer
-                .errors
-                .push(error!(self.span, "An array cannot be an lvalue"));
-        }
-    }
-}
-
-impl Expression {
-    fn get_type(&mut self, analyzer: &mut SemanticAnalyzer) -> Type {
-        use ExpressionVariant::*;
-
-        match &mut self.variant {
-            Ident(..) | ConstI(_) | CString(..) | Sizeof(..) => unreachable!(),
-
-            Member(exp, id, indirect, index) => {
-                let (ast_type, i) =
-                    check_member_type(analyzer, &self.span, &exp.ast_type, id, *indirect);
-                *index = i;
-                ast_type
-            }
-
-            Function(func, _) => func
-                .ast_type
-                .get_return_type()
-                .map(|t| t.into())
-                .unwrap_or_else(|| {
-                    analyzer
-                        .errors
-                        .push(error!(self.span, "Function call on non function"));
-                    Type::int()
-                }),
-
-            Unary(UnaryExpressionType::Address, exp) => Type::pointer().append(&exp.ast_type),
-
-            Cast(exp, ast_type) => {
-                let typ = ast_type.to_type(analyzer);
-                let span = &self.span;
-                analyzer.assert_no_name(span, ast_type);
-                analyzer.assert_in(span, &exp.ast_type, TypeClass::Scalar);
-                analyzer.assert_in(span, &typ, TypeClass::Scalar);
-                typ
-            }
-
-            Unary(op, exp) => op.get_type(analyzer, exp, &mut self.ast_type),
-
-            Binary(op, left, right) => op.get_type(analyzer, left, right),
-
-            Ternary(cond, left, right) => {
-                use TypeClass::*;
-                let cond_type = cond.ast_type.promote();
-                let left_type = left.ast_type.promote();
-                let right_type = right.ast_type.promote();
-                analyzer.assert_in(&self.span, &cond_type, Scalar);
-                if left_type.is_in(Pointer) && right_type.is_in(Pointer) {
-                    analyzer.assert_compatible(&self.span, &left_type, &right_type);
-                } else {
-                    analyzer.assert_both_in(&self.span, &left_type, &right_type, Arithmetic)
-                }
-                (left_type, right_type).promote()
-            }
-
-            Assign(left, right) => {
-                use TypeClass::*;
-                let right_type = right.ast_type.promote();
-                if left.ast_type.is_in(Pointer) && right_type.is_in(Pointer) {
-                    analyzer.assert_compatible(&self.span, &left.ast_type, &right_type);
-                } else {
-                    analyzer.assert_both_in(
-                        &self.span,
-                        &left.ast_type,
-                        &right.ast_type,
-                        Arithmetic,
-                    );
-                }
-                left.ast_type.clone()
-            }
-        }
-    }
-}
-
-impl UnaryExpressionType {
-    fn get_type(&self, analyzer: &mut SemanticAnalyzer, exp: &Expression, _: &mut Type) -> Type {
-        use TypeClass::*;
-        use UnaryExpressionType::*;
-
-        let span = &exp.span;
-        let exp_type = exp.ast_type.promote();
-        let typ = &exp_type;
-        match self {
-            Identity | Negate | BinNot => {
-                analyzer.assert_in(span, typ, self.get_type_class());
-                exp_type
-            }
-            LogNot => {
-                analyzer.assert_in(span, typ, self.get_type_class());
-                Type::int()
-            }
-            Deref => {
-                analyzer.assert_in(span, typ, Pointer);
-                exp_type.deref()
-            }
-            Address => unreachable!(),
-        }
-    }
-
-    fn get_type_class(&self) -> TypeClass {
-        use TypeClass::*;
-        use UnaryExpressionType::*;
-        match self {
-            Identity | Negate => Arithmetic,
-            BinNot => Integer,
-            LogNot => Scalar,
-
-            Deref | Address => unreachable!(),
-        }
-    }
-}
-
-impl BinaryExpressionType {
-    fn get_type(
-        &self,
-        analyzer: &mut SemanticAnalyzer,
-        left: &Expression,
-        right: &Expression,
-    ) -> Type {
-        use BinaryExpressionType::*;
-        use TypeClass::*;
-        let span = left.span.to(&right.span);
-        let span = &span;
-        let left_type = left.ast_type.promote();
-        let right_type = right.ast_type.promote();
-        {
-            match self {
-                Add => {
-                    if left_type.is_in(Pointer) && right_type.is_in(Integer) {
-                        left_type
-                    } else if left_type.is_in(Integer) && right_type.is_in(Pointer) {
-                        right_type
-                    } else {
-                        analyzer.assert_both_in(
-                            span,
-                            &left_type,
-                            &right_type,
-                            self.get_type_class(),
-                        );
-                        (left_type, right_type).promote()
-                    }
-                }
-                Subtract => {
-                    if left_type.is_in(Pointer) && right_type.is_in(Integer) {
-                        left_type
-                    } else if left_type.is_in(Pointer) && right_type.is_in(Pointer) {
-                        analyzer.assert_compatible(span, &left_type, &right_type);
-                        analyzer.type_info.size_t()
-                    } else {
-                        analyzer.assert_both_in(
-                            span,
-                            &left_type,
-                            &right_type,
-                            self.get_type_class(),
-                        );
-                        (left_type, right_type).promote()
-                    }
-                }
-                Index => {
-                    if left_type.is_in(Pointer) && right_type.is_in(Integer) {
-                        left_type.deref()
-                    } else if left_type.is_in(Integer) && right_type.is_in(Pointer) {
-                        right_type.deref()
-                    } else {
-                        analyzer.errors.push(error!(
-                            span,
-                            "Incompatible types {} and {}, whilst providing an index",
-                            left_type,
-                            right_type
-                        ));
-                        Type::int()
-                    }
-                }
-
-                Equal | Inequal | Less | LessEqual | Greater | GreaterEqual => {
-                    if left_type.is_in(Pointer) && right_type.is_in(Pointer) {
-                        analyzer.assert_compatible(span, &left_type, &right_type);
-                        Type::int()
-                    } else {
-                        analyzer.assert_both_in(
-                            span,
-                            &left_type,
-                            &right_type,
-                            self.get_type_class(),
-                        );
-                        (left_type, right_type).promote()
-                    }
-                }
-                Multiply | Divide | BinOr | BinAnd => {
-                    analyzer.assert_both_in(span, &left_type, &right_type, self.get_type_class());
-                    (left_type, right_type).promote()
-                }
-
-                LogOr | LogAnd => {
-                    analyzer.assert_both_in(span, &left_type, &right_type, self.get_type_class());
-                    Type::int()
-                }
-
-                Comma => right_type,
-            }
-        }
-    }
-
-    pub fn get_type_class(&self) -> TypeClass {
-        use BinaryExpressionType::*;
-        use TypeClass::*;
-        {
-            match self {
-                Add | Subtract | Multiply | Divide | Equal | Inequal | Less | LessEqual
-                | Greater | GreaterEqual => Arithmetic,
-                BinOr | BinAnd => Integer,
-                LogOr | LogAnd => Scalar,
-                Comma | Index => unreachable!(),
-            }
-        }
-    }
-}
+use super::analysis::Analysis;
+use super::type_class::TypeClass;
+use super::SemanticAnalyzer;
+use crate::error;
+use crate::parser::{
+    ast::*,
+    r#type::{DeclarationType, StorageClass},
+    Type, TypeNode,
+};
+use crate::semantic_analysis::type_checking::{check_arguments_function, check_member_type};
+use crate::semantic_analysis::type_promotion::TypePromotion;
+use crate::span::Span;
+
+// The analysis for expressions
+impl Analysis for Expression {
+    fn analyze(&mut self, analyzer: &mut SemanticAnalyzer) -> () {
+        use ExpressionVariant::*;
+        match &mut self.variant {
+            CString(_) | ConstI(_) | ConstF(_) => {
+                return;
+            }
+
+            Sizeof(typ) => {
+                match typ {
+                    SizeofType::Type(ast_type, typ) => {
+                        analyzer.assert_no_name(&self.span, ast_type);
+                        *typ = ast_type.to_type(analyzer);
+                        analyzer.assert_complete(&self.span, typ, "Operand of sizeof");
+                    }
+                    SizeofType::Expression(exp) => {
+                        exp.analyze(analyzer);
+                        analyzer.assert_complete(&self.span, &exp.ast_type, "Operand of sizeof");
+                        if exp.is_bit_field(analyzer) {
+                            analyzer.errors.push(error!(
+                                self.span,
+                                "Cannot take the size of bit-field {}", exp
+                            ));
+                        }
+                    }
+                }
+                self.ast_type = analyzer.type_info.size_t();
+                return;
+            }
+
+            Member(exp, ..) => exp.analyze(analyzer),
+
+            Ident(name, symbol_number, global) => {
+                if let Some(symbol) = analyzer.symbol_table.get(name) {
+                    // Enumerators are replaced by their value
+                    if let DeclarationType::Enumerator(value) = symbol.declaration_type {
+                        self.ast_type = Type::int();
+                        self.variant = ConstI(value);
+                        return;
+                    }
+                    self.ast_type = symbol.symbol_type.clone();
+                    *symbol_number = symbol.number;
+                    *global = symbol.global;
+                    // Block scope statics refer to their uniquely named global
+                    if symbol.storage_class == StorageClass::Static
+                        && analyzer.symbol_table.contains(name)
+                    {
+                        *name = format!("{}.{}", name, symbol.number);
+                    }
+                } else {
+                    analyzer
+                        .errors
+                        .push(error!(self.span, "Identifier {} is not defined", name))
+                }
+                return;
+            }
+
+            Function(func, arguments) => {
+                func.analyze_lvalue(analyzer);
+                for arg in arguments.iter_mut() {
+                    arg.analyze(analyzer);
+                }
+                check_arguments_function(analyzer, &self.span, &func.ast_type, arguments);
+            }
+
+            VaStart(list, last) => {
+                list.analyze(analyzer);
+                last.analyze(analyzer);
+                list.assert_va_list(analyzer);
+                if !analyzer.function_variadic {
+                    analyzer.errors.push(error!(
+                        self.span,
+                        "va_start used in a function with fixed arguments"
+                    ));
+                }
+            }
+
+            VaArg(list, _) | VaEnd(list) => {
+                list.analyze(analyzer);
+                list.assert_va_list(analyzer);
+            }
+
+            VaCopy(dest, src) => {
+                dest.analyze(analyzer);
+                src.analyze(analyzer);
+                dest.assert_va_list(analyzer);
+                src.assert_va_list(analyzer);
+            }
+
+            Unary(UnaryExpressionType::Address, exp) => {
+                exp.analyze_lvalue(analyzer);
+                if exp.is_bit_field(analyzer) {
+                    analyzer.errors.push(error!(
+                        self.span,
+                        "Cannot take the address of bit-field {}", exp
+                    ));
+                }
+            }
+
+            Unary(
+                UnaryExpressionType::PreIncrement
+                | UnaryExpressionType::PreDecrement
+                | UnaryExpressionType::PostIncrement
+                | UnaryExpressionType::PostDecrement,
+                exp,
+            ) => {
+                exp.analyze_lvalue(analyzer);
+                exp.assert_modifiable(analyzer);
+            }
+
+            Cast(exp, _) => {
+                exp.analyze(analyzer);
+            }
+
+            Unary(_op, exp) => {
+                exp.analyze(analyzer);
+            }
+
+            Binary(_op, left, right) => {
+                left.analyze(analyzer);
+                right.analyze(analyzer);
+            }
+
+            Ternary(cond, left, right) => {
+                cond.analyze(analyzer);
+                left.analyze(analyzer);
+                right.analyze(analyzer);
+            }
+
+            Assign(left, right) | CompoundAssign(_, left, right) => {
+                left.analyze_lvalue(analyzer);
+                left.assert_modifiable(analyzer);
+                right.analyze(analyzer);
+            }
+        }
+        self.ast_type = self.get_type(analyzer);
+    }
+}
+
+impl Expression {
+    fn analyze_lvalue(&mut self, analyzer: &mut SemanticAnalyzer) -> () {
+        use ExpressionVariant::*;
+        match &mut self.variant {
+            Ident(..) => {
+                self.analyze(analyzer);
+                if self.is_constant() {
+                    analyzer.errors.push(error!(self.span, "Expected lvalue"));
+                }
+            }
+            Unary(UnaryExpressionType::Deref, _) => self.analyze(analyzer),
+            Binary(BinaryExpressionType::Index, ..) => self.analyze(analyzer),
+            Member(..) => self.analyze(analyzer),
+            _ => {
+                analyzer.errors.push(error!(self.span, "Expected lvalue"));
+            }
+        }
+        if self.ast_type.is_array() {
+            analyzer
+                .errors
+                .push(error!(self.span, "An array cannot be an lvalue"));
+        }
+    }
+
+    // A va_list is an array containing a single struct, which decays to a pointer to it
+    fn assert_va_list(&self, analyzer: &mut SemanticAnalyzer) {
+        let typ = self.ast_type.array_promotion();
+        if !typ.is_pointer() || !typ.clone().deref().is_struct() {
+            analyzer
+                .errors
+                .push(error!(self.span, "Expected va_list, but found {}", self.ast_type));
+        }
+    }
+
+    // Bit-fields have neither an address nor a size in bytes
+    fn is_bit_field(&self, analyzer: &SemanticAnalyzer) -> bool {
+        let (exp, index) = match &self.variant {
+            ExpressionVariant::Member(exp, _, _, index) => (exp, *index as usize),
+            _ => return false,
+        };
+        let typ = exp.ast_type.array_promotion();
+        let struct_type = match (typ.nodes.get(0), typ.nodes.get(1)) {
+            (Some(TypeNode::Struct(i)), _)
+            | (Some(TypeNode::Pointer), Some(TypeNode::Struct(i))) => {
+                &analyzer.struct_table.structs[*i]
+            }
+            _ => return false,
+        };
+        struct_type
+            .bit_fields
+            .get(index)
+            .map_or(false, Option::is_some)
+    }
+
+    // Objects with a const type cannot be modified after their initialization
+    fn assert_modifiable(&self, analyzer: &mut SemanticAnalyzer) {
+        if self.ast_type.is_const() {
+            analyzer.errors.push(error!(
+                self.span,
+                "Cannot modify {} as it has const type {}", self, self.ast_type
+            ));
+        }
+    }
+}
+
+impl Expression {
+    fn get_type(&mut self, analyzer: &mut SemanticAnalyzer) -> Type {
+        use ExpressionVariant::*;
+
+        match &mut self.variant {
+            Ident(..) | ConstI(_) | ConstF(_) | CString(..) | Sizeof(..) => unreachable!(),
+
+            Member(exp, id, indirect, index) => {
+                let (ast_type, i) =
+                    check_member_type(analyzer, &self.span, &exp.ast_type, id, *indirect);
+                *index = i;
+                ast_type
+            }
+
+            Function(func, _) => func
+                .ast_type
+                .get_qualified_return_type()
+                .map(Type::unqualified)
+                .unwrap_or_else(|| {
+                    analyzer
+                        .errors
+                        .push(error!(self.span, "Function call on non function"));
+                    Type::int()
+                }),
+
+            VaStart(..) | VaEnd(..) | VaCopy(..) => vec![TypeNode::Void].into(),
+
+            VaArg(_, ast_type) => {
+                analyzer.assert_no_name(&self.span, ast_type);
+                ast_type.to_type(analyzer).unqualified()
+            }
+
+            Unary(UnaryExpressionType::Address, exp) => Type::pointer().append(&exp.ast_type),
+
+            Cast(exp, ast_type) => {
+                let typ = ast_type.to_type(analyzer);
+                let span = &self.span;
+                analyzer.assert_no_name(span, ast_type);
+                // Arrays and functions are cast as pointers to their first element or themselves
+                let exp_type = exp.ast_type.array_promotion();
+                analyzer.assert_in(span, &exp_type, TypeClass::Scalar);
+                analyzer.assert_in(span, &typ, TypeClass::Scalar);
+                if (exp_type.is_pointer() && typ.is_float())
+                    || (exp_type.is_float() && typ.is_pointer())
+                {
+                    analyzer
+                        .errors
+                        .push(error!(span, "Cannot cast {} to {}", exp_type, typ));
+                }
+                typ
+            }
+
+            Unary(op, exp) => op.get_type(analyzer, exp, &mut self.ast_type),
+
+            Binary(op, left, right) => op.get_type(analyzer, left, right),
+
+            Ternary(cond, left, right) => {
+                use TypeClass::*;
+                let cond_type = cond.ast_type.promote();
+                let left_type = left.ast_type.promote();
+                let right_type = right.ast_type.promote();
+                analyzer.assert_in(&self.span, &cond_type, Scalar);
+                if left_type.is_in(Pointer) && right_type.is_in(Pointer) {
+                    analyzer.assert_compatible(&self.span, &left_type, &right_type);
+                } else {
+                    analyzer.assert_both_in(&self.span, &left_type, &right_type, Arithmetic)
+                }
+                (left_type, right_type).promote()
+            }
+
+            Assign(left, right) => {
+                use TypeClass::*;
+                let right_type = right.ast_type.promote();
+                if left.ast_type.is_in(Pointer) && right_type.is_in(Pointer) {
+                    analyzer.assert_compatible(&self.span, &left.ast_type, &right_type);
+                    analyzer.assert_qualifiers_kept(&self.span, &right_type, &left.ast_type);
+                } else if left.ast_type.is_struct() || right_type.is_struct() {
+                    analyzer.assert_compatible(&self.span, &left.ast_type, &right_type);
+                } else {
+                    analyzer.assert_both_in(
+                        &self.span,
+                        &left.ast_type,
+                        &right.ast_type,
+                        Arithmetic,
+                    );
+                }
+                left.ast_type.clone()
+            }
+
+            // The operation is checked like a normal binary expression
+            // Its result must then be assignable to the left side
+            CompoundAssign(op, left, right) => {
+                use TypeClass::*;
+                let typ = op.get_type(analyzer, left, right);
+                if left.ast_type.is_in(Pointer) != typ.is_in(Pointer) {
+                    analyzer.errors.push(error!(
+                        self.span,
+                        "Result of {} cannot be assigned to {}",
+                        typ,
+                        left.ast_type
+                    ));
+                }
+                left.ast_type.clone()
+            }
+        }
+    }
+}
+
+impl UnaryExpressionType {
+    fn get_type(&self, analyzer: &mut SemanticAnalyzer, exp: &Expression, _: &mut Type) -> Type {
+        use TypeClass::*;
+        use UnaryExpressionType::*;
+
+        let span = &exp.span;
+        let exp_type = exp.ast_type.promote();
+        let typ = &exp_type;
+        match self {
+            Identity | Negate | BinNot => {
+                analyzer.assert_in(span, typ, self.get_type_class());
+                exp_type
+            }
+            LogNot => {
+                analyzer.assert_in(span, typ, self.get_type_class());
+                Type::int()
+            }
+            Deref => {
+                analyzer.assert_in(span, typ, Pointer);
+                exp_type.deref()
+            }
+            // The result has the type of the lvalue itself
+            PreIncrement | PreDecrement | PostIncrement | PostDecrement => {
+                analyzer.assert_in(span, typ, self.get_type_class());
+                exp.ast_type.clone()
+            }
+            Address => unreachable!(),
+        }
+    }
+
+    fn get_type_class(&self) -> TypeClass {
+        use TypeClass::*;
+        use UnaryExpressionType::*;
+        match self {
+            Identity | Negate => Arithmetic,
+            BinNot => Integer,
+            LogNot | PreIncrement | PreDecrement | PostIncrement | PostDecrement => Scalar,
+
+            Deref | Address => unreachable!(),
+        }
+    }
+}
+
+impl BinaryExpressionType {
+    fn get_type(
+        &self,
+        analyzer: &mut SemanticAnalyzer,
+        left: &Expression,
+        right: &Expression,
+    ) -> Type {
+        use BinaryExpressionType::*;
+        use TypeClass::*;
+        let span = left.span.to(&right.span);
+        let span = &span;
+        let left_type = left.ast_type.promote();
+        let right_type = right.ast_type.promote();
+        {
+            match self {
+                Add => {
+                    if left_type.is_in(Pointer) && right_type.is_in(Integer) {
+                        assert_complete_pointee(analyzer, span, &left_type);
+                        left_type
+                    } else if left_type.is_in(Integer) && right_type.is_in(Pointer) {
+                        assert_complete_pointee(analyzer, span, &right_type);
+                        right_type
+                    } else {
+                        analyzer.assert_both_in(
+                            span,
+                            &left_type,
+                            &right_type,
+                            self.get_type_class(),
+                        );
+                        (left_type, right_type).promote()
+                    }
+                }
+                Subtract => {
+                    if left_type.is_in(Pointer) && right_type.is_in(Integer) {
+                        assert_complete_pointee(analyzer, span, &left_type);
+                        left_type
+                    } else if left_type.is_in(Pointer) && right_type.is_in(Pointer) {
+                        analyzer.assert_compatible(span, &left_type, &right_type);
+                        assert_complete_pointee(analyzer, span, &left_type);
+                        analyzer.type_info.ptrdiff_t()
+                    } else {
+                        analyzer.assert_both_in(
+                            span,
+                            &left_type,
+                            &right_type,
+                            self.get_type_class(),
+                        );
+                        (left_type, right_type).promote()
+                    }
+                }
+                Index => {
+                    if left_type.is_in(Pointer) && right_type.is_in(Integer) {
+                        assert_complete_pointee(analyzer, span, &left_type);
+                        left_type.deref()
+                    } else if left_type.is_in(Integer) && right_type.is_in(Pointer) {
+                        assert_complete_pointee(analyzer, span, &right_type);
+                        right_type.deref()
+                    } else {
+                        analyzer.errors.push(error!(
+                            span,
+                            "Incompatible types {} and {}, whilst providing an index",
+                            left_type,
+                            right_type
+                        ));
+                        Type::int()
+                    }
+                }
+
+                // Comparisons always result in an int
+                Equal | Inequal | Less | LessEqual | Greater | GreaterEqual => {
+                    if left_type.is_in(Pointer) && right_type.is_in(Pointer) {
+                        analyzer.assert_compatible(span, &left_type, &right_type);
+                    } else {
+                        analyzer.assert_both_in(
+                            span,
+                            &left_type,
+                            &right_type,
+                            self.get_type_class(),
+                        );
+                    }
+                    Type::int()
+                }
+                Multiply | Divide | Modulo | BinOr | BinAnd | BinXor => {
+                    analyzer.assert_both_in(span, &left_type, &right_type, self.get_type_class());
+                    (left_type, right_type).promote()
+                }
+
+                // The type of a shift is the promoted type of the left operand
+                LeftShift | RightShift => {
+                    analyzer.assert_both_in(span, &left_type, &right_type, self.get_type_class());
+                    left_type
+                }
+
+                LogOr | LogAnd => {
+                    analyzer.assert_both_in(span, &left_type, &right_type, self.get_type_class());
+                    Type::int()
+                }
+
+                Comma => right_type,
+            }
+        }
+    }
+
+    pub fn get_type_class(&self) -> TypeClass {
+        use BinaryExpressionType::*;
+        use TypeClass::*;
+        {
+            match self {
+                Add | Subtract | Multiply | Divide | Equal | Inequal | Less | LessEqual
+                | Greater | GreaterEqual => Arithmetic,
+                Modulo | BinOr | BinAnd | BinXor | LeftShift | RightShift => Integer,
+                LogOr | LogAnd => Scalar,
+                Comma | Index => unreachable!(),
+            }
+        }
+    }
+}
+
+// Pointer arithmetic is done in units of the pointed-to object, so its size must be known
+// Arithmetic on void pointers is done in bytes
+fn assert_complete_pointee(analyzer: &mut SemanticAnalyzer, span: &Span, pointer: &Type) {
+    let pointee = pointer.clone().deref();
+    if !pointee.is_void() {
+        analyzer.assert_complete(span, &pointee, "Object of pointer arithmetic");
+    }
+}
@@ -1,188 +1,276 @@
-use super::analysis::Analysis;
-use super::SemanticAnalyzer;
-use crate::parser::ast::ExternalDeclaration;
-use crate::parser::Type;
-use crate::semantic_analysis::type_checking::{compare_arguments, compare_return_types};
-use crate::{error, parser::r#type::DeclarationType};
-
-impl ExternalDeclaration {
-    fn insert_or_update(
-        &mut self,
-        analyzer: &mut SemanticAnalyzer,
-        declaration_type: DeclarationType,
-    ) {
-        let name = self.name.as_ref().unwrap();
-
-        if let Err(()) = analyzer
-            .symbol_table
-            .try_insert(name, &self.decl_type, declaration_type)
-        {
-            let old_definition = analyzer.symbol_table.get(name).unwrap().clone();
-            compare_return_types(
-                analyzer,
-                &self.span,
-                name,
-                &old_definition.symbol_type,
-                &self.decl_type,
-            );
-            use DeclarationType::*;
-            match (old_definition.declaration_type, declaration_type) {
-                (Declaration, Declaration) => (),
-                (Definition | Prototype, Declaration) => (),
-                (Declaration, Prototype | Definition) => {
-                    let symbol = analyzer.symbol_table.get_mut(name).unwrap();
-                    symbol.symbol_type = self.decl_type.clone();
-                    symbol.declaration_type = declaration_type;
-                }
-
-                (Prototype, Prototype | Definition) => {
-                    compare_arguments(
-                        analyzer,
-                        &self.span,
-                        name,
-                        &old_definition.symbol_type,
-                        &self.decl_type,
-                    );
-                    let symbol = analyzer.symbol_table.get_mut(name).unwrap();
-                    symbol.symbol_type = self.decl_type.clone();
-                    symbol.declaration_type = declaration_type;
-                }
-                (Definition, Prototype) => {
-                    compare_arguments(
-                        analyzer,
-                        &self.span,
-                        name,
-                        &old_definition.symbol_type,
-                        &self.decl_type,
-                    );
-                }
-
-                (Definition, Definition) => analyzer.errors.push(error!(
-                    self.span,
-                    "Global
-                     {} redefined",
-                    name
-                )),
-            }
-        }
-    }
-}
-
-impl Analysis for ExternalDeclaration {
-    fn analyze(&mut self, analyzer: &mut SemanticAnalyzer) -> () {
-        self.decl_type = self.ast_type.to_type(analyzer);
-        log::debug!("function name: {:?}", self.name);
-        if self.name.is_none() {
-            if !self.ast_type.is_type_declaration() {
-                analyzer
-                    .errors
-                    .push(error!(self.span, "Global definition without name found"));
-            }
-            return;
-        }
-        log::debug!("function return type: {}", self.decl_type);
-        //let name = self.name.as_ref().unwrap();
-
-        if let Some(_) = self.function_body {
-            if !self.decl_type.is_function() {
-                analyzer.errors.push(error!(
-                    self.span,
-                    "Function body defined for global variable"
-                ))
-            } else {
-                self.insert_or_update(analyzer, DeclarationType::Definition)
-            }
-        } else {
-            if self.decl_type.is_function() {
-                if self.decl_type.is_declaration() {
-                    self.insert_or_update(analyzer, DeclarationType::Declaration)
-                } else {
-                    self.insert_or_update(analyzer, DeclarationType::Prototype)
-                }
-            } else if let Some(_) = self.expression {
-                self.insert_or_update(analyzer, DeclarationType::Definition)
-            } else {
-                self.insert_or_update(analyzer, DeclarationType::Declaration)
-            }
-        }
-
-        if self.decl_type.is_function() {
-            let return_type: Type = self.decl_type.get_return_type().unwrap().into();
-            if return_type.is_array() || return_type.is_struct() {
-                analyzer
-                    .errors
-                    .push(error!(self.span, "Cannot return an array or struct"));
-            }
-        } else {
-            if self.decl_type.is_void() {
-                analyzer
-                    .errors
-                    .push(error!(self.span, "Global variable cannot have type void"));
-            }
-        }
-
-        let function_body = self.function_body.is_some();
-        if let Some(statements) = &mut self.function_body {
-            analyzer.function_return_type = self.decl_type.get_return_type().unwrap().into();
-
-            analyzer.enter_scope();
-            let arguments = self.ast_type.get_function_arguments(analyzer);
-            let mut first = true;
-            for (typ, name) in arguments {
-                if typ.is_void() {
-                    if !first {
-                        analyzer.errors.push(error!(
-                            self.span,
-                            "Expected 'void' to be the first argument"
-                        ))
-                    }
-                }
-
-                if let Some(name) = name {
-                    let symbol_type = typ.array_promotion();
-                    if symbol_type.is_struct() {
-                        analyzer.errors.push(error!(
-                            self.span,
-                            "Structs as arguments currently not yet supported\n"
-                        ))
-                    } else if symbol_type.is_void() {
-                        analyzer
-                            .errors
-                            .push(error!(self.span, "Void is not a named argument\n"))
-                    }
-
-                    if let Err(()) = analyzer.symbol_table.try_insert(
-                        &name,
-                        &symbol_type,
-                        DeclarationType::Definition,
-                    ) {
-                        analyzer.errors.push(error!(
-                            self.span,
-                            "Argument {} with type {} already defined as type {}",
-                            &name,
-                            &symbol_type,
-                            &analyzer.symbol_table.get(&name).unwrap().symbol_type
-                        ));
-                    }
-                } else if function_body && !typ.is_void() {
-                    analyzer.errors.push(error!(
-                        self.span,
-                        "Function argument without name in {}",
-                        self.name.clone().unwrap_or_default()
-                    ));
-                }
-                first = false;
-            }
-
-            for statement in statements {
-                statement.analyze(analyzer);
-            }
-            analyzer.leave_scope();
-        }
-
-        if let Some(expression) = &mut self.expression {
-            expression.analyze(analyzer);
-            expression.force_const_eval(analyzer);
-        }
-    }
-}
+use super::analysis::Analysis;
+use super::SemanticAnalyzer;
+use crate::error;
+use crate::parser::ast::ExternalDeclaration;
+use crate::parser::r#type::{DeclarationType, StorageClass};
+use crate::parser::Type;
+use crate::semantic_analysis::type_checking::{compare_arguments, compare_return_types};
+
+impl ExternalDeclaration {
+    fn insert_or_update(
+        &mut self,
+        analyzer: &mut SemanticAnalyzer,
+        declaration_type: DeclarationType,
+    ) {
+        let name = self.name.as_ref().unwrap();
+        let storage_class = self.ast_type.get_storage_class();
+
+        if let Err(()) =
+            analyzer
+                .symbol_table
+                .try_insert(name, &self.decl_type, declaration_type, storage_class)
+        {
+            let old_definition = analyzer.symbol_table.get(name).unwrap().clone();
+            compare_return_types(
+                analyzer,
+                &self.span,
+                name,
+                &old_definition.symbol_type,
+                &self.decl_type,
+            );
+            if !self.decl_type.is_function()
+                && !old_definition.symbol_type.is_function()
+                && old_definition.symbol_type != self.decl_type
+            {
+                analyzer.errors.push(error!(
+                    self.span,
+                    "Global {} previously declared as '{}' is redeclared as '{}'",
+                    name,
+                    old_definition.symbol_type,
+                    self.decl_type
+                ));
+            }
+            self.check_linkage(analyzer, old_definition.storage_class, storage_class);
+
+            use DeclarationType::*;
+            match (old_definition.declaration_type, declaration_type) {
+                (Declaration, Declaration) => (),
+                (Definition | Prototype | Tentative, Declaration) => (),
+                (Definition | Tentative, Tentative) => (),
+                (Declaration, Prototype | Definition | Tentative) | (Tentative, Definition) => {
+                    let symbol = analyzer.symbol_table.get_mut(name).unwrap();
+                    symbol.symbol_type = self.decl_type.clone();
+                    symbol.declaration_type = declaration_type;
+                }
+
+                (Prototype, Prototype | Definition) => {
+                    compare_arguments(
+                        analyzer,
+                        &self.span,
+                        name,
+                        &old_definition.symbol_type,
+                        &self.decl_type,
+                    );
+                    let symbol = analyzer.symbol_table.get_mut(name).unwrap();
+                    symbol.symbol_type = self.decl_type.clone();
+                    symbol.declaration_type = declaration_type;
+                }
+                (Definition, Prototype) => {
+                    compare_arguments(
+                        analyzer,
+                        &self.span,
+                        name,
+                        &old_definition.symbol_type,
+                        &self.decl_type,
+                    );
+                }
+
+                (Definition, Definition) => analyzer.errors.push(error!(
+                    self.span,
+                    "Global
+                     {} redefined",
+                    name
+                )),
+                (Typedef | Enumerator(_), _)
+                | (_, Typedef | Enumerator(_))
+                | (Prototype, Tentative)
+                | (Tentative, Prototype) => {
+                    analyzer.errors.push(error!(
+                        self.span,
+                        "{} redeclared as a different kind of symbol",
+                        name
+                    ))
+                }
+            }
+        }
+    }
+
+    // The first declaration decides the linkage of a global
+    // Later extern declarations refer to a static global, but other declarations must agree
+    fn check_linkage(
+        &self,
+        analyzer: &mut SemanticAnalyzer,
+        old_storage_class: StorageClass,
+        storage_class: StorageClass,
+    ) {
+        let name = self.name.as_ref().unwrap();
+        match (old_storage_class, storage_class) {
+            (StorageClass::Static, StorageClass::Default) if !self.decl_type.is_function() => {
+                analyzer.errors.push(error!(
+                    self.span,
+                    "Non-static declaration of {} follows static declaration",
+                    name
+                ))
+            }
+            (StorageClass::Default | StorageClass::Extern, StorageClass::Static) => {
+                analyzer.errors.push(error!(
+                    self.span,
+                    "Static declaration of {} follows non-static declaration",
+                    name
+                ))
+            }
+            _ => (),
+        }
+    }
+}
+
+impl ExternalDeclaration {
+    // Tentative definitions can be completed later in the file, so their type is checked at its end
+    pub(super) fn check_tentative_type(&self, analyzer: &mut SemanticAnalyzer) {
+        let tentative = self.name.is_some()
+            && !self.ast_type.is_typedef()
+            && self.function_body.is_none()
+            && self.expression.is_none()
+            && !self.decl_type.is_function()
+            && self.ast_type.get_storage_class() != StorageClass::Extern;
+        if tentative && !self.decl_type.is_void() {
+            let usage = format!("Global {}", self.name.as_ref().unwrap());
+            analyzer.assert_complete(&self.span, &self.decl_type, &usage);
+        }
+    }
+}
+
+impl Analysis for ExternalDeclaration {
+    fn analyze(&mut self, analyzer: &mut SemanticAnalyzer) -> () {
+        if self.name.is_none() && self.ast_type.declare_tag(analyzer) {
+            return;
+        }
+        self.decl_type = self.ast_type.to_type(analyzer);
+        log::debug!("function name: {:?}", self.name);
+        if self.name.is_none() {
+            if !self.ast_type.is_type_declaration() {
+                analyzer
+                    .errors
+                    .push(error!(self.span, "Global definition without name found"));
+            }
+            return;
+        }
+        log::debug!("function return type: {}", self.decl_type);
+        //let name = self.name.as_ref().unwrap();
+
+        if self.ast_type.is_typedef() {
+            if self.function_body.is_some() || self.expression.is_some() {
+                analyzer
+                    .errors
+                    .push(error!(self.span, "Typedef cannot be initialized"));
+            }
+            let name = self.name.as_ref().unwrap();
+            analyzer.declare_typedef(&self.span, name, &self.decl_type);
+            return;
+        }
+
+        if let Some(_) = self.function_body {
+            if !self.decl_type.is_function() {
+                analyzer.errors.push(error!(
+                    self.span,
+                    "Function body defined for global variable"
+                ))
+            } else {
+                self.insert_or_update(analyzer, DeclarationType::Definition)
+            }
+        } else {
+            if self.decl_type.is_function() {
+                if self.decl_type.is_declaration() {
+                    self.insert_or_update(analyzer, DeclarationType::Declaration)
+                } else {
+                    self.insert_or_update(analyzer, DeclarationType::Prototype)
+                }
+            } else if let Some(_) = self.expression {
+                self.insert_or_update(analyzer, DeclarationType::Definition)
+            } else if self.ast_type.get_storage_class() == StorageClass::Extern {
+                self.insert_or_update(analyzer, DeclarationType::Declaration)
+            } else {
+                self.insert_or_update(analyzer, DeclarationType::Tentative)
+            }
+        }
+
+        if self.decl_type.is_function() {
+            let return_type: Type = self.decl_type.get_return_type().unwrap().into();
+            if return_type.is_array() {
+                analyzer
+                    .errors
+                    .push(error!(self.span, "Cannot return an array"));
+            }
+        } else {
+            if self.decl_type.is_void() {
+                analyzer
+                    .errors
+                    .push(error!(self.span, "Global variable cannot have type void"));
+            }
+        }
+
+        let function_body = self.function_body.is_some();
+        if let Some(statements) = &mut self.function_body {
+            analyzer.function_return_type = self.decl_type.get_qualified_return_type().unwrap();
+            analyzer.function_variadic = self.decl_type.is_variadic();
+
+            analyzer.enter_scope();
+            let arguments = self.ast_type.get_function_arguments(analyzer);
+            let mut first = true;
+            for (typ, name) in arguments {
+                if typ.is_void() {
+                    if !first {
+                        analyzer.errors.push(error!(
+                            self.span,
+                            "Expected 'void' to be the first argument"
+                        ))
+                    }
+                }
+
+                if let Some(name) = name {
+                    let symbol_type = typ.array_promotion();
+                    if symbol_type.is_void() {
+                        analyzer
+                            .errors
+                            .push(error!(self.span, "Void is not a named argument\n"))
+                    }
+
+                    if let Err(()) = analyzer.symbol_table.try_insert(
+                        &name,
+                        &symbol_type,
+                        DeclarationType::Definition,
+                        StorageClass::Default,
+                    ) {
+                        analyzer.errors.push(error!(
+                            self.span,
+                            "Argument {} with type {} already defined as type {}",
+                            &name,
+                            &symbol_type,
+                            &analyzer.symbol_table.get(&name).unwrap().symbol_type
+                        ));
+                    }
+                } else if function_body && !typ.is_void() {
+                    analyzer.errors.push(error!(
+                        self.span,
+                        "Function argument without name in {}",
+                        self.name.clone().unwrap_or_default()
+                    ));
+                }
+                first = false;
+            }
+
+            for statement in statements {
+                statement.analyze(analyzer);
+            }
+            analyzer.check_labels();
+            analyzer.leave_scope();
+        }
+
+        if let Some(initializer) = &mut self.expression {
+            let usage = format!("Global {}", self.name.as_ref().unwrap());
+            if analyzer.assert_complete(&self.span, &self.decl_type, &usage) {
+                initializer.analyze(analyzer, &self.decl_type, true);
+            }
+        }
+    }
+}
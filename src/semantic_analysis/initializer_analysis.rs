@@ -0,0 +1,393 @@
+use super::analysis::Analysis;
+use super::type_class::TypeClass;
+use super::SemanticAnalyzer;
+use crate::error;
+use crate::parser::ast::*;
+use crate::parser::{Type, TypeNode};
+use crate::span::Span;
+
+impl Initializer {
+    // Checks the initializer of an object, which must be constant for globals and statics
+    // Braced lists and strings initializing a char array are flattened into the values they set
+    pub(super) fn analyze(&mut self, analyzer: &mut SemanticAnalyzer, typ: &Type, constant: bool) {
+        self.analyze_expressions(analyzer);
+        let mut flattener = Flattener {
+            analyzer,
+            values: Vec::new(),
+            constant,
+        };
+        match self {
+            Initializer::Expression(expression) if is_string_initializer(typ, expression) => {
+                let span = expression.span.clone();
+                flattener.initialize_string(typ, Vec::new(), expression);
+                let expression = Initializer::Expression(expression.clone());
+                *self = Initializer::List(InitializerList {
+                    span,
+                    entries: vec![(Vec::new(), expression)],
+                    values: flattener.values,
+                });
+            }
+            Initializer::Expression(expression) if typ.is_array() => {
+                flattener.analyzer.errors.push(error!(
+                    expression.span,
+                    "An array must be initialized by a braced list"
+                ));
+            }
+            Initializer::Expression(expression) => {
+                check_value(flattener.analyzer, typ, expression, constant)
+            }
+            Initializer::List(list) => {
+                flattener.initialize_list(typ, Vec::new(), &list.entries, &list.span);
+                list.values = flattener.values;
+            }
+        }
+    }
+
+    // Array designators are evaluated here, as they are checked while flattening
+    fn analyze_expressions(&mut self, analyzer: &mut SemanticAnalyzer) {
+        match self {
+            Initializer::Expression(expression) => expression.analyze(analyzer),
+            Initializer::List(list) => {
+                for (designators, initializer) in &mut list.entries {
+                    for designator in designators {
+                        if let Designator::Index(index) = designator {
+                            index.analyze(analyzer);
+                            *index = index.clone().const_eval(analyzer);
+                        }
+                    }
+                    initializer.analyze_expressions(analyzer);
+                }
+            }
+        }
+    }
+}
+
+fn check_value(
+    analyzer: &mut SemanticAnalyzer,
+    typ: &Type,
+    expression: &mut Expression,
+    constant: bool,
+) {
+    let span = expression.span.clone();
+    if typ.is_struct() || expression.ast_type.is_struct() {
+        analyzer.assert_compatible(&span, &expression.ast_type, typ);
+    }
+    analyzer.assert_qualifiers_kept(&span, &expression.ast_type, typ);
+    if constant {
+        expression.force_initializer_eval(analyzer, typ);
+    }
+}
+
+fn is_string_initializer(typ: &Type, expression: &Expression) -> bool {
+    matches!(expression.variant, ExpressionVariant::CString(_))
+        && typ.is_array()
+        && typ.nodes.len() == 2
+        && matches!(typ.nodes[1], TypeNode::Char | TypeNode::UChar)
+}
+
+fn is_union(typ: &Type, analyzer: &SemanticAnalyzer) -> bool {
+    typ.is_struct() && analyzer.struct_table.structs[typ.get_struct_index()].is_union
+}
+
+type Entries = [(Vec<Designator>, Initializer)];
+
+// Keeps track of the values set while an initializer is flattened
+// Every value is stored with the indices of the members and elements leading to it
+// Without braces the initializer of a member takes as many entries as the member needs
+// After a designator the following entries continue with the members after the designated one
+struct Flattener<'a> {
+    analyzer: &'a mut SemanticAnalyzer,
+    values: Vec<(Vec<usize>, Expression)>,
+    constant: bool,
+}
+
+impl Flattener<'_> {
+    // Initializes an object with the entry at the index, which is moved past all entries used
+    fn initialize(&mut self, typ: &Type, path: Vec<usize>, entries: &Entries, index: &mut usize) {
+        match &entries[*index].1 {
+            Initializer::List(list) => {
+                *index += 1;
+                self.initialize_list(typ, path, &list.entries, &list.span);
+            }
+            Initializer::Expression(expression) if is_string_initializer(typ, expression) => {
+                *index += 1;
+                self.initialize_string(typ, path, expression);
+            }
+            Initializer::Expression(expression)
+                if typ.is_array() || (typ.is_struct() && !expression.ast_type.is_struct()) =>
+            {
+                self.initialize_elided(typ, path, 0, entries, index, true);
+            }
+            Initializer::Expression(expression) => {
+                *index += 1;
+                self.initialize_value(typ, path, expression);
+            }
+        }
+    }
+
+    fn initialize_list(&mut self, typ: &Type, path: Vec<usize>, entries: &Entries, span: &Span) {
+        self.clear(&path);
+        if !typ.is_array() && !typ.is_struct() {
+            if let Some((designators, _)) = entries.get(0) {
+                if !designators.is_empty() {
+                    self.analyzer
+                        .errors
+                        .push(error!(span, "Designator in the initializer of {}", typ));
+                }
+                self.initialize(typ, path, entries, &mut 0);
+            }
+            if entries.len() > 1 {
+                self.analyzer.errors.push(error!(
+                    span,
+                    "Excess elements in the initializer of {}", typ
+                ));
+            }
+            return;
+        }
+
+        // A char array can also be initialized by a string in braces
+        if let [(designators, Initializer::Expression(expression))] = entries {
+            if designators.is_empty() && is_string_initializer(typ, expression) {
+                self.initialize_string(typ, path, expression);
+                return;
+            }
+        }
+
+        let length = self.get_member_count(typ);
+        let mut index = 0;
+        let mut member = 0;
+        while index < entries.len() {
+            let designators = &entries[index].0;
+            if !designators.is_empty() {
+                match self.designate(typ, &path, designators, entries, &mut index, span) {
+                    Some(next) => member = next,
+                    None => index += 1,
+                }
+                continue;
+            }
+            if member >= length {
+                self.analyzer.errors.push(error!(
+                    span,
+                    "Excess elements in the initializer of {}", typ
+                ));
+                break;
+            }
+            if self.is_unnamed_member(typ, member) {
+                member += 1;
+                continue;
+            }
+            self.initialize_member(typ, &path, member, entries, &mut index);
+            member += 1;
+        }
+    }
+
+    // Initializes the members starting at the given member with the entries outside of braces
+    // This stops when all members are initialized, or when another designator is found
+    fn initialize_elided(
+        &mut self,
+        typ: &Type,
+        path: Vec<usize>,
+        start: usize,
+        entries: &Entries,
+        index: &mut usize,
+        designated: bool,
+    ) {
+        let mut designated = designated;
+        for member in start..self.get_member_count(typ) {
+            if self.is_unnamed_member(typ, member) {
+                continue;
+            }
+            match entries.get(*index) {
+                Some((designators, _)) if designated || designators.is_empty() => {
+                    designated = false;
+                    self.initialize_member(typ, &path, member, entries, index);
+                }
+                _ => break,
+            }
+        }
+    }
+
+    // Initializes the object the designators lead to with the entry at the index
+    // Gives the member of the current object that following entries continue with
+    fn designate(
+        &mut self,
+        typ: &Type,
+        path: &[usize],
+        designators: &[Designator],
+        entries: &Entries,
+        index: &mut usize,
+        span: &Span,
+    ) -> Option<usize> {
+        let member = self.get_designated_member(typ, &designators[0], span)?;
+        // Only one member of a union can be initialized
+        if is_union(typ, self.analyzer) {
+            self.clear(path);
+        }
+        let member_type = self.get_member_type(typ, member);
+        let member_path = [path, &[member]].concat();
+        if designators.len() == 1 {
+            self.initialize(&member_type, member_path, entries, index);
+        } else {
+            let next = self.designate(
+                &member_type,
+                &member_path,
+                &designators[1..],
+                entries,
+                index,
+                span,
+            )?;
+            self.initialize_elided(&member_type, member_path, next, entries, index, false);
+        }
+        Some(member + 1)
+    }
+
+    fn initialize_member(
+        &mut self,
+        typ: &Type,
+        path: &[usize],
+        member: usize,
+        entries: &Entries,
+        index: &mut usize,
+    ) {
+        let member_type = self.get_member_type(typ, member);
+        self.initialize(&member_type, [path, &[member]].concat(), entries, index);
+    }
+
+    // Every character of the string is set, the remainder including the terminator is zeroed
+    fn initialize_string(&mut self, typ: &Type, path: Vec<usize>, expression: &Expression) {
+        self.clear(&path);
+        let string = match &expression.variant {
+            ExpressionVariant::CString(string) => string,
+            _ => unreachable!(),
+        };
+        let length = self.get_member_count(typ);
+        if string.len() > length {
+            self.analyzer.errors.push(error!(
+                expression.span,
+                "String is too long to initialize {}", typ
+            ));
+        }
+
+        let element = typ.clone().deref().unqualified();
+        for (i, &c) in string.iter().take(length).enumerate() {
+            let value = match element.is_unsigned() {
+                true => c as i128,
+                false => c as i8 as i128,
+            };
+            let character = Expression {
+                span: expression.span.clone(),
+                ast_type: element.clone(),
+                variant: ExpressionVariant::ConstI(value),
+            };
+            self.values.push(([&path[..], &[i]].concat(), character));
+        }
+    }
+
+    fn initialize_value(&mut self, typ: &Type, path: Vec<usize>, expression: &Expression) {
+        self.clear(&path);
+        let mut expression = expression.clone();
+        check_value(self.analyzer, typ, &mut expression, self.constant);
+        self.values.push((path, expression));
+    }
+
+    // Removes the values set earlier in an object that is initialized again
+    fn clear(&mut self, path: &[usize]) {
+        self.values
+            .retain(|(other, _)| !other.starts_with(path) && !path.starts_with(other));
+    }
+
+    // Positional entries initialize all elements of an array, but only the first member of a union
+    fn get_member_count(&self, typ: &Type) -> usize {
+        match typ.nodes.get(0) {
+            Some(&TypeNode::Array(length)) => length,
+            Some(&TypeNode::Struct(index)) => {
+                let struct_type = &self.analyzer.struct_table.structs[index];
+                let count = struct_type.members.as_ref().map_or(0, Vec::len);
+                match struct_type.is_union {
+                    true => std::cmp::min(count, 1),
+                    false => count,
+                }
+            }
+            _ => 0,
+        }
+    }
+
+    // Unnamed bit-fields are skipped, as they are not members
+    fn is_unnamed_member(&self, typ: &Type, member: usize) -> bool {
+        match typ.nodes.get(0) {
+            Some(&TypeNode::Struct(index)) => {
+                let members = self.analyzer.struct_table.structs[index].members.as_ref();
+                members.map_or(false, |members| members[member].0.is_empty())
+            }
+            _ => false,
+        }
+    }
+
+    fn get_member_type(&self, typ: &Type, member: usize) -> Type {
+        if typ.is_array() {
+            typ.clone().deref()
+        } else {
+            let index = typ.get_struct_index();
+            let members = self.analyzer.struct_table.structs[index]
+                .members
+                .as_ref()
+                .unwrap();
+            members[member].1.clone()
+        }
+    }
+
+    fn get_designated_member(
+        &mut self,
+        typ: &Type,
+        designator: &Designator,
+        span: &Span,
+    ) -> Option<usize> {
+        match (designator, typ.nodes.get(0)) {
+            (Designator::Index(index), Some(&TypeNode::Array(length))) => {
+                if !index.is_constant() || !index.ast_type.is_in(TypeClass::Integer) {
+                    self.analyzer.errors.push(error!(
+                        index.span,
+                        "Array designator must be a constant integer"
+                    ));
+                    return None;
+                }
+                let value = index.get_const_value();
+                if value < 0 || value >= length as i128 {
+                    self.analyzer.errors.push(error!(
+                        index.span,
+                        "Array designator {} is outside of {}", value, typ
+                    ));
+                    return None;
+                }
+                Some(value as usize)
+            }
+            (Designator::Member(name), Some(&TypeNode::Struct(index))) => {
+                let mut members = self.analyzer.struct_table.structs[index]
+                    .members
+                    .iter()
+                    .flatten();
+                let member = members.position(|(member, _)| member == name);
+                if member.is_none() {
+                    self.analyzer
+                        .errors
+                        .push(error!(span, "{} has no member {}", typ, name));
+                }
+                member
+            }
+            (Designator::Index(index), _) => {
+                self.analyzer.errors.push(error!(
+                    index.span,
+                    "Array designator used in the initializer of {}", typ
+                ));
+                None
+            }
+            (Designator::Member(name), _) => {
+                self.analyzer.errors.push(error!(
+                    span,
+                    "Member designator .{} used in the initializer of {}", name, typ
+                ));
+                None
+            }
+        }
+    }
+}
@@ -1,199 +1,323 @@
-use super::analysis::Analysis;
-use super::SemanticAnalyzer;
-use crate::parser::ast::Statement;
-use crate::parser::r#type::DeclarationType;
-use crate::{error, warning};
-
-impl Statement {
-    fn check_for_declaration(&self, analyzer: &mut SemanticAnalyzer) -> () {
-        if let Statement::Declaration { span, .. } = self {
-            analyzer.errors.push(warning!(
-                span,
-                "A declaration can not be used as the body of a control flow statement"
-            ));
-        }
-    }
-}
-
-impl Analysis for Statement {
-    fn analyze(&mut self, analyzer: &mut SemanticAnalyzer) -> () {
-        use Statement::*;
-        match self {
-            Break { .. } | Continue { .. } | For { .. } | If { .. } | While { .. } => {
-                self.analyze_control_flow(analyzer)
-            }
-
-            Return {
-                span,
-                expression,
-                ast_type,
-            } => {
-                *ast_type = analyzer.function_return_type.clone();
-                if expression.is_none() {
-                    log::debug!("Void return");
-                    if !ast_type.is_void() {
-                        analyzer
-                            .errors
-                            .push(error!(span, "Return without value in none void function"));
-                    }
-                    return;
-                } else {
-                    log::debug!("Normal return");
-                }
-                let expression = expression.as_mut().unwrap();
-                expression.analyze(analyzer);
-                if !expression
-                    .ast_type
-                    .is_compatible(&analyzer.function_return_type)
-                {
-                    analyzer.errors.push(error!(
-                        span,
-                        "Incompatible return types: function returns {}, but {} is returned",
-                        analyzer.function_return_type,
-                        expression.ast_type
-                    ));
-                }
-            }
-
-            Expression {
-                span: _,
-                expression,
-            } => {
-                expression.analyze(analyzer);
-            }
-
-            Empty(_) => (),
-
-            Declaration {
-                span,
-                ident,
-                ast_type,
-                decl_type: symbol_type,
-                init,
-            } => {
-                *symbol_type = ast_type.to_type(analyzer);
-                if ident.is_none() && !ast_type.is_type_declaration() {
-                    analyzer
-                        .errors
-                        .push(error!(span, "Declaration with a name"));
-                }
-                if ident.is_none() {
-                    return;
-                }
-                if symbol_type.is_function() && init.is_some() {
-                    analyzer
-                        .errors
-                        .push(error!(span, "Function declaration with a value"));
-                }
-
-                let ident = ident.as_ref().unwrap();
-                log::trace!("Declaration of {} with type {}", ident, symbol_type);
-                if let Some(init) = init {
-                    init.analyze(analyzer);
-                }
-                if let Err(()) = analyzer.symbol_table.try_insert(
-                    ident,
-                    symbol_type,
-                    DeclarationType::Definition,
-                ) {
-                    analyzer.errors.push(error!(
-                        span,
-                        "Identifier {} with type {} already defined as type {}",
-                        ident,
-                        symbol_type,
-                        &analyzer.symbol_table.get(ident).unwrap().symbol_type
-                    ));
-                }
-            }
-
-            Compound {
-                span: _,
-                statements,
-            } => {
-                analyzer.enter_scope();
-                for stmt in statements {
-                    stmt.analyze(analyzer);
-                }
-                analyzer.leave_scope();
-            }
-        }
-    }
-}
-
-impl Statement {
-    fn analyze_control_flow(&mut self, analyzer: &mut SemanticAnalyzer) -> () {
-        use Statement::*;
-        match self {
-            Break { span } => {
-                if !analyzer.in_loop() {
-                    analyzer
-                        .errors
-                        .push(error!(span, "'break' must be in a loop")); //Or switch statement later
-                }
-            }
-
-            Continue { span } => {
-                if !analyzer.in_loop() {
-                    analyzer
-                        .errors
-                        .push(error!(span, "'continue' must be in a loop"))
-                }
-            }
-
-            For {
-                span: _,
-                init,
-                condition,
-                expression,
-                statement,
-            } => {
-                analyzer.enter_scope();
-                analyzer.enter_loop();
-
-                init.as_mut().map(|init| init.analyze(analyzer));
-                condition
-                    .as_mut()
-                    .map(|condition| condition.analyze(analyzer));
-                expression
-                    .as_mut()
-                    .map(|expression| expression.analyze(analyzer));
-
-                statement.analyze(analyzer);
-                statement.check_for_declaration(analyzer);
-
-                analyzer.leave_loop();
-                analyzer.leave_scope();
-            }
-
-            If {
-                span: _,
-                expression,
-                statement,
-                else_statement,
-            } => {
-                expression.analyze(analyzer);
-                statement.analyze(analyzer);
-                statement.check_for_declaration(analyzer);
-                if let Some(statement) = else_statement {
-                    statement.analyze(analyzer);
-                    statement.check_for_declaration(analyzer);
-                }
-            }
-
-            While {
-                span: _,
-                expression,
-                statement,
-                do_while: _,
-            } => {
-                analyzer.enter_loop();
-                expression.analyze(analyzer);
-                statement.analyze(analyzer);
-                statement.check_for_declaration(analyzer);
-                analyzer.leave_loop();
-            }
-
-            _ => unreachable!(),
-        }
-    }
-}
+use super::analysis::Analysis;
+use super::const_eval::wrap;
+use super::type_class::TypeClass;
+use super::type_promotion::TypePromotion;
+use super::SemanticAnalyzer;
+use crate::error;
+use crate::parser::ast::{self, ExpressionVariant, Statement};
+use crate::parser::r#type::{DeclarationType, StorageClass};
+
+impl Analysis for Statement {
+    fn analyze(&mut self, analyzer: &mut SemanticAnalyzer) -> () {
+        use Statement::*;
+        match self {
+            Break { .. }
+            | Continue { .. }
+            | For { .. }
+            | If { .. }
+            | While { .. }
+            | Switch { .. }
+            | Case { .. }
+            | Default { .. }
+            | Goto { .. }
+            | Label { .. } => self.analyze_control_flow(analyzer),
+
+            Return {
+                span,
+                expression,
+                ast_type,
+            } => {
+                *ast_type = analyzer.function_return_type.clone();
+                if expression.is_none() {
+                    log::debug!("Void return");
+                    if !ast_type.is_void() {
+                        analyzer
+                            .errors
+                            .push(error!(span, "Return without value in none void function"));
+                    }
+                    return;
+                } else {
+                    log::debug!("Normal return");
+                }
+                let expression = expression.as_mut().unwrap();
+                expression.analyze(analyzer);
+                if !expression
+                    .ast_type
+                    .is_compatible(&analyzer.function_return_type)
+                {
+                    analyzer.errors.push(error!(
+                        span,
+                        "Incompatible return types: function returns {}, but {} is returned",
+                        analyzer.function_return_type,
+                        expression.ast_type
+                    ));
+                }
+                let return_type = analyzer.function_return_type.clone();
+                analyzer.assert_qualifiers_kept(span, &expression.ast_type, &return_type);
+            }
+
+            Expression {
+                span: _,
+                expression,
+            } => {
+                expression.analyze(analyzer);
+            }
+
+            Empty(_) => (),
+
+            Declaration {
+                span,
+                ident,
+                ast_type,
+                decl_type: symbol_type,
+                init,
+            } => {
+                if ident.is_none() && ast_type.declare_tag(analyzer) {
+                    return;
+                }
+                *symbol_type = ast_type.to_type(analyzer);
+                if ident.is_none() && !ast_type.is_type_declaration() {
+                    analyzer
+                        .errors
+                        .push(error!(span, "Declaration with a name"));
+                }
+                if ident.is_none() {
+                    return;
+                }
+                if ast_type.is_typedef() {
+                    if init.is_some() {
+                        analyzer
+                            .errors
+                            .push(error!(span, "Typedef cannot be initialized"));
+                    }
+                    analyzer.declare_typedef(span, ident.as_ref().unwrap(), symbol_type);
+                    return;
+                }
+                if symbol_type.is_function() && init.is_some() {
+                    analyzer
+                        .errors
+                        .push(error!(span, "Function declaration with a value"));
+                }
+
+                let ident = ident.as_mut().unwrap();
+                log::trace!("Declaration of {} with type {}", ident, symbol_type);
+
+                // Function declarations in a block always refer to a global function
+                let storage_class = match ast_type.get_storage_class() {
+                    StorageClass::Default if symbol_type.is_function() => StorageClass::Extern,
+                    storage_class => storage_class,
+                };
+                if symbol_type.is_function() && storage_class == StorageClass::Static {
+                    analyzer.errors.push(error!(
+                        span,
+                        "Function {} cannot be declared static in a block",
+                        ident
+                    ));
+                }
+                if storage_class == StorageClass::Extern
+                    && init.is_some()
+                    && !symbol_type.is_function()
+                {
+                    analyzer.errors.push(error!(
+                        span,
+                        "Extern variable {} cannot be initialized in a block",
+                        ident
+                    ));
+                }
+
+                // Objects are only created for definitions, which need the size of their type
+                let complete = symbol_type.is_function()
+                    || storage_class == StorageClass::Extern
+                    || analyzer.assert_complete(span, symbol_type, &format!("Variable {}", ident));
+                if let (Some(init), true) = (init, complete) {
+                    let constant = storage_class == StorageClass::Static;
+                    init.analyze(analyzer, symbol_type, constant);
+                }
+                let declaration_type = match storage_class {
+                    StorageClass::Extern => DeclarationType::Declaration,
+                    _ => DeclarationType::Definition,
+                };
+                if let Err(()) = analyzer.symbol_table.try_insert(
+                    ident,
+                    symbol_type,
+                    declaration_type,
+                    storage_class,
+                ) {
+                    analyzer.errors.push(error!(
+                        span,
+                        "Identifier {} with type {} already defined as type {}",
+                        ident,
+                        symbol_type,
+                        &analyzer.symbol_table.get(ident).unwrap().symbol_type
+                    ));
+                }
+
+                // Block scope statics are stored in a uniquely named global
+                if storage_class == StorageClass::Static {
+                    let number = analyzer.symbol_table.get(ident).unwrap().number;
+                    *ident = format!("{}.{}", ident, number);
+                }
+            }
+
+            Compound {
+                span: _,
+                statements,
+            } => {
+                analyzer.enter_scope();
+                for stmt in statements {
+                    stmt.analyze(analyzer);
+                }
+                analyzer.leave_scope();
+            }
+        }
+    }
+}
+
+impl Statement {
+    fn analyze_control_flow(&mut self, analyzer: &mut SemanticAnalyzer) -> () {
+        use Statement::*;
+        match self {
+            Break { span } => {
+                if !analyzer.in_loop() && !analyzer.in_switch() {
+                    analyzer
+                        .errors
+                        .push(error!(span, "'break' must be in a loop or switch statement"));
+                }
+            }
+
+            Continue { span } => {
+                if !analyzer.in_loop() {
+                    analyzer
+                        .errors
+                        .push(error!(span, "'continue' must be in a loop"))
+                }
+            }
+
+            For {
+                span: _,
+                init,
+                condition,
+                expression,
+                statement,
+            } => {
+                analyzer.enter_scope();
+                analyzer.enter_loop();
+
+                for init in init {
+                    init.analyze(analyzer);
+                }
+                condition
+                    .as_mut()
+                    .map(|condition| condition.analyze(analyzer));
+                expression
+                    .as_mut()
+                    .map(|expression| expression.analyze(analyzer));
+
+                statement.analyze(analyzer);
+
+                analyzer.leave_loop();
+                analyzer.leave_scope();
+            }
+
+            If {
+                span: _,
+                expression,
+                statement,
+                else_statement,
+            } => {
+                expression.analyze(analyzer);
+                statement.analyze(analyzer);
+                if let Some(statement) = else_statement {
+                    statement.analyze(analyzer);
+                }
+            }
+
+            While {
+                span: _,
+                expression,
+                statement,
+                do_while: _,
+            } => {
+                analyzer.enter_loop();
+                expression.analyze(analyzer);
+                statement.analyze(analyzer);
+                analyzer.leave_loop();
+            }
+
+            Switch {
+                span,
+                expression,
+                statement,
+            } => {
+                expression.analyze(analyzer);
+                analyzer.assert_in(span, &expression.ast_type, TypeClass::Integer);
+                analyzer.enter_switch(expression.ast_type.promote());
+                statement.analyze(analyzer);
+                analyzer.leave_switch();
+            }
+
+            Case {
+                span,
+                expression,
+                statement,
+            } => {
+                expression.analyze(analyzer);
+                let constant = std::mem::replace(expression, ast::Expression::default(span));
+                *expression = constant.const_eval(analyzer);
+
+                if !analyzer.in_switch() {
+                    analyzer
+                        .errors
+                        .push(error!(span, "'case' must be in a switch statement"));
+                } else if !expression.is_constant()
+                    || !expression.ast_type.is_in(TypeClass::Integer)
+                {
+                    analyzer
+                        .errors
+                        .push(error!(span, "Case label must be an integer constant"));
+                } else {
+                    // The case value is converted to the promoted type of the switch expression
+                    let typ = analyzer.switch_cases.last().unwrap().typ.clone();
+                    let value = wrap(expression.get_const_value(), &typ, analyzer);
+                    let cases = analyzer.switch_cases.last_mut().unwrap();
+                    if !cases.values.insert(value) {
+                        analyzer
+                            .errors
+                            .push(error!(span, "Duplicate case value {}", value));
+                    }
+                    expression.ast_type = typ;
+                    expression.variant = ExpressionVariant::ConstI(value);
+                }
+
+                statement.analyze(analyzer);
+            }
+
+            Default { span, statement } => {
+                match analyzer.switch_cases.last_mut() {
+                    None => analyzer
+                        .errors
+                        .push(error!(span, "'default' must be in a switch statement")),
+                    Some(cases) if cases.default => analyzer
+                        .errors
+                        .push(error!(span, "Multiple default labels in one switch")),
+                    Some(cases) => cases.default = true,
+                }
+
+                statement.analyze(analyzer);
+            }
+
+            Goto { span, label } => analyzer.gotos.push((span.clone(), label.clone())),
+
+            Label {
+                span,
+                label,
+                statement,
+            } => {
+                analyzer.declare_label(span, label);
+                statement.analyze(analyzer);
+            }
+
+            _ => unreachable!(),
+        }
+    }
+}
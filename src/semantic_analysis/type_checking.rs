@@ -1,347 +1,668 @@
-use super::SemanticAnalyzer;
-use crate::error;
-use crate::parser::ast::{ASTStruct, ASTType, ASTTypeNode, Expression};
-use crate::parser::r#type::{StructType, Type, TypeNode};
-use crate::semantic_analysis::analysis::Analysis;
-use crate::semantic_analysis::type_class::TypeClass;
-use crate::span::Span;
-
-pub fn check_arguments_function(
-    analyzer: &mut SemanticAnalyzer,
-    span: &Span,
-    function_type: &Type,
-    arguments: &Vec<Expression>,
-) {
-    if !Type::is_callable(function_type) {
-        analyzer
-            .errors
-            .push(error!(span, "Cannot call '{}'", function_type));
-        return;
-    }
-    let argument_type = Type::get_function_arguments(function_type).unwrap();
-
-    // Functions that do  have unspecified arguments are per definition correctly called
-    if argument_type.len() == 0 {
-        return;
-    }
-
-    // Functions can have a special void argument, which means that function actually has no arguments
-    if argument_type[0].is_void() {
-        if arguments.len() != 0 {
-            analyzer.errors.push(error!(
-                span,
-                "The amount of arguments in the function(0) does not match the amount supplied({})",
-                arguments.len()
-            ))
-        }
-        return;
-    }
-
-    if argument_type.len() != arguments.len() {
-        analyzer.errors.push(error!(
-            span,
-            "The amount of arguments in the function({}) does not match the amount supplied({})",
-            argument_type.len(),
-            arguments.len()
-        ))
-    }
-    for (argument, argument_type) in arguments.iter().zip(argument_type.iter()) {
-        analyzer.assert_compatible(span, &argument.ast_type, argument_type)
-    }
-}
-
-pub fn compare_arguments(
-    analyzer: &mut SemanticAnalyzer,
-    span: &Span,
-    name: &String,
-    lhs: &Type,
-    rhs: &Type,
-) {
-    if lhs.get_function_arguments() != rhs.get_function_arguments() {
-        analyzer.errors.push(error!(
-            span,
-            "Global {} previously defined with {} is redefined with {}",
-            name,
-            lhs.get_function_arguments()
-                .map(|a| format!("function arguments '{:?}'", a))
-                .unwrap_or("no function arguments".to_string()),
-            rhs.get_function_arguments()
-                .map(|a| format!("function arguments '{:?}'", a))
-                .unwrap_or("no function arguments".to_string())
-        ));
-    }
-}
-
-pub fn compare_return_types(
-    analyzer: &mut SemanticAnalyzer,
-    span: &Span,
-    name: &String,
-    lhs: &Type,
-    rhs: &Type,
-) {
-    if lhs.get_return_type() != rhs.get_return_type() {
-        analyzer.errors.push(error!(
-            span,
-            "Global {} previously defined as '{}' is redefined as '{}'",
-            name,
-            lhs.get_return_type()
-                .map(|t| t.into())
-                .unwrap_or(Type::empty()),
-            rhs.get_return_type()
-                .map(|t| t.into())
-                .unwrap_or(Type::empty())
-        ));
-    }
-}
-
-pub fn check_member_type(
-    analyzer: &mut SemanticAnalyzer,
-    span: &Span,
-    ast_type: &Type,
-    id: &String,
-    indirect: bool,
-) -> (Type, u16) {
-    //Check and account for possible indirection
-    let struct_type = if indirect {
-        let ast_type = ast_type.array_promotion();
-        analyzer.assert_in(span, &ast_type, TypeClass::Pointer);
-        ast_type.deref()
-    } else {
-        ast_type.clone()
-    };
-
-    //Check if we are even processing a struct
-    if !struct_type.is_struct() {
-        analyzer
-            .errors
-            .push(error!(span, "Expected a struct, but found {}", ast_type));
-        return (Type::error(), 0);
-    }
-
-    // Get the definition of the type and check if it is qualified(fully defined)
-    let index = struct_type.get_struct_index();
-    let struct_def = &(&analyzer.struct_table)[index];
-    if !struct_def.is_qualified() {
-        analyzer.errors.push(error!(
-            span,
-            "{} is unqualifed and cannot be accesed by {}", struct_type, id
-        ));
-        return (Type::error(), 0);
-    }
-
-    // Check if the id matches any known members
-    // (Iteration is probably faster then hashing here as n is generally small)
-    for ((member, typ), i) in struct_def.members.as_ref().unwrap().iter().zip(0..) {
-        if member == id {
-            return (typ.clone(), i);
-        }
-    }
-
-    // If we do not match any members then we have reached an error
-    analyzer.errors.push(error!(
-        span,
-        "{} does not have a member named {}", struct_type, id
-    ));
-    (Type::error(), 0)
-}
-
-impl ASTType {
-    pub fn to_type(&mut self, analyzer: &mut SemanticAnalyzer) -> Type {
-        use super::ASTTypeNode::*;
-        use TypeNode::*;
-        type AST = ASTTypeNode;
-        let mut declarator = Vec::new();
-        let mut type_specifiers = Vec::new();
-        for entry in &mut self.list {
-            match entry {
-                Simple(t @ (Char | Int | Long | Short | Void)) => {
-                    type_specifiers.push(t.clone());
-                }
-                Simple(Pointer) => declarator.push(Pointer),
-                Simple(_) => unreachable!(),
-                AST::Name(_) => (),
-                AST::Struct(s) => {
-                    type_specifiers.push(s.to_type(&self.span, analyzer));
-                }
-                AST::Function(arguments) => {
-                    let (arguments, _) = ASTType::tranform_function_arguments(arguments, analyzer);
-                    analyzer.assert_function_arguments(&self.span, &arguments);
-                    declarator.push(TypeNode::Function(Box::new(arguments)));
-                }
-                AST::Array(exp) => {
-                    exp.analyze(analyzer);
-                    exp.force_const_eval(analyzer);
-                    let value = exp.get_const_value();
-                    if value.is_negative() {
-                        analyzer
-                            .errors
-                            .push(error!(self.span, "Size of array must be positive"));
-                    }
-                    let value = std::cmp::max(value, 0);
-                    declarator.push(TypeNode::Array(value as usize));
-                }
-            }
-        }
-        let base_type = analyzer.check_declaration_specifiers(&self.span, &type_specifiers);
-        let declarator: Type = declarator.into();
-        let typ = Type::combine(base_type, declarator);
-        typ
-    }
-    pub fn get_function_arguments(
-        &mut self,
-        analyzer: &mut SemanticAnalyzer,
-    ) -> Vec<(Type, Option<String>)> {
-        type AST = ASTTypeNode;
-        let mut arguments = None;
-        log::debug!("get function arguments of {:?}", self);
-        for entry in &mut self.list {
-            match entry {
-                AST::Name(_) => continue,
-                AST::Simple(TypeNode::Pointer) => continue,
-                AST::Function(args) => {
-                    arguments = Some(args);
-                    break;
-                }
-                t => {
-                    log::error!("unexpected {:?}", t);
-                    unreachable!()
-                }
-            }
-        }
-        let arguments = arguments.unwrap();
-        let (types, names) = ASTType::tranform_function_arguments(arguments, analyzer);
-        types.into_iter().zip(names.into_iter()).collect()
-    }
-    fn tranform_function_arguments(
-        arguments: &mut Vec<ASTType>,
-        analyzer: &mut SemanticAnalyzer,
-    ) -> (Vec<Type>, Vec<Option<String>>) {
-        arguments
-            .iter_mut()
-            .map(|ast| {
-                let name = ast.get_name();
-                let typ = ast.to_type(analyzer);
-                (typ, name)
-            })
-            .unzip()
-    }
-}
-
-impl ASTStruct {
-    fn to_type(&mut self, span: &Span, analyzer: &mut SemanticAnalyzer) -> TypeNode {
-        let name = self.name.as_ref();
-        if self.members.is_none() {
-            let name = name.unwrap();
-            if analyzer.struct_table.contains(name) {
-                let index = analyzer.struct_table.get_index(name).unwrap();
-                return TypeNode::Struct(index);
-            } else {
-                let index = analyzer.struct_table.try_insert(Some(name)).unwrap();
-                return TypeNode::Struct(index);
-            }
-        }
-
-        // If a struct is already in the table and already qualified(definied) we raise an error
-        if name.is_some()
-            && analyzer.struct_table.contains(name.unwrap())
-            && analyzer
-                .struct_table
-                .get(name.unwrap())
-                .unwrap()
-                .is_qualified()
-        {
-            analyzer
-                .errors
-                .push(error!(span, "Struct {} redefined", name.unwrap()));
-            return TypeNode::error();
-        }
-
-        // If the struct is not yet defined, insert it
-        // Otherwise we can get the index from the struct table
-        let index = analyzer
-            .struct_table
-            .try_insert(name)
-            .or_else(|_| -> Result<usize, ()> {
-                Ok(analyzer.struct_table.get_index(name.unwrap()).unwrap())
-            })
-            .unwrap();
-
-        let ast_members = self.members.as_mut().unwrap();
-        let mut members = Vec::new();
-        for member in ast_members {
-            let name = member.get_name();
-            if name.is_none() {
-                analyzer
-                    .errors
-                    .push(error!(span, "Missing member name in struct definition"));
-                continue;
-            }
-
-            let name = name.unwrap();
-            let typ = member.to_type(analyzer);
-            if !typ.is_qualified(&analyzer.struct_table) {
-                analyzer.errors.push(error!(
-                    span,
-                    "Missing member {} is not qualified in struct definition", name
-                ));
-                continue;
-            }
-            members.push((name, typ))
-        }
-
-        let entry = StructType {
-            name: self.name.clone(),
-            members: Some(members),
-        };
-        analyzer
-            .struct_table
-            .qualify(&analyzer.type_info, index, entry);
-
-        TypeNode::Struct(index)
-    }
-}
-
-impl SemanticAnalyzer {
-    fn check_declaration_specifiers(&mut self, span: &Span, typ: &[TypeNode]) -> Type {
-        use TypeNode::*;
-        let mut type_specifier = None;
-        let mut int_seen = false;
-        for node in typ {
-            match node {
-                TypeNode::Int => {
-                    if int_seen {
-                        self.invalid_type(span, &typ);
-                    } else if let Some(TypeNode::Long | TypeNode::Short) = type_specifier {
-                        int_seen = true;
-                    } else if type_specifier.is_none() {
-                        type_specifier = Some(Int);
-                        int_seen = true;
-                    } else {
-                        self.invalid_type(span, typ);
-                    }
-                }
-                t @ (TypeNode::Long | TypeNode::Short) => {
-                    if let Some(TypeNode::Int) | None = type_specifier {
-                        type_specifier = Some(t.clone());
-                    } else {
-                        self.invalid_type(span, &typ);
-                    }
-                }
-                TypeNode::Struct(..) | TypeNode::Char | TypeNode::Void => {
-                    if let Some(_) = type_specifier {
-                        self.invalid_type(span, &typ);
-                    }
-                    type_specifier = Some(node.clone());
-                }
-
-                TypeNode::Function(..) | TypeNode::Array(..) | TypeNode::Pointer => unreachable!(),
-            }
-        }
-        vec![type_specifier.expect("failure to check for type specifer")].into()
-    }
-    fn invalid_type(&mut self, span: &Span, typ: &[TypeNode]) {
-        let typ: Type = typ.into();
-        self.errors
-            .push(error!(span, "Invalid type specifiers provided: {}", typ));
-    }
-}
+use super::SemanticAnalyzer;
+use crate::error;
+use crate::eval::evaluation_context::EvaluateSize;
+use crate::parser::ast::{ASTEnum, ASTStruct, ASTType, ASTTypeNode, Expression};
+use crate::parser::r#type::{
+    BitField, DeclarationType, EnumType, Qualifiers, StorageClass, StructType, Type, TypeNode,
+};
+use crate::semantic_analysis::analysis::Analysis;
+use crate::semantic_analysis::type_class::TypeClass;
+use crate::span::Span;
+
+pub fn check_arguments_function(
+    analyzer: &mut SemanticAnalyzer,
+    span: &Span,
+    function_type: &Type,
+    arguments: &Vec<Expression>,
+) {
+    if !Type::is_callable(function_type) {
+        analyzer
+            .errors
+            .push(error!(span, "Cannot call '{}'", function_type));
+        return;
+    }
+    let argument_type = Type::get_function_arguments(function_type).unwrap();
+
+    // Functions that do  have unspecified arguments are per definition correctly called
+    if argument_type.len() == 0 {
+        return;
+    }
+
+    // Functions can have a special void argument, which means that function actually has no arguments
+    if argument_type[0].is_void() {
+        if arguments.len() != 0 {
+            analyzer.errors.push(error!(
+                span,
+                "The amount of arguments in the function(0) does not match the amount supplied({})",
+                arguments.len()
+            ))
+        }
+        return;
+    }
+
+    // Variadic functions take any amount of arguments after the named arguments
+    if function_type.is_variadic() {
+        if arguments.len() < argument_type.len() {
+            analyzer.errors.push(error!(
+                span,
+                "The function takes at least {} arguments, but only {} were supplied",
+                argument_type.len(),
+                arguments.len()
+            ))
+        }
+    } else if argument_type.len() != arguments.len() {
+        analyzer.errors.push(error!(
+            span,
+            "The amount of arguments in the function({}) does not match the amount supplied({})",
+            argument_type.len(),
+            arguments.len()
+        ))
+    }
+    for (argument, argument_type) in arguments.iter().zip(argument_type.iter()) {
+        let typ = argument.ast_type.array_promotion();
+        analyzer.assert_compatible(span, &typ, argument_type);
+        analyzer.assert_qualifiers_kept(span, &argument.ast_type, argument_type);
+    }
+}
+
+pub fn compare_arguments(
+    analyzer: &mut SemanticAnalyzer,
+    span: &Span,
+    name: &String,
+    lhs: &Type,
+    rhs: &Type,
+) {
+    if lhs.get_function_arguments() != rhs.get_function_arguments()
+        || lhs.is_variadic() != rhs.is_variadic()
+    {
+        analyzer.errors.push(error!(
+            span,
+            "Global {} previously defined with {} is redefined with {}",
+            name,
+            lhs.get_function_arguments()
+                .map(|a| format!("function arguments '{:?}'", a))
+                .unwrap_or("no function arguments".to_string()),
+            rhs.get_function_arguments()
+                .map(|a| format!("function arguments '{:?}'", a))
+                .unwrap_or("no function arguments".to_string())
+        ));
+    }
+}
+
+pub fn compare_return_types(
+    analyzer: &mut SemanticAnalyzer,
+    span: &Span,
+    name: &String,
+    lhs: &Type,
+    rhs: &Type,
+) {
+    if lhs.get_return_type() != rhs.get_return_type() {
+        analyzer.errors.push(error!(
+            span,
+            "Global {} previously defined as '{}' is redefined as '{}'",
+            name,
+            lhs.get_return_type()
+                .map(|t| t.into())
+                .unwrap_or(Type::empty()),
+            rhs.get_return_type()
+                .map(|t| t.into())
+                .unwrap_or(Type::empty())
+        ));
+    }
+}
+
+pub fn check_member_type(
+    analyzer: &mut SemanticAnalyzer,
+    span: &Span,
+    ast_type: &Type,
+    id: &String,
+    indirect: bool,
+) -> (Type, u16) {
+    //Check and account for possible indirection
+    let struct_type = if indirect {
+        let ast_type = ast_type.array_promotion();
+        analyzer.assert_in(span, &ast_type, TypeClass::Pointer);
+        ast_type.deref()
+    } else {
+        ast_type.clone()
+    };
+
+    //Check if we are even processing a struct
+    if !struct_type.is_struct() {
+        analyzer
+            .errors
+            .push(error!(span, "Expected a struct, but found {}", ast_type));
+        return (Type::error(), 0);
+    }
+
+    // Get the definition of the type and check if it is qualified(fully defined)
+    let index = struct_type.get_struct_index();
+    let struct_def = &(&analyzer.struct_table)[index];
+    if !struct_def.is_qualified() {
+        analyzer.errors.push(error!(
+            span,
+            "Cannot access member {} of incomplete type {}", id, struct_type
+        ));
+        return (Type::error(), 0);
+    }
+
+    // Check if the id matches any known members
+    // (Iteration is probably faster then hashing here as n is generally small)
+    for ((member, typ), i) in struct_def.members.as_ref().unwrap().iter().zip(0..) {
+        if member == id {
+            // Bit-fields narrower than an int are used as an int, as all their values fit in it
+            let int_width = 8 * analyzer.sizeof(&Type::int());
+            let typ = match struct_def.bit_fields[i as usize] {
+                Some(bit_field) if bit_field.width < int_width => {
+                    Type::int().qualify(0, typ.get_qualifiers())
+                }
+                _ => typ.clone(),
+            };
+            // Members of a qualified struct have the same qualifiers
+            let typ = typ.qualify(0, struct_type.get_qualifiers());
+            return (typ, i);
+        }
+    }
+
+    // If we do not match any members then we have reached an error
+    analyzer.errors.push(error!(
+        span,
+        "{} does not have a member named {}", struct_type, id
+    ));
+    (Type::error(), 0)
+}
+
+impl ASTType {
+    pub fn to_type(&mut self, analyzer: &mut SemanticAnalyzer) -> Type {
+        use super::ASTTypeNode::*;
+        use TypeNode::*;
+        type AST = ASTTypeNode;
+        let mut declarator = Vec::new();
+        let mut type_specifiers = Vec::new();
+        let mut typedef_type = None;
+        let mut storage_classes = 0;
+        // Qualifiers are collected until the pointer they apply to is found
+        // Any remaining qualifiers apply to the base type
+        let mut qualifiers = Qualifiers::default();
+        let mut pointer_qualifiers = Vec::new();
+        for entry in &mut self.list {
+            match entry {
+                Simple(
+                    t @ (Char | Int | Long | Short | Float | Double | Signed | Unsigned | Void),
+                ) => {
+                    type_specifiers.push(t.clone());
+                }
+                Simple(Pointer) => {
+                    pointer_qualifiers.push((declarator.len(), qualifiers));
+                    qualifiers = Qualifiers::default();
+                    declarator.push(Pointer);
+                }
+                Simple(_) => unreachable!(),
+                AST::Name(_) => (),
+                AST::Typedef | AST::Static | AST::Extern => storage_classes += 1,
+                AST::Const => qualifiers.is_const = true,
+                AST::Volatile => qualifiers.is_volatile = true,
+                AST::TypedefName(name) => {
+                    typedef_type = Some(analyzer.get_typedef(&self.span, name));
+                }
+                AST::Struct(s) => {
+                    type_specifiers.push(s.to_type(&self.span, analyzer));
+                }
+                AST::Enum(e) => {
+                    type_specifiers.push(e.to_type(&self.span, analyzer));
+                }
+                AST::Function(arguments, variadic) => {
+                    if arguments.iter().any(ASTType::has_storage_class) {
+                        analyzer.errors.push(error!(
+                            self.span,
+                            "Storage class specified for function argument"
+                        ));
+                    }
+                    let (arguments, _) = ASTType::tranform_function_arguments(arguments, analyzer);
+                    analyzer.assert_function_arguments(&self.span, &arguments);
+                    declarator.push(TypeNode::Function(Box::new(arguments), *variadic));
+                }
+                AST::Array(exp) => {
+                    exp.analyze(analyzer);
+                    exp.force_const_eval(analyzer);
+                    analyzer.assert_in(&self.span, &exp.ast_type, TypeClass::Integer);
+                    let value = exp.get_const_value();
+                    if value.is_negative() {
+                        analyzer
+                            .errors
+                            .push(error!(self.span, "Size of array must be positive"));
+                    }
+                    let value = std::cmp::max(value, 0);
+                    declarator.push(TypeNode::Array(value as usize));
+                }
+            }
+        }
+        if storage_classes > 1 {
+            analyzer
+                .errors
+                .push(error!(self.span, "Multiple storage classes in declaration"));
+        }
+        let base_type = match typedef_type {
+            Some(typ) if type_specifiers.is_empty() => typ,
+            Some(typ) => {
+                analyzer.invalid_type(&self.span, &type_specifiers);
+                typ
+            }
+            None => analyzer.check_declaration_specifiers(&self.span, &type_specifiers),
+        };
+        let base_type = base_type.qualify(0, qualifiers);
+        let declarator: Type = declarator.into();
+        let declarator = pointer_qualifiers
+            .into_iter()
+            .fold(declarator, |typ, (level, qualifiers)| {
+                typ.qualify(level, qualifiers)
+            });
+        let typ = Type::combine(base_type, declarator);
+        typ
+    }
+    pub fn get_function_arguments(
+        &mut self,
+        analyzer: &mut SemanticAnalyzer,
+    ) -> Vec<(Type, Option<String>)> {
+        type AST = ASTTypeNode;
+        let mut arguments = None;
+        log::debug!("get function arguments of {:?}", self);
+        for entry in &mut self.list {
+            match entry {
+                AST::Name(_) => continue,
+                AST::Simple(TypeNode::Pointer) | AST::Const | AST::Volatile => continue,
+                AST::Function(args, _) => {
+                    arguments = Some(args);
+                    break;
+                }
+                t => {
+                    log::error!("unexpected {:?}", t);
+                    unreachable!()
+                }
+            }
+        }
+        let arguments = arguments.unwrap();
+        let (types, names) = ASTType::tranform_function_arguments(arguments, analyzer);
+        types.into_iter().zip(names.into_iter()).collect()
+    }
+    fn tranform_function_arguments(
+        arguments: &mut Vec<ASTType>,
+        analyzer: &mut SemanticAnalyzer,
+    ) -> (Vec<Type>, Vec<Option<String>>) {
+        arguments
+            .iter_mut()
+            .map(|ast| {
+                let name = ast.get_name();
+                // Arguments of function type are adjusted to pointers to that function
+                let typ = match ast.to_type(analyzer) {
+                    typ if typ.is_function() => typ.array_promotion(),
+                    typ => typ,
+                };
+                (typ, name)
+            })
+            .unzip()
+    }
+
+    // A declaration of only a struct tag declares it in the current scope,
+    // which shadows a struct with the same tag from an outer scope
+    // Returns whether the declaration was such a tag declaration
+    pub fn declare_tag(&self, analyzer: &mut SemanticAnalyzer) -> bool {
+        let ast_struct = match self.list.as_slice() {
+            [ASTTypeNode::Struct(s)] if s.members.is_none() && s.name.is_some() => s,
+            _ => return false,
+        };
+        let name = ast_struct.name.as_ref().unwrap();
+        match analyzer.struct_table.get_index_in_scope(name) {
+            Some(index) => ast_struct.check_kind(&self.span, analyzer, index),
+            None => {
+                let _ = analyzer
+                    .struct_table
+                    .try_insert(Some(name), ast_struct.is_union);
+            }
+        }
+        true
+    }
+}
+
+impl ASTStruct {
+    fn to_type(&mut self, span: &Span, analyzer: &mut SemanticAnalyzer) -> TypeNode {
+        let name = self.name.as_ref();
+        if self.members.is_none() {
+            // Only declarators sharing the specifiers of an anonymous definition refer to it
+            let name = match name {
+                Some(name) => name,
+                None => {
+                    return match self.index.get() {
+                        Some(index) => TypeNode::Struct(index),
+                        None => TypeNode::error(),
+                    }
+                }
+            };
+            if analyzer.struct_table.contains(name) {
+                let index = analyzer.struct_table.get_index(name).unwrap();
+                self.check_kind(span, analyzer, index);
+                return TypeNode::Struct(index);
+            } else {
+                let index = analyzer
+                    .struct_table
+                    .try_insert(Some(name), self.is_union)
+                    .unwrap();
+                return TypeNode::Struct(index);
+            }
+        }
+
+        // If a struct is already qualified(definied) in the current scope we raise an error
+        // A struct with the same tag from an outer scope is shadowed instead
+        let table = &analyzer.struct_table;
+        let in_scope = name.and_then(|name| table.get_index_in_scope(name));
+        if in_scope.map_or(false, |index| table.structs[index].is_qualified()) {
+            analyzer
+                .errors
+                .push(error!(span, "Struct {} redefined", name.unwrap()));
+            return TypeNode::error();
+        }
+
+        // If the struct is not yet declared in this scope, insert it
+        // Otherwise the incomplete struct from the struct table is completed
+        let index = analyzer
+            .struct_table
+            .try_insert(name, self.is_union)
+            .or_else(|_| -> Result<usize, ()> {
+                Ok(analyzer.struct_table.get_index(name.unwrap()).unwrap())
+            })
+            .unwrap();
+        self.check_kind(span, analyzer, index);
+        self.index.set(Some(index));
+
+        let ast_members = self.members.as_mut().unwrap();
+        let mut members = Vec::new();
+        let mut bit_fields = Vec::new();
+        for (member, width) in ast_members {
+            let name = member.get_name();
+            if name.is_none() && width.is_none() {
+                analyzer
+                    .errors
+                    .push(error!(span, "Missing member name in struct definition"));
+                continue;
+            }
+
+            // Unnamed bit-fields only take up space and cannot be accessed
+            let name = name.unwrap_or_default();
+            if member.has_storage_class() {
+                analyzer
+                    .errors
+                    .push(error!(span, "Storage class specified for member {}", name));
+            }
+            let typ = member.to_type(analyzer);
+            if !typ.is_qualified(&analyzer.struct_table) {
+                analyzer.errors.push(error!(
+                    span,
+                    "Missing member {} is not qualified in struct definition", name
+                ));
+                continue;
+            }
+            let bit_field = match width {
+                Some(width) => match check_bit_field(analyzer, span, &name, &typ, width) {
+                    Some(bit_field) => Some(bit_field),
+                    None => continue,
+                },
+                None => None,
+            };
+            members.push((name, typ));
+            bit_fields.push(bit_field);
+        }
+
+        let entry = StructType {
+            name: self.name.clone(),
+            members: Some(members),
+            bit_fields,
+            is_union: self.is_union,
+        };
+        analyzer
+            .struct_table
+            .qualify(&analyzer.type_info, index, entry);
+
+        TypeNode::Struct(index)
+    }
+
+    // Structs and unions share their tags, so a tag cannot be used for both
+    fn check_kind(&self, span: &Span, analyzer: &mut SemanticAnalyzer, index: usize) {
+        if analyzer.struct_table.structs[index].is_union != self.is_union {
+            analyzer.errors.push(error!(
+                span,
+                "{} defined as the wrong kind of tag",
+                self.name.as_ref().unwrap()
+            ));
+        }
+    }
+}
+
+// The width of a bit-field is a constant that fits in its integer type
+// Only unnamed bit-fields can have a width of zero, which ends the current storage unit
+fn check_bit_field(
+    analyzer: &mut SemanticAnalyzer,
+    span: &Span,
+    name: &String,
+    typ: &Type,
+    width: &mut Expression,
+) -> Option<BitField> {
+    width.analyze(analyzer);
+    let constant = std::mem::replace(width, Expression::default(span));
+    *width = constant.const_eval(analyzer);
+    if !width.is_constant() || !width.ast_type.is_in(TypeClass::Integer) {
+        analyzer.errors.push(error!(
+            span,
+            "Width of bit-field {} must be an integer constant", name
+        ));
+        return None;
+    }
+    if !typ.is_in(TypeClass::Integer) {
+        analyzer.errors.push(error!(
+            span,
+            "Bit-field {} has non-integer type {}", name, typ
+        ));
+        return None;
+    }
+
+    let value = width.get_const_value();
+    let bits = 8 * analyzer.sizeof(typ) as i128;
+    if value < 0 || value > bits {
+        analyzer.errors.push(error!(
+            span,
+            "Width {} of bit-field {} does not fit in {}", value, name, typ
+        ));
+        return None;
+    }
+    if value == 0 && !name.is_empty() {
+        analyzer
+            .errors
+            .push(error!(span, "Bit-field {} has a width of zero", name));
+        return None;
+    }
+    Some(BitField {
+        width: value as u32,
+        offset: 0,
+    })
+}
+
+impl ASTEnum {
+    // Enums have type int and their enumerators are inserted as constants
+    fn to_type(&mut self, span: &Span, analyzer: &mut SemanticAnalyzer) -> TypeNode {
+        if self.members.is_none() {
+            let name = self.name.as_ref().unwrap();
+            if analyzer.enum_table.get(name).is_none() {
+                analyzer
+                    .errors
+                    .push(error!(span, "Enum {} is not defined", name));
+            }
+            return TypeNode::Int;
+        }
+
+        let mut members = Vec::new();
+        let mut value = 0;
+        for (name, expression) in self.members.as_mut().unwrap() {
+            if let Some(expression) = expression {
+                expression.analyze(analyzer);
+                let constant = std::mem::replace(expression, Expression::default(span));
+                *expression = constant.const_eval(analyzer);
+                if !expression.is_constant() || !expression.ast_type.is_in(TypeClass::Integer) {
+                    analyzer.errors.push(error!(
+                        span,
+                        "Value of enumerator {} must be an integer constant",
+                        name
+                    ));
+                }
+                value = expression.get_const_value();
+            }
+
+            if value < i32::MIN as i128 || value > i32::MAX as i128 {
+                analyzer.errors.push(error!(
+                    span,
+                    "Value {} of enumerator {} does not fit in an int",
+                    value,
+                    name
+                ));
+            }
+
+            if let Err(()) = analyzer.symbol_table.try_insert(
+                name,
+                &Type::int(),
+                DeclarationType::Enumerator(value),
+                StorageClass::Default,
+            ) {
+                analyzer
+                    .errors
+                    .push(error!(span, "Enumerator {} redefined", name));
+            }
+            members.push((name.clone(), value));
+            value += 1;
+        }
+
+        let entry = EnumType {
+            name: self.name.clone(),
+            members,
+        };
+        if let Err(()) = analyzer.enum_table.try_insert(entry) {
+            analyzer
+                .errors
+                .push(error!(span, "Enum {} redefined", self.name.as_ref().unwrap()));
+        }
+
+        TypeNode::Int
+    }
+}
+
+impl SemanticAnalyzer {
+    fn check_declaration_specifiers(&mut self, span: &Span, typ: &[TypeNode]) -> Type {
+        use TypeNode::*;
+        let mut type_specifier = None;
+        let mut int_seen = false;
+        let mut sign = None;
+        for node in typ {
+            match node {
+                TypeNode::Int => {
+                    if int_seen {
+                        self.invalid_type(span, &typ);
+                    } else if let Some(TypeNode::Long | TypeNode::Short) = type_specifier {
+                        int_seen = true;
+                    } else if type_specifier.is_none() {
+                        type_specifier = Some(Int);
+                        int_seen = true;
+                    } else {
+                        self.invalid_type(span, typ);
+                    }
+                }
+                t @ (TypeNode::Long | TypeNode::Short) => {
+                    if let Some(TypeNode::Int) | None = type_specifier {
+                        type_specifier = Some(t.clone());
+                    } else {
+                        self.invalid_type(span, &typ);
+                    }
+                }
+                TypeNode::Struct(..)
+                | TypeNode::Char
+                | TypeNode::Float
+                | TypeNode::Double
+                | TypeNode::Void => {
+                    if let Some(_) = type_specifier {
+                        self.invalid_type(span, &typ);
+                    }
+                    type_specifier = Some(node.clone());
+                }
+
+                TypeNode::Signed | TypeNode::Unsigned => {
+                    if sign.is_some() {
+                        self.invalid_type(span, &typ);
+                    }
+                    sign = Some(node.clone());
+                }
+
+                TypeNode::UChar | TypeNode::UInt | TypeNode::ULong | TypeNode::UShort => {
+                    unreachable!()
+                }
+                TypeNode::Function(..) | TypeNode::Array(..) | TypeNode::Pointer => unreachable!(),
+            }
+        }
+
+        // A lone signed or unsigned is an int
+        let type_specifier = match (type_specifier, sign) {
+            (None, Some(_)) => Int,
+            (Some(t), None) | (Some(t @ (Char | Short | Int | Long)), Some(Signed)) => t,
+            (Some(Char), Some(_)) => UChar,
+            (Some(Short), Some(_)) => UShort,
+            (Some(Int), Some(_)) => UInt,
+            (Some(Long), Some(_)) => ULong,
+            (Some(t), Some(_)) => {
+                self.invalid_type(span, &typ);
+                t
+            }
+            (None, None) => {
+                self.errors
+                    .push(error!(span, "Missing type specifier in declaration"));
+                Int
+            }
+        };
+        vec![type_specifier].into()
+    }
+
+    // Gets the type a typedef name refers to
+    fn get_typedef(&mut self, span: &Span, name: &String) -> Type {
+        match self.symbol_table.get(name) {
+            Some(symbol) if symbol.declaration_type == DeclarationType::Typedef => {
+                symbol.symbol_type.clone()
+            }
+            _ => {
+                self.errors.push(error!(span, "{} does not name a type", name));
+                Type::error()
+            }
+        }
+    }
+
+    // Declares a typedef name in the current scope
+    // Redefining a typedef with the same type is allowed
+    pub fn declare_typedef(&mut self, span: &Span, name: &String, typ: &Type) {
+        if let Err(()) =
+            self.symbol_table
+                .try_insert(name, typ, DeclarationType::Typedef, StorageClass::Default)
+        {
+            let old_definition = self.symbol_table.get(name).unwrap();
+            if old_definition.declaration_type != DeclarationType::Typedef
+                || old_definition.symbol_type != *typ
+            {
+                self.errors.push(error!(
+                    span,
+                    "{} previously defined as '{}' is redefined as typedef '{}'",
+                    name,
+                    old_definition.symbol_type,
+                    typ
+                ));
+            }
+        }
+    }
+
+    fn invalid_type(&mut self, span: &Span, typ: &[TypeNode]) {
+        let typ: Type = typ.into();
+        self.errors
+            .push(error!(span, "Invalid type specifiers provided: {}", typ));
+    }
+}
@@ -1,50 +1,66 @@
-use crate::parser::{Type, TypeNode};
-use std::cmp;
-
-pub trait TypePromotion {
-    fn promote(self) -> Type;
-}
-
-impl TypePromotion for &Type {
-    fn promote(self) -> Type {
-        use TypeNode::*;
-        match self.nodes.get(0) {
-            Some(Char | Short) | None => Type::int(),
-            _ => self.array_promotion(),
-        }
-    }
-}
-
-impl TypePromotion for (Type, Type) {
-    fn promote(self) -> Type {
-        use TypeNode::*;
-        let (lhs, rhs) = self;
-        cmp::max_by_key(lhs, rhs, |typ| match typ.nodes.get(0) {
-            Some(Char) => 0,
-            Some(Short) => 10,
-            Some(Int) => 20,
-            Some(Long) => 30,
-            _ => i32::MAX,
-        })
-    }
-}
-
-impl Type {
-    pub fn promote2(self, rhs: Type) -> Type {
-        (self, rhs).promote()
-    }
-    pub fn array_promotion(&self) -> Type {
-        match self.nodes.get(0) {
-            Some(TypeNode::Array(..)) => {
-                let mut result = self.clone();
-                result.nodes[0] = TypeNode::Pointer;
-                result
-            }
-            Some(TypeNode::Function(..)) => {
-                let result = Type::pointer();
-                result.append(&mut self.clone())
-            }
-            _ => self.clone(),
-        }
-    }
-}
+use crate::parser::{Type, TypeNode};
+use std::cmp;
+
+pub trait TypePromotion {
+    fn promote(self) -> Type;
+}
+
+impl TypePromotion for &Type {
+    fn promote(self) -> Type {
+        use TypeNode::*;
+        match self.nodes.get(0) {
+            Some(Char | Short | UChar | UShort) | None => Type::int(),
+            _ => self.array_promotion().unqualified(),
+        }
+    }
+}
+
+impl TypePromotion for (Type, Type) {
+    fn promote(self) -> Type {
+        use TypeNode::*;
+        let (lhs, rhs) = self;
+        // Unsigned types rank above the signed type of the same size
+        cmp::max_by_key(lhs, rhs, |typ| match typ.nodes.get(0) {
+            Some(Char) => 0,
+            Some(UChar) => 1,
+            Some(Short) => 10,
+            Some(UShort) => 11,
+            Some(Int) => 20,
+            Some(UInt) => 21,
+            Some(Long) => 30,
+            Some(ULong) => 31,
+            Some(Float) => 40,
+            Some(Double) => 41,
+            _ => i32::MAX,
+        })
+    }
+}
+
+impl Type {
+    pub fn promote2(self, rhs: Type) -> Type {
+        (self, rhs).promote()
+    }
+
+    // Arguments without a prototype and unnamed arguments are also promoted from float to double
+    pub fn argument_promotion(&self) -> Type {
+        match self.nodes.get(0) {
+            Some(TypeNode::Float) => vec![TypeNode::Double].into(),
+            _ => self.promote(),
+        }
+    }
+
+    pub fn array_promotion(&self) -> Type {
+        match self.nodes.get(0) {
+            Some(TypeNode::Array(..)) => {
+                let mut result = self.clone();
+                result.nodes[0] = TypeNode::Pointer;
+                result
+            }
+            Some(TypeNode::Function(..)) => {
+                let result = Type::pointer();
+                result.append(&mut self.clone())
+            }
+            _ => self.clone(),
+        }
+    }
+}
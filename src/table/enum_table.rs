@@ -0,0 +1,59 @@
+use crate::parser::r#type::EnumType;
+use std::collections::HashMap;
+
+#[derive(Clone)]
+pub struct EnumTable {
+    pub enums: Vec<EnumType>,
+    local_table: Vec<HashMap<String, usize>>,
+    global_table: HashMap<String, usize>,
+}
+
+impl EnumTable {
+    pub fn new() -> EnumTable {
+        EnumTable {
+            enums: Vec::new(),
+            local_table: Vec::new(),
+            global_table: HashMap::new(),
+        }
+    }
+
+    pub fn enter_scope(&mut self) {
+        self.local_table.push(HashMap::new());
+    }
+
+    pub fn leave_scope(&mut self) {
+        self.local_table.pop();
+    }
+
+    // Inserts a defined enum, which fails if the tag is already defined in the current scope
+    pub fn try_insert(&mut self, entry: EnumType) -> Result<usize, ()> {
+        let index = self.enums.len();
+        if let Some(key) = &entry.name {
+            let map = match self.local_table.last_mut() {
+                Some(map) => map,
+                None => &mut self.global_table,
+            };
+            if map.contains_key(key) {
+                return Err(());
+            }
+            map.insert(key.clone(), index);
+        }
+        self.enums.push(entry);
+        Ok(index)
+    }
+
+    pub fn get_index(&self, key: &String) -> Option<usize> {
+        for map in self.local_table.iter().rev() {
+            let result = map.get(key).map(|i| *i);
+            if result.is_some() {
+                return result;
+            }
+        }
+        self.global_table.get(key).map(|i| *i)
+    }
+
+    pub fn get<'a>(&'a self, key: &String) -> Option<&'a EnumType> {
+        let index = self.get_index(key)?;
+        Some(&self.enums[index])
+    }
+}
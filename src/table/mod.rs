@@ -1,4 +1,6 @@
+pub mod enum_table;
 pub mod struct_table;
 pub mod symbol_table;
+pub use enum_table::*;
 pub use struct_table::*;
 pub use symbol_table::*;
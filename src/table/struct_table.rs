@@ -45,12 +45,14 @@ impl StructTable {
         self.global_table.get(key).is_some()
     }
 
-    pub fn try_insert(&mut self, key: Option<&String>) -> Result<usize, ()> {
+    pub fn try_insert(&mut self, key: Option<&String>, is_union: bool) -> Result<usize, ()> {
         let index = self.counter;
         let name = key.map(|s| s.clone());
         let symbol = StructType {
             name,
             members: None,
+            bit_fields: Vec::new(),
+            is_union,
         };
         if let Some(key) = key {
             if let Some(map) = self.local_table.last_mut() {
@@ -79,8 +81,11 @@ impl StructTable {
     }
 
     pub fn qualify(&mut self, type_info: &TypeInfoTable, index: usize, entry: StructType) {
-        let (info, offsets) = entry.to_info(type_info, &self.info);
-        self.structs[index] = entry;
+        let (info, offsets, bit_fields) = entry.to_info(type_info, &self.info);
+        self.structs[index] = StructType {
+            bit_fields,
+            ..entry
+        };
         self.offsets[index] = offsets;
         self.info[index] = info;
     }
@@ -95,6 +100,15 @@ impl StructTable {
         self.global_table.get(key).map(|i| *i)
     }
 
+    // Tags are only looked up in the innermost scope when they are declared or defined,
+    // so that they shadow the tags of outer scopes
+    pub fn get_index_in_scope(&self, key: &String) -> Option<usize> {
+        match self.local_table.last() {
+            Some(map) => map.get(key).map(|i| *i),
+            None => self.global_table.get(key).map(|i| *i),
+        }
+    }
+
     pub fn get<'a>(&'a self, key: &String) -> Option<&'a StructType> {
         let index = self.get_index(key)?;
         Some(&self.structs[index])
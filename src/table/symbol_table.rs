@@ -1,4 +1,4 @@
-use crate::parser::r#type::{DeclarationType, Type};
+use crate::parser::r#type::{DeclarationType, StorageClass, Type};
 use std::collections::HashMap;
 
 #[derive(Clone, Debug)]
@@ -6,12 +6,14 @@ pub struct Symbol {
     pub number: u32,
     pub symbol_type: Type,
     pub declaration_type: DeclarationType,
+    pub storage_class: StorageClass,
     pub global: bool,
 }
 
 #[derive(Clone, Debug)]
 pub struct SymbolTable {
     counter: u32,
+    static_counter: u32,
     local_table: Vec<HashMap<String, Symbol>>,
     pub global_table: HashMap<String, Symbol>,
 }
@@ -20,6 +22,7 @@ impl SymbolTable {
     pub fn new() -> SymbolTable {
         SymbolTable {
             counter: 0,
+            static_counter: 0,
             local_table: Vec::new(),
             global_table: HashMap::new(),
         }
@@ -36,7 +39,7 @@ impl SymbolTable {
         }
     }
 
-    #[allow(dead_code)]
+    // Checks if the key is declared in a block scope
     pub fn contains(&self, key: &String) -> bool {
         for map in self.local_table.iter().rev() {
             if map.contains_key(key) {
@@ -51,26 +54,39 @@ impl SymbolTable {
         key: &String,
         symbol_type: &Type,
         declaration_type: DeclarationType,
+        storage_class: StorageClass,
     ) -> Result<(), ()> {
-        let number = self.counter;
+        // Block scope statics are numbered to give them a unique global name
+        let number = match storage_class {
+            StorageClass::Static if !self.local_table.is_empty() => self.static_counter,
+            _ => self.counter,
+        };
+        let symbol = Symbol {
+            number,
+            symbol_type: symbol_type.clone(),
+            declaration_type,
+            storage_class,
+            global: self.local_table.is_empty() || storage_class != StorageClass::Default,
+        };
         if let Some(map) = self.local_table.last_mut() {
             log::trace!("Local insertion of {} with type {}", key, symbol_type);
-            SymbolTable::try_insert2(map, key, symbol_type, declaration_type, number, false)
+            SymbolTable::try_insert2(map, key, symbol)
         } else {
             log::trace!("Global insertion of {} with type {}", key, symbol_type);
-            SymbolTable::try_insert2(
-                &mut self.global_table,
-                key,
-                symbol_type,
+            SymbolTable::try_insert2(&mut self.global_table, key, symbol)
+        }?;
+        // Typedef names, enumerators and statics or externs do not take up a variable
+        if self.local_table.is_empty() {
+            self.counter = 0;
+        } else if storage_class == StorageClass::Static {
+            self.static_counter += 1;
+        } else if storage_class == StorageClass::Default
+            && !matches!(
                 declaration_type,
-                number,
-                true,
+                DeclarationType::Typedef | DeclarationType::Enumerator(_)
             )
-        }?;
-        if !self.local_table.is_empty() {
+        {
             self.counter += 1;
-        } else {
-            self.counter = 0;
         }
         Ok(())
     }
@@ -78,21 +94,10 @@ impl SymbolTable {
     fn try_insert2(
         map: &mut HashMap<String, Symbol>,
         key: &String,
-        symbol_type: &Type,
-        declaration_type: DeclarationType,
-        number: u32,
-        global: bool,
+        symbol: Symbol,
     ) -> Result<(), ()> {
         if !map.contains_key(key) {
-            map.insert(
-                key.clone(),
-                Symbol {
-                    number,
-                    symbol_type: symbol_type.clone(),
-                    declaration_type,
-                    global,
-                },
-            );
+            map.insert(key.clone(), symbol);
             Ok(())
         } else {
             Err(())
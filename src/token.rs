@@ -1,221 +1,389 @@
-use std::fmt::Display;
-
-use crate::span::Span;
-// Stores the specific type of a token and any associated values
-#[allow(dead_code)]
-#[derive(PartialEq, Eq, Clone, Debug)]
-pub enum TokenType {
-    //Type keywords
-    Char,
-    Int,
-    Long,
-    Short,
-    Struct,
-    Void,
-
-    //Control flow keywords
-    If,
-    Else,
-    While,
-    For,
-    Do,
-    Break,
-    Continue,
-    Return,
-
-    //Keywords
-    Sizeof,
-
-    //Punctuation symbols
-    LBrace,
-    RBrace,
-    LParenthesis,
-    RParenthesis,
-    Semicolon,
-    LSquare,
-    RSquare,
-
-    //Operators symbols
-    Assign,
-    Plus,
-    Minus,
-    Asterisk,
-    Divide,
-    Tilde,
-    Exclamation,
-    Equal,
-    Inequal,
-    Less,
-    LessEqual,
-    Greater,
-    GreaterEqual,
-    Question,
-    Colon,
-    LogicalOr,
-    LogicalAnd,
-    Or,
-    And,
-    Comma,
-    Period,
-    Arrow,
-
-    //Types with a value
-    ConstI(u64),
-    Ident(String),
-    CString(String),
-}
-
-// Stores the location and type of a lexed token
-#[allow(dead_code)]
-#[derive(PartialEq, Eq, Clone, Debug)]
-pub struct Token {
-    span: Span,
-    token: TokenType,
-}
-
-// Convert a punctuation character or string into the assocated tokentype for readability
-#[allow(dead_code)]
-pub fn punct<T: Into<TokenType>>(input: T) -> TokenType {
-    input.into()
-}
-
-#[allow(dead_code)]
-impl Token {
-    pub fn new(token: TokenType, span: Span) -> Token {
-        Token { span, token }
-    }
-    pub fn span(&self) -> &Span {
-        &self.span
-    }
-    pub fn token(&self) -> TokenType {
-        self.token.clone()
-    }
-}
-
-// Conversion of a characacter into the tokentype for punctuation characters
-impl From<char> for TokenType {
-    fn from(c: char) -> TokenType {
-        use TokenType::*;
-        match c {
-            '{' => LBrace,
-            '}' => RBrace,
-            '(' => LParenthesis,
-            ')' => RParenthesis,
-            '[' => LSquare,
-            ']' => RSquare,
-            ';' => Semicolon,
-            '=' => Assign,
-            '+' => Plus,
-            '-' => Minus,
-            '*' => Asterisk,
-            '/' => Divide,
-            '~' => Tilde,
-            '!' => Exclamation,
-            '<' => Less,
-            '>' => Greater,
-            '?' => Question,
-            ':' => Colon,
-            '|' => Or,
-            '&' => And,
-            '.' => Period,
-            ',' => Comma,
-            _ => {
-                log::warn!(
-                    "char to TokenType conversion with unimplemented character {}",
-                    c
-                );
-                Semicolon
-            }
-        }
-    }
-}
-
-// Conversion of a string into the tokentype for multicharacter punctuation characters
-impl From<&str> for TokenType {
-    fn from(s: &str) -> TokenType {
-        use TokenType::*;
-        match s {
-            "==" => Equal,
-            "!=" => Inequal,
-            "<=" => LessEqual,
-            ">=" => GreaterEqual,
-            "||" => LogicalOr,
-            "&&" => LogicalAnd,
-            "->" => Arrow,
-            _ => {
-                log::warn!(
-                    "&str to TokenType conversion with unimplemented string {}",
-                    s
-                );
-                Semicolon
-            }
-        }
-    }
-}
-
-impl Display for TokenType {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        use TokenType::*;
-        match self {
-            Char => write!(f, "'char'"),
-            Int => write!(f, "'int'"),
-            Long => write!(f, "'long'"),
-            Short => write!(f, "'short'"),
-            Struct => write!(f, "'struct'"),
-            Void => write!(f, "'void'"),
-
-            If => write!(f, "'if'"),
-            Else => write!(f, "'else'"),
-            While => write!(f, "'while'"),
-            For => write!(f, "'for'"),
-            Do => write!(f, "'do'"),
-            Break => write!(f, "'break'"),
-            Continue => write!(f, "'continue'"),
-            Return => write!(f, "'return'"),
-
-            Sizeof => write!(f, "'sizeof'"),
-
-            LBrace => write!(f, "'{{'"),
-            RBrace => write!(f, "'}}'"),
-            LParenthesis => write!(f, "'('"),
-            RParenthesis => write!(f, "')'"),
-            LSquare => write!(f, "'['"),
-            RSquare => write!(f, "']'"),
-            Semicolon => write!(f, "';'"),
-
-            Assign => write!(f, "'='"),
-            Plus => write!(f, "'+'"),
-            Minus => write!(f, "'-'"),
-            Asterisk => write!(f, "'*'"),
-            Divide => write!(f, "'/'"),
-            Tilde => write!(f, "'~'"),
-            Exclamation => write!(f, "'!'"),
-            Equal => write!(f, "'=='"),
-            Inequal => write!(f, "'!='"),
-            Less => write!(f, "'<'"),
-            LessEqual => write!(f, "'<='"),
-            Greater => write!(f, "'>'"),
-            GreaterEqual => write!(f, "'>='"),
-            Question => write!(f, "'?'"),
-            Colon => write!(f, "':'"),
-            LogicalOr => write!(f, "'||'"),
-            LogicalAnd => write!(f, "'&&'"),
-            Or => write!(f, "'|'"),
-            And => write!(f, "'&'"),
-            Comma => write!(f, "','"),
-            Period => write!(f, "'.'"),
-            Arrow => write!(f, "'->'"),
-
-            Ident(val) => write!(f, "'{}'", val),
-            ConstI(val) => write!(f, "'{}'", val),
-            CString(val) => write!(f, "\"{}\"", val),
-        }
-    }
-}
-
-// Display a token using std::fmt
-impl Display for Token {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.token())
-    }
-}
+use std::fmt::Display;
+
+use crate::parser::ast_print::print_c_string::format_c_string;
+use crate::parser::TypeNode;
+use crate::span::Span;
+// Stores the specific type of a token and any associated values
+#[allow(dead_code)]
+#[derive(PartialEq, Clone, Debug)]
+pub enum TokenType {
+    //Type keywords
+    Char,
+    Int,
+    Long,
+    Short,
+    Float,
+    Double,
+    Signed,
+    Unsigned,
+    Struct,
+    Union,
+    Enum,
+    Void,
+
+    //Storage class keywords
+    Typedef,
+    Static,
+    Extern,
+
+    //Type qualifier keywords
+    Const,
+    Volatile,
+
+    //Control flow keywords
+    If,
+    Else,
+    While,
+    For,
+    Do,
+    Break,
+    Continue,
+    Switch,
+    Case,
+    Default,
+    Goto,
+    Return,
+
+    //Keywords
+    Sizeof,
+    VaStart,
+    VaArg,
+    VaEnd,
+    VaCopy,
+
+    //Punctuation symbols
+    LBrace,
+    RBrace,
+    LParenthesis,
+    RParenthesis,
+    Semicolon,
+    LSquare,
+    RSquare,
+
+    //Operators symbols
+    Assign,
+    Plus,
+    Minus,
+    Asterisk,
+    Divide,
+    Percent,
+    Tilde,
+    Exclamation,
+    Equal,
+    Inequal,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    LeftShift,
+    RightShift,
+    Question,
+    Colon,
+    LogicalOr,
+    LogicalAnd,
+    Or,
+    And,
+    Caret,
+    Comma,
+    Period,
+    Ellipsis,
+    Arrow,
+    Increment,
+    Decrement,
+    PlusAssign,
+    MinusAssign,
+    AsteriskAssign,
+    DivideAssign,
+    PercentAssign,
+    LeftShiftAssign,
+    RightShiftAssign,
+    OrAssign,
+    AndAssign,
+    CaretAssign,
+
+    //Preprocessing symbols
+    Hash,
+    HashHash,
+    Newline,
+
+    //Types with a value
+    ConstI(u64, TypeNode),
+    ConstF(f64),
+    ConstD(f64),
+    Ident(String),
+    CString(Vec<u8>),
+}
+
+// Stores the location and type of a lexed token
+#[allow(dead_code)]
+#[derive(PartialEq, Clone, Debug)]
+pub struct Token {
+    span: Span,
+    token: TokenType,
+    // The preprocessor keeps the text of a token in the source and if whitespace preceded it
+    source: Option<String>,
+    leading_space: bool,
+}
+
+// Convert a punctuation character or string into the assocated tokentype for readability
+#[allow(dead_code)]
+pub fn punct<T: Into<TokenType>>(input: T) -> TokenType {
+    input.into()
+}
+
+#[allow(dead_code)]
+impl Token {
+    pub fn new(token: TokenType, span: Span) -> Token {
+        Token {
+            span,
+            token,
+            source: None,
+            leading_space: false,
+        }
+    }
+    pub fn with_source(self, source: String, leading_space: bool) -> Token {
+        Token {
+            source: Some(source),
+            leading_space,
+            ..self
+        }
+    }
+    // Moves a token to a new location, such as a macro invocation, keeping its source
+    pub fn relocate(&self, span: &Span) -> Token {
+        Token {
+            span: span.clone(),
+            ..self.clone()
+        }
+    }
+    pub fn span(&self) -> &Span {
+        &self.span
+    }
+    pub fn token(&self) -> TokenType {
+        self.token.clone()
+    }
+    // Returns the token as it was written, which differs from its spelling for literals
+    pub fn source(&self) -> String {
+        match &self.source {
+            Some(source) => source.clone(),
+            None => self.token.spelling(),
+        }
+    }
+    pub fn has_leading_space(&self) -> bool {
+        self.leading_space
+    }
+}
+
+impl TokenType {
+    // Returns the token as it would be written in the source
+    // Used by the preprocessor for stringification, token pasting and -E output
+    pub fn spelling(&self) -> String {
+        use TokenType::*;
+        match self {
+            Ident(name) => name.clone(),
+            ConstI(value, typ) => match typ {
+                TypeNode::UInt => format!("{}u", value),
+                TypeNode::Long => format!("{}l", value),
+                TypeNode::ULong => format!("{}ul", value),
+                _ => value.to_string(),
+            },
+            ConstF(value) => format!("{:?}f", value),
+            ConstD(value) => format!("{:?}", value),
+            CString(string) => format!("\"{}\"", format_c_string(string)),
+            Newline => String::from("\n"),
+            _ => {
+                // All other tokens are displayed as their spelling surrounded by quotes
+                let string = self.to_string();
+                string[1..string.len() - 1].to_string()
+            }
+        }
+    }
+}
+
+// Conversion of a characacter into the tokentype for punctuation characters
+impl From<char> for TokenType {
+    fn from(c: char) -> TokenType {
+        use TokenType::*;
+        match c {
+            '{' => LBrace,
+            '}' => RBrace,
+            '(' => LParenthesis,
+            ')' => RParenthesis,
+            '[' => LSquare,
+            ']' => RSquare,
+            ';' => Semicolon,
+            '=' => Assign,
+            '+' => Plus,
+            '-' => Minus,
+            '*' => Asterisk,
+            '/' => Divide,
+            '%' => Percent,
+            '~' => Tilde,
+            '!' => Exclamation,
+            '<' => Less,
+            '>' => Greater,
+            '?' => Question,
+            ':' => Colon,
+            '|' => Or,
+            '&' => And,
+            '^' => Caret,
+            '.' => Period,
+            ',' => Comma,
+            _ => {
+                log::warn!(
+                    "char to TokenType conversion with unimplemented character {}",
+                    c
+                );
+                Semicolon
+            }
+        }
+    }
+}
+
+// Conversion of a string into the tokentype for multicharacter punctuation characters
+impl From<&str> for TokenType {
+    fn from(s: &str) -> TokenType {
+        use TokenType::*;
+        match s {
+            "==" => Equal,
+            "!=" => Inequal,
+            "<=" => LessEqual,
+            ">=" => GreaterEqual,
+            "<<" => LeftShift,
+            ">>" => RightShift,
+            "||" => LogicalOr,
+            "&&" => LogicalAnd,
+            "->" => Arrow,
+            "..." => Ellipsis,
+            "++" => Increment,
+            "--" => Decrement,
+            "+=" => PlusAssign,
+            "-=" => MinusAssign,
+            "*=" => AsteriskAssign,
+            "/=" => DivideAssign,
+            "%=" => PercentAssign,
+            "<<=" => LeftShiftAssign,
+            ">>=" => RightShiftAssign,
+            "|=" => OrAssign,
+            "&=" => AndAssign,
+            "^=" => CaretAssign,
+            _ => {
+                log::warn!(
+                    "&str to TokenType conversion with unimplemented string {}",
+                    s
+                );
+                Semicolon
+            }
+        }
+    }
+}
+
+impl Display for TokenType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use TokenType::*;
+        match self {
+            Char => write!(f, "'char'"),
+            Int => write!(f, "'int'"),
+            Long => write!(f, "'long'"),
+            Short => write!(f, "'short'"),
+            Float => write!(f, "'float'"),
+            Double => write!(f, "'double'"),
+            Signed => write!(f, "'signed'"),
+            Unsigned => write!(f, "'unsigned'"),
+            Struct => write!(f, "'struct'"),
+            Union => write!(f, "'union'"),
+            Enum => write!(f, "'enum'"),
+            Void => write!(f, "'void'"),
+
+            Typedef => write!(f, "'typedef'"),
+            Static => write!(f, "'static'"),
+            Extern => write!(f, "'extern'"),
+
+            Const => write!(f, "'const'"),
+            Volatile => write!(f, "'volatile'"),
+
+            If => write!(f, "'if'"),
+            Else => write!(f, "'else'"),
+            While => write!(f, "'while'"),
+            For => write!(f, "'for'"),
+            Do => write!(f, "'do'"),
+            Break => write!(f, "'break'"),
+            Continue => write!(f, "'continue'"),
+            Switch => write!(f, "'switch'"),
+            Case => write!(f, "'case'"),
+            Default => write!(f, "'default'"),
+            Goto => write!(f, "'goto'"),
+            Return => write!(f, "'return'"),
+
+            Sizeof => write!(f, "'sizeof'"),
+            VaStart => write!(f, "'__builtin_va_start'"),
+            VaArg => write!(f, "'__builtin_va_arg'"),
+            VaEnd => write!(f, "'__builtin_va_end'"),
+            VaCopy => write!(f, "'__builtin_va_copy'"),
+
+            LBrace => write!(f, "'{{'"),
+            RBrace => write!(f, "'}}'"),
+            LParenthesis => write!(f, "'('"),
+            RParenthesis => write!(f, "')'"),
+            LSquare => write!(f, "'['"),
+            RSquare => write!(f, "']'"),
+            Semicolon => write!(f, "';'"),
+
+            Assign => write!(f, "'='"),
+            Plus => write!(f, "'+'"),
+            Minus => write!(f, "'-'"),
+            Asterisk => write!(f, "'*'"),
+            Divide => write!(f, "'/'"),
+            Percent => write!(f, "'%'"),
+            Tilde => write!(f, "'~'"),
+            Exclamation => write!(f, "'!'"),
+            Equal => write!(f, "'=='"),
+            Inequal => write!(f, "'!='"),
+            Less => write!(f, "'<'"),
+            LessEqual => write!(f, "'<='"),
+            Greater => write!(f, "'>'"),
+            GreaterEqual => write!(f, "'>='"),
+            LeftShift => write!(f, "'<<'"),
+            RightShift => write!(f, "'>>'"),
+            Question => write!(f, "'?'"),
+            Colon => write!(f, "':'"),
+            LogicalOr => write!(f, "'||'"),
+            LogicalAnd => write!(f, "'&&'"),
+            Or => write!(f, "'|'"),
+            And => write!(f, "'&'"),
+            Caret => write!(f, "'^'"),
+            Comma => write!(f, "','"),
+            Period => write!(f, "'.'"),
+            Ellipsis => write!(f, "'...'"),
+            Arrow => write!(f, "'->'"),
+            Increment => write!(f, "'++'"),
+            Decrement => write!(f, "'--'"),
+            PlusAssign => write!(f, "'+='"),
+            MinusAssign => write!(f, "'-='"),
+            AsteriskAssign => write!(f, "'*='"),
+            DivideAssign => write!(f, "'/='"),
+            PercentAssign => write!(f, "'%='"),
+            LeftShiftAssign => write!(f, "'<<='"),
+            RightShiftAssign => write!(f, "'>>='"),
+            OrAssign => write!(f, "'|='"),
+            AndAssign => write!(f, "'&='"),
+            CaretAssign => write!(f, "'^='"),
+
+            Hash => write!(f, "'#'"),
+            HashHash => write!(f, "'##'"),
+            Newline => write!(f, "'newline'"),
+
+            Ident(val) => write!(f, "'{}'", val),
+            ConstI(val, _) => write!(f, "'{}'", val),
+            ConstF(val) => write!(f, "'{:?}f'", val),
+            ConstD(val) => write!(f, "'{:?}'", val),
+            CString(val) => write!(f, "\"{}\"", format_c_string(val)),
+        }
+    }
+}
+
+// Display a token using std::fmt
+impl Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.token())
+    }
+}
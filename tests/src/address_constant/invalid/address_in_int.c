@@ -0,0 +1,7 @@
+int value;
+int address = (int)&value;
+
+int main(void)
+{
+    return 0;
+}
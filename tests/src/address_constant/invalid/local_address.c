@@ -0,0 +1,6 @@
+int main(void)
+{
+    int local = 3;
+    static int *pointer = &local;
+    return *pointer;
+}
@@ -0,0 +1,8 @@
+int numbers[3];
+int *pointer = &numbers[1];
+long difference = pointer - numbers;
+
+int main(void)
+{
+    return difference;
+}
@@ -0,0 +1,8 @@
+int value;
+int *pointer = &value;
+int *copy = pointer;
+
+int main(void)
+{
+    return *copy;
+}
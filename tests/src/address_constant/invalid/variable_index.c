@@ -0,0 +1,8 @@
+int index = 1;
+int numbers[3];
+int *element = &numbers[index];
+
+int main(void)
+{
+    return *element;
+}
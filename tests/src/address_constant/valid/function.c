@@ -0,0 +1,17 @@
+int add(int a, int b)
+{
+    return a + b;
+}
+
+int multiply(int a, int b)
+{
+    return a * b;
+}
+
+int (*operation)(int, int) = add;
+int (*table[2])(int, int) = {&add, multiply};
+
+int main(void)
+{
+    return operation(2, 3) + table[0](4, 5) + table[1](6, 7);
+}
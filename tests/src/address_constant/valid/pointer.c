@@ -0,0 +1,27 @@
+struct pair
+{
+    char first;
+    int second[3];
+};
+
+int value = 4;
+int numbers[4] = {1, 2, 3, 4};
+struct pair pairs[2] = {{'a', {5, 6, 7}}, {'b', {8, 9, 10}}};
+
+int *pointer = &value;
+int *element = &numbers[2];
+int *offset = numbers + 3;
+int *before = &numbers[3] - 2;
+int *member = &pairs[1].second[1];
+int *decayed = pairs[0].second;
+char *tag = &pairs[1].first;
+struct pair *last = pairs + 1;
+int *self = (int *)&pointer;
+long address = (long)&value;
+
+int main(void)
+{
+    *pointer = *pointer + 1;
+    return value + *element + *offset + *before + *member + *decayed + *tag - 'b' +
+           last->second[2] + (self == (int *)&pointer) + (*(int *)address == 5);
+}
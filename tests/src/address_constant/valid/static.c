@@ -0,0 +1,26 @@
+int counter;
+
+int *next(void)
+{
+    static int values[3] = {1, 2, 3};
+    static int *current = values;
+    static int **indirect = &current;
+    int *result = *indirect;
+    current = current + 1;
+    return result;
+}
+
+int increment(void)
+{
+    static int *target = &counter;
+    *target = *target + 1;
+    return counter;
+}
+
+int main(void)
+{
+    int first = *next();
+    int second = *next();
+    increment();
+    return first * 10 + second + increment();
+}
@@ -0,0 +1,27 @@
+char *message = "hello";
+const char *words[3] = {"one", "three", "seven"};
+char *suffix = "world" + 2;
+
+struct entry
+{
+    const char *name;
+    int length;
+};
+
+struct entry entries[2] = {{"first", 5}, {.name = "second", .length = 6}};
+
+int length(const char *string)
+{
+    int result = 0;
+    while (string[result])
+        result = result + 1;
+    return result;
+}
+
+int main(void)
+{
+    static char *local = "static";
+    return length(message) + length(words[0]) + length(words[1]) + length(words[2]) +
+           (suffix[0] == 'r') + entries[1].length - length(entries[1].name) + length(local) +
+           (entries[0].name[4] == 't');
+}
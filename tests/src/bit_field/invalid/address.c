@@ -0,0 +1,10 @@
+struct flags {
+    int enabled : 1;
+};
+
+int main(void)
+{
+    struct flags f;
+    int *pointer = &f.enabled;
+    return 0;
+}
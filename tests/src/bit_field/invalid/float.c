@@ -0,0 +1,8 @@
+struct flags {
+    float value : 3;
+};
+
+int main(void)
+{
+    return 0;
+}
@@ -0,0 +1,8 @@
+struct flags {
+    int value : ;
+};
+
+int main(void)
+{
+    return 0;
+}
@@ -0,0 +1,9 @@
+struct flags {
+    char c;
+    int value : 0;
+};
+
+int main(void)
+{
+    return 0;
+}
@@ -0,0 +1,8 @@
+struct flags {
+    int value : -1;
+};
+
+int main(void)
+{
+    return 0;
+}
@@ -0,0 +1,10 @@
+int width = 3;
+
+struct flags {
+    int value : width;
+};
+
+int main(void)
+{
+    return 0;
+}
@@ -0,0 +1,9 @@
+struct flags {
+    unsigned int mode : 2;
+};
+
+int main(void)
+{
+    struct flags f;
+    return sizeof(f.mode);
+}
@@ -0,0 +1,8 @@
+struct flags {
+    int value : 33;
+};
+
+int main(void)
+{
+    return 0;
+}
@@ -0,0 +1,27 @@
+// Compiled by gcc, to check that both compilers agree on the layout of bit-fields around structs
+struct pair {
+    short a;
+    char b;
+};
+
+struct mixed {
+    unsigned int low : 3;
+    struct pair p;
+    unsigned int high : 5;
+    char c;
+    struct pair q[2];
+    unsigned int last : 1;
+};
+
+unsigned long mixed_layout(void)
+{
+    struct mixed m;
+    char *base = (char *)&m;
+    return sizeof(m) * 1000000 + ((char *)&m.p - base) * 10000 + (&m.c - base) * 100
+           + ((char *)&m.q - base);
+}
+
+int sum_mixed(struct mixed *m)
+{
+    return m->low + m->p.a + m->p.b + m->high + m->c + m->q[1].a + m->last;
+}
@@ -0,0 +1,27 @@
+// Bit-fields are initialized in their storage unit, unnamed bit-fields are skipped
+struct packet {
+    unsigned int kind : 3;
+    int : 5;
+    int value : 8;
+    char tag;
+    unsigned int rest : 12;
+};
+
+struct packet global = {5, -3, 'x', 4095};
+struct packet designated = {.value = 100, .kind = 2};
+static struct packet array[2] = {{1, 2, 3, 4}, [1].rest = 7};
+
+int main(void)
+{
+    struct packet local = {6, -128, 'y', 1000};
+    struct packet partial = {.rest = 1};
+    int result = 0;
+    result += global.kind == 5 && global.value == -3;
+    result += 2 * (global.tag == 'x' && global.rest == 4095);
+    result += 4 * (designated.value == 100 && designated.kind == 2 && designated.rest == 0);
+    result += 8 * (array[0].rest == 4 && array[1].rest == 7 && array[1].kind == 0);
+    result += 16 * (local.kind == 6 && local.value == -128);
+    result += 32 * (local.tag == 'y' && local.rest == 1000);
+    result += 64 * (partial.kind == 0 && partial.value == 0 && partial.rest == 1);
+    return result;
+}
@@ -0,0 +1,51 @@
+// Bit-fields share a storage unit until the next one would cross its boundary
+struct flags {
+    unsigned int a : 1;
+    unsigned int b : 3;
+    unsigned int c : 28;
+};
+
+struct crossing {
+    int a : 30;
+    int b : 4;
+};
+
+struct mixed {
+    char c;
+    int a : 4;
+    char d;
+};
+
+struct zero_width {
+    char c;
+    int : 0;
+    char d;
+};
+
+struct unnamed {
+    char c;
+    int : 4;
+};
+
+struct wide {
+    long a : 40;
+    long b : 30;
+};
+
+union variant {
+    int a : 3;
+    char c;
+};
+
+int main(void)
+{
+    int result = 0;
+    result += sizeof(struct flags) == 4;
+    result += 2 * (sizeof(struct crossing) == 8);
+    result += 4 * (sizeof(struct mixed) == 4);
+    result += 8 * (sizeof(struct zero_width) == 5);
+    result += 16 * (sizeof(struct unnamed) == 2);
+    result += 32 * (sizeof(struct wide) == 16);
+    result += 64 * (sizeof(union variant) == 4);
+    return result;
+}
@@ -0,0 +1,38 @@
+// Struct members after bit-fields are aligned by their own alignment
+int printf(const char *format, ...);
+
+struct pair {
+    short a;
+    char b;
+};
+
+struct mixed {
+    unsigned int low : 3;
+    struct pair p;
+    unsigned int high : 5;
+    char c;
+    struct pair q[2];
+    unsigned int last : 1;
+};
+
+unsigned long mixed_layout(void);
+int sum_mixed(struct mixed *m);
+
+int main(void)
+{
+    struct mixed m;
+    char *base = (char *)&m;
+    unsigned long layout = sizeof(m) * 1000000 + ((char *)&m.p - base) * 10000
+                           + (&m.c - base) * 100 + ((char *)&m.q - base);
+    printf("%lu %lu\n", layout, mixed_layout());
+
+    m.low = 5;
+    m.p.a = 300;
+    m.p.b = 7;
+    m.high = 17;
+    m.c = 9;
+    m.q[1].a = 11;
+    m.last = 1;
+    printf("%d\n", sum_mixed(&m));
+    return layout == mixed_layout();
+}
@@ -0,0 +1,39 @@
+// Writing a bit-field leaves the other bits of its storage unit unchanged
+struct header {
+    unsigned char version : 4;
+    unsigned char length : 4;
+    unsigned short flags : 3;
+    unsigned short offset : 13;
+    unsigned int checksum : 16;
+    unsigned int id : 16;
+};
+
+void set(struct header *h, int version, int length)
+{
+    h->version = version;
+    h->length = length;
+}
+
+int main(void)
+{
+    struct header h;
+    h.flags = 0;
+    h.offset = 0;
+    h.checksum = 0xffff;
+    h.id = 0;
+    set(&h, 4, 5);
+    h.flags = 7;
+    h.offset = 8191;
+    h.id = 1234;
+    h.checksum ^= 0xff;
+    if (h.version != 4 || h.length != 5)
+        return 1;
+    if (h.flags != 7 || h.offset != 8191)
+        return 2;
+    if (h.checksum != 0xff00 || h.id != 1234)
+        return 3;
+    h.offset = 0;
+    if (h.flags != 7)
+        return 4;
+    return h.version * 10 + h.length + sizeof(h);
+}
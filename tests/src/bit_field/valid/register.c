@@ -0,0 +1,24 @@
+// A hardware register can be accessed both as a whole and through its fields
+union control {
+    unsigned int raw;
+    struct {
+        unsigned int enable : 1;
+        unsigned int mode : 2;
+        unsigned int : 5;
+        unsigned int divider : 8;
+        unsigned int reserved : 16;
+    } bits;
+};
+
+int main(void)
+{
+    union control reg;
+    reg.raw = 0;
+    reg.bits.enable = 1;
+    reg.bits.mode = 2;
+    reg.bits.divider = 0x12;
+    if (reg.raw != 0x1205)
+        return 1;
+    reg.raw |= 0xff;
+    return reg.bits.mode + reg.bits.divider + (reg.bits.reserved == 0);
+}
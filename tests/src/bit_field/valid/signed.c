@@ -0,0 +1,28 @@
+// Signed bit-fields are sign extended and wrap around within their width
+struct values {
+    int small : 3;
+    int medium : 12;
+    signed char tiny : 2;
+    long large : 40;
+};
+
+int main(void)
+{
+    struct values v;
+    int result = 0;
+    v.small = -1;
+    v.medium = 2047;
+    v.tiny = 1;
+    v.large = -5;
+    result += v.small == -1;
+    result += 2 * (v.medium == 2047);
+    result += 4 * (v.large == -5);
+
+    v.small = 3;
+    v.small++;
+    result += 8 * (v.small == -4);
+    result += 16 * ((v.medium += 1) == -2048);
+    result += 32 * ((v.tiny = 3) == -1);
+    result += 64 * (v.large + 5 == 0);
+    return result + (v.small < 0);
+}
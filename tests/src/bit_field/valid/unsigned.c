@@ -0,0 +1,28 @@
+// Unsigned bit-fields wrap around and are promoted to int when they fit in it
+struct counter {
+    unsigned int low : 4;
+    unsigned int high : 4;
+    unsigned int full : 32;
+};
+
+int main(void)
+{
+    struct counter c = {0, 0, 0};
+    int result = 0;
+    c.low = 15;
+    c.low += 2;
+    result += c.low == 1;
+    c.high = 20;
+    result += 2 * (c.high == 4);
+    result += 4 * (c.low - 2 < 0);
+    c.full = 0;
+    result += 8 * (c.full - 1 > 0);
+    c.high--;
+    c.high--;
+    c.high--;
+    c.high--;
+    c.high--;
+    result += 16 * (c.high == 15);
+    result += 32 * (c.low == 1);
+    return result + c.high;
+}
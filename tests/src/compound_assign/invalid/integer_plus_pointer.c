@@ -0,0 +1,7 @@
+int main(void)
+{
+    int a = 1;
+    int *p = &a;
+    a += p;
+    return 0;
+}
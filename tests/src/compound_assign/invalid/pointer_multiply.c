@@ -0,0 +1,7 @@
+int main(void)
+{
+    int a = 1;
+    int *p = &a;
+    p *= 2;
+    return 0;
+}
@@ -0,0 +1,7 @@
+int main(void)
+{
+    int sum = 0;
+    for (int i = 0; i < 10; i += 1)
+        sum += i;
+    return sum;
+}
@@ -0,0 +1,14 @@
+int main(void)
+{
+    int a = 100;
+    a -= 10;
+    a *= 3;
+    a /= 4;
+    a %= 50;
+    a <<= 3;
+    a >>= 1;
+    a |= 1;
+    a &= 29;
+    a ^= 6;
+    return a;
+}
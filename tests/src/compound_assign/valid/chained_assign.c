@@ -0,0 +1,7 @@
+int main(void)
+{
+    int a = 2;
+    int b = 3;
+    a += b *= 4;
+    return a + b;
+}
@@ -0,0 +1,7 @@
+int main(void)
+{
+    char c = 100;
+    c += 100;
+    int i = c;
+    return -i;
+}
@@ -0,0 +1,22 @@
+int putchar(int c);
+
+int count;
+
+int next(void)
+{
+    count += 1;
+    return count;
+}
+
+int main(void)
+{
+    int a[4];
+    a[0] = 0;
+    a[1] = 10;
+    a[2] = 20;
+    a[3] = 30;
+    a[next()] += 5;
+    putchar('0' + count);
+    putchar('\n');
+    return a[1] + count;
+}
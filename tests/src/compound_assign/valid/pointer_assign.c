@@ -0,0 +1,10 @@
+int main(void)
+{
+    long a[6];
+    for (int i = 0; i < 6; i += 1)
+        a[i] = i * 3;
+    long *p = a;
+    p += 4;
+    p -= 1;
+    return *p;
+}
@@ -0,0 +1,6 @@
+int main(void)
+{
+    int a = 12;
+    int b = 10;
+    return (a ^ b) + (a ^ b & 3 | 16);
+}
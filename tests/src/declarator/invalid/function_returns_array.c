@@ -0,0 +1,6 @@
+int (f(void))[4];
+
+int main(void)
+{
+    return 0;
+}
@@ -0,0 +1,5 @@
+int main(void)
+{
+    int m[3][4];
+    return m[1][2][0];
+}
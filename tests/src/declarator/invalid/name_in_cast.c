@@ -0,0 +1,6 @@
+int main(void)
+{
+    int m[3][4];
+    int (*p)[4] = (int (*q)[4])m;
+    return 0;
+}
@@ -0,0 +1,7 @@
+int main(void)
+{
+    int m[3][4];
+    int (*p)[5];
+    p = m;
+    return 0;
+}
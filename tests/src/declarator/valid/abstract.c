@@ -0,0 +1,34 @@
+// Abstract declarators name types without declaring an object
+typedef int row[4];
+
+int twice(int x)
+{
+    return 2 * x;
+}
+
+int call(int (int), int);
+
+int call(int f(int), int x)
+{
+    return f(x);
+}
+
+int main(void)
+{
+    int m[3][4] = {{1, 2, 3, 4}, {5, 6, 7, 8}, {9, 10, 11, 12}};
+    if (sizeof(int (*)[4]) != sizeof(void *))
+        return 1;
+    if (sizeof(int[3][4]) != 12 * sizeof(int) || sizeof(row[3]) != sizeof m)
+        return 2;
+    if (sizeof(int *[5]) != 5 * sizeof(int *))
+        return 3;
+    if (sizeof(int (*)(void)) != sizeof(void *))
+        return 4;
+    if (sizeof m[0] + 1 != 4 * sizeof(int) + 1)
+        return 5;
+
+    void *v = m;
+    int (*p)[4] = (int (*)[4])v;
+    int (*f)(int) = (int (*)(int))twice;
+    return p[2][1] + call(f, 7) + call(twice, 1);
+}
@@ -0,0 +1,34 @@
+// Declarators nest pointers, arrays and functions inside each other
+int add(int a, int b)
+{
+    return a + b;
+}
+
+int sub(int a, int b)
+{
+    return a - b;
+}
+
+int (*operations[2])(int, int) = {add, sub};
+
+int (*choose(int index))(int, int)
+{
+    return operations[index];
+}
+
+int apply(int operation(int, int), int a, int b)
+{
+    return operation(a, b);
+}
+
+int (*(*table)[2])(int, int) = &operations;
+
+int main(void)
+{
+    int (*op)(int, int) = choose(1);
+    int result = op(10, 3);
+    result += (*choose(0))(4, 5);
+    result += apply(sub, 20, 5);
+    result += (*table)[0](1, 2);
+    return result;
+}
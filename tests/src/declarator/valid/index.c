@@ -0,0 +1,21 @@
+// Indexing is commutative and scales by the size of the indexed element
+int main(void)
+{
+    int m[2][3][4];
+    for (int i = 0; i < 2; i++)
+        for (int j = 0; j < 3; j++)
+            for (int k = 0; k < 4; k++)
+                m[i][j][k] = i * 100 + j * 10 + k;
+
+    int i = 1;
+    if (i[m][2][3] != 123)
+        return 1;
+    if (2[m[1]][1] != 121)
+        return 2;
+    if (*(*(*(m + 1) + 1) + 2) != 112)
+        return 3;
+    int *last = &m[1][2][3];
+    if (last - 1 != &m[1][2][2] || last - 4 != m[1][1] + 3)
+        return 4;
+    return m[1][2][1] - (m + 1)[0][1][1];
+}
@@ -0,0 +1,26 @@
+// A pointer to an array steps over whole rows of a matrix
+int sum_row(int (*row)[4])
+{
+    int total = 0;
+    for (int i = 0; i < 4; i++)
+        total += (*row)[i];
+    return total;
+}
+
+int main(void)
+{
+    int m[3][4];
+    for (int i = 0; i < 3; i++)
+        for (int j = 0; j < 4; j++)
+            m[i][j] = i * 4 + j;
+
+    int (*p)[4] = m;
+    p++;
+    if (p[1][2] != 10)
+        return 1;
+    if ((char *)p - (char *)m != sizeof(int[4]))
+        return 2;
+    if (&m[2] - m != 2)
+        return 3;
+    return sum_row(p) + sum_row(m + 2);
+}
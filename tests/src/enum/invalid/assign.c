@@ -0,0 +1,10 @@
+enum
+{
+    A
+};
+
+int main(void)
+{
+    A = 3;
+    return 0;
+}
@@ -0,0 +1,11 @@
+enum
+{
+    A,
+    B,
+    A
+};
+
+int main(void)
+{
+    return 0;
+}
@@ -0,0 +1,9 @@
+int main(void)
+{
+    int a = 3;
+    enum
+    {
+        B = a
+    };
+    return B;
+}
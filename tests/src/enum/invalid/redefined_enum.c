@@ -0,0 +1,14 @@
+enum e
+{
+    A
+};
+
+enum e
+{
+    B
+};
+
+int main(void)
+{
+    return 0;
+}
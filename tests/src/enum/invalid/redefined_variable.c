@@ -0,0 +1,11 @@
+int A;
+
+enum
+{
+    A
+};
+
+int main(void)
+{
+    return 0;
+}
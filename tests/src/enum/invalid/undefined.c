@@ -0,0 +1,5 @@
+int main(void)
+{
+    enum e x;
+    return 0;
+}
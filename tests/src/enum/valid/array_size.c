@@ -0,0 +1,16 @@
+enum size
+{
+    SIZE = 4
+};
+
+int values[SIZE];
+
+int main(void)
+{
+    int local[SIZE + 1];
+    int i;
+    for (i = 0; i < SIZE; i++)
+        values[i] = i;
+    local[SIZE] = 10;
+    return values[SIZE - 1] + local[SIZE] + sizeof(local);
+}
@@ -0,0 +1,19 @@
+typedef enum state
+{
+    OFF,
+    ON
+} state;
+
+state toggle(state s)
+{
+    if (s == OFF)
+        return ON;
+    return OFF;
+}
+
+int main(void)
+{
+    state s = OFF;
+    enum state t = toggle(s);
+    return t + sizeof(state) + sizeof(enum state);
+}
@@ -0,0 +1,14 @@
+enum
+{
+    A = 5,
+    B,
+    C = A * 4,
+    D,
+    E = -3,
+    F
+};
+
+int main(void)
+{
+    return A + B + C + D + E + F;
+}
@@ -0,0 +1,13 @@
+enum
+{
+    FIRST = 3,
+    SECOND
+};
+
+int a = SECOND * 2;
+long b = FIRST + SECOND;
+
+int main(void)
+{
+    return a + b;
+}
@@ -0,0 +1,12 @@
+enum color
+{
+    RED,
+    GREEN,
+    BLUE,
+};
+
+int main(void)
+{
+    enum color c = BLUE;
+    return c * 10 + GREEN;
+}
@@ -0,0 +1,19 @@
+enum
+{
+    X = 1,
+    Y
+};
+
+int main(void)
+{
+    int result = X;
+    {
+        int X = 10;
+        enum
+        {
+            Y = 20
+        };
+        result = result + X + Y;
+    }
+    return result + Y;
+}
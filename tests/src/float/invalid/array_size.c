@@ -0,0 +1,5 @@
+int main(void)
+{
+    int a[2.0];
+    return 0;
+}
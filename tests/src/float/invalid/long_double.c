@@ -0,0 +1,5 @@
+int main(void)
+{
+    long double x = 1.0;
+    return 0;
+}
@@ -0,0 +1,5 @@
+int main(void)
+{
+    double x = 5.5;
+    return x % 2;
+}
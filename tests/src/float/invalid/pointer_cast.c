@@ -0,0 +1,6 @@
+int main(void)
+{
+    int x = 0;
+    double d = (double)&x;
+    return 0;
+}
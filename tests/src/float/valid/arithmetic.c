@@ -0,0 +1,16 @@
+double average(double a, double b)
+{
+    return (a + b) / 2;
+}
+
+int main(void)
+{
+    float f = 1.5f;
+    double d = 2.25;
+    f = f * 4 - 0.5f;
+    d = d / 0.5 + f;
+    d -= average(d, 1.0);
+    f++;
+    --d;
+    return (int)(d * 10) + (int)f + (int)-d;
+}
@@ -0,0 +1,16 @@
+double sum(int a, double b, long c, float d, double e, double f, double g, double h, double i,
+    double j, double k, int l)
+{
+    return a + b + c + d + e + f + g + h + i + j + k + l;
+}
+
+float scale(float value, int factor)
+{
+    return value * factor;
+}
+
+int main(void)
+{
+    double total = sum(1, 2.5, 3, 4.5f, 5, 6, 7, 8, 9, 10, 11.5, 12);
+    return (int)total + (int)scale(2.5f, 4);
+}
@@ -0,0 +1,22 @@
+int main(void)
+{
+    double zero = 0.0;
+    double nan = zero / zero;
+    float one = 1;
+    int result = 0;
+
+    if (nan != nan)
+        result += 1;
+    if (!(nan == nan))
+        result += 2;
+    if (!(nan < 1.0) && !(nan >= 1.0))
+        result += 4;
+    if (one < 2 && one <= 1 && one > 0.5 && one >= 1)
+        result += 8;
+    if (zero || one)
+        result += 16;
+    if (!zero)
+        result += 32;
+    result += (one > zero) + (nan > zero);
+    return result;
+}
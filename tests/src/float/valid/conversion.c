@@ -0,0 +1,29 @@
+long truncate(double value)
+{
+    return value;
+}
+
+int main(void)
+{
+    unsigned long big = 18446744073709551615ul;
+    unsigned small = 4000000000u;
+    char c = -3;
+    double d = big;
+    float f = small;
+    double e = c;
+    int result = 0;
+
+    if (d == 18446744073709551616.0)
+        result += 1;
+    if (f == 4000000000.0f)
+        result += 2;
+    if (e == -3.0)
+        result += 4;
+    if (truncate(-2.75) == -2)
+        result += 8;
+    if ((float)0.1 != 0.1)
+        result += 16;
+    if ((short)65537.9 == 1)
+        result += 32;
+    return result;
+}
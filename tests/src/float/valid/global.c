@@ -0,0 +1,11 @@
+float half = 0.5f;
+double third = 1.0 / 3;
+double integer = 7;
+int truncated = 3.9;
+double zero;
+
+int main(void)
+{
+    zero += half;
+    return (int)(third * 30) + (int)integer + truncated + (int)(zero * 4);
+}
@@ -0,0 +1,21 @@
+// Floating point values from 2^63 are converted to unsigned long without overflowing
+int main(void)
+{
+    double big = 1.5 * 9223372036854775808.0;
+    float huge = 1.75e19f;
+    double small = -3.75;
+    unsigned long a = big;
+    unsigned long b = (unsigned long)huge;
+    unsigned long c = 9223372036854775807.0;
+    long d = small;
+    unsigned e = 4000000000.0;
+    if (a != 13835058055282163712ul || big != 1.5 * 9223372036854775808.0)
+        return 1;
+    if (b != 17499999691008376832ul || huge != 1.75e19f)
+        return 2;
+    if (c != 9223372036854775808ul)
+        return 3;
+    if (d != -3 || e != 4000000000u)
+        return 4;
+    return (int)(a >> 60) + (int)(b >> 60);
+}
@@ -0,0 +1,9 @@
+int main(void)
+{
+    double a = .5;
+    double b = 1e3;
+    double c = 2.5e-1;
+    double d = 3.;
+    float e = 1.25E+1f;
+    return (int)(a * 2 + b / 100 + c * 4 + d + e);
+}
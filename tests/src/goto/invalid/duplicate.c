@@ -0,0 +1,8 @@
+int main(void)
+{
+    int x = 0;
+label:
+    x++;
+label:
+    return x;
+}
@@ -0,0 +1,5 @@
+int main(void)
+{
+    goto;
+    return 0;
+}
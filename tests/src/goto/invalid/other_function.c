@@ -0,0 +1,11 @@
+int other(void)
+{
+target:
+    return 1;
+}
+
+int main(void)
+{
+    goto target;
+    return 0;
+}
@@ -0,0 +1,5 @@
+int main(void)
+{
+    goto missing;
+    return 0;
+}
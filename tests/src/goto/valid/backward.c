@@ -0,0 +1,11 @@
+int main(void)
+{
+    int i = 0;
+    int total = 0;
+again:
+    total = total + i;
+    i++;
+    if (i < 10)
+        goto again;
+    return total;
+}
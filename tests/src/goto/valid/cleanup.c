@@ -0,0 +1,17 @@
+int process(int value)
+{
+    int result = 0;
+    if (value < 0)
+        goto out;
+    result = value * 2;
+    if (result > 10)
+        goto out;
+    result = result + 1;
+out:
+    return result;
+}
+
+int main(void)
+{
+    return process(-1) + process(3) * 2 + process(7);
+}
@@ -0,0 +1,17 @@
+int main(void)
+{
+    int x = 0;
+    int i = 0;
+    if (x == 0)
+        goto second;
+
+first:
+    x = x + 3;
+    i++;
+
+second:
+    x = x + 1;
+    if (i < 5)
+        goto first;
+    return x;
+}
@@ -0,0 +1,37 @@
+int run(char *input)
+{
+    int count = 0;
+    int i = 0;
+    goto start;
+
+in_word:
+    if (input[i] == 0)
+        goto done;
+    if (input[i] == ' ')
+    {
+        i++;
+        goto start;
+    }
+    i++;
+    goto in_word;
+
+start:
+    if (input[i] == 0)
+        goto done;
+    if (input[i] == ' ')
+    {
+        i++;
+        goto start;
+    }
+    count++;
+    i++;
+    goto in_word;
+
+done:
+    return count;
+}
+
+int main(void)
+{
+    return run("  a state  machine with goto ") * 10 + run("");
+}
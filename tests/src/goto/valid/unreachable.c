@@ -0,0 +1,13 @@
+int main(void)
+{
+    int x = 1;
+    goto end;
+
+loop:
+    x = x + 1;
+    if (x < 100)
+        goto loop;
+
+end:
+    return x;
+}
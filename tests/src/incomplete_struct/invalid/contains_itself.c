@@ -0,0 +1,9 @@
+struct node {
+    int value;
+    struct node next;
+};
+
+int main(void)
+{
+    return 0;
+}
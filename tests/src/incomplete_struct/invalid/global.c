@@ -0,0 +1,6 @@
+struct incomplete global;
+
+int main(void)
+{
+    return 0;
+}
@@ -0,0 +1,11 @@
+struct incomplete;
+
+int get(struct incomplete *pointer)
+{
+    return pointer->value;
+}
+
+int main(void)
+{
+    return 0;
+}
@@ -0,0 +1,8 @@
+struct S;
+
+int main(void)
+{
+    struct S *p = 0;
+    p = p + 1;
+    return 0;
+}
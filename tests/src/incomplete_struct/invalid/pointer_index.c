@@ -0,0 +1,8 @@
+struct S;
+
+int main(void)
+{
+    struct S *p = 0;
+    &p[0];
+    return 0;
+}
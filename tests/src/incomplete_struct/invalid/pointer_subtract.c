@@ -0,0 +1,8 @@
+struct S;
+
+int main(void)
+{
+    struct S *p = 0;
+    struct S *q = 0;
+    return p - q;
+}
@@ -0,0 +1,10 @@
+int main(void)
+{
+    struct value {
+        int a;
+    };
+    struct value {
+        int b;
+    };
+    return 0;
+}
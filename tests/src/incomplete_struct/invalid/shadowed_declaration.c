@@ -0,0 +1,10 @@
+struct value {
+    int a;
+};
+
+int main(void)
+{
+    struct value;
+    struct value local;
+    return 0;
+}
@@ -0,0 +1,6 @@
+struct incomplete;
+
+int main(void)
+{
+    return sizeof(struct incomplete);
+}
@@ -0,0 +1,7 @@
+struct incomplete;
+
+int main(void)
+{
+    struct incomplete value;
+    return 0;
+}
@@ -0,0 +1,35 @@
+// Pointers to a declared struct can be used before it is defined
+struct tree;
+typedef struct tree tree;
+
+int depth(tree *node);
+
+struct pair {
+    struct tree *left;
+    struct tree *right;
+};
+
+struct tree {
+    int value;
+    struct pair children;
+};
+
+int depth(tree *node)
+{
+    if (!node)
+        return 0;
+    int left = depth(node->children.left);
+    int right = depth(node->children.right);
+    return 1 + (left > right ? left : right);
+}
+
+struct tree global;
+
+int main(void)
+{
+    struct tree leaf = {1, {0, 0}};
+    struct tree middle = {2, {&leaf, 0}};
+    struct tree root = {3, {&middle, &leaf}};
+    global = root;
+    return depth(&global) + sizeof(struct tree);
+}
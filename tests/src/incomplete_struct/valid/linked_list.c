@@ -0,0 +1,21 @@
+// A struct can point to itself while it is being defined
+struct node {
+    int value;
+    struct node *next;
+};
+
+int sum(struct node *list)
+{
+    int result = 0;
+    for (; list; list = list->next)
+        result += list->value;
+    return result;
+}
+
+int main(void)
+{
+    struct node third = {3, 0};
+    struct node second = {2, &third};
+    struct node first = {1, &second};
+    return sum(&first) + first.next->next->value;
+}
@@ -0,0 +1,23 @@
+// Structs can refer to each other through pointers
+struct even;
+
+struct odd {
+    int value;
+    struct even *next;
+};
+
+struct even {
+    int value;
+    struct odd *next;
+};
+
+int main(void)
+{
+    struct even zero;
+    struct odd one;
+    zero.value = 0;
+    zero.next = &one;
+    one.value = 1;
+    one.next = &zero;
+    return zero.next->next->next->value + sizeof(zero) + sizeof(one);
+}
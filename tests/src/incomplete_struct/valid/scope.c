@@ -0,0 +1,26 @@
+// A struct tag in a block shadows the tag from an outer scope
+struct value {
+    int a;
+};
+
+int main(void)
+{
+    struct value outer = {1};
+    int result = sizeof(struct value);
+    {
+        struct value {
+            long a, b;
+        } inner = {2, 3};
+        result += sizeof(struct value) + inner.b;
+    }
+    {
+        struct value;
+        struct value *pointer = 0;
+        struct value {
+            char c;
+        } local = {4};
+        pointer = &local;
+        result += sizeof(*pointer) + pointer->c;
+    }
+    return result + sizeof(struct value) + outer.a;
+}
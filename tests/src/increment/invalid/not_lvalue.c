@@ -0,0 +1,6 @@
+int main(void)
+{
+    int a = 1;
+    (a + 1)++;
+    return a;
+}
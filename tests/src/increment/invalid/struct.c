@@ -0,0 +1,10 @@
+struct s {
+    int a;
+};
+
+int main(void)
+{
+    struct s v;
+    v++;
+    return 0;
+}
@@ -0,0 +1,17 @@
+int counter;
+
+int next(void)
+{
+    return counter++;
+}
+
+int main(void)
+{
+    long a[3];
+    a[0] = 7;
+    a[1] = 8;
+    a[2] = 9;
+    a[next()]++;
+    ++a[next()];
+    return a[0] + a[1] + counter;
+}
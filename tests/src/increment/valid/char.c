@@ -0,0 +1,9 @@
+int main(void)
+{
+    char c = 127;
+    c++;
+    int i = c;
+    short s = -1;
+    ++s;
+    return -i + s;
+}
@@ -0,0 +1,9 @@
+int main(void)
+{
+    int sum = 0;
+    for (int i = 0; i < 10; i++)
+        sum += i;
+    for (int i = 10; i > 0; --i)
+        sum += i;
+    return sum;
+}
@@ -0,0 +1,12 @@
+int main(void)
+{
+    int a[5];
+    for (int i = 0; i < 5; ++i)
+        a[i] = i * 2 + 1;
+    int *p = a;
+    p++;
+    ++p;
+    int first = *p++;
+    int second = *--p;
+    return first * 10 + second + *++p;
+}
@@ -0,0 +1,7 @@
+int main(void)
+{
+    int a = 5;
+    int b = ++a;
+    int c = --a;
+    return a + b * 10 + c * 100;
+}
@@ -0,0 +1,16 @@
+struct point {
+    int x;
+    int y;
+};
+
+int main(void)
+{
+    struct point p;
+    struct point *q = &p;
+    p.x = 1;
+    p.y = 2;
+    p.x++;
+    --q->y;
+    q->x++;
+    return p.x * 10 + p.y;
+}
@@ -0,0 +1,5 @@
+int main(void)
+{
+    int a[3] = 5;
+    return a[0];
+}
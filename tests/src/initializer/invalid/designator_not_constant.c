@@ -0,0 +1,6 @@
+int main(void)
+{
+    int i = 1;
+    int a[3] = {[i] = 1};
+    return a[0];
+}
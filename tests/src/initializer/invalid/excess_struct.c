@@ -0,0 +1,11 @@
+struct point
+{
+    int x;
+    int y;
+};
+
+int main(void)
+{
+    struct point p = {1, 2, 3};
+    return p.x;
+}
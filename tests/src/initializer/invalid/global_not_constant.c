@@ -0,0 +1,7 @@
+int value = 3;
+int values[2] = {1, value};
+
+int main(void)
+{
+    return values[0];
+}
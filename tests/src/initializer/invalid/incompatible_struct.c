@@ -0,0 +1,16 @@
+struct a
+{
+    int x;
+};
+
+struct b
+{
+    struct a inner;
+};
+
+int main(void)
+{
+    struct b value = {{1}};
+    struct a other[1] = {value};
+    return other[0].x;
+}
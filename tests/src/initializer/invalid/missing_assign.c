@@ -0,0 +1,5 @@
+int main(void)
+{
+    int a[3] = {[1] 2};
+    return a[1];
+}
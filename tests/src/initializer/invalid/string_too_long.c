@@ -0,0 +1,5 @@
+int main(void)
+{
+    char s[3] = "abcd";
+    return s[0];
+}
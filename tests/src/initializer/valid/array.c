@@ -0,0 +1,7 @@
+int main(void)
+{
+    int a[5] = {1, 2, 3};
+    long b[3] = {4, 5, 6,};
+    char c[4] = {7};
+    return a[0] + a[1] + a[2] + a[3] + a[4] + b[0] + b[1] + b[2] + c[0] + c[1] + c[3];
+}
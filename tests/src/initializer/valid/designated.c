@@ -0,0 +1,25 @@
+struct point
+{
+    int x;
+    int y;
+    int z;
+};
+
+struct shape
+{
+    struct point center;
+    int sides;
+    int angles[4];
+};
+
+int main(void)
+{
+    struct point p = {.z = 3, .x = 1};
+    int a[6] = {[4] = 5, 6, [1] = 2};
+    // Following values continue after the designated member
+    struct shape s = {.center.y = 7, 8, .angles[2] = 9, 10, .sides = 4};
+    // Later values override earlier ones
+    int b[3] = {1, 2, 3, [1] = 20};
+    return p.x + p.y + p.z + a[0] + a[1] + a[4] + a[5] + s.center.x + s.center.y + s.center.z +
+           s.sides + s.angles[0] + s.angles[2] + s.angles[3] + b[1];
+}
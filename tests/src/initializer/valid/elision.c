@@ -0,0 +1,22 @@
+struct pair
+{
+    int first;
+    int second;
+};
+
+struct line
+{
+    struct pair from;
+    struct pair to;
+    int values[2];
+};
+
+int main(void)
+{
+    // Members without braces take as many values as they need
+    struct line l = {1, 2, 3, 4, 5, 6};
+    struct pair pairs[3] = {7, 8, {9}, 10};
+    return l.from.first + l.from.second + l.to.first + l.to.second + l.values[0] + l.values[1] +
+           pairs[0].first + pairs[0].second + pairs[1].first + pairs[1].second + pairs[2].first +
+           pairs[2].second;
+}
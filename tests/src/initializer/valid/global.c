@@ -0,0 +1,28 @@
+struct point
+{
+    char tag;
+    double x;
+    float y;
+};
+
+int numbers[5] = {1, 2, [4] = 5};
+struct point origin = {'o', 1.5, 2.5f};
+struct point points[3] = {{1, 0.5}, [2] = {.y = 4}};
+const char message[8] = "global";
+long big[2][2] = {{1}, {2, 3}};
+
+int count(void)
+{
+    static int counts[3] = {10, 20};
+    counts[2] = counts[2] + 1;
+    return counts[0] + counts[1] + counts[2];
+}
+
+int main(void)
+{
+    count();
+    return numbers[0] + numbers[1] + numbers[3] + numbers[4] + origin.tag - 'o' +
+           (int)(origin.x + origin.y) + (int)(points[0].x * 2) + points[0].tag + (int)points[2].y +
+           points[1].tag + message[5] - 'l' + message[6] + big[0][0] + big[0][1] + big[1][1] +
+           count();
+}
@@ -0,0 +1,10 @@
+int main(void)
+{
+    int matrix[2][3] = {{1, 2, 3}, {4}};
+    int flat[2][3] = {1, 2, 3, 4, 5};
+    int total = 0;
+    for (int i = 0; i < 2; i += 1)
+        for (int j = 0; j < 3; j += 1)
+            total = total + matrix[i][j] * flat[i][j];
+    return total + matrix[1][2] + flat[1][2];
+}
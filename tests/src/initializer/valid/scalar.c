@@ -0,0 +1,7 @@
+int main(void)
+{
+    int a = {5};
+    double b = {2.5};
+    char *p = {0};
+    return a + (int)(b * 2) + (p == 0);
+}
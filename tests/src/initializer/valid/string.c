@@ -0,0 +1,16 @@
+struct named
+{
+    char name[8];
+    int value;
+};
+
+int main(void)
+{
+    char hello[6] = "hello";
+    char exact[3] = "abc";
+    char braced[8] = {"hi"};
+    struct named n = {"name", 5};
+    unsigned char bytes[4] = "ab";
+    return hello[0] - 'h' + hello[4] - 'o' + hello[5] + exact[2] - 'c' + braced[1] - 'i' +
+           braced[7] + n.name[3] - 'e' + n.name[4] + n.value + bytes[3];
+}
@@ -0,0 +1,22 @@
+struct point
+{
+    int x;
+    int y;
+};
+
+struct rectangle
+{
+    struct point corner;
+    struct point size;
+    char filled;
+    long area;
+};
+
+int main(void)
+{
+    struct point p = {3, 4};
+    struct rectangle r = {{1, 2}, {p.x * 2, p.y * 2}, 1};
+    struct rectangle empty = {0};
+    return p.x + p.y + r.corner.x + r.corner.y + r.size.x + r.size.y + r.filled + r.area +
+           empty.size.y;
+}
@@ -0,0 +1,15 @@
+struct pair
+{
+    int first;
+    int second;
+};
+
+int main(void)
+{
+    struct pair a = {1, 2};
+    struct pair b = {3, 4};
+    struct pair pairs[3] = {b, a};
+    struct pair copy = {.second = 6};
+    return pairs[0].first + pairs[1].second + pairs[2].first + pairs[2].second + copy.first +
+           copy.second;
+}
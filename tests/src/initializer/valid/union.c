@@ -0,0 +1,15 @@
+union value
+{
+    int integer;
+    char bytes[4];
+};
+
+union value global = {258};
+
+int main(void)
+{
+    union value first = {16909060};
+    union value designated = {.bytes = {1, 2}};
+    union value replaced = {.bytes = {7, 7, 7, 7}, .integer = 3};
+    return first.bytes[0] + designated.integer + replaced.integer + global.bytes[1];
+}
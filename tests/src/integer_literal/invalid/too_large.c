@@ -0,0 +1,4 @@
+int main(void)
+{
+    return 0x10000000000000000 != 0;
+}
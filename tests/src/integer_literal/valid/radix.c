@@ -0,0 +1,7 @@
+int main(void)
+{
+    int hex = 0x1F + 0X10 + 0xaB - 0xAb;
+    int octal = 017 + 0 + 00 + 0777 / 0100;
+    int binary = 0b101 + 0B11;
+    return hex + octal + binary;
+}
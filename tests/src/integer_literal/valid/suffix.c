@@ -0,0 +1,7 @@
+int main(void)
+{
+    unsigned a = 10u + 10U;
+    long b = 5l + 5L + 3ll + 3LL;
+    unsigned long c = 1ul + 1UL + 1lu + 1LU + 1ull + 1ULL + 1llu + 1LLU + 1uLL + 1Ull;
+    return a + b + c + 0x10u + 010L + 0b1ul;
+}
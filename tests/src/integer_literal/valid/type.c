@@ -0,0 +1,27 @@
+// The type of a constant is the first type in its list that can represent the value
+int main(void)
+{
+    int result = 0;
+    result += sizeof(2147483647) == 4;
+    result += sizeof(2147483648) == 8;
+    result += sizeof(0x7FFFFFFF) == 4;
+    result += sizeof(0xFFFFFFFF) == 4;
+    result += sizeof(0x100000000) == 8;
+    result += sizeof(037777777777) == 4;
+    result += sizeof(1u) == 4;
+    result += sizeof(1l) == 8;
+    result += sizeof(1ull) == 8;
+    result += sizeof(4294967295u) == 4;
+    result += sizeof(4294967296u) == 8;
+
+    // Unsigned types change the comparison with negative numbers
+    result += -1 < 0xFFFFFFFF;
+    result += -1 < 4294967295;
+    result += -1 < 0u;
+    result += -1 < 0x7FFFFFFFFFFFFFFF;
+    result += -1 < 0xFFFFFFFFFFFFFFFF;
+    result += -1l < 0xFFFFFFFF;
+    result += 0xFFFFFFFF + 1 == 0;
+    result += 4294967295 + 1 == 4294967296;
+    return result;
+}
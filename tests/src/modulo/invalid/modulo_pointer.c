@@ -0,0 +1,6 @@
+int main(void)
+{
+    int a = 5;
+    int *b = &a;
+    return b % 2;
+}
@@ -0,0 +1,6 @@
+int main(void)
+{
+    long a = 10000000000;
+    long b = 7;
+    return a % b;
+}
@@ -0,0 +1,8 @@
+int main(void)
+{
+    int sum = 0;
+    for (int i = 0; i < 30; i = i + 1)
+        if (i % 3 == 0)
+            sum = sum + i % 7;
+    return sum;
+}
@@ -0,0 +1,6 @@
+int main(void)
+{
+    int a = -17;
+    int b = 5;
+    return (a % b) * -10 + 17 % -5;
+}
@@ -0,0 +1,7 @@
+int main(void)
+{
+    int a = 100;
+    int b = 7;
+    int c = a / b;
+    return a % b + c;
+}
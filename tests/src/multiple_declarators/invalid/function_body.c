@@ -0,0 +1,9 @@
+int a, f(void)
+{
+    return 0;
+}
+
+int main(void)
+{
+    return a;
+}
@@ -0,0 +1,5 @@
+int main(void)
+{
+    int a, b c;
+    return 0;
+}
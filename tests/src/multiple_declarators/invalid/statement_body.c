@@ -0,0 +1,8 @@
+// A declaration can not be the body of an if statement, even with several declarators
+int main(void)
+{
+    int x = 1;
+    if (x)
+        int a = 1, b = 2;
+    return x;
+}
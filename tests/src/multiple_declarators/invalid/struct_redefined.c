@@ -0,0 +1,10 @@
+int main(void)
+{
+    struct s {
+        int a;
+    } x, y;
+    struct s {
+        int b;
+    } z;
+    return 0;
+}
@@ -0,0 +1,16 @@
+// Later declarators refer to the anonymous struct of the first declarator,
+// even when other anonymous structs are defined inside that declarator
+struct { int a; } g = { sizeof(struct { char c[8]; }) }, h;
+struct { long a; } f(struct { int b; } *p), k;
+
+int main(void)
+{
+    struct { short a, b; } x = { sizeof(struct { char c[20]; }) }, y;
+    y.a = 3;
+    y.b = 4;
+    h.a = 5;
+    k.a = 6;
+    if (sizeof(y) != 2 * sizeof(short) || sizeof(h) != sizeof(int) || sizeof(k) != sizeof(long))
+        return 1;
+    return g.a + x.a + y.a * y.b + h.a + k.a;
+}
@@ -0,0 +1,8 @@
+// The clause of a for loop can declare multiple variables
+int main(void)
+{
+    int sum = 0;
+    for (int i = 0, j = 10, *p = &sum; i < j; i++, j--)
+        *p += j - i;
+    return sum;
+}
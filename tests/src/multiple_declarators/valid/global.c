@@ -0,0 +1,21 @@
+// Globals declared together share their specifiers
+int a = 1, b, *c = &a, d[3] = {4, 5, 6};
+static long e = 7, f;
+int first(void), second(int);
+
+int first(void)
+{
+    return a + b;
+}
+
+int second(int value)
+{
+    return value + d[2];
+}
+
+int main(void)
+{
+    b = 2;
+    f = e + 1;
+    return first() + second(*c) + f + sizeof(d);
+}
@@ -0,0 +1,11 @@
+// Every declarator has its own type and initializer
+int main(void)
+{
+    int a = 3, *b = &a, c[4], d;
+    c[0] = 1;
+    c[3] = *b + 1;
+    d = sizeof(c) + sizeof(b);
+    const long e = 5, f = e * 2;
+    int g = f, h = g + a;
+    return a + c[0] + c[3] + d + e + f + g + h;
+}
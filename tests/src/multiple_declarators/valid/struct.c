@@ -0,0 +1,32 @@
+// A struct defined in a declaration is shared by all of its declarators
+struct point {
+    int x, y;
+} origin, unit = {1, 1}, *pointer = &unit;
+
+typedef struct {
+    long a, b;
+} pair, *pair_pointer;
+
+enum { RED, GREEN, BLUE } first = GREEN, second = BLUE;
+
+int main(void)
+{
+    struct {
+        int value;
+        struct {
+            char c;
+        } inner, other;
+    } left, right;
+    left.value = 4;
+    left.inner.c = 2;
+    left.other = left.inner;
+    right = left;
+
+    pair p = {3, 4}, q;
+    pair_pointer pp = &q;
+    q = p;
+
+    struct point points[2], copy = *pointer;
+    points[1] = copy;
+    return origin.x + points[1].y + right.value + right.other.c + pp->b + first + second;
+}
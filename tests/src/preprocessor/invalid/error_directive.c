@@ -0,0 +1,10 @@
+#define VERSION 1
+
+#if VERSION < 2
+#error "Version is too old"
+#endif
+
+int main()
+{
+    return 0;
+}
@@ -0,0 +1,6 @@
+#include "this_file_does_not_exist.h"
+
+int main()
+{
+    return 0;
+}
@@ -0,0 +1,5 @@
+#ifdef SOMETHING
+int main()
+{
+    return 0;
+}
@@ -0,0 +1,6 @@
+#define ADD(a, b) ((a) + (b))
+
+int main()
+{
+    return ADD(1);
+}
@@ -0,0 +1,42 @@
+#define VERSION 3
+#define FEATURE
+
+#if VERSION > 2 && defined(FEATURE)
+#define RESULT 10
+#elif VERSION == 2
+#define RESULT 20
+#else
+#define RESULT 30
+#endif
+
+#ifdef UNDEFINED_MACRO
+#error "This should not be reached"
+#endif
+
+#ifndef FEATURE
+int missing_function();
+#else
+int feature()
+{
+    return 1;
+}
+#endif
+
+#if 0
+This text is never compiled
+#if 1
+#error "Nested groups in skipped groups are skipped"
+#endif
+#endif
+
+#undef FEATURE
+#if defined FEATURE || !defined VERSION
+#define OTHER 100
+#else
+#define OTHER 0
+#endif
+
+int main()
+{
+    return RESULT + OTHER + feature();
+}
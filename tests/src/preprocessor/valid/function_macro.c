@@ -0,0 +1,9 @@
+#define MAX(a, b) ((a) > (b) ? (a) : (b))
+#define SQUARE(x) ((x) * (x))
+#define ZERO() 0
+
+int main()
+{
+    int x = 3;
+    return MAX(SQUARE(x + 1), 10) + ZERO();
+}
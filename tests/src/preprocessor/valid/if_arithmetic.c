@@ -0,0 +1,34 @@
+// Operands skipped by '&&', '||' and '?:' are not evaluated,
+// and values are computed as intmax_t or uintmax_t
+#if defined(X) && 10 / X > 1
+#define A 1
+#else
+#define A 2
+#endif
+
+#if 1 || 1 % 0
+#define B 4
+#endif
+
+#if (0 ? 1 / 0 : 8) == 8 && (1 ? 16 : 1 / 0) == 16
+#define C 8
+#endif
+
+#if -1 > 0u
+#define D 16
+#else
+#define D 0
+#endif
+
+#if -1 < 0 && (-1 >> 63) == -1 && (0xffffffffffffffff >> 63) == 1
+#define E 32
+#endif
+
+#if 0x7fffffffffffffff + 1 < 0
+#define F 64
+#endif
+
+int main(void)
+{
+    return A + B + C + D + E + F;
+}
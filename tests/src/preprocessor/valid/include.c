@@ -0,0 +1,10 @@
+#include "include_header.h"
+#include "include_header.h"
+
+int main()
+{
+    // A line comment
+    /* A block
+       comment */
+    return add(HEADER_VALUE, 2);
+}
@@ -0,0 +1,8 @@
+#pragma once
+
+#define HEADER_VALUE 40
+
+int add(int a, int b)
+{
+    return a + b;
+}
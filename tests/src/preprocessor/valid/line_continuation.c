@@ -0,0 +1,8 @@
+#define LONG_MACRO(a, b) \
+    ((a) *            \
+     (b))
+
+int main()
+{
+    return LONG_MACRO(__LINE__, 2);
+}
@@ -0,0 +1,9 @@
+#define SIZE 4
+#define DOUBLE_SIZE (SIZE + SIZE)
+
+int main()
+{
+    int a[SIZE];
+    a[SIZE - 1] = DOUBLE_SIZE;
+    return a[3];
+}
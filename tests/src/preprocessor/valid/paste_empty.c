@@ -0,0 +1,11 @@
+// An empty argument of '##' leaves the other operand unchanged
+#define SIZE(a, b) sizeof a##b
+#define JOIN(a, b, c) a##b##c
+
+int main(void)
+{
+    int x;
+    int xy = 5;
+    char s[3];
+    return SIZE(, s) + SIZE(, x) + JOIN(x, , y) + JOIN(, , 7);
+}
@@ -0,0 +1,13 @@
+int foo = 3;
+int bar(int x)
+{
+    return x;
+}
+
+#define foo foo
+#define bar(x) bar(x) + x
+
+int main()
+{
+    return bar(foo);
+}
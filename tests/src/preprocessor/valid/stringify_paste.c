@@ -0,0 +1,14 @@
+int puts(char *s);
+
+#define STRING(x) #x
+#define CONCAT(a, b) a##b
+#define VARIABLE(n) CONCAT(variable_, n)
+
+int main()
+{
+    int VARIABLE(1) = 5;
+    int variable_2 = 7;
+    puts(STRING(hello   world));
+    puts(STRING("quoted" text));
+    return VARIABLE(1) + CONCAT(variable_, 2) + CONCAT(1, 2);
+}
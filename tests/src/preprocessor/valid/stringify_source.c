@@ -0,0 +1,20 @@
+// Stringification keeps literals and the spacing between tokens as they were written
+int puts(const char *s);
+
+#define STRING(x) #x
+#define EXPAND(x) STRING(x)
+#define SUM(a, b) a + b
+#define HEX 0x10
+
+int main(void)
+{
+    puts(STRING(0x10));
+    puts(STRING('a' + 1.5e3f+010));
+    puts(STRING(  spaced   out+together  ));
+    puts(STRING("text\n" 'x'));
+    puts(STRING(split
+across lines));
+    puts(EXPAND(HEX));
+    puts(EXPAND(SUM(1u,2L)));
+    return 0;
+}
@@ -0,0 +1,7 @@
+const long total = 5;
+
+int main(void)
+{
+    total += 2;
+    return total;
+}
@@ -0,0 +1,15 @@
+struct point {
+    int x;
+    int y;
+};
+
+int move(const struct point *p)
+{
+    p->x = 3;
+    return p->y;
+}
+
+int main(void)
+{
+    return 0;
+}
@@ -0,0 +1,11 @@
+int set(int *p)
+{
+    *p = 1;
+    return 0;
+}
+
+int main(void)
+{
+    const int x = 0;
+    return set(&x);
+}
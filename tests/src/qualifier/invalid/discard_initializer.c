@@ -0,0 +1,6 @@
+int main(void)
+{
+    const char *s = "text";
+    char *t = s;
+    return t[0];
+}
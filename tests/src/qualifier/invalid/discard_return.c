@@ -0,0 +1,9 @@
+char *get(const char *s)
+{
+    return s;
+}
+
+int main(void)
+{
+    return 0;
+}
@@ -0,0 +1,7 @@
+int main(void)
+{
+    volatile int x = 0;
+    int *p;
+    p = &x;
+    return *p;
+}
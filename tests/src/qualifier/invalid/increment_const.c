@@ -0,0 +1,6 @@
+int main(void)
+{
+    const int x = 0;
+    x++;
+    return x;
+}
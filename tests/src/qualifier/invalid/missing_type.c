@@ -0,0 +1,5 @@
+int main(void)
+{
+    const x = 0;
+    return x;
+}
@@ -0,0 +1,15 @@
+const int answer = 21;
+static const long offset = 4;
+const int zero;
+const double half = 0.5;
+
+int get(void)
+{
+    static const int local = 3;
+    return local;
+}
+
+int main(void)
+{
+    return answer * 2 + offset + zero + get() + (int)(half * 4);
+}
@@ -0,0 +1,29 @@
+int length(const char *string)
+{
+    const char *p = string;
+    while (*p)
+        p++;
+    return p - string;
+}
+
+int sum(const int *values, int count)
+{
+    int result = 0;
+    for (int i = 0; i < count; i++)
+        result += values[i];
+    return result;
+}
+
+int main(void)
+{
+    char buffer[4];
+    int values[3];
+    buffer[0] = 'a';
+    buffer[1] = 'b';
+    buffer[2] = 0;
+    values[0] = 3;
+    values[1] = 4;
+    values[2] = 5;
+    const char *string = "hello";
+    return length(buffer) + length(string) + sum(values, 3);
+}
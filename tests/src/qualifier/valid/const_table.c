@@ -0,0 +1,27 @@
+// Const tables of addresses are relocated when the program is loaded
+const char *const names[] = {"a", "bc", "def"};
+
+static int twice(int x)
+{
+    return 2 * x;
+}
+
+static int square(int x)
+{
+    return x * x;
+}
+
+int (*const operations[])(int) = {twice, square};
+
+static const int values[] = {5, 7, 11};
+const int *const last = &values[2];
+
+int main(void)
+{
+    int sum = 0;
+    for (int i = 0; i < 3; i++)
+    {
+        sum += names[i][0] - 'a' + names[i][i];
+    }
+    return sum + operations[0](*last) + operations[1](values[0]);
+}
@@ -0,0 +1,13 @@
+int main(void)
+{
+    int x = 1;
+    int y = 2;
+    int *const p = &x;
+    const int *volatile q = &y;
+    int *const *r = &p;
+    const int *const *s = &q;
+    *p = 10;
+    q = &x;
+    **r += 5;
+    return **s + *q + y;
+}
@@ -0,0 +1,23 @@
+typedef const int cint;
+typedef char *string;
+
+struct point {
+    int x;
+    int y;
+};
+
+int sum(const struct point *p)
+{
+    return p->x + p->y;
+}
+
+int main(void)
+{
+    cint a = 3;
+    const string s = "text";
+    struct point p;
+    p.x = 4;
+    p.y = 5;
+    char c = s[1];
+    return a + sum(&p) + (c == 'e');
+}
@@ -0,0 +1,18 @@
+int counter(volatile int *flag)
+{
+    int count = 0;
+    while (*flag > 0) {
+        *flag = *flag - 1;
+        count++;
+    }
+    return count;
+}
+
+int main(void)
+{
+    volatile int flag = 5;
+    volatile int total = 0;
+    for (volatile int i = 0; i < 4; i++)
+        total += i;
+    return counter(&flag) + total + flag;
+}
@@ -0,0 +1,9 @@
+int *function(void)
+{
+    return 1l;
+}
+
+int main(void)
+{
+    return *function();
+}
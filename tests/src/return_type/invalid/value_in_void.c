@@ -0,0 +1,10 @@
+void function(void)
+{
+    return 1;
+}
+
+int main(void)
+{
+    function();
+    return 0;
+}
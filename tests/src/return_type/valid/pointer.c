@@ -0,0 +1,32 @@
+// Pointers are returned with all 64 bits
+int values[4] = {1, 2, 3, 4};
+int counter;
+
+int *element(int index)
+{
+    return &values[index];
+}
+
+char *string(void)
+{
+    return "returned";
+}
+
+void increment(int amount)
+{
+    counter += amount;
+    return;
+}
+
+int main(void)
+{
+    int result = 0;
+    result += *element(2) == 3;
+    result += element(3) - element(0) == 3;
+    *element(1) = 10;
+    result += values[1] == 10;
+    result += string()[1] == 'e';
+    increment(4);
+    increment(5);
+    return result + counter;
+}
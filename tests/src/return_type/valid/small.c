@@ -0,0 +1,32 @@
+// Small return values are converted to their type and extended to an int
+char to_char(int value)
+{
+    return value;
+}
+
+unsigned char to_unsigned_char(int value)
+{
+    return value;
+}
+
+short to_short(long value)
+{
+    return value;
+}
+
+unsigned short to_unsigned_short(long value)
+{
+    return value;
+}
+
+int main(void)
+{
+    int result = 0;
+    result += to_char(255) == -1;
+    result += to_char(0x141) == 'A';
+    result += to_unsigned_char(-1) == 255;
+    result += to_short(0x18000) == -32768;
+    result += to_unsigned_short(-1) == 65535;
+    int sum = to_char(-2) + to_unsigned_char(300);
+    return result + sum;
+}
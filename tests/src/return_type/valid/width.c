@@ -0,0 +1,31 @@
+// Return values keep the full width of their type
+long large(void)
+{
+    return 0x123456789;
+}
+
+unsigned long unsigned_large(int shift)
+{
+    return 1ul << shift;
+}
+
+long widened(int value)
+{
+    return value;
+}
+
+int narrowed(long value)
+{
+    return value;
+}
+
+int main(void)
+{
+    int result = 0;
+    result += large() == 0x123456789;
+    result += (large() >> 32) == 1;
+    result += unsigned_large(63) == 0x8000000000000000;
+    result += widened(-5) == -5l;
+    result += narrowed(0x100000005) == 5;
+    return result;
+}
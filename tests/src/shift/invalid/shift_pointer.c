@@ -0,0 +1,7 @@
+int main(void)
+{
+    int a = 1;
+    int *b = &a;
+    b = b << 1;
+    return 0;
+}
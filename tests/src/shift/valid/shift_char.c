@@ -0,0 +1,7 @@
+int main(void)
+{
+    char c = 1;
+    long l = 40;
+    long result = l << c;
+    return result >> 2;
+}
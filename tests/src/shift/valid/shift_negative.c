@@ -0,0 +1,6 @@
+int main(void)
+{
+    int a = -64;
+    int b = 3;
+    return -(a >> b);
+}
@@ -0,0 +1,7 @@
+int main(void)
+{
+    int a = 3;
+    int b = 5;
+    int c = a << b;
+    return c >> 2;
+}
@@ -0,0 +1,5 @@
+int main(void)
+{
+    extern int x = 1;
+    return x;
+}
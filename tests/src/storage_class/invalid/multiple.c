@@ -0,0 +1,6 @@
+static extern int x;
+
+int main(void)
+{
+    return 0;
+}
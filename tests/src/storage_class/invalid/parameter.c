@@ -0,0 +1,9 @@
+int f(static int x)
+{
+    return x;
+}
+
+int main(void)
+{
+    return f(1);
+}
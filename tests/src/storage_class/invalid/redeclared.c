@@ -0,0 +1,7 @@
+int x;
+long x;
+
+int main(void)
+{
+    return 0;
+}
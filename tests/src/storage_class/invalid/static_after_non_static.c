@@ -0,0 +1,7 @@
+int x;
+static int x;
+
+int main(void)
+{
+    return x;
+}
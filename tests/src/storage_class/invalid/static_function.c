@@ -0,0 +1,5 @@
+int main(void)
+{
+    static int helper(void);
+    return 0;
+}
@@ -0,0 +1,16 @@
+extern int value;
+
+int get(void)
+{
+    extern int other;
+    return value * other;
+}
+
+int value = 4;
+int other = 3;
+
+int main(void)
+{
+    value++;
+    return get();
+}
@@ -0,0 +1,17 @@
+static int add(int a, int b);
+
+int main(void)
+{
+    int twice(int);
+    return add(twice(3), 4);
+}
+
+int add(int a, int b)
+{
+    return a + b;
+}
+
+int twice(int x)
+{
+    return x * 2;
+}
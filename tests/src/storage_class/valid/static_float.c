@@ -0,0 +1,14 @@
+double accumulate(double value)
+{
+    static double total = 0.5;
+    total += value;
+    return total;
+}
+
+int main(void)
+{
+    int local = 2;
+    accumulate(1.25);
+    accumulate(local);
+    return (int)(accumulate(0.25) * 4);
+}
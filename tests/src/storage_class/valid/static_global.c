@@ -0,0 +1,15 @@
+static int counter;
+static int step = 3;
+
+static int increment(void)
+{
+    counter += step;
+    return counter;
+}
+
+int main(void)
+{
+    increment();
+    increment();
+    return increment();
+}
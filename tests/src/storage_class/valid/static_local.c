@@ -0,0 +1,24 @@
+int next(void)
+{
+    static int count = 10;
+    count++;
+    return count;
+}
+
+int other(void)
+{
+    static int count;
+    {
+        static int count = 100;
+        count--;
+    }
+    return ++count;
+}
+
+int main(void)
+{
+    next();
+    next();
+    other();
+    return next() + other();
+}
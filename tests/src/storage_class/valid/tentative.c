@@ -0,0 +1,14 @@
+int a;
+int a;
+int a = 5;
+int b;
+extern int b;
+static int c;
+static int c = 2;
+extern int c;
+
+int main(void)
+{
+    b = 7;
+    return a + b + c;
+}
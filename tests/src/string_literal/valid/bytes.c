@@ -0,0 +1,14 @@
+// Bytes that are not valid UTF-8 are kept as they are
+int main(void)
+{
+    unsigned char *string = (unsigned char *)"\xff\x80\xc3";
+    unsigned char array[3] = "\xfe\x01";
+    int result = 0;
+    result += string[0] == 255;
+    result += string[1] == 128;
+    result += string[2] == 195;
+    result += string[3] == 0;
+    result += array[0] == 254;
+    result += array[1] == 1;
+    return result + sizeof(array);
+}
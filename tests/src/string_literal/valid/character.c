@@ -0,0 +1,12 @@
+// Character constants have the value of a char converted to int
+int main(void)
+{
+    int result = 0;
+    result += '\xff' == -1;
+    result += '\377' < 0;
+    result += (unsigned char)'\xff' == 255;
+    result += 'ab' == 0x6162;
+    result += 'abcd' == 0x61626364;
+    result += sizeof('a') == 4;
+    return result;
+}
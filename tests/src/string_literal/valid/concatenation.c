@@ -0,0 +1,26 @@
+#define PREFIX "log: "
+#define LOG(message) PREFIX message "\n"
+
+int length(char *string)
+{
+    int i = 0;
+    while (string[i])
+        i++;
+    return i;
+}
+
+// Adjacent string literals are joined into a single string
+int main(void)
+{
+    char *string = "abc" "def"
+                   "ghi";
+    char array[3] = "x" "y";
+    char *message = LOG("started");
+    int result = length(string);
+    result += string[3] == 'd';
+    result += sizeof(array);
+    result += length(message);
+    result += message[12] == '\n';
+    result += length("\x41" "B");
+    return result;
+}
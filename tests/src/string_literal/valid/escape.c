@@ -0,0 +1,24 @@
+// Every escape sequence gives the value of a single char
+int main(void)
+{
+    char *string = "\a\b\f\v\?\'\"\\\0\177\x1b\x41\101";
+    int result = 0;
+    result += string[0] == 7;
+    result += string[1] == 8;
+    result += string[2] == 12;
+    result += string[3] == 11;
+    result += string[4] == '?';
+    result += string[5] == 39;
+    result += string[6] == 34;
+    result += string[7] == 92;
+    result += string[8] == 0;
+    result += string[9] == 127;
+    result += string[10] == 27;
+    result += string[11] == 'A';
+    result += string[12] == 'A';
+    result += string[13] == 0;
+    result += '\0' == 0;
+    result += '\12' == '\n';
+    result += '\x7f' == 127;
+    return result;
+}
@@ -0,0 +1,11 @@
+struct a
+{
+    int x;
+};
+
+int main(void)
+{
+    struct a value;
+    value = 1;
+    return value.x;
+}
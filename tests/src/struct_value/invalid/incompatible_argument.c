@@ -0,0 +1,21 @@
+struct a
+{
+    int x;
+};
+
+struct b
+{
+    int x;
+};
+
+int get(struct a value)
+{
+    return value.x;
+}
+
+int main(void)
+{
+    struct b value;
+    value.x = 1;
+    return get(value);
+}
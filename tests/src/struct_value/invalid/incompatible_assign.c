@@ -0,0 +1,18 @@
+struct a
+{
+    int x;
+};
+
+struct b
+{
+    int x;
+};
+
+int main(void)
+{
+    struct a first;
+    struct b second;
+    first.x = 1;
+    second = first;
+    return second.x;
+}
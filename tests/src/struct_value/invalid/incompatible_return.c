@@ -0,0 +1,14 @@
+struct a
+{
+    int x;
+};
+
+struct a make(void)
+{
+    return 1;
+}
+
+int main(void)
+{
+    return make().x;
+}
@@ -0,0 +1,17 @@
+struct a
+{
+    int x;
+};
+
+struct b
+{
+    int x;
+};
+
+int main(void)
+{
+    struct a first;
+    first.x = 1;
+    struct b second = first;
+    return second.x;
+}
@@ -0,0 +1,8 @@
+typedef int array[4];
+
+array make(void);
+
+int main(void)
+{
+    return 0;
+}
@@ -0,0 +1,54 @@
+// Compiled by gcc, to check that both compilers agree on the layout of nested structs
+struct inner {
+    int a, b, c;
+};
+
+struct outer {
+    char c;
+    struct inner s;
+};
+
+struct half {
+    short x;
+    char y;
+};
+
+struct odd {
+    char c;
+    struct half h;
+    char d;
+};
+
+struct outer make_outer(char c, int a, int b, int d)
+{
+    struct outer o = {c, {a, b, d}};
+    return o;
+}
+
+int sum_outer(struct outer o)
+{
+    return o.c + 2 * o.s.a + 3 * o.s.b + 4 * o.s.c;
+}
+
+struct odd make_odd(char c, short x, char y, char d)
+{
+    struct odd o = {c, {x, y}, d};
+    return o;
+}
+
+int sum_odd(struct odd o)
+{
+    return o.c + 2 * o.h.x + 3 * o.h.y + 4 * o.d;
+}
+
+unsigned long outer_layout(void)
+{
+    struct outer o;
+    return sizeof(o) * 100 + ((char *)&o.s - (char *)&o);
+}
+
+unsigned long odd_layout(void)
+{
+    struct odd o;
+    return sizeof(o) * 100 + ((char *)&o.d - (char *)&o);
+}
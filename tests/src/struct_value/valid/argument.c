@@ -0,0 +1,39 @@
+struct pair
+{
+    int first;
+    int second;
+};
+
+struct triple
+{
+    long a;
+    char b;
+    short c;
+    int d;
+};
+
+int sum(struct pair p)
+{
+    int result = p.first + p.second;
+    p.first = 100;
+    return result;
+}
+
+long combine(int scale, struct triple t, struct pair p)
+{
+    return scale * (t.a + t.b + t.c + t.d) + p.first - p.second;
+}
+
+int main(void)
+{
+    struct pair p;
+    p.first = 3;
+    p.second = 7;
+    struct triple t;
+    t.a = 1;
+    t.b = 2;
+    t.c = 3;
+    t.d = 4;
+    int s = sum(p);
+    return s + p.first + combine(2, t, p);
+}
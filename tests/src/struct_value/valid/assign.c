@@ -0,0 +1,31 @@
+struct point
+{
+    int x;
+    int y;
+};
+
+struct line
+{
+    struct point from;
+    struct point to;
+    char name[3];
+};
+
+int main(void)
+{
+    struct point a;
+    struct point b;
+    struct point c;
+    a.x = 3;
+    a.y = 4;
+    c = b = a;
+    a.x = 10;
+
+    struct line l;
+    l.from = c;
+    l.to = a;
+    l.name[2] = 5;
+    struct line m = l;
+    l.to.y = 0;
+    return m.from.x + m.from.y + m.to.x + m.to.y + m.name[2] + b.x;
+}
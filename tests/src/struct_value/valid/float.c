@@ -0,0 +1,44 @@
+struct vector
+{
+    float x;
+    float y;
+    float z;
+};
+
+struct mixed
+{
+    double d;
+    int i;
+};
+
+struct vector add(struct vector a, struct vector b)
+{
+    struct vector result;
+    result.x = a.x + b.x;
+    result.y = a.y + b.y;
+    result.z = a.z + b.z;
+    return result;
+}
+
+struct mixed make_mixed(int i, double d)
+{
+    struct mixed m;
+    m.d = d;
+    m.i = i;
+    return m;
+}
+
+double sum(struct mixed m)
+{
+    return m.d + m.i;
+}
+
+int main(void)
+{
+    struct vector a;
+    a.x = 1.5f;
+    a.y = 2.0f;
+    a.z = 3.5f;
+    struct vector b = add(a, a);
+    return (int)(b.x + b.y + b.z) + (int)sum(make_mixed(3, 4.5));
+}
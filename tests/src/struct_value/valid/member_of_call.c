@@ -0,0 +1,32 @@
+struct inner
+{
+    int value;
+    char flag;
+};
+
+struct outer
+{
+    struct inner first;
+    struct inner second;
+};
+
+struct outer make(int a, int b)
+{
+    struct outer o;
+    o.first.value = a;
+    o.first.flag = 1;
+    o.second.value = b;
+    o.second.flag = 2;
+    return o;
+}
+
+struct inner get_second(struct outer o)
+{
+    return o.second;
+}
+
+int main(void)
+{
+    struct outer o;
+    return make(5, 6).first.value + get_second(make(7, 8)).value + (o = make(9, 10)).second.flag;
+}
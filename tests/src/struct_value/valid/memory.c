@@ -0,0 +1,49 @@
+struct big
+{
+    long values[3];
+};
+
+struct huge
+{
+    int values[32];
+    char tag;
+};
+
+struct big scale(struct big b, long factor)
+{
+    b.values[0] = b.values[0] * factor;
+    b.values[1] = b.values[1] * factor;
+    b.values[2] = b.values[2] * factor;
+    return b;
+}
+
+struct huge fill(int start)
+{
+    struct huge h;
+    for (int i = 0; i < 32; i++)
+        h.values[i] = start + i;
+    h.tag = 7;
+    return h;
+}
+
+int total(struct huge h)
+{
+    int result = h.tag;
+    for (int i = 0; i < 32; i++)
+        result += h.values[i];
+    return result;
+}
+
+int main(void)
+{
+    struct big b;
+    b.values[0] = 1;
+    b.values[1] = 2;
+    b.values[2] = 3;
+    struct big c = scale(b, 3);
+    struct huge h = fill(1);
+    struct huge copy;
+    copy = h;
+    h.values[0] = 100;
+    return c.values[0] + c.values[1] + c.values[2] + b.values[2] + total(copy) - 500;
+}
@@ -0,0 +1,47 @@
+// Nested structs are aligned by their own alignment, not by their size
+int printf(const char *format, ...);
+
+struct inner {
+    int a, b, c;
+};
+
+struct outer {
+    char c;
+    struct inner s;
+};
+
+struct half {
+    short x;
+    char y;
+};
+
+struct odd {
+    char c;
+    struct half h;
+    char d;
+};
+
+struct outer make_outer(char c, int a, int b, int d);
+int sum_outer(struct outer o);
+struct odd make_odd(char c, short x, char y, char d);
+int sum_odd(struct odd o);
+unsigned long outer_layout(void);
+unsigned long odd_layout(void);
+
+int main(void)
+{
+    struct outer o = make_outer(1, 2, 3, 4);
+    printf("%d %d %d %d\n", o.c, o.s.a, o.s.b, o.s.c);
+    o.s.b = 10;
+    printf("%d\n", sum_outer(o));
+
+    struct odd d = make_odd(5, 6, 7, 8);
+    printf("%d %d %d %d\n", d.c, d.h.x, d.h.y, d.d);
+    d.h.x = 20;
+    printf("%d\n", sum_odd(d));
+
+    unsigned long outer = sizeof(o) * 100 + ((char *)&o.s - (char *)&o);
+    unsigned long odd = sizeof(d) * 100 + ((char *)&d.d - (char *)&d);
+    printf("%lu %lu %lu %lu\n", outer, outer_layout(), odd, odd_layout());
+    return outer == outer_layout() && odd == odd_layout();
+}
@@ -0,0 +1,59 @@
+struct small
+{
+    char a;
+    char b;
+    char c;
+};
+
+struct pair
+{
+    long first;
+    long second;
+};
+
+struct odd
+{
+    int a;
+    int b;
+    int c;
+};
+
+struct small make_small(char a, char b, char c)
+{
+    struct small s;
+    s.a = a;
+    s.b = b;
+    s.c = c;
+    return s;
+}
+
+struct pair make_pair(long first, long second)
+{
+    struct pair p;
+    p.first = first;
+    p.second = second;
+    return p;
+}
+
+struct odd make_odd(int value)
+{
+    struct odd o;
+    o.a = value;
+    o.b = value * 2;
+    o.c = value * 3;
+    return o;
+}
+
+struct pair swap(struct pair p)
+{
+    return make_pair(p.second, p.first);
+}
+
+int main(void)
+{
+    struct small s = make_small(1, 2, 3);
+    struct pair p = swap(make_pair(10, 20));
+    struct odd o;
+    o = make_odd(4);
+    return s.a + s.b + s.c + p.first - p.second + o.a + o.b + o.c;
+}
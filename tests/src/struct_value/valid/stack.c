@@ -0,0 +1,25 @@
+struct pair
+{
+    long first;
+    long second;
+};
+
+// The last pair no longer fits in registers and is passed on the stack
+long sum(long a, long b, long c, struct pair p, struct pair q, long d, struct pair r)
+{
+    return a + b + c + p.first + p.second + q.first + q.second + d + r.first * r.second;
+}
+
+int main(void)
+{
+    struct pair p;
+    p.first = 1;
+    p.second = 2;
+    struct pair q;
+    q.first = 3;
+    q.second = 4;
+    struct pair r;
+    r.first = 5;
+    r.second = 6;
+    return sum(10, 20, 30, p, q, 40, r);
+}
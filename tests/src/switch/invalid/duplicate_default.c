@@ -0,0 +1,14 @@
+int main(void)
+{
+    int x = 1;
+    switch (x)
+    {
+    default:
+        x = 2;
+    case 1:
+        return 1;
+    default:
+        return 2;
+    }
+    return 0;
+}
@@ -0,0 +1,11 @@
+int main(void)
+{
+    int x = 1;
+    int y = 1;
+    switch (x)
+    {
+    case y:
+        return 1;
+    }
+    return 0;
+}
@@ -0,0 +1,10 @@
+int main(void)
+{
+    int x = 1;
+    if (x)
+    {
+    case 1:
+        return 1;
+    }
+    return 0;
+}
@@ -0,0 +1,10 @@
+int main(void)
+{
+    int *p = 0;
+    switch (p)
+    {
+    case 0:
+        return 1;
+    }
+    return 0;
+}
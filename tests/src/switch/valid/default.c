@@ -0,0 +1,26 @@
+int classify(int c)
+{
+    switch (c)
+    {
+    case 'a':
+    case 'e':
+    case 'i':
+    case 'o':
+    case 'u':
+        return 1;
+    default:
+        return 2;
+    case ' ':
+        return 3;
+    }
+}
+
+int main(void)
+{
+    char *text = "a quiet dog";
+    int total = 0;
+    int i;
+    for (i = 0; text[i]; i++)
+        total = total + classify(text[i]);
+    return total;
+}
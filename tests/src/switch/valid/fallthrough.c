@@ -0,0 +1,22 @@
+int count(int n)
+{
+    int result = 0;
+    switch (n)
+    {
+    case 3:
+        result = result + 1;
+    case 2:
+        result = result + 1;
+    case 1:
+        result = result + 1;
+        break;
+    case 0:
+        result = 10;
+    }
+    return result;
+}
+
+int main(void)
+{
+    return count(3) * 10 + count(1) + count(0) + count(5);
+}
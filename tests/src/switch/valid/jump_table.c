@@ -0,0 +1,40 @@
+int day_length(int month)
+{
+    switch (month)
+    {
+    case 1:
+        return 31;
+    case 2:
+        return 28;
+    case 3:
+        return 31;
+    case 4:
+        return 30;
+    case 5:
+        return 31;
+    case 6:
+        return 30;
+    case 8:
+    case 7:
+        return 31;
+    case 9:
+        return 30;
+    case 10:
+        return 31;
+    case 11:
+        return 30;
+    case 12:
+        return 31;
+    default:
+        return 0;
+    }
+}
+
+int main(void)
+{
+    int days = 0;
+    int month;
+    for (month = -1; month < 15; month++)
+        days = days + day_length(month);
+    return days - 300;
+}
@@ -0,0 +1,27 @@
+int main(void)
+{
+    int total = 0;
+    int i;
+    for (i = 0; i < 10; i++)
+    {
+        switch (i % 4)
+        {
+        case 0:
+            continue;
+        case 1:
+            switch (i)
+            {
+            case 1:
+                total = total + 100;
+                break;
+            default:
+                total = total + 1;
+            }
+            break;
+        case 2:
+            total = total + 2;
+        }
+        total = total + 10;
+    }
+    return total;
+}
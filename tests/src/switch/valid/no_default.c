@@ -0,0 +1,26 @@
+int main(void)
+{
+    int x = 5;
+    int y = 0;
+    switch (x)
+    {
+    case 1:
+        y = 1;
+        break;
+    case 2:
+        y = 2;
+        break;
+    case 3:
+        y = 3;
+        break;
+    case 4:
+        y = 4;
+        break;
+    case 6:
+        y = 6;
+        break;
+    }
+    switch (x + 1)
+        y = y + 1;
+    return y + 3;
+}
@@ -0,0 +1,25 @@
+int lookup(long key)
+{
+    switch (key)
+    {
+    case -100000:
+        return 1;
+    case 7:
+        return 2;
+    case 1000:
+        return 3;
+    case 123456789:
+        return 4;
+    case 10000000000:
+        return 5;
+    case -3:
+        return 6;
+    }
+    return 0;
+}
+
+int main(void)
+{
+    return lookup(-100000) + lookup(7) * 10 + lookup(1000) * 100 - lookup(123456789) * 20
+           + lookup(10000000000) + lookup(-3) + lookup(8);
+}
@@ -0,0 +1,38 @@
+int test(unsigned int value)
+{
+    switch (value)
+    {
+    case 0:
+        return 1;
+    case 1:
+        return 2;
+    case 4294967295:
+        return 3;
+    case -2:
+        return 4;
+    }
+    return 5;
+}
+
+int negative(char c)
+{
+    switch (c)
+    {
+    case -1:
+        return 1;
+    case -2:
+        return 2;
+    case -3:
+        return 3;
+    case -4:
+        return 4;
+    case -5:
+        return 5;
+    }
+    return 0;
+}
+
+int main(void)
+{
+    return test(0) + test(1) * 2 + test(-1) * 4 + test(-2) * 8 + test(7) * 16 + negative(-4);
+}
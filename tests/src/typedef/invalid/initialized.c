@@ -0,0 +1,5 @@
+int main(void)
+{
+    typedef int T = 1;
+    return 0;
+}
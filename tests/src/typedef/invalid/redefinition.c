@@ -0,0 +1,7 @@
+typedef int T;
+typedef long T;
+
+int main(void)
+{
+    return 0;
+}
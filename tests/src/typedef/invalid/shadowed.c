@@ -0,0 +1,8 @@
+typedef int T;
+
+int main(void)
+{
+    int T = 1;
+    T a = 2;
+    return a;
+}
@@ -0,0 +1,7 @@
+int T;
+typedef int T;
+
+int main(void)
+{
+    return 0;
+}
@@ -0,0 +1,7 @@
+typedef unsigned char byte;
+
+int main(void)
+{
+    int a = 300;
+    return (byte)a + sizeof(byte);
+}
@@ -0,0 +1,10 @@
+typedef long T;
+
+int main(void)
+{
+    typedef char T;
+    T c = 7;
+    typedef char T;
+    int a = sizeof(T);
+    return c + a;
+}
@@ -0,0 +1,17 @@
+typedef int T;
+
+int f(int T)
+{
+    return T * 2;
+}
+
+int main(void)
+{
+    T a = 2;
+    {
+        int T = 5;
+        a = a + T;
+    }
+    T b = f(a);
+    return b;
+}
@@ -0,0 +1,8 @@
+typedef int T;
+
+int main(void)
+{
+    unsigned T = 3;
+    long T2 = 4;
+    return T + T2;
+}
@@ -0,0 +1,10 @@
+typedef int vector[4];
+
+int main(void)
+{
+    vector v;
+    int i;
+    for (i = 0; i < 4; i++)
+        v[i] = i * 2;
+    return v[3] + sizeof(vector);
+}
@@ -0,0 +1,12 @@
+typedef int integer;
+
+integer add(integer a, integer b)
+{
+    return a + b;
+}
+
+int main(void)
+{
+    integer a = 3;
+    return add(a, 4);
+}
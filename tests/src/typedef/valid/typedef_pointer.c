@@ -0,0 +1,11 @@
+typedef int *int_ptr;
+typedef int_ptr *int_ptr_ptr;
+
+int main(void)
+{
+    int a = 5;
+    int_ptr p = &a;
+    int_ptr_ptr pp = &p;
+    **pp = 11;
+    return a;
+}
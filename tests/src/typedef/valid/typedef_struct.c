@@ -0,0 +1,18 @@
+typedef struct point
+{
+    int x;
+    int y;
+} point;
+
+int length(point *p)
+{
+    return p->x + p->y;
+}
+
+int main(void)
+{
+    point p;
+    p.x = 3;
+    p.y = 4;
+    return length(&p);
+}
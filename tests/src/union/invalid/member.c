@@ -0,0 +1,12 @@
+union u
+{
+    int a;
+    char b;
+};
+
+int main(void)
+{
+    union u x;
+    x.c = 1;
+    return 0;
+}
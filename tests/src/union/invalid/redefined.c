@@ -0,0 +1,14 @@
+union u
+{
+    int a;
+};
+
+union u
+{
+    char c;
+};
+
+int main(void)
+{
+    return 0;
+}
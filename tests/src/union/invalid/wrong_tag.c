@@ -0,0 +1,10 @@
+struct s
+{
+    int a;
+};
+
+int main(void)
+{
+    union s x;
+    return 0;
+}
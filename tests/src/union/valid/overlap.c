@@ -0,0 +1,13 @@
+union value
+{
+    int i;
+    char c;
+};
+
+int main(void)
+{
+    union value v;
+    v.i = 0;
+    v.c = 42;
+    return v.i;
+}
@@ -0,0 +1,19 @@
+union number
+{
+    long l;
+    int i;
+};
+
+void set(union number *n, int value)
+{
+    n->i = value;
+}
+
+int main(void)
+{
+    union number n;
+    union number *p = &n;
+    n.l = 0;
+    set(p, 9);
+    return p->l + p->i;
+}
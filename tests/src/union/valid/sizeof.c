@@ -0,0 +1,17 @@
+union value
+{
+    char c;
+    int i;
+    long l;
+};
+
+union small
+{
+    char c;
+    short s;
+};
+
+int main(void)
+{
+    return sizeof(union value) * 10 + sizeof(union small);
+}
@@ -0,0 +1,34 @@
+enum kind
+{
+    INT,
+    POINTER
+};
+
+struct tagged
+{
+    enum kind kind;
+    union
+    {
+        int i;
+        int *p;
+    } value;
+};
+
+int get(struct tagged *t)
+{
+    if (t->kind == INT)
+        return t->value.i;
+    return *t->value.p;
+}
+
+int main(void)
+{
+    int a = 7;
+    struct tagged x;
+    struct tagged y;
+    x.kind = INT;
+    x.value.i = 5;
+    y.kind = POINTER;
+    y.value.p = &a;
+    return get(&x) * 10 + get(&y) + sizeof(struct tagged);
+}
@@ -0,0 +1,5 @@
+int main(void)
+{
+    unsigned signed int a = 0;
+    return a;
+}
@@ -0,0 +1,5 @@
+int main(void)
+{
+    unsigned unsigned a = 0;
+    return a;
+}
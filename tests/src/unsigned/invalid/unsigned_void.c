@@ -0,0 +1,8 @@
+unsigned void f(void)
+{
+}
+
+int main(void)
+{
+    return 0;
+}
@@ -0,0 +1,11 @@
+unsigned long widen(unsigned long x)
+{
+    return x >> 31;
+}
+
+int main(void)
+{
+    unsigned u = -1;
+    unsigned short v = -1;
+    return widen(u) + (widen(v) == 0) * 2;
+}
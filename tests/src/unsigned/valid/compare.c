@@ -0,0 +1,13 @@
+int main(void)
+{
+    unsigned a = 1;
+    int b = -1;
+    int result = 0;
+    if (b < a)
+        result = result + 1;
+    if (a < b)
+        result = result + 2;
+    if (sizeof(int) > -1)
+        result = result + 4;
+    return result + 10 * (b > a);
+}
@@ -0,0 +1,8 @@
+int g = (unsigned char)-1;
+int h = -1 < (unsigned)0;
+unsigned k = (unsigned)-1 / 2 > 1;
+
+int main(void)
+{
+    return g - 200 + h + k;
+}
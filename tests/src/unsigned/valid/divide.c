@@ -0,0 +1,6 @@
+int main(void)
+{
+    unsigned a = -2;
+    unsigned b = 1000000000;
+    return a / b + a % 100;
+}
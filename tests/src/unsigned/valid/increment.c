@@ -0,0 +1,9 @@
+int main(void)
+{
+    unsigned char c = 254;
+    unsigned i = 0;
+    c++;
+    c++;
+    i--;
+    return c + (i > 1000) + (i == (unsigned)-1) * 2;
+}
@@ -0,0 +1,15 @@
+// Sizes are unsigned, but the distance between two pointers is signed
+int main(void)
+{
+    int a[8];
+    int *p = &a[1];
+    int *q = &a[6];
+    if (!(p - q < 0))
+        return 1;
+    if ((p - q) / 2 != -2)
+        return 2;
+    if (sizeof(a) - 40 < 0)
+        return 3;
+    long distance = p - q;
+    return (q - p) * 10 - distance;
+}
@@ -0,0 +1,6 @@
+int main(void)
+{
+    unsigned a = -16;
+    int b = -16;
+    return (a >> 28) + (b >> 28) * 10 + 50;
+}
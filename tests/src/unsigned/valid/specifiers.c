@@ -0,0 +1,9 @@
+int main(void)
+{
+    signed char c = -5;
+    signed s = 3;
+    short unsigned int x = 65535;
+    long unsigned y = 2;
+    unsigned int z = 1;
+    return c + s + x - 65530 + y + z;
+}
@@ -0,0 +1,8 @@
+int main(void)
+{
+    unsigned char c = 200;
+    char s = c;
+    int x = c;
+    c = c + 100;
+    return x - 100 + c + (s < 0);
+}
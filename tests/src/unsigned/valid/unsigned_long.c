@@ -0,0 +1,6 @@
+int main(void)
+{
+    unsigned long a = -1;
+    unsigned long b = a / 2;
+    return (a > 0) + (a >> 60) + (b >> 62) * 20;
+}
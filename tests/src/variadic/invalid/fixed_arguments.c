@@ -0,0 +1,15 @@
+#include <stdarg.h>
+
+int first(int count)
+{
+    va_list arguments;
+    va_start(arguments, count);
+    int result = va_arg(arguments, int);
+    va_end(arguments);
+    return result;
+}
+
+int main(void)
+{
+    return first(1);
+}
@@ -0,0 +1,13 @@
+#include <stdarg.h>
+
+int f(int count, ...)
+{
+    int arguments;
+    va_start(arguments, count);
+    return va_arg(arguments, int);
+}
+
+int main(void)
+{
+    return f(1, 2);
+}
@@ -0,0 +1,9 @@
+int f(...)
+{
+    return 0;
+}
+
+int main(void)
+{
+    return f(1, 2);
+}
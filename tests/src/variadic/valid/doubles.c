@@ -0,0 +1,36 @@
+#include <stdarg.h>
+
+double average(int count, ...)
+{
+    va_list arguments;
+    va_start(arguments, count);
+    double total = 0.0;
+    for (int i = 0; i < count; i++)
+        total += va_arg(arguments, double);
+    va_end(arguments);
+    return total / count;
+}
+
+// Integer and floating point arguments are taken from separate registers
+double mixed(double first, ...)
+{
+    va_list arguments;
+    va_start(arguments, first);
+    double result = first;
+    for (int i = 0; i < 5; i++)
+    {
+        result += va_arg(arguments, int);
+        result *= va_arg(arguments, double);
+    }
+    va_end(arguments);
+    return result;
+}
+
+int main(void)
+{
+    float f = 2.5f;
+    double small = average(4, 1.0, f, 3.5, 5.0);
+    double large = average(10, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0);
+    double both = mixed(1.0, 1, 2.0, 2, 0.5, 3, 2.0, 4, 0.25, 5, 4.0);
+    return (int)(small * 10) + (int)(large * 10) + (int)both;
+}
@@ -0,0 +1,71 @@
+#include <stdarg.h>
+
+// Counts the characters a minimal printf would write
+int vlength(const char *format, va_list arguments)
+{
+    int length = 0;
+    for (; *format; format++)
+    {
+        if (*format != '%')
+        {
+            length++;
+            continue;
+        }
+        format++;
+        if (*format == 'd')
+        {
+            int value = va_arg(arguments, int);
+            if (value < 0)
+            {
+                length++;
+                value = -value;
+            }
+            do
+            {
+                length++;
+                value /= 10;
+            } while (value);
+        }
+        else if (*format == 's')
+        {
+            const char *string = va_arg(arguments, const char *);
+            while (*string++)
+                length++;
+        }
+        else if (*format == 'c')
+        {
+            va_arg(arguments, int);
+            length++;
+        }
+    }
+    return length;
+}
+
+// Both copies of the list start at the same argument
+int twice(const char *format, ...)
+{
+    va_list arguments;
+    va_list copy;
+    va_start(arguments, format);
+    va_copy(copy, arguments);
+    int first = vlength(format, arguments);
+    int second = vlength(format, copy);
+    va_end(copy);
+    va_end(arguments);
+    return first + second;
+}
+
+int length(const char *format, ...)
+{
+    va_list arguments;
+    va_start(arguments, format);
+    int result = vlength(format, arguments);
+    va_end(arguments);
+    return result;
+}
+
+int main(void)
+{
+    return length("%d items: %s%c", -1234, "apples", '!') +
+           twice("%s %d %d %d %d %d %d", "x", 1, 22, 333, 4444, 55555, 666666);
+}
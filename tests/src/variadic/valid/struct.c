@@ -0,0 +1,94 @@
+#include <stdarg.h>
+
+struct pair
+{
+    long a;
+    long b;
+};
+
+struct point
+{
+    double x;
+    double y;
+};
+
+struct tagged
+{
+    double value;
+    long tag;
+};
+
+struct counted
+{
+    int count;
+    double value;
+};
+
+struct large
+{
+    long values[4];
+};
+
+struct small
+{
+    char c;
+    int i;
+};
+
+// Each letter in the format selects the type of the next argument
+long total(const char *format, ...)
+{
+    va_list arguments;
+    va_start(arguments, format);
+    long result = 0;
+    for (; *format; format++)
+    {
+        if (*format == 'p')
+        {
+            struct pair p = va_arg(arguments, struct pair);
+            result += p.a - p.b;
+        }
+        else if (*format == 'o')
+        {
+            struct point p = va_arg(arguments, struct point);
+            result += (long)(p.x * p.y);
+        }
+        else if (*format == 't')
+        {
+            struct tagged t = va_arg(arguments, struct tagged);
+            result += (long)t.value * t.tag;
+        }
+        else if (*format == 'c')
+        {
+            struct counted c = va_arg(arguments, struct counted);
+            result += c.count + (long)c.value;
+        }
+        else if (*format == 'l')
+        {
+            struct large l = va_arg(arguments, struct large);
+            result += l.values[0] + l.values[3];
+        }
+        else if (*format == 's')
+        {
+            result += va_arg(arguments, struct small).i;
+        }
+        else
+        {
+            result += va_arg(arguments, int);
+        }
+    }
+    va_end(arguments);
+    return result;
+}
+
+int main(void)
+{
+    struct pair p = {10, 3};
+    struct point o = {1.5, 4.0};
+    struct tagged t = {2.0, 7};
+    struct counted c = {5, 6.5};
+    struct large l = {{1, 2, 3, 4}};
+    struct small s = {'a', 9};
+    return total("potcls", p, o, t, c, l, s) + total("ipppp", 1, p, p, p, p) +
+           total("ooooootccc", o, o, o, o, o, o, t, c, c, c);
+}
@@ -0,0 +1,34 @@
+#include <stdarg.h>
+
+int sum(int count, ...)
+{
+    va_list arguments;
+    va_start(arguments, count);
+    int result = 0;
+    for (int i = 0; i < count; i++)
+        result += va_arg(arguments, int);
+    va_end(arguments);
+    return result;
+}
+
+long weighted(long weight, int count, ...)
+{
+    va_list arguments;
+    va_start(arguments, count);
+    long result = 0;
+    while (count--)
+    {
+        result += weight * va_arg(arguments, long);
+        weight++;
+    }
+    va_end(arguments);
+    return result;
+}
+
+int main(void)
+{
+    char c = 3;
+    short s = 4;
+    return sum(0) + sum(3, 1, 2, c) + sum(10, 1, 2, 3, 4, 5, 6, 7, 8, 9, s) +
+           weighted(2, 6, 1L, 2L, 3L, 4L, 5L, 6L) - 100;
+}